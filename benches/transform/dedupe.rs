@@ -38,6 +38,7 @@ fn dedupe(c: &mut Criterion) {
     );
     let cache = CacheConfig {
         num_events: NonZeroUsize::new(4).unwrap(),
+        persistence: None,
     };
     for param in &[
         // Measurement where field "message" is ignored. This field is
@@ -129,6 +130,7 @@ fn dedupe(c: &mut Criterion) {
                     let dedupe = Transform::event_task(Dedupe::new(
                         config.cache.num_events,
                         config.fields.unwrap(),
+                        None,
                     ))
                     .into_task();
                     (Box::new(dedupe), Box::pin(param.input.clone()))
@@ -46,6 +46,7 @@ fn reduce(c: &mut Criterion) {
                 ends_when: None,
                 starts_when: None,
                 max_events: None,
+                state_snapshot: None,
             },
         };
         group.throughput(Throughput::Elements(param.input.len() as u64));
@@ -7,6 +7,7 @@ use std::{
 
 use chrono::{DateTime, Utc};
 use dashmap::DashMap;
+use fslock::LockFile;
 use serde::{Deserialize, Serialize};
 use tokio::{
     fs::{self, File},
@@ -15,10 +16,14 @@ use tokio::{
 };
 use tracing::{error, info, warn};
 
-use super::{FilePosition, fingerprinter::FileFingerprint};
+use super::{
+    FilePosition,
+    fingerprinter::{ContentChecksum, FileFingerprint},
+};
 
 const TMP_FILE_NAME: &str = "checkpoints.new.json";
 pub const CHECKPOINT_FILE_NAME: &str = "checkpoints.json";
+const LOCK_FILE_NAME: &str = "checkpoints.lock";
 
 /// This enum represents the file format of checkpoints persisted to disk. Right
 /// now there is only one variant, but any incompatible changes will require and
@@ -35,10 +40,35 @@ enum State {
 /// fingerprints as objects cannot be keys in a plain JSON map.
 #[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq, Ord, PartialOrd)]
 #[serde(rename_all = "snake_case")]
-struct Checkpoint {
-    fingerprint: FileFingerprint,
-    position: FilePosition,
-    modified: DateTime<Utc>,
+pub struct Checkpoint {
+    pub fingerprint: FileFingerprint,
+    pub position: FilePosition,
+    pub modified: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content_checksum: Option<ContentChecksum>,
+}
+
+/// Advisory lock on the checkpoints of a data directory, released when dropped.
+///
+/// The file source holds it for as long as it runs, so that the checkpoints aren't rewritten by
+/// another process, such as `vector checkpoints`, while they're in use.
+pub struct CheckpointsLock {
+    _lock: LockFile,
+}
+
+impl CheckpointsLock {
+    /// Locks the checkpoints of `data_dir`, waiting for the lock to be released if it is held.
+    pub fn lock(data_dir: &Path) -> io::Result<Self> {
+        let mut lock = LockFile::open(&data_dir.join(LOCK_FILE_NAME))?;
+        lock.lock()?;
+        Ok(Self { _lock: lock })
+    }
+
+    /// Locks the checkpoints of `data_dir`, returning `None` if the lock is already held.
+    pub fn try_lock(data_dir: &Path) -> io::Result<Option<Self>> {
+        let mut lock = LockFile::open(&data_dir.join(LOCK_FILE_NAME))?;
+        Ok(lock.try_lock()?.then_some(Self { _lock: lock }))
+    }
 }
 
 pub struct Checkpointer {
@@ -55,6 +85,7 @@ pub struct CheckpointsView {
     checkpoints: DashMap<FileFingerprint, FilePosition>,
     modified_times: DashMap<FileFingerprint, DateTime<Utc>>,
     removed_times: DashMap<FileFingerprint, DateTime<Utc>>,
+    content_checksums: DashMap<FileFingerprint, ContentChecksum>,
 }
 
 impl CheckpointsView {
@@ -68,6 +99,51 @@ impl CheckpointsView {
         self.checkpoints.get(&fng).map(|r| *r.value())
     }
 
+    /// Records the checksum of the data last read from a file, which is used to detect whether
+    /// the file was truncated or replaced when it is next seen.
+    pub fn update_content_checksum(&self, fng: FileFingerprint, checksum: ContentChecksum) {
+        self.content_checksums.insert(fng, checksum);
+    }
+
+    pub fn get_content_checksum(&self, fng: FileFingerprint) -> Option<ContentChecksum> {
+        self.content_checksums.get(&fng).map(|r| *r.value())
+    }
+
+    pub fn remove_content_checksum(&self, fng: FileFingerprint) {
+        self.content_checksums.remove(&fng);
+    }
+
+    /// Gets the positions and content checksums of the other files with the same header as
+    /// `fng`, which may hold the same data, such as when a file is copied.
+    pub fn with_same_header(
+        &self,
+        fng: FileFingerprint,
+    ) -> Vec<(FileFingerprint, FilePosition, ContentChecksum)> {
+        self.content_checksums
+            .iter()
+            .filter(|entry| *entry.key() != fng && entry.key().same_header(&fng))
+            .filter_map(|entry| {
+                self.get(*entry.key())
+                    .map(|position| (*entry.key(), position, *entry.value()))
+            })
+            .collect()
+    }
+
+    /// Removes the checkpoint of a file, so that it is read according to `read_from` when next seen.
+    pub fn remove(&self, fng: FileFingerprint) -> Option<FilePosition> {
+        self.modified_times.remove(&fng);
+        self.removed_times.remove(&fng);
+        self.content_checksums.remove(&fng);
+        self.checkpoints.remove(&fng).map(|(_, position)| position)
+    }
+
+    /// Gets all checkpoints, ordered by fingerprint.
+    pub fn list(&self) -> Vec<Checkpoint> {
+        match self.get_state() {
+            State::V1 { checkpoints } => checkpoints.into_iter().collect(),
+        }
+    }
+
     pub fn set_dead(&self, fng: FileFingerprint) {
         self.removed_times.insert(fng, Utc::now());
     }
@@ -84,6 +160,10 @@ impl CheckpointsView {
         if let Some((_, value)) = self.removed_times.remove(&old) {
             self.removed_times.insert(new, value);
         }
+
+        if let Some((_, value)) = self.content_checksums.remove(&old) {
+            self.content_checksums.insert(new, value);
+        }
     }
 
     pub fn remove_expired(&self) {
@@ -107,14 +187,19 @@ impl CheckpointsView {
             self.checkpoints.remove(&fng);
             self.modified_times.remove(&fng);
             self.removed_times.remove(&fng);
+            self.content_checksums.remove(&fng);
         }
     }
 
-    fn load(&self, checkpoint: Checkpoint) {
+    pub fn load(&self, checkpoint: Checkpoint) {
         self.checkpoints
             .insert(checkpoint.fingerprint, checkpoint.position);
         self.modified_times
             .insert(checkpoint.fingerprint, checkpoint.modified);
+        if let Some(content_checksum) = checkpoint.content_checksum {
+            self.content_checksums
+                .insert(checkpoint.fingerprint, content_checksum);
+        }
     }
 
     fn set_state(&self, state: State, ignore_before: Option<DateTime<Utc>>) {
@@ -148,6 +233,7 @@ impl CheckpointsView {
                             .get(fingerprint)
                             .map(|r| *r.value())
                             .unwrap_or_else(Utc::now),
+                        content_checksum: self.get_content_checksum(*fingerprint),
                     }
                 })
                 .collect(),
@@ -226,6 +312,24 @@ impl Checkpointer {
         Ok(self.checkpoints.checkpoints.len())
     }
 
+    /// Serializes the checkpoints in the same format they are persisted in.
+    pub fn export(&self) -> Result<Vec<u8>, io::Error> {
+        serde_json::to_vec_pretty(&self.checkpoints.get_state()).map_err(io::Error::from)
+    }
+
+    /// Loads checkpoints that were serialized with [`Checkpointer::export`], replacing the
+    /// checkpoints for the same files, and returns how many were loaded.
+    pub fn import(&self, data: &[u8]) -> Result<usize, io::Error> {
+        let state: State = serde_json::from_slice(data)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        let State::V1 { checkpoints } = state;
+        let count = checkpoints.len();
+        for checkpoint in checkpoints {
+            self.checkpoints.load(checkpoint);
+        }
+        Ok(count)
+    }
+
     /// Read persisted checkpoints from disk, preferring the new JSON file
     /// format but falling back to the legacy system when those files are found
     /// instead.
@@ -292,8 +396,8 @@ mod test {
     use tokio::fs;
 
     use super::{
-        CHECKPOINT_FILE_NAME, Checkpoint, Checkpointer, FileFingerprint, FilePosition,
-        TMP_FILE_NAME,
+        CHECKPOINT_FILE_NAME, Checkpoint, Checkpointer, ContentChecksum, FileFingerprint,
+        FilePosition, TMP_FILE_NAME,
     };
 
     #[test]
@@ -334,6 +438,7 @@ mod test {
                     fingerprint: *fingerprint,
                     position,
                     modified: *modified,
+                    content_checksum: None,
                 });
                 assert_eq!(chkptr.get_checkpoint(*fingerprint), Some(position));
                 chkptr.write_checkpoints().await.unwrap();
@@ -375,6 +480,81 @@ mod test {
         }
     }
 
+    #[tokio::test]
+    async fn test_checkpointer_restart_with_content_checksum() {
+        let fingerprint = FileFingerprint::ContentHash(78910, 1, 2);
+        let copy_fingerprint = FileFingerprint::ContentHash(78910, 1, 3);
+        let position: FilePosition = 1234;
+        let content_checksum = ContentChecksum::new(position, b"the last line\n");
+        let data_dir = tempdir().unwrap();
+        {
+            let mut chkptr = Checkpointer::new(data_dir.path());
+            chkptr.update_checkpoint(fingerprint, position);
+            chkptr
+                .checkpoints
+                .update_content_checksum(fingerprint, content_checksum);
+            chkptr.write_checkpoints().await.unwrap();
+        }
+        {
+            let mut chkptr = Checkpointer::new(data_dir.path());
+            chkptr.read_checkpoints(None).await;
+            assert_eq!(chkptr.get_checkpoint(fingerprint), Some(position));
+            assert_eq!(
+                chkptr.checkpoints.get_content_checksum(fingerprint),
+                Some(content_checksum)
+            );
+            assert_eq!(
+                chkptr.checkpoints.with_same_header(copy_fingerprint),
+                vec![(fingerprint, position, content_checksum)]
+            );
+            assert!(chkptr.checkpoints.with_same_header(fingerprint).is_empty());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_checkpointer_export_import() {
+        let position: FilePosition = 1234;
+        let fingerprints = [
+            FileFingerprint::DevInode(1, 2),
+            FileFingerprint::FirstLinesChecksum(78910),
+        ];
+
+        let data_dir = tempdir().unwrap();
+        let mut chkptr = Checkpointer::new(data_dir.path());
+        for fingerprint in fingerprints {
+            chkptr.update_checkpoint(fingerprint, position);
+        }
+        let exported = chkptr.export().unwrap();
+
+        let other_data_dir = tempdir().unwrap();
+        let mut other = Checkpointer::new(other_data_dir.path());
+        other.update_checkpoint(fingerprints[0], 1);
+        other.update_checkpoint(FileFingerprint::DevInode(3, 4), 1);
+        assert_eq!(other.import(&exported).unwrap(), 2);
+        assert_eq!(other.get_checkpoint(fingerprints[0]), Some(position));
+        assert_eq!(other.get_checkpoint(fingerprints[1]), Some(position));
+        assert_eq!(
+            other.get_checkpoint(FileFingerprint::DevInode(3, 4)),
+            Some(1)
+        );
+
+        assert_eq!(other.checkpoints.remove(fingerprints[0]), Some(position));
+        assert_eq!(
+            other
+                .checkpoints
+                .list()
+                .into_iter()
+                .map(|checkpoint| checkpoint.fingerprint)
+                .collect::<Vec<_>>(),
+            vec![
+                FileFingerprint::FirstLinesChecksum(78910),
+                FileFingerprint::DevInode(3, 4),
+            ]
+        );
+
+        assert!(other.import(b"not json").is_err());
+    }
+
     #[tokio::test]
     async fn test_checkpointer_file_upgrades() {
         let fingerprint = FileFingerprint::DevInode(1, 2);
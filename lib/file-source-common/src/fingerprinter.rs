@@ -1,21 +1,26 @@
 use std::{
     collections::HashMap,
+    fmt,
     io::{ErrorKind, Result, SeekFrom},
     path::{Path, PathBuf},
+    str::FromStr,
     time,
 };
 
-use async_compression::tokio::bufread::GzipDecoder;
+use async_compression::tokio::bufread::{BzDecoder, GzipDecoder, XzDecoder, ZstdDecoder};
 use crc::Crc;
 use serde::{Deserialize, Serialize};
 use tokio::{
     fs::{self, File},
     io::{AsyncBufRead, AsyncBufReadExt, AsyncRead, AsyncReadExt, AsyncSeekExt, BufReader},
 };
-use vector_common::constants::GZIP_MAGIC;
+use vector_common::constants::{
+    BZIP2_BLOCK_MAGIC, BZIP2_EOS_MAGIC, BZIP2_MAGIC, GZIP_MAGIC, XZ_MAGIC, ZSTD_MAGIC,
+};
 
 use crate::{
-    AsyncFileInfo, internal_events::FileSourceInternalEvents, metadata_ext::PortableFileExt,
+    AsyncFileInfo, FilePosition, internal_events::FileSourceInternalEvents,
+    metadata_ext::PortableFileExt,
 };
 
 const FINGERPRINT_CRC: Crc<u64> = Crc::<u64>::new(&crc::CRC_64_ECMA_182);
@@ -50,6 +55,12 @@ pub enum FingerprintStrategy {
         lines: usize,
     },
     DevInode,
+    /// Combines the checksum of the first lines with the device and inode, and verifies the content
+    /// of files against the checksum of the data last read from them.
+    ContentHash {
+        ignored_header_bytes: usize,
+        lines: usize,
+    },
 }
 
 #[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Serialize, Deserialize, Ord, PartialOrd)]
@@ -58,22 +69,212 @@ pub enum FileFingerprint {
     #[serde(alias = "first_line_checksum")]
     FirstLinesChecksum(u64),
     DevInode(u64, u64),
+    ContentHash(u64, u64, u64),
+}
+
+impl FileFingerprint {
+    /// Gets the fingerprints that the existing strategies would have given the same file, whose
+    /// checkpoints can be migrated to this fingerprint.
+    pub fn legacy_fingerprints(&self) -> Vec<FileFingerprint> {
+        match *self {
+            FileFingerprint::ContentHash(checksum, dev, ino) => vec![
+                FileFingerprint::DevInode(dev, ino),
+                FileFingerprint::FirstLinesChecksum(checksum),
+            ],
+            FileFingerprint::FirstLinesChecksum(_) | FileFingerprint::DevInode(_, _) => Vec::new(),
+        }
+    }
+
+    /// Whether both fingerprints have the same checksum of the first lines, but may belong to
+    /// different files, such as a file and its copy.
+    pub fn same_header(&self, other: &FileFingerprint) -> bool {
+        matches!(
+            (self, other),
+            (FileFingerprint::ContentHash(a, _, _), FileFingerprint::ContentHash(b, _, _)) if a == b
+        )
+    }
 }
 
-#[derive(Debug, Copy, Clone)]
-enum SupportedCompressionAlgorithms {
+impl fmt::Display for FileFingerprint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FileFingerprint::FirstLinesChecksum(checksum) => {
+                write!(f, "first_lines_checksum:{checksum}")
+            }
+            FileFingerprint::DevInode(dev, ino) => write!(f, "dev_inode:{dev}:{ino}"),
+            FileFingerprint::ContentHash(checksum, dev, ino) => {
+                write!(f, "content_hash:{checksum}:{dev}:{ino}")
+            }
+        }
+    }
+}
+
+impl FromStr for FileFingerprint {
+    type Err = String;
+
+    /// Parses a fingerprint in the format it is displayed in, such as `dev_inode:2049:1234`.
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let (kind, values) = s
+            .split_once(':')
+            .ok_or_else(|| format!("missing fingerprint type in {s:?}"))?;
+        let values = values
+            .split(':')
+            .map(|value| {
+                value
+                    .parse::<u64>()
+                    .map_err(|error| format!("invalid fingerprint value {value:?}: {error}"))
+            })
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+
+        match (kind, values.as_slice()) {
+            ("first_lines_checksum" | "first_line_checksum", &[checksum]) => {
+                Ok(FileFingerprint::FirstLinesChecksum(checksum))
+            }
+            ("dev_inode", &[dev, ino]) => Ok(FileFingerprint::DevInode(dev, ino)),
+            ("content_hash", &[checksum, dev, ino]) => {
+                Ok(FileFingerprint::ContentHash(checksum, dev, ino))
+            }
+            _ => Err(format!("invalid fingerprint {s:?}")),
+        }
+    }
+}
+
+/// A checksum of the data just before a position in a file.
+///
+/// This is used to tell whether a file still holds the data that was read from it up to that
+/// position, or if it has since been truncated or replaced.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Serialize, Deserialize, Ord, PartialOrd)]
+pub struct ContentChecksum {
+    /// The position the checksummed data ends at.
+    pub position: FilePosition,
+    /// The length of the checksummed data.
+    pub len: u64,
+    pub checksum: u64,
+}
+
+impl ContentChecksum {
+    /// The maximum number of bytes to checksum.
+    pub const MAX_LEN: usize = 256;
+
+    /// Creates a checksum over the end of `data`, which ends at `position` in the file.
+    pub fn new(position: FilePosition, data: &[u8]) -> Self {
+        let data = &data[data.len().saturating_sub(Self::MAX_LEN)..];
+        Self {
+            position,
+            len: data.len() as u64,
+            checksum: FINGERPRINT_CRC.checksum(data),
+        }
+    }
+
+    /// Checks whether the file at `path` still holds the checksummed data.
+    ///
+    /// Compressed files are assumed to be unchanged, as positions in them refer to the
+    /// decompressed data, which can't be read from without decompressing the whole file.
+    pub async fn matches(&self, path: &Path) -> Result<bool> {
+        let mut fp = File::open(path).await?;
+        if UncompressedReaderImpl::check(&mut fp).await?.is_some() {
+            return Ok(true);
+        }
+
+        let Some(start) = self.position.checked_sub(self.len) else {
+            return Ok(false);
+        };
+        if fp.metadata().await?.len() < self.position {
+            return Ok(false);
+        }
+
+        let mut data = vec![0; self.len as usize];
+        fp.seek(SeekFrom::Start(start)).await?;
+        match fp.read_exact(&mut data).await {
+            Ok(_) => Ok(FINGERPRINT_CRC.checksum(&data) == self.checksum),
+            // Truncated since we checked its size.
+            Err(error) if error.kind() == ErrorKind::UnexpectedEof => Ok(false),
+            Err(error) => Err(error),
+        }
+    }
+}
+
+/// Compression formats that files can be transparently decompressed from, detected by their magic
+/// header bytes.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum SupportedCompressionAlgorithms {
     Gzip,
+    Bzip2,
+    Zstd,
+    Xz,
 }
 
 impl SupportedCompressionAlgorithms {
     fn values() -> Vec<SupportedCompressionAlgorithms> {
-        // Enumerate these from smallest magic_header_bytes to largest
-        vec![SupportedCompressionAlgorithms::Gzip]
+        // Enumerate these from smallest magic_header_len to largest
+        vec![
+            SupportedCompressionAlgorithms::Gzip,
+            SupportedCompressionAlgorithms::Zstd,
+            SupportedCompressionAlgorithms::Xz,
+            SupportedCompressionAlgorithms::Bzip2,
+        ]
+    }
+
+    /// Returns the number of leading bytes needed to recognize the algorithm.
+    fn magic_header_len(&self) -> usize {
+        match self {
+            SupportedCompressionAlgorithms::Gzip => GZIP_MAGIC.len(),
+            // "BZh", the block size digit, then the magic of the first block
+            SupportedCompressionAlgorithms::Bzip2 => {
+                BZIP2_MAGIC.len() + 1 + BZIP2_BLOCK_MAGIC.len()
+            }
+            SupportedCompressionAlgorithms::Zstd => ZSTD_MAGIC.len(),
+            SupportedCompressionAlgorithms::Xz => XZ_MAGIC.len(),
+        }
+    }
+
+    /// Returns whether `header_bytes` starts with the magic header of the algorithm.
+    ///
+    /// As the "BZh" magic of bzip2 can start plain text, the block size digit and the magic of the
+    /// first block (or of the end of the stream, for empty streams) that follow it are checked too.
+    fn matches(&self, header_bytes: &[u8]) -> bool {
+        match self {
+            SupportedCompressionAlgorithms::Gzip => header_bytes.starts_with(GZIP_MAGIC),
+            SupportedCompressionAlgorithms::Bzip2 => {
+                let Some(rest) = header_bytes.strip_prefix(BZIP2_MAGIC) else {
+                    return false;
+                };
+                match rest.split_first() {
+                    Some((block_size, rest)) if (b'1'..=b'9').contains(block_size) => {
+                        rest.starts_with(BZIP2_BLOCK_MAGIC) || rest.starts_with(BZIP2_EOS_MAGIC)
+                    }
+                    _ => false,
+                }
+            }
+            SupportedCompressionAlgorithms::Zstd => header_bytes.starts_with(ZSTD_MAGIC),
+            SupportedCompressionAlgorithms::Xz => header_bytes.starts_with(XZ_MAGIC),
+        }
+    }
+
+    /// Detects the compression algorithm from the first bytes of a file, if any.
+    pub fn detect(header_bytes: &[u8]) -> Option<SupportedCompressionAlgorithms> {
+        Self::values()
+            .into_iter()
+            .find(|algorithm| algorithm.matches(header_bytes))
     }
 
-    fn magic_header_bytes(&self) -> &'static [u8] {
+    /// Wraps a reader of compressed data in a reader of the decompressed data.
+    pub fn decoder<'a, R>(self, reader: R) -> Box<dyn AsyncBufRead + Unpin + Send + 'a>
+    where
+        R: AsyncBufRead + Unpin + Send + 'a,
+    {
+        // To support new compression algorithms, add them below
         match self {
-            SupportedCompressionAlgorithms::Gzip => GZIP_MAGIC,
+            SupportedCompressionAlgorithms::Gzip => {
+                Box::new(BufReader::new(GzipDecoder::new(reader)))
+            }
+            SupportedCompressionAlgorithms::Bzip2 => {
+                Box::new(BufReader::new(BzDecoder::new(reader)))
+            }
+            SupportedCompressionAlgorithms::Zstd => {
+                Box::new(BufReader::new(ZstdDecoder::new(reader)))
+            }
+            SupportedCompressionAlgorithms::Xz => Box::new(BufReader::new(XzDecoder::new(reader))),
         }
     }
 }
@@ -100,22 +301,28 @@ impl UncompressedReader for UncompressedReaderImpl {
     /// - `Err(std::io::Error)` if an I/O error occurs.
     async fn check(fp: &mut File) -> Result<Option<SupportedCompressionAlgorithms>> {
         let mut algorithm: Option<SupportedCompressionAlgorithms> = None;
-        for compression_algorithm in SupportedCompressionAlgorithms::values() {
+        for (i, compression_algorithm) in SupportedCompressionAlgorithms::values()
+            .into_iter()
+            .enumerate()
+        {
             // magic headers for algorithms can be of different lengths, and using a buffer too long could exceed the length of the file
             // so instantiate and check the various sizes in monotonically increasing order
-            let magic_header_bytes = compression_algorithm.magic_header_bytes();
-
-            let mut magic = vec![0u8; magic_header_bytes.len()];
+            let mut magic = vec![0u8; compression_algorithm.magic_header_len()];
 
             fp.seek(SeekFrom::Start(0)).await?;
             let result = fp.read_exact(&mut magic).await;
 
             if let Err(err) = result {
                 fp.seek(SeekFrom::Start(0)).await?;
+                // A file that is too short for this header was already checked against the
+                // shorter ones, so it's only too small if it can't fit the shortest header.
+                if err.kind() == ErrorKind::UnexpectedEof && i > 0 {
+                    break;
+                }
                 return Err(err);
             }
 
-            if magic == magic_header_bytes {
+            if compression_algorithm.matches(&magic) {
                 algorithm = Some(compression_algorithm);
                 break;
             }
@@ -125,11 +332,8 @@ impl UncompressedReader for UncompressedReaderImpl {
     }
 
     async fn reader<'a>(fp: &'a mut File) -> Result<Box<dyn AsyncBufRead + Unpin + Send + 'a>> {
-        // To support new compression algorithms, add them below
         match Self::check(fp).await? {
-            Some(SupportedCompressionAlgorithms::Gzip) => Ok(Box::new(BufReader::new(
-                GzipDecoder::new(BufReader::new(fp)),
-            ))),
+            Some(algorithm) => Ok(algorithm.decoder(BufReader::new(fp))),
             // No compression, or read the raw bytes
             None => Ok(Box::new(BufReader::new(fp))),
         }
@@ -169,7 +373,7 @@ impl Fingerprinter {
     }
 
     /// Returns the `FileFingerprint` of a file, depending on `Fingerprinter::strategy`
-    pub(crate) async fn fingerprint(&mut self, path: &Path) -> Result<FileFingerprint> {
+    pub async fn fingerprint(&mut self, path: &Path) -> Result<FileFingerprint> {
         use FileFingerprint::*;
 
         match self.strategy {
@@ -184,18 +388,47 @@ impl Fingerprinter {
                 ignored_header_bytes,
                 lines,
             } => {
-                let buffer = self.buffer.resize_slice_mut(self.max_line_length);
                 let mut fp = File::open(path).await?;
-                let mut reader = UncompressedReaderImpl::reader(&mut fp).await?;
-
-                skip_first_n_bytes(&mut reader, ignored_header_bytes).await?;
-                let bytes_read = fingerprinter_read_until(reader, b'\n', lines, buffer).await?;
-                let fingerprint = FINGERPRINT_CRC.checksum(&buffer[..bytes_read]);
+                let fingerprint = self
+                    .first_lines_checksum(&mut fp, ignored_header_bytes, lines)
+                    .await?;
                 Ok(FirstLinesChecksum(fingerprint))
             }
+            FingerprintStrategy::ContentHash {
+                ignored_header_bytes,
+                lines,
+            } => {
+                let mut fp = File::open(path).await?;
+                let file_info = fp.file_info().await?;
+                let (dev, ino) = (file_info.portable_dev(), file_info.portable_ino());
+                let fingerprint = self
+                    .first_lines_checksum(&mut fp, ignored_header_bytes, lines)
+                    .await?;
+                Ok(ContentHash(fingerprint, dev, ino))
+            }
         }
     }
 
+    /// Whether files' content should be verified against the checksum of the data last read from
+    /// them, to detect truncation and replacement.
+    pub fn verifies_content(&self) -> bool {
+        matches!(self.strategy, FingerprintStrategy::ContentHash { .. })
+    }
+
+    async fn first_lines_checksum(
+        &mut self,
+        fp: &mut File,
+        ignored_header_bytes: usize,
+        lines: usize,
+    ) -> Result<u64> {
+        let buffer = self.buffer.resize_slice_mut(self.max_line_length);
+        let mut reader = UncompressedReaderImpl::reader(fp).await?;
+
+        skip_first_n_bytes(&mut reader, ignored_header_bytes).await?;
+        let bytes_read = fingerprinter_read_until(reader, b'\n', lines, buffer).await?;
+        Ok(FINGERPRINT_CRC.checksum(&buffer[..bytes_read]))
+    }
+
     pub async fn fingerprint_or_emit(
         &mut self,
         path: &Path,
@@ -279,11 +512,14 @@ async fn fingerprinter_read_until(
 mod test {
     use std::{collections::HashMap, fs, io::Error, path::Path, time::Duration};
 
-    use async_compression::tokio::bufread::GzipEncoder;
+    use async_compression::tokio::bufread::{BzEncoder, GzipEncoder, XzEncoder, ZstdEncoder};
     use bytes::BytesMut;
     use tempfile::{TempDir, tempdir};
 
-    use super::{FileSourceInternalEvents, FingerprintStrategy, Fingerprinter};
+    use super::{
+        FileFingerprint, FileSourceInternalEvents, FingerprintStrategy, Fingerprinter,
+        SupportedCompressionAlgorithms,
+    };
 
     use tokio::io::AsyncReadExt;
 
@@ -294,6 +530,25 @@ mod test {
         encoder.read_to_end(&mut out).await.expect("Failed to read");
         out
     }
+
+    async fn compress(algorithm: SupportedCompressionAlgorithms, data: &[u8]) -> Vec<u8> {
+        let mut out = Vec::new();
+        let result = match algorithm {
+            SupportedCompressionAlgorithms::Gzip => {
+                GzipEncoder::new(data).read_to_end(&mut out).await
+            }
+            SupportedCompressionAlgorithms::Bzip2 => {
+                BzEncoder::new(data).read_to_end(&mut out).await
+            }
+            SupportedCompressionAlgorithms::Zstd => {
+                ZstdEncoder::new(data).read_to_end(&mut out).await
+            }
+            SupportedCompressionAlgorithms::Xz => XzEncoder::new(data).read_to_end(&mut out).await,
+        };
+        result.expect("Failed to read");
+        out
+    }
+
     fn read_byte_content(target_dir: &TempDir, file: &str) -> Vec<u8> {
         use std::{fs::File, io::Read};
 
@@ -593,6 +848,97 @@ mod test {
         );
     }
 
+    #[tokio::test]
+    async fn test_checksum_fingerprint_compressed() {
+        let mut fingerprinter = Fingerprinter::new(
+            FingerprintStrategy::FirstLinesChecksum {
+                ignored_header_bytes: 0,
+                lines: 1,
+            },
+            1024,
+            false,
+        );
+
+        let target_dir = tempdir().unwrap();
+        let plain_path = target_dir.path().join("plain.log");
+        fs::write(&plain_path, b"hello world\nthe next line\n").unwrap();
+        let expected = fingerprinter.fingerprint(&plain_path).await.unwrap();
+
+        for algorithm in SupportedCompressionAlgorithms::values() {
+            let data = compress(algorithm, b"hello world\nthe next line\n").await;
+            assert_eq!(
+                Some(algorithm),
+                SupportedCompressionAlgorithms::detect(&data)
+            );
+
+            let path = target_dir.path().join(format!("{algorithm:?}.log"));
+            fs::write(&path, data).unwrap();
+            assert_eq!(expected, fingerprinter.fingerprint(&path).await.unwrap());
+        }
+
+        // Files shorter than the longer magic headers can still be fingerprinted.
+        let short_path = target_dir.path().join("short.log");
+        fs::write(&short_path, b"hi\n").unwrap();
+        assert!(fingerprinter.fingerprint(&short_path).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_content_hash_fingerprint() {
+        let strategy_options = (0, 1);
+        let mut fingerprinter = Fingerprinter::new(
+            FingerprintStrategy::ContentHash {
+                ignored_header_bytes: strategy_options.0,
+                lines: strategy_options.1,
+            },
+            1024,
+            false,
+        );
+        let mut checksum_fingerprinter = Fingerprinter::new(
+            FingerprintStrategy::FirstLinesChecksum {
+                ignored_header_bytes: strategy_options.0,
+                lines: strategy_options.1,
+            },
+            1024,
+            false,
+        );
+
+        let target_dir = tempdir().unwrap();
+        let path = target_dir.path().join("file.log");
+        let copy_path = target_dir.path().join("file.log.1");
+        fs::write(&path, b"header\nfirst line\n").unwrap();
+        fs::write(&copy_path, b"header\nsecond line\n").unwrap();
+
+        let fingerprint = fingerprinter.fingerprint(&path).await.unwrap();
+        let copy_fingerprint = fingerprinter.fingerprint(&copy_path).await.unwrap();
+        assert_ne!(fingerprint, copy_fingerprint);
+        assert!(fingerprint.same_header(&copy_fingerprint));
+        assert!(
+            fingerprint
+                .legacy_fingerprints()
+                .contains(&checksum_fingerprinter.fingerprint(&path).await.unwrap())
+        );
+    }
+
+    #[test]
+    fn test_fingerprint_display_round_trip() {
+        for fingerprint in [
+            FileFingerprint::FirstLinesChecksum(78910),
+            FileFingerprint::DevInode(2049, 1234),
+            FileFingerprint::ContentHash(78910, 2049, 1234),
+        ] {
+            assert_eq!(
+                Ok(fingerprint),
+                fingerprint.to_string().parse::<FileFingerprint>()
+            );
+        }
+        assert_eq!(
+            Ok(FileFingerprint::FirstLinesChecksum(1)),
+            "first_line_checksum:1".parse::<FileFingerprint>()
+        );
+        assert!("dev_inode:1".parse::<FileFingerprint>().is_err());
+        assert!("inode:1:2".parse::<FileFingerprint>().is_err());
+    }
+
     #[tokio::test]
     async fn test_inode_fingerprint() {
         let mut fingerprinter = Fingerprinter::new(FingerprintStrategy::DevInode, 42, false);
@@ -649,9 +995,32 @@ mod test {
         let algos = super::SupportedCompressionAlgorithms::values();
         let mut smallest_byte_length = 0;
         for algo in algos {
-            let magic_header_bytes = algo.magic_header_bytes();
-            assert!(smallest_byte_length <= magic_header_bytes.len());
-            smallest_byte_length = magic_header_bytes.len();
+            let magic_header_len = algo.magic_header_len();
+            assert!(smallest_byte_length <= magic_header_len);
+            smallest_byte_length = magic_header_len;
+        }
+    }
+
+    #[tokio::test]
+    async fn bzip2_requires_block_header() {
+        let compressed = compress(SupportedCompressionAlgorithms::Bzip2, b"hello\n").await;
+        assert_eq!(
+            SupportedCompressionAlgorithms::detect(&compressed),
+            Some(SupportedCompressionAlgorithms::Bzip2)
+        );
+        let empty = compress(SupportedCompressionAlgorithms::Bzip2, b"").await;
+        assert_eq!(
+            SupportedCompressionAlgorithms::detect(&empty),
+            Some(SupportedCompressionAlgorithms::Bzip2)
+        );
+
+        // Plain text that starts like a bzip2 stream
+        for text in [
+            &b"BZh is not compressed\n"[..],
+            b"BZh9 is not compressed\n",
+            b"BZh",
+        ] {
+            assert_eq!(SupportedCompressionAlgorithms::detect(text), None);
         }
     }
     #[derive(Clone)]
@@ -694,6 +1063,12 @@ mod test {
             panic!()
         }
 
+        fn emit_inotify_watches(&self, _: usize) {}
+
+        fn emit_inotify_fallback(&self, _: Error) {
+            panic!()
+        }
+
         fn emit_file_line_too_long(&self, _: &BytesMut, _: usize, _: usize) {
             panic!()
         }
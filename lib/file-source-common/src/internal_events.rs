@@ -29,6 +29,10 @@ pub trait FileSourceInternalEvents: Send + Sync + Clone + 'static {
 
     fn emit_path_globbing_failed(&self, path: &Path, error: &Error);
 
+    fn emit_inotify_watches(&self, count: usize);
+
+    fn emit_inotify_fallback(&self, error: Error);
+
     fn emit_file_line_too_long(
         &self,
         truncated_bytes: &BytesMut,
@@ -10,8 +10,11 @@ mod metadata_ext;
 use vector_config::configurable_component;
 
 pub use self::{
-    checkpointer::{CHECKPOINT_FILE_NAME, Checkpointer, CheckpointsView},
-    fingerprinter::{FileFingerprint, FingerprintStrategy, Fingerprinter},
+    checkpointer::{CHECKPOINT_FILE_NAME, Checkpointer, CheckpointsLock, CheckpointsView},
+    fingerprinter::{
+        ContentChecksum, FileFingerprint, FingerprintStrategy, Fingerprinter,
+        SupportedCompressionAlgorithms,
+    },
     internal_events::FileSourceInternalEvents,
     metadata_ext::{AsyncFileInfo, PortableFileExt},
 };
@@ -38,6 +41,23 @@ pub enum ReadFromConfig {
     End,
 }
 
+/// Method used to discover files, and to detect when files have new data to read.
+#[configurable_component]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DiscoveryMethod {
+    /// Periodically search for files, and poll watched files for new data.
+    #[default]
+    Poll,
+
+    /// Use inotify to be notified of new files, and of watched files with new data.
+    ///
+    /// Files are only searched for again when a watched directory changes, and files are only read
+    /// when they have been written to. This is only supported on Linux. Polling is used instead on
+    /// other platforms, or when the inotify watch limit (`fs.inotify.max_user_watches`) is reached.
+    Inotify,
+}
+
 impl From<ReadFromConfig> for ReadFrom {
     fn from(rfc: ReadFromConfig) -> Self {
         match rfc {
@@ -0,0 +1,186 @@
+//! Event-driven file discovery and read readiness, backed by inotify.
+
+use std::{
+    collections::HashSet,
+    io, mem,
+    path::PathBuf,
+    sync::{Arc, Mutex},
+};
+
+use file_source_common::FileSourceInternalEvents;
+use notify::{
+    ErrorKind, Event, EventKind, RecursiveMode, Watcher,
+    event::{ModifyKind, RenameMode},
+};
+use tokio::sync::Notify;
+use tracing::{debug, trace};
+
+/// Changes reported since they were last taken.
+#[derive(Debug, Default)]
+pub(crate) struct PendingChanges {
+    /// Whether the set of files may have changed, and files should be searched for again.
+    pub rescan: bool,
+
+    /// Whether events may have been lost, and all watched files should be read.
+    pub read_all: bool,
+
+    /// Files that have been written to.
+    pub modified: HashSet<PathBuf>,
+}
+
+impl PendingChanges {
+    fn merge_event(&mut self, event: Event) {
+        if event.need_rescan() {
+            self.rescan = true;
+            self.read_all = true;
+            return;
+        }
+
+        match event.kind {
+            EventKind::Create(_)
+            | EventKind::Remove(_)
+            | EventKind::Modify(ModifyKind::Name(_) | ModifyKind::Any) => {
+                self.rescan = true;
+                // A file that was moved into place or created may already have data to read.
+                if !matches!(
+                    event.kind,
+                    EventKind::Remove(_) | EventKind::Modify(ModifyKind::Name(RenameMode::From))
+                ) {
+                    self.modified.extend(event.paths);
+                }
+            }
+            EventKind::Modify(ModifyKind::Data(_)) => self.modified.extend(event.paths),
+            _ => {}
+        }
+    }
+}
+
+/// Watches directories with inotify, and collects the changes made to them.
+///
+/// Directories, rather than files, are watched: inotify reports writes to the files in a watched
+/// directory, so watching the directories containing the watched files is enough to know which of
+/// them have new data, while using a lot fewer watches.
+pub(crate) struct FileEvents {
+    watcher: Box<dyn Watcher + Send>,
+    watched_dirs: HashSet<PathBuf>,
+    pending: Arc<Mutex<PendingChanges>>,
+    notify: Arc<Notify>,
+}
+
+impl FileEvents {
+    /// Creates a new [`FileEvents`].
+    ///
+    /// If inotify is unavailable, the error is emitted and `None` is returned, in which case the
+    /// caller should fall back to polling.
+    pub(crate) fn new(emitter: &impl FileSourceInternalEvents) -> Option<Self> {
+        let pending = Arc::new(Mutex::new(PendingChanges::default()));
+        let notify = Arc::new(Notify::new());
+
+        let handler = {
+            let pending = Arc::clone(&pending);
+            let notify = Arc::clone(&notify);
+            move |result: notify::Result<Event>| {
+                let mut pending = pending.lock().expect("pending changes lock poisoned");
+                match result {
+                    Ok(event) => {
+                        trace!(message = "Received file event.", ?event);
+                        pending.merge_event(event);
+                    }
+                    Err(error) => {
+                        debug!(message = "Error while watching files.", %error);
+                        pending.rescan = true;
+                        pending.read_all = true;
+                    }
+                }
+                notify.notify_one();
+            }
+        };
+
+        match create_watcher(handler) {
+            Ok(watcher) => Some(Self {
+                watcher,
+                watched_dirs: HashSet::new(),
+                pending,
+                notify,
+            }),
+            Err(error) => {
+                emitter.emit_inotify_fallback(io::Error::other(error));
+                None
+            }
+        }
+    }
+
+    /// Takes the changes reported since the last call.
+    pub(crate) fn take_pending(&self) -> PendingChanges {
+        mem::take(&mut *self.pending.lock().expect("pending changes lock poisoned"))
+    }
+
+    /// Gets a handle that is notified whenever a change is reported.
+    pub(crate) fn notified(&self) -> Arc<Notify> {
+        Arc::clone(&self.notify)
+    }
+
+    /// Updates the watched directories.
+    ///
+    /// Any newly watched directory triggers a rescan, as files may have been created in it before it
+    /// was watched.
+    ///
+    /// If the inotify watch limit is reached, the error is emitted and `false` is returned, in which
+    /// case the caller should fall back to polling.
+    pub(crate) fn sync_dirs(
+        &mut self,
+        dirs: impl IntoIterator<Item = PathBuf>,
+        emitter: &impl FileSourceInternalEvents,
+    ) -> bool {
+        let dirs = dirs.into_iter().collect::<HashSet<_>>();
+
+        for dir in self.watched_dirs.difference(&dirs) {
+            // The watch is already gone if the directory was removed, so errors are expected here.
+            _ = self.watcher.unwatch(dir);
+        }
+        self.watched_dirs.retain(|dir| dirs.contains(dir));
+
+        let mut added = false;
+        for dir in dirs {
+            if self.watched_dirs.contains(&dir) {
+                continue;
+            }
+            match self.watcher.watch(&dir, RecursiveMode::NonRecursive) {
+                Ok(()) => {
+                    self.watched_dirs.insert(dir);
+                    added = true;
+                }
+                Err(error) if matches!(error.kind, ErrorKind::MaxFilesWatch) => {
+                    emitter.emit_inotify_fallback(io::Error::other(error));
+                    emitter.emit_inotify_watches(0);
+                    return false;
+                }
+                Err(error) => {
+                    // Most likely, the directory was removed since it was found, which the next
+                    // rescan will pick up on.
+                    debug!(message = "Failed to watch directory.", dir = ?dir, %error);
+                }
+            }
+        }
+
+        if added {
+            self.pending
+                .lock()
+                .expect("pending changes lock poisoned")
+                .rescan = true;
+        }
+        emitter.emit_inotify_watches(self.watched_dirs.len());
+        true
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn create_watcher(handler: impl notify::EventHandler) -> notify::Result<Box<dyn Watcher + Send>> {
+    let watcher = notify::INotifyWatcher::new(handler, notify::Config::default())?;
+    Ok(Box::new(watcher))
+}
+
+#[cfg(not(target_os = "linux"))]
+fn create_watcher(_handler: impl notify::EventHandler) -> notify::Result<Box<dyn Watcher + Send>> {
+    Err(notify::Error::generic("inotify is only supported on Linux"))
+}
@@ -1,7 +1,7 @@
 use std::{
     cmp,
-    collections::{BTreeMap, HashMap},
-    path::PathBuf,
+    collections::{BTreeMap, HashMap, HashSet},
+    path::{Path, PathBuf},
     sync::Arc,
     time::{self, Duration},
 };
@@ -9,7 +9,7 @@ use std::{
 use bytes::Bytes;
 use chrono::{DateTime, Utc};
 use file_source_common::{
-    FileFingerprint, FileSourceInternalEvents, Fingerprinter, ReadFrom,
+    DiscoveryMethod, FileFingerprint, FileSourceInternalEvents, Fingerprinter, ReadFrom,
     checkpointer::{Checkpointer, CheckpointsView},
 };
 use futures::{
@@ -27,6 +27,7 @@ use tokio::{
 use tracing::{debug, error, info, trace};
 
 use crate::{
+    file_events::FileEvents,
     file_watcher::{FileWatcher, RawLineResult},
     paths_provider::PathsProvider,
 };
@@ -34,8 +35,10 @@ use crate::{
 /// `FileServer` is a Source which cooperatively schedules reads over files,
 /// converting the lines of said files into `LogLine` structures. As
 /// `FileServer` is intended to be useful across multiple operating systems with
-/// POSIX filesystem semantics `FileServer` must poll for changes. That is, no
-/// event notification is used by `FileServer`.
+/// POSIX filesystem semantics `FileServer` polls for changes by default. On
+/// Linux, inotify can be used instead through [`DiscoveryMethod::Inotify`], in
+/// which case `FileServer` falls back to polling if inotify is unavailable or
+/// runs out of watches.
 ///
 /// `FileServer` is configured on a path to watch. The files do _not_ need to
 /// exist at startup. `FileServer` will discover new files which match
@@ -58,6 +61,7 @@ where
     pub remove_after: Option<Duration>,
     pub emitter: E,
     pub rotate_wait: Duration,
+    pub discovery: DiscoveryMethod,
 }
 
 /// `FileServer` as Source
@@ -145,6 +149,28 @@ where
         }
         self.emitter.emit_files_open(fp_map.len());
 
+        // With event-driven discovery, we only read files that have been written to since they were
+        // last read, which are tracked here. Newly watched files are always read once, as they may
+        // have been written to before being watched.
+        let mut file_events = match self.discovery {
+            DiscoveryMethod::Poll => None,
+            DiscoveryMethod::Inotify => FileEvents::new(&self.emitter),
+        };
+        let mut ready_paths: Option<HashSet<PathBuf>> = None;
+        let provider_watches_dirs = self.paths_provider.watch_dirs().is_some();
+        if let Some(events) = &mut file_events {
+            if events.sync_dirs(self.dirs_to_watch(&fp_map), &self.emitter) {
+                ready_paths = Some(
+                    fp_map
+                        .values()
+                        .map(|watcher| watcher.path.clone())
+                        .collect(),
+                );
+            } else {
+                file_events = None;
+            }
+        }
+
         let mut stats = TimingStats::default();
 
         // Spawn the checkpoint writer task
@@ -166,9 +192,31 @@ where
         // or write new checkpoints, on every iteration.
         let mut next_glob_time = time::Instant::now();
         loop {
-            // Glob find files to follow, but not too often.
+            // Glob find files to follow, but not too often. With event-driven discovery, only do so
+            // once a watched directory has changed, or a file that was too small to fingerprint has
+            // been written to, unless the paths provider can't tell us which directories to watch.
+            let pending = file_events
+                .as_ref()
+                .map(FileEvents::take_pending)
+                .unwrap_or_default();
             let now_time = time::Instant::now();
-            if next_glob_time <= now_time {
+            let discovery_due = if file_events.is_some() && provider_watches_dirs {
+                pending.rescan
+                    || pending
+                        .modified
+                        .iter()
+                        .any(|path| known_small_files.contains_key(path))
+            } else {
+                next_glob_time <= now_time
+            };
+
+            if let Some(ready_paths) = &mut ready_paths {
+                if pending.read_all {
+                    ready_paths.extend(fp_map.values().map(|watcher| watcher.path.clone()));
+                }
+                ready_paths.extend(pending.modified);
+            }
+            if discovery_due {
                 // Schedule the next glob time.
                 next_glob_time = now_time.checked_add(self.glob_minimum_cooldown).unwrap();
 
@@ -200,6 +248,18 @@ where
                                     message = "Continue watching file.",
                                     path = ?path,
                                 );
+                                if self.fingerprinter.verifies_content()
+                                    && !self.content_matches(watcher, &path).await
+                                {
+                                    info!(
+                                        message = "Watched file has been truncated or replaced, reading it from the beginning.",
+                                        path = ?path,
+                                    );
+                                    watcher.restart().await.ok(); // ok if this fails: might fix next cycle
+                                    if let Some(ready_paths) = &mut ready_paths {
+                                        ready_paths.insert(path);
+                                    }
+                                }
                             } else if !was_found_this_cycle {
                                 // matches a file with a different path
                                 info!(
@@ -208,6 +268,9 @@ where
                                     old_path = ?watcher.path
                                 );
                                 watcher.update_path(path).await.ok(); // ok if this fails: might fix next cycle
+                                if let Some(ready_paths) = &mut ready_paths {
+                                    ready_paths.insert(watcher.path.clone());
+                                }
                             } else {
                                 info!(
                                     message = "More than one file has the same fingerprint.",
@@ -226,16 +289,39 @@ where
                                         old_modified_time = ?old_modified_time,
                                     );
                                     watcher.update_path(path).await.ok(); // ok if this fails: might fix next cycle
+                                    if let Some(ready_paths) = &mut ready_paths {
+                                        ready_paths.insert(watcher.path.clone());
+                                    }
                                 }
                             }
                         } else {
                             // untracked file fingerprint
+                            if let Some(ready_paths) = &mut ready_paths {
+                                ready_paths.insert(path.clone());
+                            }
                             self.watch_new_file(path, file_id, &mut fp_map, &checkpoints, false)
                                 .await;
                             self.emitter.emit_files_open(fp_map.len());
                         }
                     }
                 }
+
+                // Watch any new directories, or fall back to polling if we ran out of watches.
+                if let Some(events) = &mut file_events
+                    && !events.sync_dirs(self.dirs_to_watch(&fp_map), &self.emitter)
+                {
+                    file_events = None;
+                    ready_paths = None;
+                }
+
+                // Forget about writes to files we're not watching.
+                if let Some(ready_paths) = &mut ready_paths {
+                    let watched_paths = fp_map
+                        .values()
+                        .map(|watcher| watcher.path.as_path())
+                        .collect::<HashSet<_>>();
+                    ready_paths.retain(|path| watched_paths.contains(path.as_path()));
+                }
                 stats.record("discovery", start.elapsed());
             }
 
@@ -283,12 +369,22 @@ where
             let mut global_bytes_read: usize = 0;
             let mut maxed_out_reading_single_file = false;
             for (&file_id, watcher) in &mut fp_map {
-                if !watcher.should_read() {
+                let should_read = match &ready_paths {
+                    Some(ready_paths) => ready_paths.contains(&watcher.path),
+                    None => watcher.should_read(),
+                };
+                if !should_read {
+                    // Files that haven't been written to are never read with event-driven reads, so
+                    // we have to check whether they should be removed here instead.
+                    if ready_paths.is_some() {
+                        self.remove_if_idle(watcher).await;
+                    }
                     continue;
                 }
 
                 let start = time::Instant::now();
                 let mut bytes_read: usize = 0;
+                let mut maxed_out_reading_this_file = false;
                 while let Ok(RawLineResult {
                     raw_line: Some(line),
                     discarded_for_size_and_truncated,
@@ -322,30 +418,31 @@ where
 
                     if bytes_read > self.max_read_bytes {
                         maxed_out_reading_single_file = true;
+                        maxed_out_reading_this_file = true;
                         break;
                     }
                 }
                 stats.record("reading", start.elapsed());
 
+                if bytes_read > 0
+                    && self.fingerprinter.verifies_content()
+                    && let Some(content_checksum) = watcher.content_checksum()
+                {
+                    checkpoints.update_content_checksum(file_id, content_checksum);
+                }
+
+                // Unless we stopped reading early, we've read everything written so far, so there's
+                // no need to read the file again until it's written to.
+                if let Some(ready_paths) = &mut ready_paths
+                    && !maxed_out_reading_this_file
+                {
+                    ready_paths.remove(&watcher.path);
+                }
+
                 if bytes_read > 0 {
                     global_bytes_read = global_bytes_read.saturating_add(bytes_read);
                 } else {
-                    // Should the file be removed
-                    if let Some(grace_period) = self.remove_after
-                        && watcher.last_read_success().elapsed() >= grace_period
-                    {
-                        // Try to remove
-                        match remove_file(&watcher.path).await {
-                            Ok(()) => {
-                                self.emitter.emit_file_deleted(&watcher.path);
-                                watcher.set_dead();
-                            }
-                            Err(error) => {
-                                // We will try again after some time.
-                                self.emitter.emit_file_delete_error(&watcher.path, error);
-                            }
-                        }
-                    }
+                    self.remove_if_idle(watcher).await;
                 }
 
                 // Do not move on to newer files if we are behind on an older file
@@ -404,9 +501,22 @@ where
             // call. Also since we are using block_on here and in the above code,
             // this should be run in its own thread. `spawn_blocking` fulfills
             // all of these requirements.
+            //
+            // With event-driven reads, we also wake up as soon as a change is
+            // reported.
+            let notified = file_events.as_ref().map(FileEvents::notified);
             let sleep = async move {
                 if backoff > 0 {
-                    sleep(Duration::from_millis(backoff as u64)).await;
+                    let sleep = sleep(Duration::from_millis(backoff as u64));
+                    match notified {
+                        Some(notified) => {
+                            tokio::select! {
+                                _ = sleep => {},
+                                _ = notified.notified() => {},
+                            }
+                        }
+                        None => sleep.await,
+                    }
                 }
             };
             futures::pin_mut!(sleep);
@@ -432,6 +542,101 @@ where
         }
     }
 
+    /// Removes a file once it has not been read from for longer than `remove_after`, if set.
+    async fn remove_if_idle(&self, watcher: &mut FileWatcher) {
+        if let Some(grace_period) = self.remove_after
+            && watcher.last_read_success().elapsed() >= grace_period
+        {
+            match remove_file(&watcher.path).await {
+                Ok(()) => {
+                    self.emitter.emit_file_deleted(&watcher.path);
+                    watcher.set_dead();
+                }
+                Err(error) => {
+                    // We will try again after some time.
+                    self.emitter.emit_file_delete_error(&watcher.path, error);
+                }
+            }
+        }
+    }
+
+    /// Checks whether the file at `path` still holds the data that was last read by `watcher`.
+    async fn content_matches(&self, watcher: &FileWatcher, path: &Path) -> bool {
+        match watcher.content_checksum() {
+            Some(content_checksum) => content_checksum.matches(path).await.unwrap_or(true),
+            None => true,
+        }
+    }
+
+    /// Finds where to start reading a file from when its content is verified, based on the
+    /// checkpoint of the file itself, of the same file under the previous fingerprint strategies,
+    /// or of another file it is a copy of.
+    async fn verified_checkpoint(
+        &self,
+        path: &Path,
+        file_id: FileFingerprint,
+        checkpoints: &CheckpointsView,
+    ) -> Option<ReadFrom> {
+        if let Some(position) = checkpoints.get(file_id) {
+            let matches = match checkpoints.get_content_checksum(file_id) {
+                Some(content_checksum) => content_checksum.matches(path).await.unwrap_or(true),
+                None => true,
+            };
+            return Some(if matches {
+                ReadFrom::Checkpoint(position)
+            } else {
+                info!(
+                    message = "File has been truncated or replaced, reading it from the beginning.",
+                    path = ?path,
+                );
+                ReadFrom::Beginning
+            });
+        }
+
+        for legacy_id in file_id.legacy_fingerprints() {
+            if let Some(position) = checkpoints.get(legacy_id) {
+                debug!(
+                    message = "Migrating checkpoint to new fingerprint.",
+                    path = ?path,
+                    old_fingerprint = ?legacy_id,
+                    new_fingerprint = ?file_id,
+                );
+                checkpoints.update_key(legacy_id, file_id);
+                return Some(ReadFrom::Checkpoint(position));
+            }
+        }
+
+        // A copy of another file, such as with copy-truncate rotation, holds the data that was
+        // read from the original, so pick up where the original left off.
+        for (other_id, position, content_checksum) in checkpoints.with_same_header(file_id) {
+            if content_checksum.matches(path).await.unwrap_or(false) {
+                info!(
+                    message = "File is a copy of another file, resuming from its checkpoint.",
+                    path = ?path,
+                    other_fingerprint = ?other_id,
+                    file_position = position,
+                );
+                return Some(ReadFrom::Checkpoint(position));
+            }
+        }
+
+        None
+    }
+
+    /// Gets the directories to watch for event-driven discovery and reads.
+    ///
+    /// Besides the directories the paths provider asks for, this includes the directories of all
+    /// watched files, as inotify reports writes to a file through the directory containing it.
+    fn dirs_to_watch(&self, fp_map: &IndexMap<FileFingerprint, FileWatcher>) -> Vec<PathBuf> {
+        let mut dirs = self.paths_provider.watch_dirs().unwrap_or_default();
+        dirs.extend(
+            fp_map
+                .values()
+                .filter_map(|watcher| watcher.path.parent().map(PathBuf::from)),
+        );
+        dirs
+    }
+
     async fn watch_new_file(
         &self,
         path: PathBuf,
@@ -457,13 +662,17 @@ where
         // `kubernetes_logs` source returns the files well after start-up, once it has populated
         // them from the k8s metadata, so we now just always use the checkpoints unless opted out.
         // https://github.com/vectordotdev/vector/issues/7139
-        let read_from = if !self.ignore_checkpoints {
+        let read_from = if self.ignore_checkpoints {
+            fallback
+        } else if self.fingerprinter.verifies_content() {
+            self.verified_checkpoint(&path, file_id, checkpoints)
+                .await
+                .unwrap_or(fallback)
+        } else {
             checkpoints
                 .get(file_id)
                 .map(ReadFrom::Checkpoint)
                 .unwrap_or(fallback)
-        } else {
-            fallback
         };
 
         match FileWatcher::new(
@@ -1,4 +1,3 @@
-use async_compression::tokio::bufread::GzipDecoder;
 use bytes::{Bytes, BytesMut};
 use chrono::{DateTime, Utc};
 use std::{
@@ -12,10 +11,10 @@ use tokio::{
     time::Instant,
 };
 use tracing::debug;
-use vector_common::constants::GZIP_MAGIC;
 
 use file_source_common::{
-    AsyncFileInfo, FilePosition, PortableFileExt, ReadFrom,
+    AsyncFileInfo, ContentChecksum, FilePosition, PortableFileExt, ReadFrom,
+    SupportedCompressionAlgorithms,
     buffer::{ReadResult, read_until_with_max_size},
 };
 
@@ -65,6 +64,8 @@ pub struct FileWatcher {
     max_line_bytes: usize,
     line_delimiter: Bytes,
     buf: BytesMut,
+    /// The last line read, and the position it ends at, if it was followed by the delimiter.
+    last_line: Option<(FilePosition, Bytes)>,
 }
 
 impl FileWatcher {
@@ -100,53 +101,51 @@ impl FileWatcher {
             false
         };
 
-        let gzipped = is_gzipped(&mut reader).await?;
+        let compression = detect_compression(&mut reader).await?;
 
         // Determine the actual position at which we should start reading
         let (reader, file_position): (Box<dyn AsyncBufRead + Send + Unpin>, FilePosition) =
-            match (gzipped, too_old, read_from) {
-                (true, true, _) => {
+            match (compression, too_old, read_from) {
+                (Some(_), true, _) => {
                     debug!(
-                        message = "Not reading gzipped file older than `ignore_older`.",
+                        message = "Not reading compressed file older than `ignore_older`.",
                         ?path,
                     );
                     (Box::new(null_reader()), 0)
                 }
-                (true, _, ReadFrom::Checkpoint(file_position)) => {
+                (Some(_), _, ReadFrom::Checkpoint(file_position)) => {
                     debug!(
-                        message = "Not re-reading gzipped file with existing stored offset.",
+                        message = "Not re-reading compressed file with existing stored offset.",
                         ?path,
                         %file_position
                     );
                     (Box::new(null_reader()), file_position)
                 }
-                // TODO: This may become the default, leading us to stop reading gzipped files that
+                // TODO: This may become the default, leading us to stop reading compressed files that
                 // we were reading before. Should we merge this and the next branch to read
                 // compressed file from the beginning even when `read_from = "end"` (implicitly via
                 // default or explicitly via config)?
-                (true, _, ReadFrom::End) => {
+                (Some(_), _, ReadFrom::End) => {
                     debug!(
                         message = "Can't read from the end of already-compressed file.",
                         ?path,
                     );
                     (Box::new(null_reader()), 0)
                 }
-                (true, false, ReadFrom::Beginning) => {
-                    (Box::new(BufReader::new(GzipDecoder::new(reader))), 0)
-                }
-                (false, true, _) => {
+                (Some(compression), false, ReadFrom::Beginning) => (compression.decoder(reader), 0),
+                (None, true, _) => {
                     let pos = reader.seek(SeekFrom::End(0)).await.unwrap();
                     (Box::new(reader), pos)
                 }
-                (false, false, ReadFrom::Checkpoint(file_position)) => {
+                (None, false, ReadFrom::Checkpoint(file_position)) => {
                     let pos = reader.seek(SeekFrom::Start(file_position)).await.unwrap();
                     (Box::new(reader), pos)
                 }
-                (false, false, ReadFrom::Beginning) => {
+                (None, false, ReadFrom::Beginning) => {
                     let pos = reader.seek(SeekFrom::Start(0)).await.unwrap();
                     (Box::new(reader), pos)
                 }
-                (false, false, ReadFrom::End) => {
+                (None, false, ReadFrom::End) => {
                     let pos = reader.seek(SeekFrom::End(0)).await.unwrap();
                     (Box::new(reader), pos)
                 }
@@ -175,26 +174,45 @@ impl FileWatcher {
             max_line_bytes,
             line_delimiter,
             buf: BytesMut::new(),
+            last_line: None,
         })
     }
 
+    /// Starts reading the file from the beginning again, such as after it was truncated.
+    pub async fn restart(&mut self) -> io::Result<()> {
+        let mut reader = BufReader::new(File::open(&self.path).await?);
+        self.reader = match detect_compression(&mut reader).await? {
+            Some(compression) => compression.decoder(reader),
+            None => Box::new(reader),
+        };
+        self.file_position = 0;
+        self.buf.clear();
+        self.last_line = None;
+        self.reached_eof = false;
+        self.read_retry_delay = EOF_READ_BACKOFF_MIN;
+        Ok(())
+    }
+
     pub async fn update_path(&mut self, path: PathBuf) -> io::Result<()> {
         let file_handle = File::open(&path).await?;
 
         let file_info = file_handle.file_info().await?;
         if (file_info.portable_dev(), file_info.portable_ino()) != (self.devno, self.inode) {
             let mut reader = BufReader::new(File::open(&path).await?);
-            let gzipped = is_gzipped(&mut reader).await?;
-            let new_reader: Box<dyn AsyncBufRead + Send + Unpin> = if gzipped {
-                if self.file_position != 0 {
-                    Box::new(null_reader())
+            let compression = detect_compression(&mut reader).await?;
+            let new_reader: Box<dyn AsyncBufRead + Send + Unpin> =
+                if let Some(compression) = compression {
+                    // A rotated file that was compressed after being read from can't be resumed at
+                    // the same position, as positions refer to the decompressed data.
+                    if self.file_position != 0 {
+                        Box::new(null_reader())
+                    } else {
+                        compression.decoder(reader)
+                    }
                 } else {
-                    Box::new(BufReader::new(GzipDecoder::new(reader)))
-                }
-            } else {
-                reader.seek(io::SeekFrom::Start(self.file_position)).await?;
-                Box::new(reader)
-            };
+                    reader.seek(io::SeekFrom::Start(self.file_position)).await?;
+                    Box::new(reader)
+                };
             self.reader = new_reader;
 
             let file_info = file_handle.file_info().await?;
@@ -255,10 +273,15 @@ impl FileWatcher {
                 discarded_for_size_and_truncated,
             }) => {
                 self.track_read_success();
+                let bytes = self.buf.split().freeze();
+                // Lines read after discarding data aren't necessarily right before the position.
+                if discarded_for_size_and_truncated.is_empty() {
+                    self.last_line = Some((*file_position, bytes.clone()));
+                }
                 Ok(RawLineResult {
                     raw_line: Some(RawLine {
                         offset: initial_position,
-                        bytes: self.buf.split().freeze(),
+                        bytes,
                     }),
                     discarded_for_size_and_truncated,
                 })
@@ -306,6 +329,16 @@ impl FileWatcher {
         }
     }
 
+    /// Gets the checksum of the last line read and its delimiter, which can be used to check
+    /// whether the file still holds the data read from it.
+    pub fn content_checksum(&self) -> Option<ContentChecksum> {
+        let (position, line) = self.last_line.as_ref()?;
+        let line_len = ContentChecksum::MAX_LEN.saturating_sub(self.line_delimiter.len());
+        let mut data = line[line.len().saturating_sub(line_len)..].to_vec();
+        data.extend_from_slice(&self.line_delimiter);
+        Some(ContentChecksum::new(*position, &data))
+    }
+
     #[inline]
     fn track_read_attempt(&mut self) {
         self.last_read_attempt = Instant::now();
@@ -357,11 +390,13 @@ impl FileWatcher {
     }
 }
 
-async fn is_gzipped(r: &mut BufReader<File>) -> io::Result<bool> {
+async fn detect_compression(
+    r: &mut BufReader<File>,
+) -> io::Result<Option<SupportedCompressionAlgorithms>> {
     let header_bytes = r.fill_buf().await?;
     // WARN: The paired `BufReader::consume` is not called intentionally. If we
-    // do we'll chop a decent part of the potential gzip stream off.
-    Ok(header_bytes.starts_with(GZIP_MAGIC))
+    // do we'll chop a decent part of the potential compressed stream off.
+    Ok(SupportedCompressionAlgorithms::detect(header_bytes))
 }
 
 fn null_reader() -> impl AsyncBufRead {
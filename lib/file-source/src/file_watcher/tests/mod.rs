@@ -7,6 +7,8 @@ use bytes::{Bytes, BytesMut};
 use quickcheck::{Arbitrary, Gen};
 use tokio::time::Instant;
 
+use file_source_common::ReadFrom;
+
 use super::{EOF_READ_BACKOFF_MAX, EOF_READ_BACKOFF_MIN, FileWatcher, null_reader};
 
 // Welcome.
@@ -194,6 +196,7 @@ fn watcher_for_timing() -> FileWatcher {
         max_line_bytes: 1024,
         line_delimiter: Bytes::from_static(b"\n"),
         buf: BytesMut::new(),
+        last_line: None,
     }
 }
 
@@ -237,6 +240,45 @@ fn caps_and_resets_eof_backoff() {
     assert!(!watcher.reached_eof());
 }
 
+#[tokio::test]
+async fn content_checksum_detects_truncation() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("file.log");
+    std::fs::write(&path, b"first line\nsecond line\n").unwrap();
+
+    let mut watcher = FileWatcher::new(
+        path.clone(),
+        ReadFrom::Beginning,
+        None,
+        1024,
+        Bytes::from_static(b"\n"),
+    )
+    .await
+    .unwrap();
+    assert!(watcher.content_checksum().is_none());
+
+    for expected in ["first line", "second line"] {
+        let line = watcher.read_line().await.unwrap().raw_line.unwrap();
+        assert_eq!(expected.as_bytes(), line.bytes);
+    }
+    let checksum = watcher.content_checksum().unwrap();
+    assert_eq!(checksum.position, watcher.get_file_position());
+    assert!(checksum.matches(&path).await.unwrap());
+
+    // Appending data keeps what was already read intact.
+    std::fs::write(&path, b"first line\nsecond line\nthird line\n").unwrap();
+    assert!(checksum.matches(&path).await.unwrap());
+
+    // Copy-truncate rotation followed by a write of the same length.
+    std::fs::write(&path, b"first line\nsecond LINE\n").unwrap();
+    assert!(!checksum.matches(&path).await.unwrap());
+
+    watcher.restart().await.unwrap();
+    assert_eq!(watcher.get_file_position(), 0);
+    let line = watcher.read_line().await.unwrap().raw_line.unwrap();
+    assert_eq!(b"first line".as_slice(), line.bytes);
+}
+
 #[inline]
 pub fn delay(attempts: u32) {
     let delay = match attempts {
@@ -1,6 +1,7 @@
 #![deny(warnings)]
 #![deny(clippy::all)]
 
+mod file_events;
 pub mod file_server;
 pub mod file_watcher;
 pub mod paths_provider;
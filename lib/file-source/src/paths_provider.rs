@@ -1,6 +1,9 @@
 //! [`Glob`] based paths provider implementation.
 
-use std::path::PathBuf;
+use std::{
+    collections::BTreeSet,
+    path::{Component, Path, PathBuf},
+};
 
 use file_source_common::internal_events::FileSourceInternalEvents;
 pub use glob::MatchOptions;
@@ -29,6 +32,15 @@ pub trait PathsProvider {
 
     /// Provides a set of paths.
     fn paths(&self) -> Self::IntoIter;
+
+    /// Provides the directories in which changes can affect the set of paths.
+    ///
+    /// This is used for event-driven discovery, where paths are only searched for again after one of
+    /// these directories has changed. Providers whose paths don't only depend on the file system
+    /// return `None`, the default, in which case paths are searched for periodically instead.
+    fn watch_dirs(&self) -> Option<Vec<PathBuf>> {
+        None
+    }
 }
 
 /// A glob-based path provider.
@@ -96,4 +108,59 @@ impl<E: FileSourceInternalEvents> PathsProvider for Glob<E> {
             })
             .collect()
     }
+
+    fn watch_dirs(&self) -> Option<Vec<PathBuf>> {
+        // New paths can show up in any directory matching the directory part of an include pattern,
+        // but also in any directory leading up to it, as those directories may themselves be created
+        // after we've started watching. Directories without wildcards that don't exist yet are
+        // watched through their nearest existing ancestor, so that their creation triggers a rescan,
+        // which then watches them.
+        let mut dirs = BTreeSet::new();
+        for include_pattern in &self.include_patterns {
+            let Some(parent) = Path::new(include_pattern).parent() else {
+                continue;
+            };
+
+            let mut prefix = PathBuf::new();
+            let mut found_glob = false;
+            for component in parent.components() {
+                let is_glob_component = matches!(
+                    component,
+                    Component::Normal(name) if name.to_str().is_some_and(is_glob)
+                );
+                if is_glob_component && !found_glob {
+                    // This is the deepest directory without any wildcards.
+                    dirs.extend(nearest_existing_dir(&prefix));
+                }
+                found_glob |= is_glob_component;
+                prefix.push(component);
+
+                if found_glob && let Some(prefix_pattern) = prefix.to_str() {
+                    dirs.extend(
+                        glob::glob_with(prefix_pattern, self.glob_match_options)
+                            .into_iter()
+                            .flatten()
+                            .filter_map(Result::ok),
+                    );
+                }
+            }
+            if !found_glob {
+                dirs.extend(nearest_existing_dir(&prefix));
+            }
+        }
+
+        dirs.retain(|dir| dir.is_dir());
+        Some(dirs.into_iter().collect())
+    }
+}
+
+/// Returns the directory itself if it exists, or else its nearest existing ancestor.
+fn nearest_existing_dir(dir: &Path) -> Option<PathBuf> {
+    dir.ancestors()
+        .find(|ancestor| ancestor.is_dir())
+        .map(Path::to_path_buf)
+}
+
+fn is_glob(path: &str) -> bool {
+    path.contains(['*', '?', '['])
 }
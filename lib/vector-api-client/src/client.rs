@@ -1,6 +1,9 @@
 use http::Uri;
 use tokio_stream::{Stream, StreamExt};
-use tonic::transport::{Channel, Endpoint};
+use tonic::{
+    Request,
+    transport::{Channel, Endpoint},
+};
 use tonic_health::pb::{
     HealthCheckRequest, health_check_response::ServingStatus, health_client::HealthClient,
 };
@@ -8,12 +11,16 @@ use tonic_health::pb::{
 use crate::{
     error::{Error, Result},
     proto::{
-        GetAllocationTracingStatusRequest, GetAllocationTracingStatusResponse,
-        GetComponentsRequest, GetComponentsResponse, GetMetaRequest, GetMetaResponse, MetricName,
+        ConfigFormat, ControlComponentResponse, DiffConfigRequest, DiffConfigResponse,
+        DrainComponentRequest, GetAllocationTracingStatusRequest,
+        GetAllocationTracingStatusResponse, GetComponentsRequest, GetComponentsResponse,
+        GetEffectiveConfigRequest, GetMetaRequest, GetMetaResponse, InjectEncoding,
+        InjectEventsRequest, InjectEventsResponse, MetricName, PauseComponentRequest,
+        ReloadConfigRequest, ReloadConfigResponse, ResumeComponentRequest,
         StreamComponentAllocatedBytesRequest, StreamComponentAllocatedBytesResponse,
         StreamComponentMetricsRequest, StreamComponentMetricsResponse, StreamHeartbeatRequest,
         StreamHeartbeatResponse, StreamOutputEventsRequest, StreamOutputEventsResponse,
-        StreamUptimeRequest, StreamUptimeResponse,
+        StreamUptimeRequest, StreamUptimeResponse, event::EventWrapper,
         observability_service_client::ObservabilityServiceClient,
     },
 };
@@ -24,6 +31,7 @@ pub struct Client {
     endpoint: Endpoint,
     channel: Option<Channel>,
     client: Option<ObservabilityServiceClient<Channel>>,
+    control_token: Option<String>,
 }
 
 impl Client {
@@ -39,9 +47,18 @@ impl Client {
             endpoint: Endpoint::from(uri),
             channel: None,
             client: None,
+            control_token: None,
         }
     }
 
+    /// Set the bearer token sent with control-plane calls
+    ///
+    /// The token must match the `api.control_token` option of the Vector instance.
+    pub fn with_control_token(mut self, token: impl Into<String>) -> Self {
+        self.control_token = Some(token.into());
+        self
+    }
+
     /// Connect to the gRPC server
     pub async fn connect(&mut self) -> Result<()> {
         let channel = self.endpoint.connect().await?;
@@ -60,6 +77,18 @@ impl Client {
         self.channel.as_ref().ok_or(Error::NotConnected)
     }
 
+    /// Build a control-plane request, attaching the bearer token if one is set
+    fn control_request<T>(&self, message: T) -> Result<Request<T>> {
+        let mut request = Request::new(message);
+        if let Some(token) = &self.control_token {
+            let value = format!("Bearer {token}")
+                .parse()
+                .map_err(|_| Error::InvalidControlToken)?;
+            request.metadata_mut().insert("authorization", value);
+        }
+        Ok(request)
+    }
+
     // ========== Unary RPCs ==========
 
     /// Check if the API server is healthy using the standard gRPC health check
@@ -198,6 +227,138 @@ impl Client {
         let response = client.stream_output_events(request).await?;
         Ok(response.into_inner().map(|r| r.map_err(Error::from)))
     }
+
+    // ========== Control Plane RPCs ==========
+
+    /// Stop a sink in place, keeping its buffer
+    ///
+    /// Requires a control token, see [`Client::with_control_token`].
+    pub async fn pause_component(
+        &mut self,
+        component_id: impl Into<String>,
+    ) -> Result<ControlComponentResponse> {
+        let request = self.control_request(PauseComponentRequest {
+            component_id: component_id.into(),
+        })?;
+        let client = self.ensure_connected()?;
+        let response = client.pause_component(request).await?;
+        Ok(response.into_inner())
+    }
+
+    /// Restart a paused sink or a drained source
+    ///
+    /// Requires a control token, see [`Client::with_control_token`].
+    pub async fn resume_component(
+        &mut self,
+        component_id: impl Into<String>,
+    ) -> Result<ControlComponentResponse> {
+        let request = self.control_request(ResumeComponentRequest {
+            component_id: component_id.into(),
+        })?;
+        let client = self.ensure_connected()?;
+        let response = client.resume_component(request).await?;
+        Ok(response.into_inner())
+    }
+
+    /// Gracefully shut down a source
+    ///
+    /// Requires a control token, see [`Client::with_control_token`].
+    pub async fn drain_component(
+        &mut self,
+        component_id: impl Into<String>,
+    ) -> Result<ControlComponentResponse> {
+        let request = self.control_request(DrainComponentRequest {
+            component_id: component_id.into(),
+        })?;
+        let client = self.ensure_connected()?;
+        let response = client.drain_component(request).await?;
+        Ok(response.into_inner())
+    }
+
+    /// Send encoded events into the input of a running transform or sink
+    ///
+    /// Requires a control token, see [`Client::with_control_token`].
+    pub async fn inject_events(
+        &mut self,
+        component_id: impl Into<String>,
+        encoded_events: Vec<String>,
+        encoding: InjectEncoding,
+    ) -> Result<InjectEventsResponse> {
+        let request = self.control_request(InjectEventsRequest {
+            component_id: component_id.into(),
+            encoded_events,
+            encoding: encoding as i32,
+            native_events: Vec::new(),
+        })?;
+        let client = self.ensure_connected()?;
+        let response = client.inject_events(request).await?;
+        Ok(response.into_inner())
+    }
+
+    /// Send events in Vector's native format into the input of a running transform or sink
+    ///
+    /// Requires a control token, see [`Client::with_control_token`].
+    pub async fn inject_native_events(
+        &mut self,
+        component_id: impl Into<String>,
+        native_events: Vec<EventWrapper>,
+    ) -> Result<InjectEventsResponse> {
+        let request = self.control_request(InjectEventsRequest {
+            component_id: component_id.into(),
+            encoded_events: Vec::new(),
+            encoding: InjectEncoding::Unspecified as i32,
+            native_events,
+        })?;
+        let client = self.ensure_connected()?;
+        let response = client.inject_events(request).await?;
+        Ok(response.into_inner())
+    }
+
+    /// Reload the configuration from disk
+    ///
+    /// The response reports whether the new configuration was applied, the components it
+    /// changed, and any errors that prevented it from being applied.
+    ///
+    /// Requires a control token, see [`Client::with_control_token`].
+    pub async fn reload_config(&mut self) -> Result<ReloadConfigResponse> {
+        let request = self.control_request(ReloadConfigRequest {})?;
+        let client = self.ensure_connected()?;
+        let response = client.reload_config(request).await?;
+        Ok(response.into_inner())
+    }
+
+    /// Compute the changes a reload would make, without applying them
+    ///
+    /// `config` is compared against the running configuration. It must be fully interpolated,
+    /// while secrets are resolved by the server. When `None`, the server's configuration on disk
+    /// is used, as [`Client::reload_config`] would.
+    ///
+    /// Requires a control token, see [`Client::with_control_token`].
+    pub async fn diff_config(
+        &mut self,
+        config: Option<String>,
+        format: ConfigFormat,
+    ) -> Result<DiffConfigResponse> {
+        let request = self.control_request(DiffConfigRequest {
+            config,
+            format: format as i32,
+        })?;
+        let client = self.ensure_connected()?;
+        let response = client.diff_config(request).await?;
+        Ok(response.into_inner())
+    }
+
+    /// Get the configuration of the running topology, with secrets redacted
+    ///
+    /// Requires a control token, see [`Client::with_control_token`].
+    pub async fn get_effective_config(&mut self, format: ConfigFormat) -> Result<String> {
+        let request = self.control_request(GetEffectiveConfigRequest {
+            format: format as i32,
+        })?;
+        let client = self.ensure_connected()?;
+        let response = client.get_effective_config(request).await?;
+        Ok(response.into_inner().config)
+    }
 }
 
 #[cfg(test)]
@@ -211,6 +372,31 @@ mod tests {
         assert!(matches!(result, Err(Error::NotConnected)));
     }
 
+    #[test]
+    fn test_control_request_sets_bearer_token() {
+        let client =
+            Client::new("http://localhost:9999".parse().unwrap()).with_control_token("secret");
+        let request = client
+            .control_request(PauseComponentRequest {
+                component_id: "sink".to_string(),
+            })
+            .unwrap();
+        assert_eq!(
+            request.metadata().get("authorization").unwrap(),
+            "Bearer secret"
+        );
+    }
+
+    #[test]
+    fn test_control_request_rejects_invalid_token() {
+        let client =
+            Client::new("http://localhost:9999".parse().unwrap()).with_control_token("bad\ntoken");
+        let result = client.control_request(PauseComponentRequest {
+            component_id: "sink".to_string(),
+        });
+        assert!(matches!(result, Err(Error::InvalidControlToken)));
+    }
+
     #[test]
     fn test_ensure_connected() {
         let mut client = Client::new("http://localhost:9999".parse().unwrap());
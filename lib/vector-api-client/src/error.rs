@@ -13,6 +13,9 @@ pub enum Error {
     #[snafu(display("Invalid URL: {}", message))]
     InvalidUrl { message: String },
 
+    #[snafu(display("Invalid control token: it must only contain visible ASCII characters"))]
+    InvalidControlToken,
+
     #[snafu(display("Not connected to gRPC server"))]
     NotConnected,
 
@@ -27,7 +30,7 @@ impl Error {
     /// Returns true if this error is permanent and should not be retried.
     pub fn is_fatal(&self) -> bool {
         match self {
-            Error::InvalidUrl { .. } => true,
+            Error::InvalidUrl { .. } | Error::InvalidControlToken => true,
             Error::Grpc { source } => matches!(
                 source.code(),
                 tonic::Code::InvalidArgument
@@ -133,7 +133,7 @@ pub async fn setup<const N: usize>(
 
     let mut builder = TopologyBuilder::default();
     variant
-        .add_to_builder(&mut builder, data_dir, id)
+        .add_to_builder(&mut builder, data_dir, id, None)
         .expect("should not fail to add variant to builder");
     let (tx, rx) = builder
         .build(String::from("benches"), Span::none())
@@ -16,7 +16,7 @@ use tokio::{select, sync::oneshot, task, time};
 use tracing::{Span, debug, info};
 use tracing_subscriber::EnvFilter;
 use vector_buffers::{
-    BufferType, Bufferable, EventCount, MemoryBufferSize, WhenFull,
+    BufferType, Bufferable, DiskBufferCompression, EventCount, MemoryBufferSize, WhenFull,
     encoding::FixedEncodable,
     topology::{
         builder::TopologyBuilder,
@@ -275,6 +275,10 @@ where
             BufferType::DiskV2 {
                 max_size: max_size_bytes,
                 when_full,
+                compression: DiskBufferCompression::None,
+                encryption: None,
+                max_age_secs: None,
+                pool: None,
             }
         }
         s => panic!(
@@ -283,7 +287,7 @@ where
     };
 
     variant
-        .add_to_builder(&mut builder, Some(data_dir), id)
+        .add_to_builder(&mut builder, Some(data_dir), id, None)
         .expect("should not fail to add variant to builder");
 
     builder
@@ -11,7 +11,10 @@ use tracing::{Instrument, Span};
 use vector_common::internal_event::emit;
 
 use crate::{
-    internal_events::{BufferCreated, BufferEventsDropped, BufferEventsReceived, BufferEventsSent},
+    internal_events::{
+        BufferCreated, BufferEventsDropped, BufferEventsReceived, BufferEventsSent,
+        BufferRecordsCompressed,
+    },
     spawn_named,
 };
 
@@ -87,6 +90,34 @@ impl CategoryMetrics {
     }
 }
 
+/// Compression metrics.
+///
+/// This tracks the size of records before and after they were compressed, for buffers that compress
+/// records before storing them.
+#[derive(Debug, Default)]
+struct CompressionMetrics {
+    uncompressed_byte_size: AtomicU64,
+    compressed_byte_size: AtomicU64,
+}
+
+impl CompressionMetrics {
+    /// Increments the uncompressed and compressed byte sizes by the given amounts.
+    fn increment(&self, uncompressed_byte_size: u64, compressed_byte_size: u64) {
+        self.uncompressed_byte_size
+            .fetch_add(uncompressed_byte_size, ORDERING);
+        self.compressed_byte_size
+            .fetch_add(compressed_byte_size, ORDERING);
+    }
+
+    /// Gets the uncompressed and compressed byte sizes by "consuming" the values.
+    fn consume(&self) -> (u64, u64) {
+        (
+            self.uncompressed_byte_size.swap(0, ORDERING),
+            self.compressed_byte_size.swap(0, ORDERING),
+        )
+    }
+}
+
 /// Running totals of events that have entered and left a buffer stage.
 ///
 /// Each reporting tick consumes the latest deltas from the atomic counters and
@@ -189,6 +220,22 @@ impl BufferUsageHandle {
         }
     }
 
+    /// Increments the number of bytes compressed by this buffer component.
+    ///
+    /// `uncompressed_byte_size` is the size of the records before compression, and
+    /// `compressed_byte_size` is their size once compressed, as actually stored by the buffer.
+    pub fn increment_compressed_byte_size(
+        &self,
+        uncompressed_byte_size: u64,
+        compressed_byte_size: u64,
+    ) {
+        if uncompressed_byte_size > 0 || compressed_byte_size > 0 {
+            self.state
+                .compressed
+                .increment(uncompressed_byte_size, compressed_byte_size);
+        }
+    }
+
     /// Increment the number of dropped events (and their total size) for this buffer component.
     pub fn increment_dropped_event_count_and_byte_size(
         &self,
@@ -204,6 +251,17 @@ impl BufferUsageHandle {
             }
         }
     }
+
+    /// Increment the number of expired events (and their total size) for this buffer component.
+    ///
+    /// This represents the oldest events in the buffer being intentionally dropped before they
+    /// could be read, either to make room for newer events or because they exceeded the maximum
+    /// age of the buffer.
+    pub fn increment_expired_event_count_and_byte_size(&self, count: u64, byte_size: u64) {
+        if count > 0 || byte_size > 0 {
+            self.state.dropped_expired.increment(count, byte_size);
+        }
+    }
 }
 
 #[derive(Debug, Default)]
@@ -213,7 +271,9 @@ struct BufferUsageData {
     sent: CategoryMetrics,
     dropped: CategoryMetrics,
     dropped_intentional: CategoryMetrics,
+    dropped_expired: CategoryMetrics,
     max_size: CategoryMetrics,
+    compressed: CompressionMetrics,
 }
 
 impl BufferUsageData {
@@ -229,6 +289,7 @@ impl BufferUsageData {
         let sent = self.sent.get();
         let dropped = self.dropped.get();
         let dropped_intentional = self.dropped_intentional.get();
+        let dropped_expired = self.dropped_expired.get();
         let max_size = self.max_size.get();
 
         BufferUsageSnapshot {
@@ -238,8 +299,10 @@ impl BufferUsageData {
             sent_byte_size: sent.event_byte_size,
             dropped_event_count: dropped.event_count,
             dropped_event_byte_size: dropped.event_byte_size,
-            dropped_event_count_intentional: dropped_intentional.event_count,
-            dropped_event_byte_size_intentional: dropped_intentional.event_byte_size,
+            dropped_event_count_intentional: dropped_intentional.event_count
+                + dropped_expired.event_count,
+            dropped_event_byte_size_intentional: dropped_intentional.event_byte_size
+                + dropped_expired.event_byte_size,
             max_size_bytes: max_size.event_byte_size,
             max_size_events: max_size
                 .event_count
@@ -276,6 +339,9 @@ impl BufferUsageData {
         let dropped_intentional = self.dropped_intentional.consume();
         current_metrics.add_left(dropped_intentional);
 
+        let dropped_expired = self.dropped_expired.consume();
+        current_metrics.add_left(dropped_expired);
+
         let current = current_metrics.current();
 
         if received.has_updates() {
@@ -325,6 +391,29 @@ impl BufferUsageData {
                 total_byte_size: current.event_byte_size,
             });
         }
+
+        if dropped_expired.has_updates() {
+            emit(BufferEventsDropped {
+                buffer_id: buffer_id.to_string(),
+                idx: self.idx,
+                intentional: true,
+                reason: "drop_oldest",
+                count: dropped_expired.event_count,
+                byte_size: dropped_expired.event_byte_size,
+                total_count: current.event_count,
+                total_byte_size: current.event_byte_size,
+            });
+        }
+
+        let (uncompressed_byte_size, compressed_byte_size) = self.compressed.consume();
+        if compressed_byte_size > 0 {
+            emit(BufferRecordsCompressed {
+                buffer_id: buffer_id.to_string(),
+                idx: self.idx,
+                uncompressed_byte_size,
+                compressed_byte_size,
+            });
+        }
     }
 }
 
@@ -499,10 +588,25 @@ mod tests {
         data.sent.increment(5, 500);
         data.dropped.increment(3, 300);
         data.dropped_intentional.increment(2, 200);
+        data.dropped_expired.increment(4, 400);
 
         data.report(&mut metrics, "test");
         let current = metrics.current();
-        assert_eq!(current.event_count, 10);
-        assert_eq!(current.event_byte_size, 1000);
+        assert_eq!(current.event_count, 6);
+        assert_eq!(current.event_byte_size, 600);
+    }
+
+    #[test]
+    fn compression_is_reported_per_tick() {
+        let handle = BufferUsageHandle::noop();
+        let mut metrics = ReporterCurrentMetrics::default();
+
+        handle.increment_compressed_byte_size(1000, 250);
+        handle.increment_compressed_byte_size(500, 250);
+        assert_eq!(handle.state.compressed.consume(), (1500, 500));
+
+        handle.increment_compressed_byte_size(100, 50);
+        handle.state.report(&mut metrics, "test");
+        assert_eq!(handle.state.compressed.consume(), (0, 0));
     }
 }
@@ -3,16 +3,20 @@ use std::{
     num::{NonZeroU64, NonZeroUsize},
     path::{Path, PathBuf},
     slice,
+    time::Duration,
 };
 
 use serde::{Deserialize, Deserializer, Serialize, de};
 use snafu::{ResultExt, Snafu};
 use tracing::Span;
-use vector_common::{config::ComponentKey, finalization::Finalizable};
+use vector_common::{
+    config::ComponentKey, finalization::Finalizable, sensitive_string::SensitiveString,
+};
 use vector_config::configurable_component;
 
 use crate::{
-    Bufferable, WhenFull,
+    BufferPriority, Bufferable, WhenFull,
+    pool::BufferPool,
     topology::{
         builder::{TopologyBuilder, TopologyError},
         channel::{BufferReceiver, BufferSender},
@@ -38,7 +42,18 @@ enum BufferTypeKind {
     DiskV2,
 }
 
-const ALL_FIELDS: [&str; 4] = ["type", "max_events", "max_size", "when_full"];
+const ALL_FIELDS: [&str; 8] = [
+    "type",
+    "max_events",
+    "max_size",
+    "when_full",
+    "compression",
+    "encryption",
+    "max_age_secs",
+    "pool",
+];
+
+const MEMORY_FIELDS: [&str; 4] = ["type", "max_events", "max_size", "when_full"];
 
 struct BufferTypeVisitor;
 
@@ -51,6 +66,10 @@ impl BufferTypeVisitor {
         let mut max_events: Option<NonZeroUsize> = None;
         let mut max_size: Option<NonZeroU64> = None;
         let mut when_full: Option<WhenFull> = None;
+        let mut compression: Option<DiskBufferCompression> = None;
+        let mut encryption: Option<DiskBufferEncryption> = None;
+        let mut max_age_secs: Option<NonZeroU64> = None;
+        let mut pool: Option<BufferPoolQuota> = None;
         while let Some(key) = map.next_key::<String>()? {
             match key.as_str() {
                 "type" => {
@@ -77,6 +96,30 @@ impl BufferTypeVisitor {
                     }
                     when_full = Some(map.next_value()?);
                 }
+                "compression" => {
+                    if compression.is_some() {
+                        return Err(de::Error::duplicate_field("compression"));
+                    }
+                    compression = Some(map.next_value()?);
+                }
+                "encryption" => {
+                    if encryption.is_some() {
+                        return Err(de::Error::duplicate_field("encryption"));
+                    }
+                    encryption = Some(map.next_value()?);
+                }
+                "max_age_secs" => {
+                    if max_age_secs.is_some() {
+                        return Err(de::Error::duplicate_field("max_age_secs"));
+                    }
+                    max_age_secs = Some(map.next_value()?);
+                }
+                "pool" => {
+                    if pool.is_some() {
+                        return Err(de::Error::duplicate_field("pool"));
+                    }
+                    pool = Some(map.next_value()?);
+                }
                 other => {
                     return Err(de::Error::unknown_field(other, &ALL_FIELDS));
                 }
@@ -86,6 +129,22 @@ impl BufferTypeVisitor {
         let when_full = when_full.unwrap_or_default();
         match kind {
             BufferTypeKind::Memory => {
+                for (field, is_set) in [
+                    ("compression", compression.is_some()),
+                    ("encryption", encryption.is_some()),
+                    ("max_age_secs", max_age_secs.is_some()),
+                    ("pool", pool.is_some()),
+                ] {
+                    if is_set {
+                        return Err(de::Error::unknown_field(field, &MEMORY_FIELDS));
+                    }
+                }
+                if when_full == WhenFull::DropOldest {
+                    return Err(de::Error::invalid_value(
+                        de::Unexpected::Str("drop_oldest"),
+                        &"`block` or `drop_newest`, as only disk buffers can drop the oldest events",
+                    ));
+                }
                 let size = match (max_events, max_size) {
                     (Some(_), Some(_)) => {
                         return Err(de::Error::unknown_field(
@@ -117,12 +176,24 @@ impl BufferTypeVisitor {
                 if max_events.is_some() {
                     return Err(de::Error::unknown_field(
                         "max_events",
-                        &["type", "max_size", "when_full"],
+                        &[
+                            "type",
+                            "max_size",
+                            "when_full",
+                            "compression",
+                            "encryption",
+                            "max_age_secs",
+                            "pool",
+                        ],
                     ));
                 }
                 Ok(BufferType::DiskV2 {
                     max_size: max_size.ok_or_else(|| de::Error::missing_field("max_size"))?,
                     when_full,
+                    compression: compression.unwrap_or_default(),
+                    encryption,
+                    max_age_secs,
+                    pool,
                 })
             }
         }
@@ -208,9 +279,88 @@ pub enum MemoryBufferSize {
     MaxSize(#[configurable(metadata(docs::type_unit = "bytes"))] NonZeroUsize),
 }
 
+/// Compression algorithm for records written to a disk buffer.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DiskBufferCompression {
+    /// Records are not compressed.
+    #[default]
+    None,
+
+    /// Records are compressed with [Zstandard][zstd].
+    ///
+    /// [zstd]: https://facebook.github.io/zstd/
+    Zstd,
+
+    /// Records are compressed with [LZ4][lz4].
+    ///
+    /// This is faster than `zstd`, but usually achieves a lower compression ratio.
+    ///
+    /// [lz4]: https://lz4.org/
+    Lz4,
+}
+
+/// Encryption at rest for disk buffers.
+///
+/// Records are encrypted with AES-256-GCM before being written to disk. Keys are 32 bytes, encoded
+/// as base64, and can be loaded from a secrets backend with `SECRET[<backend>.<key>]`.
+#[configurable_component]
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct DiskBufferEncryption {
+    /// The key used to encrypt new records.
+    #[configurable(metadata(docs::examples = "SECRET[backend.buffer_key]"))]
+    pub key: SensitiveString,
+
+    /// Keys that were previously used to encrypt records.
+    ///
+    /// When rotating keys, move the old key here so that records written before the rotation can
+    /// still be read. Once all of those records have been read, it can be removed.
+    #[serde(default)]
+    #[configurable(metadata(docs::examples = "SECRET[backend.previous_buffer_key]"))]
+    pub previous_keys: Vec<SensitiveString>,
+}
+
+/// Configuration for the disk buffer pool.
+///
+/// When configured, all disk buffers draw their space from a single pool, in addition to being
+/// limited by their own `max_size`. This allows sizing disk buffers for the node as a whole, rather
+/// than having to split the available disk space up front between every sink.
+#[configurable_component]
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct BufferPoolConfig {
+    /// The total size of the pool, shared by all disk buffers.
+    #[configurable(metadata(docs::type_unit = "bytes"))]
+    pub max_size: NonZeroU64,
+}
+
+/// The share of the disk buffer pool that a disk buffer is entitled to.
+///
+/// This only has an effect when the global `buffer_pool` option is configured.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct BufferPoolQuota {
+    /// The amount of space in the pool reserved for this buffer.
+    ///
+    /// The buffer can always grow up to this size, even when other buffers have used up the rest of
+    /// the pool. The sum of `min_size` across all disk buffers must not exceed the size of the pool.
+    #[serde(default)]
+    #[configurable(metadata(docs::type_unit = "bytes"))]
+    pub min_size: u64,
+
+    /// The priority of this buffer when the pool is full.
+    ///
+    /// Space freed up in the pool goes to the waiting buffers with the highest priority first.
+    #[serde(default)]
+    pub priority: u8,
+}
+
 /// A specific type of buffer stage.
 #[configurable_component(no_deser)]
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 #[serde(rename_all = "snake_case", tag = "type")]
 #[configurable(metadata(docs::enum_tag_description = "The type of buffer to use."))]
 pub enum BufferType {
@@ -250,6 +400,36 @@ pub enum BufferType {
         #[configurable(derived)]
         #[serde(default)]
         when_full: WhenFull,
+
+        /// Compresses records before they are written to disk.
+        ///
+        /// Each record is compressed individually, and `max_size` applies to the compressed size of
+        /// records. Records that don't shrink when compressed are stored as-is. Records already in the
+        /// buffer remain readable when the compression algorithm is changed.
+        #[serde(default)]
+        compression: DiskBufferCompression,
+
+        /// Encrypts records before they are written to disk.
+        ///
+        /// Records written while encryption was disabled remain readable after enabling it.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        encryption: Option<DiskBufferEncryption>,
+
+        /// The maximum age of the data in the buffer.
+        ///
+        /// Data files whose records are all older than this are dropped, even if they have not been
+        /// read yet. Like with `when_full = "drop_oldest"`, the data files currently being read from
+        /// and written to are never dropped.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        #[configurable(metadata(docs::type_unit = "seconds"))]
+        max_age_secs: Option<NonZeroU64>,
+
+        /// The share of the disk buffer pool this buffer is entitled to.
+        ///
+        /// When the global `buffer_pool` option is configured, `max_size` caps how much of the pool
+        /// this buffer can use.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pool: Option<BufferPoolQuota>,
     },
 }
 
@@ -304,20 +484,34 @@ impl BufferType {
         builder: &mut TopologyBuilder<T>,
         data_dir: Option<PathBuf>,
         id: String,
+        pool: Option<&BufferPool>,
     ) -> Result<(), BufferBuildError>
     where
         T: Bufferable + Clone + Finalizable,
     {
-        match *self {
+        match self {
             BufferType::Memory { size, when_full } => {
-                builder.stage(MemoryBuffer::new(size), when_full);
+                builder.stage(MemoryBuffer::new(*size), *when_full);
             }
             BufferType::DiskV2 {
                 when_full,
                 max_size,
+                compression,
+                encryption,
+                max_age_secs,
+                pool: quota,
             } => {
                 let data_dir = data_dir.ok_or(BufferBuildError::RequiresDataDir)?;
-                builder.stage(DiskV2Buffer::new(id, data_dir, max_size), when_full);
+                let pool_member = pool.map(|pool| pool.register(&id, quota.unwrap_or_default()));
+                builder.stage(
+                    DiskV2Buffer::new(id, data_dir, *max_size)
+                        .with_compression(*compression)
+                        .with_encryption(encryption.clone())
+                        .with_drop_oldest(*when_full == WhenFull::DropOldest)
+                        .with_max_age(max_age_secs.map(|secs| Duration::from_secs(secs.get())))
+                        .with_pool(pool_member),
+                    *when_full,
+                );
             }
         }
 
@@ -398,11 +592,14 @@ impl BufferConfig {
     ///
     /// If a disk buffer stage is configured and the data directory provided is `None`, an error
     /// variant will be thrown.
+    ///
+    /// When a buffer pool is given, any disk buffer stages draw their space from it.
     #[allow(clippy::needless_pass_by_value)]
     pub async fn build<T>(
         &self,
         data_dir: Option<PathBuf>,
         buffer_id: String,
+        pool: Option<&BufferPool>,
         span: Span,
     ) -> Result<(BufferSender<T>, BufferReceiver<T>), BufferBuildError>
     where
@@ -411,7 +608,7 @@ impl BufferConfig {
         let mut builder = TopologyBuilder::default();
 
         for stage in self.stages() {
-            stage.add_to_builder(&mut builder, data_dir.clone(), buffer_id.clone())?;
+            stage.add_to_builder(&mut builder, data_dir.clone(), buffer_id.clone(), pool)?;
         }
 
         builder
@@ -419,13 +616,56 @@ impl BufferConfig {
             .await
             .context(FailedToBuildTopologySnafu)
     }
+
+    /// Builds the buffer components represented by this configuration, along with a priority lane.
+    ///
+    /// The priority lane is a second buffer with the same configuration, identified by
+    /// [`BufferConfig::priority_lane_id`]. Items deemed high priority by `priority` are sent to the
+    /// lane instead, and the receiver hands out the items waiting in the lane before any others.
+    ///
+    /// As the lane has its own `max_size`, and reserves its own `pool.min_size` from the buffer pool,
+    /// the buffer as a whole can hold up to twice as much as a buffer without a lane.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`BufferConfig::build`], for either buffer.
+    pub async fn build_with_priority<T>(
+        &self,
+        data_dir: Option<PathBuf>,
+        buffer_id: String,
+        pool: Option<&BufferPool>,
+        priority: impl BufferPriority<T>,
+        span: Span,
+    ) -> Result<(BufferSender<T>, BufferReceiver<T>), BufferBuildError>
+    where
+        T: Bufferable + Clone + Finalizable,
+    {
+        let lane_id = Self::priority_lane_id(&buffer_id);
+        let (lane_tx, lane_rx) = self
+            .build(data_dir.clone(), lane_id, pool, span.clone())
+            .await?;
+        let (mut tx, mut rx) = self.build(data_dir, buffer_id, pool, span).await?;
+
+        tx.with_priority_lane(priority, lane_tx);
+        rx.with_priority_lane(lane_rx);
+
+        Ok((tx, rx))
+    }
+
+    /// Gets the ID of the priority lane of the buffer with the given ID.
+    pub fn priority_lane_id(buffer_id: &str) -> String {
+        format!("{buffer_id}.priority")
+    }
 }
 
 #[cfg(test)]
 mod test {
     use std::num::{NonZeroU64, NonZeroUsize};
 
-    use crate::{BufferConfig, BufferType, MemoryBufferSize, WhenFull};
+    use crate::{
+        BufferConfig, BufferPoolQuota, BufferType, DiskBufferCompression, DiskBufferEncryption,
+        MemoryBufferSize, WhenFull,
+    };
 
     fn check_single_stage(source: &str, expected: BufferType) {
         let config: BufferConfig = serde_yaml::from_str(source).unwrap();
@@ -568,7 +808,147 @@ max_events: 42
             BufferType::DiskV2 {
                 max_size: NonZeroU64::new(1024).unwrap(),
                 when_full: WhenFull::Block,
+                compression: DiskBufferCompression::None,
+                encryption: None,
+                max_age_secs: None,
+                pool: None,
+            },
+        );
+    }
+
+    #[test]
+    fn parse_disk_with_encryption() {
+        check_single_stage(
+            r"
+          type: disk
+          max_size: 1024
+          encryption:
+            key: current
+            previous_keys: [old]
+          ",
+            BufferType::DiskV2 {
+                max_size: NonZeroU64::new(1024).unwrap(),
+                when_full: WhenFull::Block,
+                compression: DiskBufferCompression::None,
+                encryption: Some(DiskBufferEncryption {
+                    key: "current".to_string().into(),
+                    previous_keys: vec!["old".to_string().into()],
+                }),
+                max_age_secs: None,
+                pool: None,
+            },
+        );
+    }
+
+    #[test]
+    fn parse_memory_with_encryption() {
+        let source = r"
+          type: memory
+          encryption:
+            key: current
+          ";
+        let error = serde_yaml::from_str::<BufferConfig>(source).unwrap_err();
+        assert_eq!(error.to_string(), BUFFER_CONFIG_NO_MATCH_ERR);
+    }
+
+    #[test]
+    fn parse_disk_with_compression() {
+        check_single_stage(
+            r"
+          type: disk
+          max_size: 1024
+          compression: zstd
+          ",
+            BufferType::DiskV2 {
+                max_size: NonZeroU64::new(1024).unwrap(),
+                when_full: WhenFull::Block,
+                compression: DiskBufferCompression::Zstd,
+                encryption: None,
+                max_age_secs: None,
+                pool: None,
+            },
+        );
+    }
+
+    #[test]
+    fn parse_memory_with_compression() {
+        let source = r"
+          type: memory
+          compression: lz4
+          ";
+        let error = serde_yaml::from_str::<BufferConfig>(source).unwrap_err();
+        assert_eq!(error.to_string(), BUFFER_CONFIG_NO_MATCH_ERR);
+    }
+
+    #[test]
+    fn parse_disk_with_retention() {
+        check_single_stage(
+            r"
+          type: disk
+          max_size: 1024
+          when_full: drop_oldest
+          max_age_secs: 3600
+          ",
+            BufferType::DiskV2 {
+                max_size: NonZeroU64::new(1024).unwrap(),
+                when_full: WhenFull::DropOldest,
+                compression: DiskBufferCompression::None,
+                encryption: None,
+                max_age_secs: NonZeroU64::new(3600),
+                pool: None,
+            },
+        );
+    }
+
+    #[test]
+    fn parse_memory_with_retention() {
+        for source in [
+            r"
+          type: memory
+          when_full: drop_oldest
+          ",
+            r"
+          type: memory
+          max_age_secs: 3600
+          ",
+        ] {
+            let error = serde_yaml::from_str::<BufferConfig>(source).unwrap_err();
+            assert_eq!(error.to_string(), BUFFER_CONFIG_NO_MATCH_ERR);
+        }
+    }
+
+    #[test]
+    fn parse_disk_with_pool() {
+        check_single_stage(
+            r"
+          type: disk
+          max_size: 1024
+          pool:
+            min_size: 512
+            priority: 2
+          ",
+            BufferType::DiskV2 {
+                max_size: NonZeroU64::new(1024).unwrap(),
+                when_full: WhenFull::Block,
+                compression: DiskBufferCompression::None,
+                encryption: None,
+                max_age_secs: None,
+                pool: Some(BufferPoolQuota {
+                    min_size: 512,
+                    priority: 2,
+                }),
             },
         );
     }
+
+    #[test]
+    fn parse_memory_with_pool() {
+        let source = r"
+          type: memory
+          pool:
+            priority: 2
+          ";
+        let error = serde_yaml::from_str::<BufferConfig>(source).unwrap_err();
+        assert_eq!(error.to_string(), BUFFER_CONFIG_NO_MATCH_ERR);
+    }
 }
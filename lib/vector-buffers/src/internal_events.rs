@@ -243,6 +243,38 @@ impl InternalEvent for BufferEventsDropped {
     }
 }
 
+#[derive(NamedInternalEvent)]
+pub struct BufferRecordsCompressed {
+    pub buffer_id: String,
+    pub idx: usize,
+    pub uncompressed_byte_size: u64,
+    pub compressed_byte_size: u64,
+}
+
+impl InternalEvent for BufferRecordsCompressed {
+    #[expect(clippy::cast_precision_loss)]
+    fn emit(self) {
+        counter!(
+            CounterName::BufferUncompressedBytesTotal,
+            "buffer_id" => self.buffer_id.clone(),
+            "stage" => self.idx.to_string()
+        )
+        .increment(self.uncompressed_byte_size);
+        counter!(
+            CounterName::BufferCompressedBytesTotal,
+            "buffer_id" => self.buffer_id.clone(),
+            "stage" => self.idx.to_string()
+        )
+        .increment(self.compressed_byte_size);
+        gauge!(
+            GaugeName::BufferCompressionRatio,
+            "buffer_id" => self.buffer_id,
+            "stage" => self.idx.to_string()
+        )
+        .set(self.uncompressed_byte_size as f64 / self.compressed_byte_size as f64);
+    }
+}
+
 #[derive(NamedInternalEvent)]
 pub struct BufferReadError {
     pub error_code: &'static str,
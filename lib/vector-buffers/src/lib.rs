@@ -17,7 +17,10 @@ extern crate tracing;
 mod buffer_usage_data;
 
 pub mod config;
-pub use config::{BufferConfig, BufferType, MemoryBufferSize};
+pub use config::{
+    BufferConfig, BufferPoolConfig, BufferPoolQuota, BufferType, DiskBufferCompression,
+    DiskBufferEncryption, MemoryBufferSize,
+};
 use encoding::Encodable;
 pub(crate) use vector_common::Result;
 use vector_config::configurable_component;
@@ -26,11 +29,15 @@ pub mod encoding;
 
 mod internal_events;
 
+pub mod pool;
+pub use pool::BufferPool;
+
 #[cfg(test)]
 pub mod test;
 pub mod topology;
 
 pub(crate) mod variants;
+pub use variants::disk_v2::offline;
 
 use std::fmt::Debug;
 
@@ -58,6 +65,16 @@ pub enum WhenFull {
     /// slowdown in the acceptance/consumption of events.
     DropNewest,
 
+    /// Drops the oldest events in the buffer to make room for new events.
+    ///
+    /// Events are dropped a whole data file at a time, and the data files currently being read from
+    /// and written to are never dropped, so writes still wait for free space when nothing else can
+    /// be dropped. This mode is typically used when recent events are more valuable than old ones,
+    /// such as during long outages of the sink's destination.
+    ///
+    /// This mode can only be used with disk buffers.
+    DropOldest,
+
     /// Overflows to the next stage in the buffer topology.
     ///
     /// If the current buffer stage is full, attempt to send this event to the next buffer stage.
@@ -115,6 +132,14 @@ pub trait BufferInstrumentation<T: Bufferable>: Send + Sync + 'static {
     fn on_send(&self, item: &mut T);
 }
 
+/// Hook for routing high-priority items to the priority lane of a `BufferSender`.
+pub trait BufferPriority<T: Bufferable>: Send + Sync + 'static {
+    /// Splits the item into its high-priority part and the rest, either of which may be empty.
+    ///
+    /// The relative order of the items within each part must be preserved.
+    fn split(&self, item: T) -> (Option<T>, Option<T>);
+}
+
 pub trait EventCount {
     fn event_count(&self) -> usize;
 }
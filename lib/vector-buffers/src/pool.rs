@@ -0,0 +1,353 @@
+//! A pool of disk space shared by disk buffers.
+//!
+//! Disk buffers are normally limited only by their own maximum size, which means that the disk
+//! space available to buffers has to be split up front between every sink. When a buffer pool is
+//! configured, disk buffers additionally draw their space from the pool, so that buffers for sinks
+//! that are currently healthy don't hold on to space that backed up sinks could be using.
+//!
+//! Each member of the pool has a quota, made up of a minimum size and a priority:
+//!
+//! - the minimum size is reserved for the member, so a member can always grow up to it, regardless
+//!   of how much of the pool other members are using
+//! - beyond its minimum size, a member can only grow into the space that isn't used or reserved by
+//!   any other member
+//! - when the pool is full, members with a higher priority that are waiting for space get it before
+//!   members with a lower priority
+//!
+//! Usage is tracked based on the size of the records in each buffer, and space is checked before
+//! each write, so the pool can be overcommitted by up to one record per member when multiple
+//! members write at the same time.
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex, MutexGuard},
+};
+
+use tokio::sync::Notify;
+
+use crate::config::BufferPoolQuota;
+
+#[derive(Debug)]
+struct MemberState {
+    buffer_id: String,
+    min_size: u64,
+    priority: u8,
+    usage: u64,
+    waiting: bool,
+    notify: Arc<Notify>,
+}
+
+impl MemberState {
+    /// The amount of space in the pool that this member uses or has reserved.
+    fn committed(&self) -> u64 {
+        self.usage.max(self.min_size)
+    }
+}
+
+#[derive(Debug, Default)]
+struct PoolState {
+    max_size: u64,
+    next_member_id: u64,
+    members: HashMap<u64, MemberState>,
+}
+
+impl PoolState {
+    /// Wakes up all members that are waiting for space in the pool.
+    ///
+    /// Waiting members each get notified, even if the space freed up only covers some of them, as
+    /// they all have to check again whether or not they're allowed to write.
+    fn notify_waiting_members(&self) {
+        for member in self.members.values().filter(|member| member.waiting) {
+            member.notify.notify_one();
+        }
+    }
+
+    fn try_reserve(&mut self, member_id: u64, amount: u64) -> bool {
+        let Some(member) = self.members.get(&member_id) else {
+            return true;
+        };
+
+        let new_usage = member.usage + amount;
+        let allowed = new_usage <= member.min_size || {
+            let committed = self
+                .members
+                .values()
+                .map(MemberState::committed)
+                .sum::<u64>();
+            let needed = new_usage - member.committed();
+            let outranked = self.members.iter().any(|(id, other)| {
+                *id != member_id && other.waiting && other.priority > member.priority
+            });
+
+            !outranked && committed + needed <= self.max_size
+        };
+
+        if allowed && let Some(member) = self.members.get_mut(&member_id) {
+            member.waiting = false;
+        }
+
+        allowed
+    }
+}
+
+/// A pool of disk space shared by disk buffers.
+///
+/// Cloning the pool is cheap, and all clones refer to the same pool.
+#[derive(Clone, Debug, Default)]
+pub struct BufferPool {
+    state: Arc<Mutex<PoolState>>,
+}
+
+impl BufferPool {
+    /// Creates a new `BufferPool` with the given size, in bytes.
+    pub fn new(max_size: u64) -> Self {
+        let pool = Self::default();
+        pool.set_max_size(max_size);
+        pool
+    }
+
+    fn state(&self) -> MutexGuard<'_, PoolState> {
+        self.state
+            .lock()
+            .expect("buffer pool lock should not be poisoned")
+    }
+
+    /// Gets the size of the pool, in bytes.
+    pub fn max_size(&self) -> u64 {
+        self.state().max_size
+    }
+
+    /// Sets the size of the pool, in bytes.
+    ///
+    /// Members that are over their share of the pool after shrinking it aren't forced to shrink,
+    /// but they can't grow again until enough space has been freed up.
+    pub fn set_max_size(&self, max_size: u64) {
+        let mut state = self.state();
+        state.max_size = max_size;
+        state.notify_waiting_members();
+    }
+
+    /// Gets the total amount of space used by all members of the pool, in bytes.
+    pub fn usage(&self) -> u64 {
+        self.state()
+            .members
+            .values()
+            .map(|member| member.usage)
+            .sum()
+    }
+
+    /// Registers a new member of the pool with the given quota.
+    ///
+    /// The member is removed from the pool, freeing up all of the space it used, once the returned
+    /// handle, and all of its clones, are dropped.
+    pub fn register(&self, buffer_id: &str, quota: BufferPoolQuota) -> BufferPoolMember {
+        let notify = Arc::new(Notify::new());
+
+        let mut state = self.state();
+        let member_id = state.next_member_id;
+        state.next_member_id += 1;
+        state.members.insert(
+            member_id,
+            MemberState {
+                buffer_id: buffer_id.to_string(),
+                min_size: quota.min_size,
+                priority: quota.priority,
+                usage: 0,
+                waiting: false,
+                notify: Arc::clone(&notify),
+            },
+        );
+
+        BufferPoolMember {
+            inner: Arc::new(MemberHandle {
+                pool: self.clone(),
+                member_id,
+                notify,
+            }),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct MemberHandle {
+    pool: BufferPool,
+    member_id: u64,
+    notify: Arc<Notify>,
+}
+
+impl Drop for MemberHandle {
+    fn drop(&mut self) {
+        let mut state = self.pool.state();
+        state.members.remove(&self.member_id);
+        state.notify_waiting_members();
+    }
+}
+
+/// A handle to a member of a [`BufferPool`].
+#[derive(Clone, Debug)]
+pub struct BufferPoolMember {
+    inner: Arc<MemberHandle>,
+}
+
+impl BufferPoolMember {
+    fn with_state<R>(&self, f: impl FnOnce(&mut PoolState) -> R) -> R {
+        f(&mut self.inner.pool.state())
+    }
+
+    /// Tracks that the member is using `amount` more bytes of the pool.
+    pub(crate) fn add_usage(&self, amount: u64) {
+        self.with_state(|state| {
+            if let Some(member) = state.members.get_mut(&self.inner.member_id) {
+                member.usage += amount;
+            }
+        });
+    }
+
+    /// Tracks that the member is using `amount` fewer bytes of the pool.
+    ///
+    /// Any members waiting for space are woken up.
+    pub(crate) fn remove_usage(&self, amount: u64) {
+        self.with_state(|state| {
+            if let Some(member) = state.members.get_mut(&self.inner.member_id) {
+                member.usage = member.usage.saturating_sub(amount);
+            }
+            state.notify_waiting_members();
+        });
+    }
+
+    /// Checks whether the member is allowed to use `amount` more bytes of the pool.
+    ///
+    /// This doesn't track the space as being used, which is left to `add_usage` once the space has
+    /// actually been used.
+    pub(crate) fn try_reserve(&self, amount: u64) -> bool {
+        self.with_state(|state| {
+            let allowed = state.try_reserve(self.inner.member_id, amount);
+            if !allowed && let Some(member) = state.members.get(&self.inner.member_id) {
+                trace!(
+                    buffer_id = %member.buffer_id,
+                    usage = member.usage,
+                    amount,
+                    "Buffer pool has no space available for member."
+                );
+            }
+            allowed
+        })
+    }
+
+    /// Waits until space may have been freed up in the pool.
+    ///
+    /// While waiting, members with a lower priority are not allowed to grow beyond their minimum
+    /// size, until this member has been able to reserve space again.
+    pub(crate) async fn wait_for_space(&self) {
+        self.with_state(|state| {
+            if let Some(member) = state.members.get_mut(&self.inner.member_id) {
+                member.waiting = true;
+            }
+        });
+
+        self.inner.notify.notified().await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::FutureExt;
+    use tokio_test::{assert_pending, assert_ready, task::spawn};
+
+    use super::BufferPool;
+    use crate::config::BufferPoolQuota;
+
+    fn quota(min_size: u64, priority: u8) -> BufferPoolQuota {
+        BufferPoolQuota { min_size, priority }
+    }
+
+    #[test]
+    fn members_share_unreserved_space() {
+        let pool = BufferPool::new(100);
+        let a = pool.register("a", quota(0, 0));
+        let b = pool.register("b", quota(0, 0));
+
+        assert!(a.try_reserve(60));
+        a.add_usage(60);
+        assert!(!b.try_reserve(50));
+        assert!(b.try_reserve(40));
+        b.add_usage(40);
+        assert_eq!(pool.usage(), 100);
+
+        a.remove_usage(60);
+        assert!(b.try_reserve(50));
+    }
+
+    #[test]
+    fn min_size_is_reserved() {
+        let pool = BufferPool::new(100);
+        let a = pool.register("a", quota(0, 0));
+        let b = pool.register("b", quota(30, 0));
+
+        // The space reserved for `b` can't be used by `a`, even though `b` isn't using it yet.
+        assert!(!a.try_reserve(80));
+        assert!(a.try_reserve(70));
+        a.add_usage(70);
+
+        // `b` can grow up to its minimum size even though the rest of the pool is in use, but no
+        // further than that.
+        assert!(b.try_reserve(30));
+        b.add_usage(30);
+        assert!(!b.try_reserve(1));
+    }
+
+    #[test]
+    fn waiting_members_with_higher_priority_go_first() {
+        let pool = BufferPool::new(100);
+        let low = pool.register("low", quota(0, 0));
+        let high = pool.register("high", quota(0, 1));
+
+        assert!(low.try_reserve(100));
+        low.add_usage(100);
+        assert!(!high.try_reserve(10));
+
+        // The higher priority member starts waiting for space, which won't be available until
+        // some space is freed up.
+        assert!(high.wait_for_space().now_or_never().is_none());
+        low.remove_usage(50);
+
+        // The lower priority member can't use the space that was just freed up until the higher
+        // priority member has reserved space.
+        assert!(!low.try_reserve(20));
+        assert!(high.try_reserve(20));
+        high.add_usage(20);
+        assert!(low.try_reserve(20));
+    }
+
+    #[test]
+    fn freeing_up_space_wakes_waiting_members() {
+        let pool = BufferPool::new(100);
+        let a = pool.register("a", quota(0, 0));
+        let b = pool.register("b", quota(0, 0));
+
+        a.add_usage(100);
+        assert!(!b.try_reserve(10));
+
+        let mut wait = spawn(b.wait_for_space());
+        assert_pending!(wait.poll());
+
+        a.remove_usage(10);
+        assert!(wait.is_woken());
+        assert_ready!(wait.poll());
+        assert!(b.try_reserve(10));
+    }
+
+    #[test]
+    fn dropping_member_frees_up_its_space() {
+        let pool = BufferPool::new(100);
+        let a = pool.register("a", quota(50, 0));
+        let b = pool.register("b", quota(0, 0));
+
+        a.add_usage(80);
+        assert!(!b.try_reserve(30));
+
+        drop(a);
+        assert_eq!(pool.usage(), 0);
+        assert!(b.try_reserve(100));
+    }
+}
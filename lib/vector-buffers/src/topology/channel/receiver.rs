@@ -72,10 +72,15 @@ where
 /// for querying the overflow buffer as well.  The ordering of events when operating in "overflow"
 /// is undefined, as the receiver will try to manage polling both its own buffer, as well as the
 /// overflow buffer, in order to fairly balance throughput.
+///
+/// If the buffer has a priority lane, any items waiting in the lane are always returned before the
+/// items waiting in the rest of the buffer. Items are returned in the order they were sent within
+/// each of them.
 #[derive(Debug)]
 pub struct BufferReceiver<T: Bufferable> {
     base: ReceiverAdapter<T>,
     overflow: Option<Box<BufferReceiver<T>>>,
+    priority: Option<Box<BufferReceiver<T>>>,
     instrumentation: Option<BufferUsageHandle>,
 }
 
@@ -85,6 +90,7 @@ impl<T: Bufferable> BufferReceiver<T> {
         Self {
             base,
             overflow: None,
+            priority: None,
             instrumentation: None,
         }
     }
@@ -94,6 +100,7 @@ impl<T: Bufferable> BufferReceiver<T> {
         Self {
             base,
             overflow: Some(Box::new(overflow)),
+            priority: None,
             instrumentation: None,
         }
     }
@@ -112,44 +119,31 @@ impl<T: Bufferable> BufferReceiver<T> {
         self.instrumentation = Some(handle);
     }
 
+    /// Configures this receiver to return the items of the given priority lane first.
+    pub fn with_priority_lane(&mut self, lane: BufferReceiver<T>) {
+        self.priority = Some(Box::new(lane));
+    }
+
     #[async_recursion]
     pub async fn next(&mut self) -> Option<T> {
-        // We want to poll both our base and overflow receivers without waiting for one or the
-        // other to entirely drain before checking the other.  This ensures that we're fairly
-        // servicing both receivers, and avoiding stalls in one or the other.
-        //
-        // This is primarily important in situations where an overflow-triggering event has
-        // occurred, and is over, and items are flowing through the base receiver.  If we waited to
-        // entirely drain the overflow receiver, we might cause another small stall of the pipeline
-        // attached to the base receiver.
-        let overflow = self.overflow.as_mut().map(Pin::new);
-
-        let (item, from_base) = match overflow {
-            None => match self.base.next().await {
-                Some(item) => (item, true),
-                None => return None,
+        let Self {
+            base,
+            overflow,
+            priority,
+            instrumentation,
+        } = self;
+
+        match priority {
+            None => next_in_lane(base, overflow, instrumentation.as_ref()).await,
+            // The lane is polled first, so that its items are handed out as soon as they're
+            // available, while the rest of the buffer is only read when the lane is empty.
+            Some(priority) => select! {
+                biased;
+                Some(item) = priority.next() => Some(item),
+                Some(item) = next_in_lane(base, overflow, instrumentation.as_ref()) => Some(item),
+                else => None,
             },
-            Some(mut overflow) => {
-                select! {
-                    Some(item) = overflow.next() => (item, false),
-                    Some(item) = self.base.next() => (item, true),
-                    else => return None,
-                }
-            }
-        };
-
-        // If instrumentation is enabled, and we got the item from the base receiver, then and only
-        // then do we track sending the event out.
-        if let Some(handle) = self.instrumentation.as_ref()
-            && from_base
-        {
-            handle.increment_sent_event_count_and_byte_size(
-                item.event_count() as u64,
-                item.size_of() as u64,
-            );
         }
-
-        Some(item)
     }
 
     pub fn into_stream(self) -> BufferReceiverStream<T> {
@@ -157,6 +151,49 @@ impl<T: Bufferable> BufferReceiver<T> {
     }
 }
 
+async fn next_in_lane<T: Bufferable>(
+    base: &mut ReceiverAdapter<T>,
+    overflow: &mut Option<Box<BufferReceiver<T>>>,
+    instrumentation: Option<&BufferUsageHandle>,
+) -> Option<T> {
+    // We want to poll both our base and overflow receivers without waiting for one or the
+    // other to entirely drain before checking the other.  This ensures that we're fairly
+    // servicing both receivers, and avoiding stalls in one or the other.
+    //
+    // This is primarily important in situations where an overflow-triggering event has
+    // occurred, and is over, and items are flowing through the base receiver.  If we waited to
+    // entirely drain the overflow receiver, we might cause another small stall of the pipeline
+    // attached to the base receiver.
+    let overflow = overflow.as_mut().map(Pin::new);
+
+    let (item, from_base) = match overflow {
+        None => match base.next().await {
+            Some(item) => (item, true),
+            None => return None,
+        },
+        Some(mut overflow) => {
+            select! {
+                Some(item) = overflow.next() => (item, false),
+                Some(item) = base.next() => (item, true),
+                else => return None,
+            }
+        }
+    };
+
+    // If instrumentation is enabled, and we got the item from the base receiver, then and only
+    // then do we track sending the event out.
+    if let Some(handle) = instrumentation
+        && from_base
+    {
+        handle.increment_sent_event_count_and_byte_size(
+            item.event_count() as u64,
+            item.size_of() as u64,
+        );
+    }
+
+    Some(item)
+}
+
 #[allow(clippy::large_enum_variant)]
 enum StreamState<T: Bufferable> {
     Idle(BufferReceiver<T>),
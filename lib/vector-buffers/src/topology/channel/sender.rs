@@ -8,7 +8,7 @@ use vector_common::internal_event::{InternalEventHandle, Registered, register};
 
 use super::limited_queue::LimitedSender;
 use crate::{
-    BufferInstrumentation, Bufferable, WhenFull,
+    BufferInstrumentation, BufferPriority, Bufferable, WhenFull,
     buffer_usage_data::BufferUsageHandle,
     internal_events::BufferSendDuration,
     variants::disk_v2::{self, ProductionFilesystem},
@@ -112,16 +112,23 @@ where
 /// events when the internal channel is full.
 ///
 /// When creating a buffer sender/receiver pair, callers can specify the "when full" behavior of the
-/// sender.  This controls how events are handled when the internal channel is full.  Three modes
+/// sender.  This controls how events are handled when the internal channel is full.  Four modes
 /// are possible:
 /// - block
 /// - drop newest
+/// - drop oldest
 /// - overflow
 ///
 /// In "block" mode, callers are simply forced to wait until the channel has enough capacity to
 /// accept the event.  In "drop newest" mode, any event being sent when the channel is full will be
-/// dropped and proceed no further. In "overflow" mode, events will be sent to another buffer
-/// sender.  Callers can specify the overflow sender to use when constructing their buffers initially.
+/// dropped and proceed no further. In "drop oldest" mode, the channel itself drops its oldest
+/// events to make room, and callers only wait when there's nothing left to drop, which is only
+/// supported by disk buffers. In "overflow" mode, events will be sent to another buffer sender.
+/// Callers can specify the overflow sender to use when constructing their buffers initially.
+///
+/// A sender can also have a priority lane, which is a separate buffer that high-priority events are
+/// sent to instead, so that the matching `BufferReceiver` can hand them out ahead of the events
+/// already waiting in the buffer.
 ///
 /// TODO: We should eventually rework `BufferSender`/`BufferReceiver` so that they contain a vector
 /// of the fields we already have here, but instead of cascading via calling into `overflow`, we'd
@@ -139,6 +146,8 @@ pub struct BufferSender<T: Bufferable> {
     send_duration: Option<Registered<BufferSendDuration>>,
     #[derivative(Debug = "ignore")]
     custom_instrumentation: Option<Arc<dyn BufferInstrumentation<T>>>,
+    #[derivative(Debug = "ignore")]
+    priority: Option<(Arc<dyn BufferPriority<T>>, Box<BufferSender<T>>)>,
 }
 
 impl<T: Bufferable> BufferSender<T> {
@@ -151,6 +160,7 @@ impl<T: Bufferable> BufferSender<T> {
             usage_instrumentation: None,
             send_duration: None,
             custom_instrumentation: None,
+            priority: None,
         }
     }
 
@@ -163,6 +173,7 @@ impl<T: Bufferable> BufferSender<T> {
             usage_instrumentation: None,
             send_duration: None,
             custom_instrumentation: None,
+            priority: None,
         }
     }
 
@@ -191,6 +202,12 @@ impl<T: Bufferable> BufferSender<T> {
     pub fn with_custom_instrumentation(&mut self, instrumentation: impl BufferInstrumentation<T>) {
         self.custom_instrumentation = Some(Arc::new(instrumentation));
     }
+
+    /// Configures this sender to send the high-priority items, as determined by `priority`, to the
+    /// given priority lane instead.
+    pub fn with_priority_lane(&mut self, priority: impl BufferPriority<T>, lane: BufferSender<T>) {
+        self.priority = Some((Arc::new(priority), Box::new(lane)));
+    }
 }
 
 impl<T: Bufferable> BufferSender<T> {
@@ -213,6 +230,16 @@ impl<T: Bufferable> BufferSender<T> {
         if let Some(instrumentation) = self.custom_instrumentation.as_ref() {
             instrumentation.on_send(&mut item);
         }
+        if let Some((priority, lane)) = self.priority.as_mut() {
+            let (high, rest) = priority.split(item);
+            if let Some(high) = high {
+                lane.send(high, send_reference).await?;
+            }
+            match rest {
+                Some(rest) => item = rest,
+                None => return Ok(()),
+            }
+        }
         let item_sizing = self
             .usage_instrumentation
             .as_ref()
@@ -227,7 +254,9 @@ impl<T: Bufferable> BufferSender<T> {
                 .increment_received_event_count_and_byte_size(item_count as u64, item_size as u64);
         }
         match self.when_full {
-            WhenFull::Block => self.base.send(item).await?,
+            // Dropping the oldest events is handled by the buffer itself, which only ever waits
+            // for free space when there's nothing left that it can drop.
+            WhenFull::Block | WhenFull::DropOldest => self.base.send(item).await?,
             WhenFull::DropNewest => {
                 if self.base.try_send(item).await?.is_some() {
                     was_dropped = true;
@@ -270,6 +299,9 @@ impl<T: Bufferable> BufferSender<T> {
         if let Some(overflow) = self.overflow.as_mut() {
             overflow.flush().await?;
         }
+        if let Some((_, lane)) = self.priority.as_mut() {
+            lane.flush().await?;
+        }
 
         Ok(())
     }
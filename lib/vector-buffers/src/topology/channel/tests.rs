@@ -6,10 +6,10 @@ use std::{
 use tokio::{pin, sync::Barrier, time::sleep};
 
 use crate::{
-    Bufferable, WhenFull,
+    BufferPriority, Bufferable, WhenFull,
     topology::{
         channel::{BufferReceiver, BufferSender},
-        test_util::{assert_current_send_capacity, build_buffer},
+        test_util::{Sample, assert_current_send_capacity, build_buffer},
     },
 };
 
@@ -187,6 +187,34 @@ async fn test_sender_overflow_drop_newest() {
     assert_eq!(results, vec![1, 2, 7, 8]);
 }
 
+/// Treats even values as high priority.
+struct EvenPriority;
+
+impl BufferPriority<Sample> for EvenPriority {
+    fn split(&self, item: Sample) -> (Option<Sample>, Option<Sample>) {
+        match item {
+            Sample::SingleValue(value) if value % 2 == 0 => (Some(item), None),
+            item => (None, Some(item)),
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_sender_priority_lane() {
+    let (mut tx, mut rx, _) = build_buffer(10, WhenFull::Block, None);
+    let (lane_tx, lane_rx, _) = build_buffer(10, WhenFull::Block, None);
+    tx.with_priority_lane(EvenPriority, lane_tx);
+    rx.with_priority_lane(lane_rx);
+
+    for value in 1..=6_u64 {
+        assert!(tx.send(value.into(), None).await.is_ok());
+    }
+
+    // The high-priority items come out first, and each lane keeps the order items were sent in.
+    let results: Vec<u64> = drain_receiver(tx, rx).await;
+    assert_eq!(results, vec![2, 4, 6, 1, 3, 5]);
+}
+
 #[tokio::test]
 async fn test_buffer_metrics_normal() {
     // Get a regular blocking buffer.
@@ -7,10 +7,12 @@ use crc32fast::Hasher;
 use snafu::Snafu;
 
 use super::{
+    encryption::{ENCRYPTION_OVERHEAD, RecordCipher},
     io::{Filesystem, ProductionFilesystem},
     ledger::LEDGER_LEN,
     record::RECORD_HEADER_LEN,
 };
+use crate::{config::DiskBufferCompression, pool::BufferPoolMember};
 
 // We don't want data files to be bigger than 128MB, but we might end up overshooting slightly.
 pub const DEFAULT_MAX_DATA_FILE_SIZE: usize = 128 * 1024 * 1024;
@@ -153,6 +155,37 @@ pub struct DiskBufferConfig<FS> {
     /// amount of data written since the last flush would be lost.
     pub(crate) flush_interval: Duration,
 
+    /// Compression algorithm for records written to data files.
+    ///
+    /// Records are decompressed based on the algorithm they were written with, so this only
+    /// affects new records.
+    pub(crate) compression: DiskBufferCompression,
+
+    /// Cipher used to encrypt records before they are written to data files.
+    ///
+    /// When not set, records are written unencrypted.  Encrypted records can only be read when a
+    /// cipher holding the key they were encrypted with is set.
+    pub(crate) cipher: Option<RecordCipher>,
+
+    /// Whether or not the oldest unread data files are dropped when the buffer is full.
+    ///
+    /// When enabled, the writer deletes the oldest data file that the reader has not yet started
+    /// reading, rather than waiting for the reader to make progress.  The writer only waits when
+    /// there is no such data file.
+    pub(crate) drop_oldest: bool,
+
+    /// Maximum age of the data files in the buffer.
+    ///
+    /// Data files that were last written to longer ago than this, and that the reader has not yet
+    /// started reading, are deleted by the writer when it checks for expired data files.
+    pub(crate) max_age: Option<Duration>,
+
+    /// Buffer pool that this buffer draws its space from.
+    ///
+    /// When set, the writer waits for space in the pool, in addition to waiting for the buffer
+    /// itself to have enough space, before writing a record.
+    pub(crate) pool: Option<BufferPoolMember>,
+
     /// Filesystem implementation for opening data files.
     ///
     /// We allow parameterizing the filesystem implementation for ease of testing.  The "filesystem"
@@ -161,6 +194,13 @@ pub struct DiskBufferConfig<FS> {
     pub(crate) filesystem: FS,
 }
 
+impl<FS> DiskBufferConfig<FS> {
+    /// Whether or not data files can be deleted before the reader has read them.
+    pub(crate) fn expires_data_files(&self) -> bool {
+        self.drop_oldest || self.max_age.is_some()
+    }
+}
+
 /// Builder for [`DiskBufferConfig`].
 #[derive(Clone, Debug)]
 pub struct DiskBufferConfigBuilder<FS = ProductionFilesystem>
@@ -173,6 +213,11 @@ where
     pub(crate) max_record_size: Option<usize>,
     pub(crate) write_buffer_size: Option<usize>,
     pub(crate) flush_interval: Option<Duration>,
+    pub(crate) compression: Option<DiskBufferCompression>,
+    pub(crate) cipher: Option<RecordCipher>,
+    pub(crate) drop_oldest: bool,
+    pub(crate) max_age: Option<Duration>,
+    pub(crate) pool: Option<BufferPoolMember>,
     pub(crate) filesystem: FS,
 }
 
@@ -188,6 +233,11 @@ impl DiskBufferConfigBuilder {
             max_record_size: None,
             write_buffer_size: None,
             flush_interval: None,
+            compression: None,
+            cipher: None,
+            drop_oldest: false,
+            max_age: None,
+            pool: None,
             filesystem: ProductionFilesystem,
         }
     }
@@ -267,6 +317,60 @@ where
         self
     }
 
+    /// Sets the compression algorithm for records written to the buffer.
+    ///
+    /// Defaults to no compression.
+    pub fn compression(mut self, compression: DiskBufferCompression) -> Self {
+        self.compression = Some(compression);
+        self
+    }
+
+    /// Sets the cipher used to encrypt records at rest.
+    ///
+    /// Records already in the buffer that were written without encryption can still be read, as
+    /// can records encrypted with any of the previous keys held by the cipher.
+    ///
+    /// Defaults to no encryption.
+    pub fn cipher(mut self, cipher: RecordCipher) -> Self {
+        self.cipher = Some(cipher);
+        self
+    }
+
+    /// Sets whether or not the oldest unread data files are dropped when the buffer is full.
+    ///
+    /// Data files that the reader has already started reading, and the data file currently being
+    /// written to, are never dropped, so the writer still waits for the reader when only those data
+    /// files remain.
+    ///
+    /// Defaults to `false`.
+    pub fn drop_oldest(mut self, drop_oldest: bool) -> Self {
+        self.drop_oldest = drop_oldest;
+        self
+    }
+
+    /// Sets the maximum age of the data files in the buffer.
+    ///
+    /// Data files are considered expired once they were last written to longer ago than this.  Like
+    /// with `drop_oldest`, data files that the reader has already started reading, and the data
+    /// file currently being written to, are never dropped.
+    ///
+    /// Defaults to no maximum age.
+    pub fn max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = Some(max_age);
+        self
+    }
+
+    /// Sets the buffer pool that this buffer draws its space from.
+    ///
+    /// The maximum buffer size still applies, and acts as a cap on how much of the pool this buffer
+    /// can use.
+    ///
+    /// Defaults to no buffer pool.
+    pub fn pool(mut self, pool: BufferPoolMember) -> Self {
+        self.pool = Some(pool);
+        self
+    }
+
     /// Filesystem implementation for opening data files.
     ///
     /// We allow parameterizing the filesystem implementation for ease of testing.  The "filesystem"
@@ -286,6 +390,11 @@ where
             max_record_size: self.max_record_size,
             write_buffer_size: self.write_buffer_size,
             flush_interval: self.flush_interval,
+            compression: self.compression,
+            cipher: self.cipher,
+            drop_oldest: self.drop_oldest,
+            max_age: self.max_age,
+            pool: self.pool,
             filesystem,
         }
     }
@@ -300,6 +409,11 @@ where
         let max_record_size = self.max_record_size.unwrap_or(DEFAULT_MAX_RECORD_SIZE);
         let write_buffer_size = self.write_buffer_size.unwrap_or(DEFAULT_WRITE_BUFFER_SIZE);
         let flush_interval = self.flush_interval.unwrap_or(DEFAULT_FLUSH_INTERVAL);
+        let compression = self.compression.unwrap_or_default();
+        let cipher = self.cipher;
+        let drop_oldest = self.drop_oldest;
+        let max_age = self.max_age;
+        let pool = self.pool;
         let filesystem = self.filesystem;
 
         // Validate the input parameters.
@@ -343,6 +457,16 @@ where
             });
         }
 
+        if cipher.is_some() && max_record_size <= MINIMUM_MAX_RECORD_SIZE + ENCRYPTION_OVERHEAD {
+            return Err(BuildError::InvalidParameter {
+                param_name: "max_record_size",
+                reason: format!(
+                    "must be greater than or equal to {} bytes when encryption is enabled",
+                    MINIMUM_MAX_RECORD_SIZE + ENCRYPTION_OVERHEAD
+                ),
+            });
+        }
+
         let Ok(max_record_size_converted) = u64::try_from(max_record_size) else {
             return Err(BuildError::InvalidParameter {
                 param_name: "max_record_size",
@@ -364,6 +488,13 @@ where
             });
         }
 
+        if max_age.is_some_and(|max_age| max_age.is_zero()) {
+            return Err(BuildError::InvalidParameter {
+                param_name: "max_age",
+                reason: "cannot be zero".to_string(),
+            });
+        }
+
         // Users configure the `max_size` of their disk buffers, which translates to the `max_buffer_size` field here,
         // and represents the maximum desired size of a disk buffer in terms of on-disk usage. In order to meet this
         // request, we do a few things internally and also enforce a lower bound on `max_buffer_size` to ensure we can
@@ -411,6 +542,11 @@ where
             max_record_size,
             write_buffer_size,
             flush_interval,
+            compression,
+            cipher,
+            drop_oldest,
+            max_age,
+            pool,
             filesystem,
         })
     }
@@ -418,6 +554,8 @@ where
 
 #[cfg(test)]
 mod tests {
+    use std::time::Duration;
+
     use proptest::{prop_assert, proptest, test_runner::Config};
 
     use super::{
@@ -555,6 +693,19 @@ mod tests {
             ),
             _ => panic!("expected invalid parameter error"),
         }
+
+        // Maximum age cannot be zero.
+        let result = DiskBufferConfigBuilder::from_path("/tmp/dummy/path")
+            .max_age(Duration::ZERO)
+            .build();
+
+        match result {
+            Err(BuildError::InvalidParameter { param_name, .. }) => assert_eq!(
+                param_name, "max_age",
+                "invalid parameter should have been `max_age`"
+            ),
+            _ => panic!("expected invalid parameter error"),
+        }
     }
 
     proptest! {
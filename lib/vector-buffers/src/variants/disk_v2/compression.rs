@@ -0,0 +1,97 @@
+//! Compression of record payloads.
+//!
+//! When a disk buffer is configured with a compression algorithm, the encoded payload of every
+//! record is compressed before it is encrypted (if enabled) and wrapped in a
+//! [`Record`][super::record::Record].  The algorithm is stored in reserved bits of the record
+//! metadata, which means the reader never has to know how the buffer is configured: records are
+//! decompressed based on how they were written, so changing the algorithm, or disabling compression
+//! altogether, doesn't affect records already in the buffer.
+//!
+//! Payloads that don't shrink when compressed, which is common for small records, are stored
+//! uncompressed instead.
+//!
+//! As buffer size accounting is based on the size of records as written to data files, `max_size`
+//! applies to the compressed size of records.
+
+use std::io;
+
+use snafu::{ResultExt, Snafu};
+
+use crate::config::DiskBufferCompression;
+
+/// Shift of the bits of the record metadata that hold the compression algorithm.
+const COMPRESSION_SHIFT: u32 = 29;
+
+/// Bits of the record metadata that hold the compression algorithm of the payload.
+pub const COMPRESSION_BITS: u32 = 0b11 << COMPRESSION_SHIFT;
+
+const ZSTD_LEVEL: i32 = zstd::DEFAULT_COMPRESSION_LEVEL;
+
+/// Error that occurred while compressing or decompressing a record payload.
+#[derive(Debug, Snafu)]
+pub enum CompressionError {
+    /// The record metadata referred to a compression algorithm that isn't known.
+    #[snafu(display("record compressed with unknown algorithm {}", id))]
+    UnknownAlgorithm { id: u32 },
+
+    /// Compressing or decompressing with zstd failed.
+    #[snafu(display("zstd error: {}", source))]
+    Zstd { source: io::Error },
+
+    /// Decompressing with LZ4 failed.
+    #[snafu(display("lz4 error: {}", source))]
+    Lz4 {
+        source: lz4_flex::block::DecompressError,
+    },
+}
+
+/// Gets the metadata bits marking a payload as compressed with `compression`.
+pub(super) const fn metadata_bits(compression: DiskBufferCompression) -> u32 {
+    let id = match compression {
+        DiskBufferCompression::None => 0,
+        DiskBufferCompression::Zstd => 1,
+        DiskBufferCompression::Lz4 => 2,
+    };
+    id << COMPRESSION_SHIFT
+}
+
+/// Gets the compression algorithm referred to by the given record metadata.
+pub(super) fn from_metadata(metadata: u32) -> Result<DiskBufferCompression, CompressionError> {
+    match (metadata & COMPRESSION_BITS) >> COMPRESSION_SHIFT {
+        0 => Ok(DiskBufferCompression::None),
+        1 => Ok(DiskBufferCompression::Zstd),
+        2 => Ok(DiskBufferCompression::Lz4),
+        id => Err(CompressionError::UnknownAlgorithm { id }),
+    }
+}
+
+/// Compresses `payload` with `compression`, writing the compressed payload to `dst`.
+pub(super) fn compress(
+    compression: DiskBufferCompression,
+    payload: &[u8],
+    dst: &mut Vec<u8>,
+) -> Result<(), CompressionError> {
+    dst.clear();
+    match compression {
+        DiskBufferCompression::None => dst.extend_from_slice(payload),
+        DiskBufferCompression::Zstd => {
+            *dst = zstd::bulk::compress(payload, ZSTD_LEVEL).context(ZstdSnafu)?;
+        }
+        DiskBufferCompression::Lz4 => *dst = lz4_flex::block::compress_prepend_size(payload),
+    }
+    Ok(())
+}
+
+/// Decompresses a payload that was compressed with `compression`.
+pub(super) fn decompress(
+    compression: DiskBufferCompression,
+    payload: &[u8],
+) -> Result<Vec<u8>, CompressionError> {
+    match compression {
+        DiskBufferCompression::None => Ok(payload.to_vec()),
+        DiskBufferCompression::Zstd => zstd::stream::decode_all(payload).context(ZstdSnafu),
+        DiskBufferCompression::Lz4 => {
+            lz4_flex::block::decompress_size_prepended(payload).context(Lz4Snafu)
+        }
+    }
+}
@@ -0,0 +1,217 @@
+//! Encryption of record payloads at rest.
+//!
+//! When a disk buffer is configured with an encryption key, the encoded payload of every record is
+//! sealed with AES-256-GCM before being wrapped in a [`Record`][super::record::Record].  The record
+//! ID and metadata are used as associated data, so an encrypted payload cannot be moved to another
+//! record without failing authentication.
+//!
+//! ## Payload format
+//!
+//! ```text
+//! encrypted payload:
+//!   key_id:     uint8[4]  (first four bytes of SHA-256 of the key)
+//!   nonce:      uint8[12]
+//!   ciphertext: uint8[payload_len]
+//!   tag:        uint8[16]
+//! ```
+//!
+//! Embedding the key ID in each payload allows rotating keys: new records are always encrypted with
+//! the current key, while records written before the rotation are decrypted with whichever of the
+//! previous keys has a matching ID.  Records are only marked as encrypted through a reserved bit in
+//! their metadata, so records written before encryption was enabled can still be read as-is.
+
+use std::fmt;
+
+use base64::{Engine, engine::general_purpose::STANDARD};
+use openssl::{
+    error::ErrorStack,
+    rand::rand_bytes,
+    sha::sha256,
+    symm::{Cipher, decrypt_aead, encrypt_aead},
+};
+use snafu::{ResultExt, Snafu};
+
+/// Length, in bytes, of an encryption key.
+pub const KEY_LEN: usize = 32;
+
+const KEY_ID_LEN: usize = 4;
+const NONCE_LEN: usize = 12;
+const TAG_LEN: usize = 16;
+
+/// Number of bytes that encryption adds to the payload of a record.
+pub const ENCRYPTION_OVERHEAD: usize = KEY_ID_LEN + NONCE_LEN + TAG_LEN;
+
+/// Error that occurred while loading keys, or encrypting or decrypting a record payload.
+#[derive(Debug, Snafu)]
+pub enum EncryptionError {
+    /// An encryption key was not valid base64.
+    #[snafu(display("encryption key is not valid base64: {}", source))]
+    InvalidKeyEncoding { source: base64::DecodeError },
+
+    /// An encryption key did not decode to the expected number of bytes.
+    #[snafu(display("encryption key must be {} bytes, got {} bytes", KEY_LEN, len))]
+    InvalidKeyLength { len: usize },
+
+    /// A record is encrypted, but the buffer has no encryption key configured.
+    #[snafu(display("record is encrypted but no encryption key is configured"))]
+    MissingKey,
+
+    /// A record was encrypted with a key that is neither the current key nor a previous key.
+    #[snafu(display("record was encrypted with unknown key {:08x}", key_id))]
+    UnknownKey { key_id: u32 },
+
+    /// An encrypted payload was too short to hold the key ID, nonce and tag.
+    #[snafu(display("encrypted payload is truncated ({} bytes)", len))]
+    Truncated { len: usize },
+
+    /// The underlying cryptographic operation failed.
+    ///
+    /// When decrypting, this most likely means the payload did not pass authentication.
+    #[snafu(display("cryptographic operation failed: {}", source))]
+    Crypto { source: ErrorStack },
+}
+
+#[derive(Clone)]
+struct Key {
+    id: [u8; KEY_ID_LEN],
+    bytes: [u8; KEY_LEN],
+}
+
+impl Key {
+    fn from_base64(encoded: &str) -> Result<Self, EncryptionError> {
+        let decoded = STANDARD
+            .decode(encoded.trim())
+            .context(InvalidKeyEncodingSnafu)?;
+        let bytes: [u8; KEY_LEN] = decoded
+            .as_slice()
+            .try_into()
+            .map_err(|_| EncryptionError::InvalidKeyLength { len: decoded.len() })?;
+        let mut id = [0; KEY_ID_LEN];
+        id.copy_from_slice(&sha256(&bytes)[..KEY_ID_LEN]);
+
+        Ok(Self { id, bytes })
+    }
+}
+
+/// Encrypts and decrypts record payloads.
+///
+/// Holds the current key, used to encrypt new records, and any number of previous keys, which are
+/// only used to decrypt records written before the current key was rotated in.
+#[derive(Clone)]
+pub struct RecordCipher {
+    current: Key,
+    previous: Vec<Key>,
+}
+
+impl RecordCipher {
+    /// Creates a [`RecordCipher`] from base64-encoded keys.
+    ///
+    /// # Errors
+    ///
+    /// If any key is not valid base64, or does not decode to exactly [`KEY_LEN`] bytes, an error
+    /// variant will be returned describing the error.
+    pub fn from_base64<'a>(
+        current: &str,
+        previous: impl IntoIterator<Item = &'a str>,
+    ) -> Result<Self, EncryptionError> {
+        Ok(Self {
+            current: Key::from_base64(current)?,
+            previous: previous
+                .into_iter()
+                .map(Key::from_base64)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+
+    fn find_key(&self, id: &[u8]) -> Option<&Key> {
+        std::iter::once(&self.current)
+            .chain(&self.previous)
+            .find(|key| key.id == id)
+    }
+
+    /// Encrypts `payload` with the current key, writing the encrypted payload to `dst`.
+    pub(super) fn encrypt(
+        &self,
+        id: u64,
+        metadata: u32,
+        payload: &[u8],
+        dst: &mut Vec<u8>,
+    ) -> Result<(), EncryptionError> {
+        let mut nonce = [0; NONCE_LEN];
+        rand_bytes(&mut nonce).context(CryptoSnafu)?;
+
+        let mut tag = [0; TAG_LEN];
+        let ciphertext = encrypt_aead(
+            Cipher::aes_256_gcm(),
+            &self.current.bytes,
+            Some(&nonce),
+            &associated_data(id, metadata),
+            payload,
+            &mut tag,
+        )
+        .context(CryptoSnafu)?;
+
+        dst.clear();
+        dst.reserve(ENCRYPTION_OVERHEAD + ciphertext.len());
+        dst.extend_from_slice(&self.current.id);
+        dst.extend_from_slice(&nonce);
+        dst.extend_from_slice(&ciphertext);
+        dst.extend_from_slice(&tag);
+        Ok(())
+    }
+
+    /// Decrypts an encrypted payload with whichever configured key it was encrypted with.
+    pub(super) fn decrypt(
+        &self,
+        id: u64,
+        metadata: u32,
+        payload: &[u8],
+    ) -> Result<Vec<u8>, EncryptionError> {
+        if payload.len() < ENCRYPTION_OVERHEAD {
+            return Err(EncryptionError::Truncated { len: payload.len() });
+        }
+
+        let (key_id, rest) = payload.split_at(KEY_ID_LEN);
+        let (nonce, rest) = rest.split_at(NONCE_LEN);
+        let (ciphertext, tag) = rest.split_at(rest.len() - TAG_LEN);
+
+        let key = self
+            .find_key(key_id)
+            .ok_or_else(|| EncryptionError::UnknownKey {
+                key_id: u32::from_be_bytes(key_id.try_into().expect("key ID is four bytes")),
+            })?;
+
+        decrypt_aead(
+            Cipher::aes_256_gcm(),
+            &key.bytes,
+            Some(nonce),
+            &associated_data(id, metadata),
+            ciphertext,
+            tag,
+        )
+        .context(CryptoSnafu)
+    }
+}
+
+impl fmt::Debug for RecordCipher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RecordCipher")
+            .field("current", &hex_id(&self.current))
+            .field(
+                "previous",
+                &self.previous.iter().map(hex_id).collect::<Vec<_>>(),
+            )
+            .finish()
+    }
+}
+
+fn hex_id(key: &Key) -> String {
+    format!("{:08x}", u32::from_be_bytes(key.id))
+}
+
+fn associated_data(id: u64, metadata: u32) -> [u8; 12] {
+    let mut aad = [0; 12];
+    aad[..8].copy_from_slice(&id.to_be_bytes());
+    aad[8..].copy_from_slice(&metadata.to_be_bytes());
+    aad
+}
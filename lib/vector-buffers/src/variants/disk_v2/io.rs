@@ -1,4 +1,4 @@
-use std::{io, path::Path};
+use std::{io, path::Path, time::SystemTime};
 
 use tokio::{
     fs::OpenOptions,
@@ -11,6 +11,7 @@ const FILE_MODE_OWNER_RW_GROUP_RO: u32 = 0o640;
 /// File metadata.
 pub struct Metadata {
     pub(crate) len: u64,
+    pub(crate) modified: Option<SystemTime>,
 }
 
 impl Metadata {
@@ -18,6 +19,11 @@ impl Metadata {
     pub fn len(&self) -> u64 {
         self.len
     }
+
+    /// Gets the time the file was last modified, if the filesystem tracks it.
+    pub fn modified(&self) -> Option<SystemTime> {
+        self.modified
+    }
 }
 
 /// Generalized interface for opening and deleting files from a filesystem.
@@ -218,6 +224,7 @@ impl AsyncFile for tokio::fs::File {
         let metadata = self.metadata().await?;
         Ok(Metadata {
             len: metadata.len(),
+            modified: metadata.modified().ok(),
         })
     }
 
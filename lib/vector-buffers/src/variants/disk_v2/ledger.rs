@@ -15,7 +15,11 @@ use fslock::LockFile;
 use futures::StreamExt;
 use rkyv::{Archive, Serialize, with::Atomic};
 use snafu::{ResultExt, Snafu};
-use tokio::{fs, io::AsyncWriteExt, sync::Notify};
+use tokio::{
+    fs,
+    io::AsyncWriteExt,
+    sync::{Mutex, MutexGuard, Notify},
+};
 use vector_common::finalizer::OrderedFinalizer;
 
 use super::{
@@ -121,7 +125,7 @@ impl Default for LedgerState {
 }
 
 impl ArchivedLedgerState {
-    fn get_current_writer_file_id(&self) -> u16 {
+    pub(super) fn get_current_writer_file_id(&self) -> u16 {
         self.writer_current_data_file.load(Ordering::Acquire)
     }
 
@@ -143,7 +147,7 @@ impl ArchivedLedgerState {
         previous.wrapping_add(amount)
     }
 
-    fn get_current_reader_file_id(&self) -> u16 {
+    pub(super) fn get_current_reader_file_id(&self) -> u16 {
         self.reader_current_data_file.load(Ordering::Acquire)
     }
 
@@ -231,6 +235,10 @@ where
     last_flush: AtomicCell<Instant>,
     // Tracks usage data about the buffer.
     usage_handle: BufferUsageHandle,
+    // Held while the reader opens its next data file, or while the writer drops an expired one.
+    data_file_lock: Mutex<()>,
+    // Number of events dropped by expiring data files that the reader has yet to skip over.
+    expired_events: AtomicU64,
 }
 
 impl<FS> Ledger<FS>
@@ -280,6 +288,9 @@ where
     /// Increments the total number of bytes for all unread records in the buffer.
     pub fn increment_total_buffer_size(&self, amount: u64) {
         let last_total_buffer_size = self.total_buffer_size.fetch_add(amount, Ordering::AcqRel);
+        if let Some(pool) = self.config.pool.as_ref() {
+            pool.add_usage(amount);
+        }
         trace!(
             previous_buffer_size = last_total_buffer_size,
             new_buffer_size = last_total_buffer_size + amount,
@@ -290,6 +301,9 @@ where
     /// Decrements the total number of bytes for all unread records in the buffer.
     pub fn decrement_total_buffer_size(&self, amount: u64) {
         let last_total_buffer_size = self.total_buffer_size.fetch_sub(amount, Ordering::AcqRel);
+        if let Some(pool) = self.config.pool.as_ref() {
+            pool.remove_usage(amount);
+        }
         trace!(
             previous_buffer_size = last_total_buffer_size,
             new_buffer_size = last_total_buffer_size - amount,
@@ -307,6 +321,20 @@ where
         self.state().get_offset_reader_file_id(unacked_offset)
     }
 
+    /// Returns `true` if the given file ID is a data file the reader has moved past, but has not
+    /// yet deleted.
+    ///
+    /// These data files are still waiting for their records to be acknowledged, and their file IDs
+    /// are not safe to reuse even when the data file itself no longer exists.
+    pub fn is_unacked_reader_data_file(&self, file_id: u16) -> bool {
+        let acked_reader_file_id = u32::from(self.state().get_current_reader_file_id());
+        let unacked_offset = u32::from(self.unacked_reader_file_id_offset.load(Ordering::Acquire));
+        let max_file_id = u32::from(MAX_FILE_ID);
+
+        let distance = (u32::from(file_id) + max_file_id - acked_reader_file_id) % max_file_id;
+        distance < unacked_offset
+    }
+
     /// Gets the current writer file ID.
     pub fn get_current_writer_file_id(&self) -> u16 {
         self.state().get_current_writer_file_id()
@@ -353,6 +381,14 @@ where
             .join(format!("buffer-data-{file_id}.dat"))
     }
 
+    /// Locks the data files of the buffer.
+    ///
+    /// The reader holds this lock while opening its next data file, and the writer while dropping
+    /// an expired data file, so that a data file is never dropped after the reader has opened it.
+    pub async fn lock_data_files(&self) -> MutexGuard<'_, ()> {
+        self.data_file_lock.lock().await
+    }
+
     /// Waits for a signal from the reader that progress has been made.
     ///
     /// This will only occur when a record is read, which may allow enough space (below the maximum
@@ -389,6 +425,12 @@ where
             .increment_received_event_count_and_byte_size(event_count, record_size);
     }
 
+    /// Tracks the size of a record before and after it was compressed.
+    pub fn track_compression(&self, uncompressed_size: u64, compressed_size: u64) {
+        self.usage_handle
+            .increment_compressed_byte_size(uncompressed_size, compressed_size);
+    }
+
     /// Tracks the statistics of multiple successful reads.
     pub fn track_reads(&self, event_count: u64, total_record_size: u64) {
         self.decrement_total_buffer_size(total_record_size);
@@ -523,6 +565,30 @@ where
             );
     }
 
+    /// Tracks the statistics of a data file that was dropped before being read.
+    pub fn track_expired(&self, event_count: u64, data_file_size: u64) {
+        self.decrement_total_buffer_size(data_file_size);
+        self.expired_events.fetch_add(event_count, Ordering::AcqRel);
+        self.usage_handle
+            .increment_expired_event_count_and_byte_size(event_count, data_file_size);
+    }
+
+    /// Consumes up to `count` events from those dropped by expiring data files.
+    ///
+    /// As the reader only sees the events of an expired data file as missing, it uses this to tell
+    /// apart the events that were already accounted for when expiring from those that were lost.
+    /// The number of events in `count` that were not expired is returned.
+    pub fn consume_expired_events(&self, count: u64) -> u64 {
+        let expired = self
+            .expired_events
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |n| {
+                Some(n.saturating_sub(count))
+            })
+            .unwrap_or_else(|_| unreachable!("update always returns a value"));
+
+        count.saturating_sub(expired)
+    }
+
     pub fn track_dropped_events(&self, count: u64) {
         // We don't know how many bytes are represented by dropped events because we never actually had a chance to read
         // them, so we have to use a byte size of 0 here.
@@ -644,6 +710,8 @@ where
             unacked_reader_file_id_offset: AtomicU16::new(0),
             last_flush: AtomicCell::new(Instant::now()),
             usage_handle,
+            data_file_lock: Mutex::new(()),
+            expired_events: AtomicU64::new(0),
         };
         ledger.update_buffer_size().await?;
 
@@ -728,6 +796,10 @@ where
                 &self.unacked_reader_file_id_offset.load(Ordering::Acquire),
             )
             .field("writer_done", &self.writer_done.load(Ordering::Acquire))
+            .field(
+                "expired_events",
+                &self.expired_events.load(Ordering::Acquire),
+            )
             .field("last_flush", &self.last_flush.load())
             .finish_non_exhaustive()
     }
@@ -52,6 +52,21 @@
 //! potentially changed based on the payload that a record contains. The only safe way to access the
 //! records in a disk buffer should be through the reader/writer interface in this module.
 //!
+//! #### Encryption
+//!
+//! When an encryption key is configured, the payload is encrypted before the record is wrapped and
+//! checksummed, and a reserved bit in the record metadata marks it as such.  The checksum therefore
+//! covers the encrypted payload, and records can still be validated without the key.  See the
+//! `encryption` module for the layout of encrypted payloads and how keys are rotated.
+//!
+//! #### Compression
+//!
+//! When a compression algorithm is configured, the payload is compressed before it is encrypted,
+//! and the algorithm is stored in reserved bits of the record metadata.  Like encryption, this is
+//! entirely handled when archiving and decoding records, so the rest of the reader and the ledger
+//! only ever deal with records as they are stored, which means buffer size accounting is based on
+//! compressed sizes.
+//!
 //! ### Data files
 //!
 //! Data files contain the buffered records and nothing else. Records are written
@@ -137,6 +152,28 @@
 //! that the writer can make progress as records are acknowledged, even if the buffer is close to,
 //! or at the maximum buffer size limit.
 //!
+//! ### Expiring unread records
+//!
+//! When configured to drop the oldest records when full, or with a maximum age, the writer may also
+//! delete data files that the reader has not yet opened. Like acknowledged records, we only ever
+//! drop entire data files, and never the data files currently being read from or written to, which
+//! means that a buffer with only those two data files has nothing it can drop. As the reader may
+//! be stalled, for example when the sink is unable to send, the writer deletes the data files
+//! itself, with the ledger coordinating it against the reader opening its next data file.
+//!
+//! The number of events in a dropped data file is derived from the record ID of its first record
+//! and that of the next data file, and reported as intentionally dropped. The reader simply skips
+//! over missing data files, and sees the dropped records as a gap in record IDs, which keeps the
+//! reader's position in the ledger correct without reporting those events as dropped a second time.
+//!
+//! ### Buffer pools
+//!
+//! A buffer can also be a member of a buffer pool, which is shared with other buffers. The size of
+//! the buffer is tracked in the pool as it is tracked in the ledger, and before writing a record, the
+//! writer checks that the pool, and not only the buffer itself, has room for it. When only the pool
+//! is full, the writer waits for any member of the pool to free up space, rather than for its own
+//! reader to make progress.
+//!
 //! ### Record ID generation, and its relation of events
 //!
 //! While the buffer talks a lot about writing "records", records are ostensibly a single event, or
@@ -175,17 +212,22 @@ use std::{
     marker::PhantomData,
     num::NonZeroU64,
     path::{Path, PathBuf},
-    sync::Arc,
+    sync::{Arc, Weak},
+    time::Duration,
 };
 
 use async_trait::async_trait;
 use snafu::{ResultExt, Snafu};
-use vector_common::finalization::Finalizable;
+use tokio::{sync::Mutex, time::interval};
+use vector_common::{finalization::Finalizable, sensitive_string::SensitiveString};
 
 mod backed_archive;
 mod common;
+mod compression;
+mod encryption;
 mod io;
 mod ledger;
+pub mod offline;
 mod reader;
 mod record;
 mod ser;
@@ -197,6 +239,8 @@ mod tests;
 use self::ledger::Ledger;
 pub use self::{
     common::{DiskBufferConfig, DiskBufferConfigBuilder},
+    compression::CompressionError,
+    encryption::{EncryptionError, RecordCipher},
     io::{Filesystem, ProductionFilesystem},
     ledger::LedgerLoadCreateError,
     reader::{BufferReader, ReaderError},
@@ -205,6 +249,9 @@ pub use self::{
 use crate::{
     Bufferable,
     buffer_usage_data::BufferUsageHandle,
+    config::{DiskBufferCompression, DiskBufferEncryption},
+    pool::BufferPoolMember,
+    spawn_named,
     topology::{
         builder::IntoBuffer,
         channel::{ReceiverAdapter, SenderAdapter},
@@ -301,6 +348,11 @@ pub struct DiskV2Buffer {
     id: String,
     data_dir: PathBuf,
     max_size: NonZeroU64,
+    compression: DiskBufferCompression,
+    encryption: Option<DiskBufferEncryption>,
+    drop_oldest: bool,
+    max_age: Option<Duration>,
+    pool: Option<BufferPoolMember>,
 }
 
 impl DiskV2Buffer {
@@ -309,7 +361,86 @@ impl DiskV2Buffer {
             id,
             data_dir,
             max_size,
+            compression: DiskBufferCompression::None,
+            encryption: None,
+            drop_oldest: false,
+            max_age: None,
+            pool: None,
+        }
+    }
+
+    /// Sets the compression algorithm for records written to this buffer.
+    #[must_use]
+    pub fn with_compression(mut self, compression: DiskBufferCompression) -> Self {
+        self.compression = compression;
+        self
+    }
+
+    /// Sets the encryption configuration for records written to this buffer.
+    #[must_use]
+    pub fn with_encryption(mut self, encryption: Option<DiskBufferEncryption>) -> Self {
+        self.encryption = encryption;
+        self
+    }
+
+    /// Sets whether the oldest data files are dropped, instead of waiting, when this buffer is full.
+    #[must_use]
+    pub fn with_drop_oldest(mut self, drop_oldest: bool) -> Self {
+        self.drop_oldest = drop_oldest;
+        self
+    }
+
+    /// Sets the maximum age of the data in this buffer.
+    #[must_use]
+    pub fn with_max_age(mut self, max_age: Option<Duration>) -> Self {
+        self.max_age = max_age;
+        self
+    }
+
+    /// Sets the buffer pool that this buffer draws its space from.
+    #[must_use]
+    pub fn with_pool(mut self, pool: Option<BufferPoolMember>) -> Self {
+        self.pool = pool;
+        self
+    }
+
+    async fn build<T>(
+        &self,
+        usage_handle: BufferUsageHandle,
+    ) -> Result<
+        (
+            BufferWriter<T, ProductionFilesystem>,
+            BufferReader<T, ProductionFilesystem>,
+        ),
+        Box<dyn Error + Send + Sync>,
+    >
+    where
+        T: Bufferable + Clone,
+    {
+        usage_handle.set_buffer_limits(Some(self.max_size.get()), None);
+
+        let buffer_path = get_disk_v2_data_dir_path(&self.data_dir, &self.id);
+        let mut builder = DiskBufferConfigBuilder::from_path(buffer_path)
+            .max_buffer_size(self.max_size.get())
+            .compression(self.compression)
+            .drop_oldest(self.drop_oldest);
+        if let Some(max_age) = self.max_age {
+            builder = builder.max_age(max_age);
+        }
+        if let Some(pool) = self.pool.clone() {
+            builder = builder.pool(pool);
         }
+        if let Some(encryption) = self.encryption.as_ref() {
+            let cipher = RecordCipher::from_base64(
+                encryption.key.inner(),
+                encryption.previous_keys.iter().map(SensitiveString::inner),
+            )?;
+            builder = builder.cipher(cipher);
+        }
+        let config = builder.build()?;
+        Buffer::from_config(config, usage_handle)
+            .await
+            .map_err(Into::into)
     }
 }
 
@@ -326,42 +457,41 @@ where
         self: Box<Self>,
         usage_handle: BufferUsageHandle,
     ) -> Result<(SenderAdapter<T>, ReceiverAdapter<T>), Box<dyn Error + Send + Sync>> {
-        let (writer, reader) = build_disk_v2_buffer(
-            usage_handle,
-            &self.data_dir,
-            self.id.as_str(),
-            self.max_size,
-        )
-        .await?;
-
-        Ok((writer.into(), reader.into()))
+        let (writer, reader) = self.build(usage_handle).await?;
+        let writer = Arc::new(Mutex::new(writer));
+
+        if let Some(max_age) = self.max_age {
+            let task = expire_data_files(Arc::downgrade(&writer), max_age);
+            spawn_named(task, format!("disk buffer expiry ({})", self.id).as_str());
+        }
+
+        Ok((SenderAdapter::DiskV2(writer), reader.into()))
     }
 }
 
-async fn build_disk_v2_buffer<T>(
-    usage_handle: BufferUsageHandle,
-    data_dir: &Path,
-    id: &str,
-    max_size: NonZeroU64,
-) -> Result<
-    (
-        BufferWriter<T, ProductionFilesystem>,
-        BufferReader<T, ProductionFilesystem>,
-    ),
-    Box<dyn Error + Send + Sync>,
->
-where
-    T: Bufferable + Clone,
+/// Periodically drops the data files of a buffer that are older than `max_age`.
+///
+/// Files are checked at an interval of half of `max_age`, within reason, which bounds how long
+/// expired data can linger in the buffer. The task stops once the buffer writer has been dropped.
+async fn expire_data_files<T>(
+    writer: Weak<Mutex<BufferWriter<T, ProductionFilesystem>>>,
+    max_age: Duration,
+) where
+    T: Bufferable,
 {
-    usage_handle.set_buffer_limits(Some(max_size.get()), None);
-
-    let buffer_path = get_disk_v2_data_dir_path(data_dir, id);
-    let config = DiskBufferConfigBuilder::from_path(buffer_path)
-        .max_buffer_size(max_size.get())
-        .build()?;
-    Buffer::from_config(config, usage_handle)
-        .await
-        .map_err(Into::into)
+    let mut interval =
+        interval((max_age / 2).clamp(Duration::from_secs(1), Duration::from_secs(60)));
+    loop {
+        interval.tick().await;
+
+        let Some(writer) = writer.upgrade() else {
+            break;
+        };
+        let mut writer = writer.lock().await;
+        if let Err(error) = writer.expire_data_files_older_than(max_age).await {
+            error!(%error, "Failed to drop expired disk buffer data files.");
+        }
+    }
 }
 
 pub(crate) fn get_disk_v2_data_dir_path(base_dir: &Path, buffer_id: &str) -> PathBuf {
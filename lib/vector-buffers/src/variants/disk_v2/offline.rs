@@ -0,0 +1,409 @@
+//! Offline access to disk buffers.
+//!
+//! This is used to inspect and recover disk buffers while Vector isn't using them: for example, when
+//! a data file has been corrupted, or when the sink that a buffer belonged to was removed from the
+//! configuration, leaving its data stranded on disk.
+//!
+//! Opening a buffer takes the same lock that Vector takes while using the buffer, so a buffer can't
+//! be changed out from under a running Vector process, and vice versa.  Nothing in here modifies
+//! the ledger: when data files are truncated, or a buffer is moved, the usual recovery logic of the
+//! reader and writer takes care of bringing the ledger back in line with the data files the next
+//! time the buffer is loaded.
+use std::{
+    io,
+    path::{Path, PathBuf},
+};
+
+use fslock::LockFile;
+use rkyv::AlignedVec;
+use snafu::{ResultExt, Snafu};
+use tokio::fs::{self, File, OpenOptions};
+use vector_common::sensitive_string::SensitiveString;
+
+pub use super::reader::ReaderError;
+use super::{
+    backed_archive::BackedArchive,
+    common::MAX_FILE_ID,
+    encryption::{EncryptionError, RecordCipher},
+    get_disk_v2_data_dir_path,
+    ledger::LedgerState,
+    reader::RecordReader,
+};
+use crate::{Bufferable, config::DiskBufferEncryption};
+
+/// Error that occurred while accessing a disk buffer offline.
+#[derive(Debug, Snafu)]
+pub enum OfflineError {
+    /// A general I/O error occurred.
+    #[snafu(display("I/O error: {}", source))]
+    Io { source: io::Error },
+
+    /// The buffer directory doesn't exist.
+    #[snafu(display("no disk buffer found at {}", path.display()))]
+    BufferNotFound { path: PathBuf },
+
+    /// The buffer is currently being used by a Vector process.
+    #[snafu(display(
+        "failed to lock buffer.lock; is a Vector process running and using this buffer?"
+    ))]
+    BufferInUse,
+
+    /// The target of a move already exists.
+    #[snafu(display("a disk buffer already exists at {}", path.display()))]
+    BufferAlreadyExists { path: PathBuf },
+
+    /// The ledger state was unable to be deserialized.
+    #[snafu(display("failed to deserialize ledger from buffer: {}", reason))]
+    FailedToDeserializeLedger { reason: String },
+
+    /// The encryption keys for the buffer are not valid.
+    #[snafu(display("invalid encryption configuration: {}", source))]
+    InvalidEncryption { source: EncryptionError },
+}
+
+/// The state of a disk buffer, as recorded in its ledger.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct LedgerInfo {
+    /// The record ID that the writer will use for its next record.
+    pub writer_next_record_id: u64,
+    /// The ID of the data file currently being written to.
+    pub writer_current_data_file: u16,
+    /// The ID of the data file currently being read from.
+    pub reader_current_data_file: u16,
+    /// The ID of the last record that was read, and acknowledged, by the reader.
+    pub reader_last_record_id: u64,
+}
+
+impl LedgerInfo {
+    /// Gets the number of events that have been written to the buffer but not yet acknowledged.
+    pub fn unread_events(&self) -> u64 {
+        self.writer_next_record_id
+            .wrapping_sub(self.reader_last_record_id)
+            .wrapping_sub(1)
+    }
+
+    /// Whether or not the record with the given ID has already been read, and acknowledged.
+    ///
+    /// Acknowledged records are only left in the buffer until the reader finishes the data file
+    /// they're in, and will not be read again when the buffer is loaded.
+    pub fn is_acknowledged(&self, record_id: u64) -> bool {
+        record_id <= self.reader_last_record_id
+    }
+}
+
+/// A data file of a disk buffer.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DataFile {
+    /// The ID of the data file.
+    pub id: u16,
+    /// The path to the data file.
+    pub path: PathBuf,
+    /// The size of the data file, in bytes.
+    pub size: u64,
+}
+
+/// The location of a record within a data file.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct RecordInfo {
+    /// The ID of the record.
+    pub id: u64,
+    /// The offset of the record within the data file, in bytes, including its length delimiter.
+    pub offset: u64,
+    /// The length of the record, in bytes, including its length delimiter.
+    pub len: u64,
+}
+
+/// The result of scanning a data file.
+#[derive(Debug)]
+pub struct DataFileScan<T>
+where
+    T: Bufferable,
+{
+    /// The number of valid records in the data file.
+    pub records: u64,
+    /// The length of the data file, in bytes, up to the end of the last valid record.
+    pub valid_len: u64,
+    /// The error that stopped the scan before the end of the data file, if any.
+    ///
+    /// As the reader skips the rest of a data file once it hits an invalid record, any records
+    /// after this point will never be read.
+    pub error: Option<ReaderError<T>>,
+}
+
+impl<T> DataFileScan<T>
+where
+    T: Bufferable,
+{
+    /// Whether or not the data file has a corrupted tail.
+    pub fn is_corrupted(&self) -> bool {
+        self.error.is_some()
+    }
+}
+
+/// Gets the path to the directory of the disk buffer with the given ID.
+pub fn get_buffer_data_dir(data_dir: &Path, buffer_id: &str) -> PathBuf {
+    get_disk_v2_data_dir_path(data_dir, buffer_id)
+}
+
+/// Whether or not there is a disk buffer with the given ID.
+///
+/// # Errors
+///
+/// If an I/O error occurs while checking for the buffer directory, an error variant will be
+/// returned.
+pub async fn buffer_exists(data_dir: &Path, buffer_id: &str) -> Result<bool, OfflineError> {
+    fs::try_exists(get_buffer_data_dir(data_dir, buffer_id))
+        .await
+        .context(IoSnafu)
+}
+
+/// A disk buffer opened for offline access.
+///
+/// The buffer stays locked for as long as this value is alive.
+pub struct OfflineBuffer {
+    data_dir: PathBuf,
+    ledger: LedgerInfo,
+    cipher: Option<RecordCipher>,
+    _lock: LockFile,
+}
+
+impl OfflineBuffer {
+    /// Opens the disk buffer in the given directory.
+    ///
+    /// # Errors
+    ///
+    /// If the buffer doesn't exist, is in use, or its ledger can't be loaded, an error variant will
+    /// be returned describing the error.
+    pub async fn open<P: AsRef<Path>>(data_dir: P) -> Result<Self, OfflineError> {
+        let data_dir = data_dir.as_ref().to_path_buf();
+        if !fs::try_exists(data_dir.join("buffer.db"))
+            .await
+            .context(IoSnafu)?
+        {
+            return Err(OfflineError::BufferNotFound { path: data_dir });
+        }
+
+        let mut lock = LockFile::open(&data_dir.join("buffer.lock")).context(IoSnafu)?;
+        if !lock.try_lock().context(IoSnafu)? {
+            return Err(OfflineError::BufferInUse);
+        }
+
+        let ledger_bytes = fs::read(data_dir.join("buffer.db"))
+            .await
+            .context(IoSnafu)?;
+        let mut ledger_buf = AlignedVec::with_capacity(ledger_bytes.len());
+        ledger_buf.extend_from_slice(&ledger_bytes);
+        let ledger_state =
+            BackedArchive::<_, LedgerState>::from_backing(ledger_buf).map_err(|e| {
+                OfflineError::FailedToDeserializeLedger {
+                    reason: e.into_inner(),
+                }
+            })?;
+        let state = ledger_state.get_archive_ref();
+        let ledger = LedgerInfo {
+            writer_next_record_id: state.get_next_writer_record_id(),
+            writer_current_data_file: state.get_current_writer_file_id(),
+            reader_current_data_file: state.get_current_reader_file_id(),
+            reader_last_record_id: state.get_last_reader_record_id(),
+        };
+
+        Ok(Self {
+            data_dir,
+            ledger,
+            cipher: None,
+            _lock: lock,
+        })
+    }
+
+    /// Sets the encryption configuration used to decrypt records.
+    ///
+    /// # Errors
+    ///
+    /// If any of the keys are invalid, an error variant will be returned describing the error.
+    pub fn with_encryption(
+        mut self,
+        encryption: Option<&DiskBufferEncryption>,
+    ) -> Result<Self, OfflineError> {
+        self.cipher = encryption
+            .map(|encryption| {
+                RecordCipher::from_base64(
+                    encryption.key.inner(),
+                    encryption.previous_keys.iter().map(SensitiveString::inner),
+                )
+            })
+            .transpose()
+            .context(InvalidEncryptionSnafu)?;
+        Ok(self)
+    }
+
+    /// Gets the directory of the buffer.
+    pub fn data_dir(&self) -> &Path {
+        &self.data_dir
+    }
+
+    /// Gets the state of the buffer, as recorded in its ledger.
+    pub fn ledger(&self) -> &LedgerInfo {
+        &self.ledger
+    }
+
+    /// Gets the data files of the buffer, in the order that the reader reads them.
+    ///
+    /// # Errors
+    ///
+    /// If an I/O error occurs while listing the data files, an error variant will be returned.
+    pub async fn data_files(&self) -> Result<Vec<DataFile>, OfflineError> {
+        let mut data_files = Vec::new();
+        let mut entries = fs::read_dir(&self.data_dir).await.context(IoSnafu)?;
+        while let Some(entry) = entries.next_entry().await.context(IoSnafu)? {
+            let Some(id) = entry
+                .file_name()
+                .to_str()
+                .and_then(|name| name.strip_prefix("buffer-data-"))
+                .and_then(|name| name.strip_suffix(".dat"))
+                .and_then(|id| id.parse::<u16>().ok())
+            else {
+                continue;
+            };
+
+            let metadata = entry.metadata().await.context(IoSnafu)?;
+            data_files.push(DataFile {
+                id,
+                path: entry.path(),
+                size: metadata.len(),
+            });
+        }
+
+        // File IDs wrap around, so the reader starts at its current data file, and works its way
+        // up from there.
+        let reader_file_id = self.ledger.reader_current_data_file;
+        data_files.sort_by_key(|file| {
+            (u32::from(file.id) + u32::from(MAX_FILE_ID) - u32::from(reader_file_id))
+                % u32::from(MAX_FILE_ID)
+        });
+
+        Ok(data_files)
+    }
+
+    /// Verifies the records in the given data file, without decoding them.
+    ///
+    /// # Errors
+    ///
+    /// If an I/O error occurs while reading the data file, an error variant will be returned.
+    /// Invalid records are reported in the returned scan instead.
+    pub async fn verify_data_file<T>(
+        &self,
+        file: &DataFile,
+    ) -> Result<DataFileScan<T>, OfflineError>
+    where
+        T: Bufferable,
+    {
+        self.scan_data_file(file, None::<fn(RecordInfo, Result<T, ReaderError<T>>)>)
+            .await
+    }
+
+    /// Reads the records in the given data file, calling `visit` with each record.
+    ///
+    /// Records that fail to decode are passed to `visit` as errors, and don't stop the scan, as the
+    /// records after them can still be read.
+    ///
+    /// # Errors
+    ///
+    /// If an I/O error occurs while reading the data file, an error variant will be returned.
+    /// Invalid records are reported in the returned scan instead.
+    pub async fn read_data_file<T, F>(
+        &self,
+        file: &DataFile,
+        visit: F,
+    ) -> Result<DataFileScan<T>, OfflineError>
+    where
+        T: Bufferable,
+        F: FnMut(RecordInfo, Result<T, ReaderError<T>>),
+    {
+        self.scan_data_file(file, Some(visit)).await
+    }
+
+    async fn scan_data_file<T, F>(
+        &self,
+        file: &DataFile,
+        mut visit: Option<F>,
+    ) -> Result<DataFileScan<T>, OfflineError>
+    where
+        T: Bufferable,
+        F: FnMut(RecordInfo, Result<T, ReaderError<T>>),
+    {
+        let data_file = File::open(&file.path).await.context(IoSnafu)?;
+        let mut reader = RecordReader::new(data_file).with_cipher(self.cipher.clone());
+
+        let mut scan = DataFileScan {
+            records: 0,
+            valid_len: 0,
+            error: None,
+        };
+        loop {
+            // Nothing is writing to the data file, so anything short of a full record is a partial
+            // write, and not a record that is still being written.
+            match reader.try_next_record(true).await {
+                Ok(None) => break,
+                Ok(Some(token)) => {
+                    let record = RecordInfo {
+                        id: token.record_id(),
+                        offset: scan.valid_len,
+                        len: token.record_bytes() as u64,
+                    };
+                    scan.records += 1;
+                    scan.valid_len += record.len;
+
+                    if let Some(visit) = visit.as_mut() {
+                        visit(record, reader.read_record(token));
+                    }
+                }
+                Err(ReaderError::Io { source }) => return Err(OfflineError::Io { source }),
+                Err(error) => {
+                    scan.error = Some(error);
+                    break;
+                }
+            }
+        }
+
+        Ok(scan)
+    }
+
+    /// Truncates the given data file to `len` bytes.
+    ///
+    /// This is meant to cut off a corrupted tail, using the valid length from a scan of the data
+    /// file, so that the records before it can still be read.
+    ///
+    /// # Errors
+    ///
+    /// If an I/O error occurs while truncating the data file, an error variant will be returned.
+    pub async fn truncate_data_file(&self, file: &DataFile, len: u64) -> Result<(), OfflineError> {
+        let data_file = OpenOptions::new()
+            .write(true)
+            .open(&file.path)
+            .await
+            .context(IoSnafu)?;
+        data_file.set_len(len).await.context(IoSnafu)?;
+        data_file.sync_all().await.context(IoSnafu)
+    }
+}
+
+/// Moves the disk buffer with the ID `from` so that it's used by the sink with the ID `to`.
+///
+/// The buffer is locked while checking that it's not in use, but the move itself happens after the
+/// lock is released, as the lock file moves along with the buffer.
+///
+/// # Errors
+///
+/// If the buffer doesn't exist, is in use, or there's already a buffer with the ID `to`, an error
+/// variant will be returned describing the error.
+pub async fn move_buffer(data_dir: &Path, from: &str, to: &str) -> Result<(), OfflineError> {
+    let source = get_buffer_data_dir(data_dir, from);
+    let target = get_buffer_data_dir(data_dir, to);
+    if fs::try_exists(&target).await.context(IoSnafu)? {
+        return Err(OfflineError::BufferAlreadyExists { path: target });
+    }
+
+    drop(OfflineBuffer::open(&source).await?);
+
+    fs::rename(&source, &target).await.context(IoSnafu)
+}
@@ -1,4 +1,5 @@
 use std::{
+    borrow::Cow,
     cmp, fmt,
     io::{self, ErrorKind},
     marker::PhantomData,
@@ -16,11 +17,14 @@ use vector_common::{finalization::BatchNotifier, finalizer::OrderedFinalizer};
 use super::{
     Filesystem,
     common::create_crc32c_hasher,
+    compression::{self, CompressionError},
+    encryption::{EncryptionError, RecordCipher},
     ledger::Ledger,
     record::{ArchivedRecord, Record, RecordStatus, validate_record_archive},
 };
 use crate::{
     Bufferable,
+    config::DiskBufferCompression,
     encoding::{AsMetadata, Encodable},
     internal_events::BufferReadError,
     topology::acks::{EligibleMarker, EligibleMarkerLength, MarkerError, OrderedAcknowledgements},
@@ -100,6 +104,20 @@ where
         source: <T as Encodable>::DecodeError,
     },
 
+    /// The record payload could not be decompressed.
+    ///
+    /// At this stage, the record can be assumed to have been written correctly, and read correctly
+    /// from disk, as the checksum was also validated.
+    #[snafu(display("failed to decompress record: {}", source))]
+    Decompression { source: CompressionError },
+
+    /// The record payload could not be decrypted.
+    ///
+    /// This can occur when the key a record was encrypted with is no longer configured, either as
+    /// the current key or as a previous key, or when the encrypted payload failed authentication.
+    #[snafu(display("failed to decrypt record: {}", source))]
+    Decryption { source: EncryptionError },
+
     /// The record is not compatible with this version of Vector.
     ///
     /// This can occur when records written to a buffer in previous versions of Vector are read by
@@ -144,6 +162,8 @@ where
             ReaderError::Deserialization { .. } => "deser_failed",
             ReaderError::Checksum { .. } => "checksum_mismatch",
             ReaderError::Decode { .. } => "decode_failed",
+            ReaderError::Decompression { .. } => "decompression_failed",
+            ReaderError::Decryption { .. } => "decryption_failed",
             ReaderError::Incompatible { .. } => "incompatible_record_version",
             ReaderError::PartialWrite => "partial_write",
             ReaderError::EmptyRecord => "empty_record",
@@ -159,6 +179,8 @@ where
             ReaderError::Deserialization { .. }
             | ReaderError::Checksum { .. }
             | ReaderError::Decode { .. }
+            | ReaderError::Decompression { .. }
+            | ReaderError::Decryption { .. }
             | ReaderError::Incompatible { .. }
             | ReaderError::PartialWrite => Some(BufferReadError { error_code, error }),
         }
@@ -199,6 +221,7 @@ pub(super) struct RecordReader<R, T> {
     reader: BufReader<R>,
     aligned_buf: AlignedVec,
     checksummer: Hasher,
+    cipher: Option<RecordCipher>,
     current_record_id: u64,
     _t: PhantomData<T>,
 }
@@ -217,11 +240,18 @@ where
             reader: BufReader::with_capacity(256 * 1024, reader),
             aligned_buf: AlignedVec::new(),
             checksummer: create_crc32c_hasher(),
+            cipher: None,
             current_record_id: 0,
             _t: PhantomData,
         }
     }
 
+    /// Sets the cipher used to decrypt encrypted records.
+    pub fn with_cipher(mut self, cipher: Option<RecordCipher>) -> Self {
+        self.cipher = cipher;
+        self
+    }
+
     #[cfg_attr(test, instrument(skip(self), level = "trace"))]
     async fn read_length_delimiter(
         &mut self,
@@ -374,7 +404,7 @@ where
         // - `try_next_record` does all the archive checks, checksum validation, etc
         let record = unsafe { archived_root::<Record<'_>>(&self.aligned_buf) };
 
-        decode_record_payload(record)
+        decode_record_payload(record, self.cipher.as_ref())
     }
 }
 
@@ -387,6 +417,7 @@ where
             .field("reader", &self.reader)
             .field("aligned_buf", &self.aligned_buf)
             .field("checksummer", &self.checksummer)
+            .field("cipher", &self.cipher)
             .field("current_record_id", &self.current_record_id)
             .finish()
     }
@@ -511,40 +542,53 @@ where
         // occur at all, so we're relying on this method to correct the buffer size for us.  This is
         // why `bytes_read` is optional: when it's specified, we calculate a delta for handling
         // partial-read scenarios, otherwise, we just use the entire data file size as is.
-        let data_file = self
+        //
+        // The data file may also have been dropped by the writer before we ever opened it, in which
+        // case it was already accounted for when dropped, and there's nothing left to delete.
+        let data_file = match self
             .ledger
             .filesystem()
             .open_file_readable(&data_file_path)
-            .await?;
-        let metadata = data_file.metadata().await?;
-
-        let decrease_amount = bytes_read.map_or_else(
-            || metadata.len(),
-            |bytes_read| {
-                let size_delta = metadata.len() - bytes_read;
-                if size_delta > 0 {
-                    debug!(
-                        actual_file_size = metadata.len(),
-                        bytes_read,
-                        "Data file was only partially read. Adjusting buffer size to compensate.",
-                    );
-                }
+            .await
+        {
+            Ok(data_file) => Some(data_file),
+            Err(e) if e.kind() == ErrorKind::NotFound => None,
+            Err(e) => return Err(e),
+        };
 
-                size_delta
-            },
-        );
+        if let Some(data_file) = data_file {
+            let metadata = data_file.metadata().await?;
+
+            let decrease_amount = bytes_read.map_or_else(
+                || metadata.len(),
+                |bytes_read| {
+                    let size_delta = metadata.len() - bytes_read;
+                    if size_delta > 0 {
+                        debug!(
+                            actual_file_size = metadata.len(),
+                            bytes_read,
+                            "Data file was only partially read. Adjusting buffer size to compensate.",
+                        );
+                    }
 
-        if decrease_amount > 0 {
-            self.ledger.decrement_total_buffer_size(decrease_amount);
-        }
+                    size_delta
+                },
+            );
 
-        drop(data_file);
+            if decrease_amount > 0 {
+                self.ledger.decrement_total_buffer_size(decrease_amount);
+            }
 
-        // Delete the current data file, and increment our actual reader file ID.
-        self.ledger
-            .filesystem()
-            .delete_file(&data_file_path)
-            .await?;
+            drop(data_file);
+
+            // Delete the current data file.
+            self.ledger
+                .filesystem()
+                .delete_file(&data_file_path)
+                .await?;
+        }
+
+        // Increment our actual reader file ID.
         self.ledger.increment_acked_reader_file_id();
         self.ledger.flush()?;
 
@@ -634,9 +678,14 @@ where
                     .add_acknowledgements(records_acknowledged);
             }
 
-            // If any events were skipped, do our logging/metrics for that.
+            // If any events were skipped, do our logging/metrics for that, leaving out any events
+            // that were skipped because their data file expired, as those were already tracked
+            // when the data file was dropped.
             if events_skipped > 0 {
-                self.ledger.track_dropped_events(events_skipped);
+                let events_lost = self.ledger.consume_expired_events(events_skipped);
+                if events_lost > 0 {
+                    self.ledger.track_dropped_events(events_lost);
+                }
             }
         }
 
@@ -753,6 +802,9 @@ where
         // we'll simply wait for the writer to signal to us that progress has been made, which
         // implies a data file existing.
         loop {
+            // Make sure the writer can't drop the data file out from under us while we open it.
+            let data_files_guard = self.ledger.lock_data_files().await;
+
             let (reader_file_id, writer_file_id) = self.ledger.get_current_reader_writer_file_id();
             let data_file_path = self.ledger.get_current_reader_data_file_path();
             let data_file = match self
@@ -772,7 +824,16 @@ where
                                 data_file_path = data_file_path.to_string_lossy().as_ref(),
                                 "Data file does not yet exist. Waiting for writer to create."
                             );
+                            drop(data_files_guard);
                             self.ledger.wait_for_writer().await;
+                        } else if self.ready_to_read {
+                            // The writer dropped this data file before we got to it, so skip over
+                            // it, but only delete it once the data files before it are deleted.
+                            debug!(
+                                data_file_path = data_file_path.to_string_lossy().as_ref(),
+                                "Data file was dropped before being read. Skipping."
+                            );
+                            self.roll_to_next_data_file();
                         } else {
                             self.ledger.increment_acked_reader_file_id();
                         }
@@ -788,7 +849,8 @@ where
                 "Opened data file for reading."
             );
 
-            self.reader = Some(RecordReader::new(data_file));
+            self.reader =
+                Some(RecordReader::new(data_file).with_cipher(self.ledger.config().cipher.clone()));
             return Ok(());
         }
     }
@@ -854,7 +916,8 @@ where
                     let record = try_as_record_archive(data_file_mmap.as_ref())
                         .expect("record was already validated");
 
-                    let Ok(item) = decode_record_payload::<T>(record) else {
+                    let cipher = self.ledger.config().cipher.as_ref();
+                    let Ok(item) = decode_record_payload::<T>(record, cipher) else {
                         // If there's an error decoding the item, just fall back to the slow path,
                         // because this file might actually be where we left off, so we don't want
                         // to incorrectly skip ahead or anything.
@@ -1134,6 +1197,7 @@ where
 
 pub(crate) fn decode_record_payload<T: Bufferable>(
     record: &ArchivedRecord<'_>,
+    cipher: Option<&RecordCipher>,
 ) -> Result<T, ReaderError<T>> {
     // Try and convert the raw record metadata into the true metadata type used by `T`, and then
     // also verify that `T` is able to decode records with the metadata used for this record in particular.
@@ -1150,6 +1214,25 @@ pub(crate) fn decode_record_payload<T: Bufferable>(
         });
     }
 
+    // Records describe how their payload is stored, rather than relying on how the buffer is
+    // currently configured, so that records written before enabling or changing encryption or
+    // compression can still be read.  Payloads are compressed first, and then encrypted.
+    let mut payload = Cow::Borrowed(record.payload());
+    if record.is_encrypted() {
+        let decrypted = cipher
+            .ok_or(EncryptionError::MissingKey)
+            .and_then(|cipher| cipher.decrypt(record.id(), record.metadata(), &payload))
+            .context(DecryptionSnafu)?;
+        payload = Cow::Owned(decrypted);
+    }
+
+    let compression = record.compression().context(DecompressionSnafu)?;
+    if compression != DiskBufferCompression::None {
+        let decompressed =
+            compression::decompress(compression, &payload).context(DecompressionSnafu)?;
+        payload = Cow::Owned(decompressed);
+    }
+
     // Now we can finally try decoding.
-    T::decode(metadata, record.payload()).context(DecodeSnafu)
+    T::decode(metadata, &payload[..]).context(DecodeSnafu)
 }
@@ -10,11 +10,23 @@ use rkyv::{
 
 use super::{
     common::align16,
+    compression::{self, COMPRESSION_BITS, CompressionError},
     ser::{DeserializeError, try_as_archive},
 };
+use crate::config::DiskBufferCompression;
 
 pub const RECORD_HEADER_LEN: usize = align16(mem::size_of::<ArchivedRecord<'_>>() + 8);
 
+/// Bit of the record metadata marking the payload as encrypted.
+///
+/// `Encodable` metadata is never expected to use this bit, so it is masked out before the metadata
+/// is handed back to the decoder.  Older versions of Vector, which don't mask it, will see encrypted
+/// records as incompatible rather than trying to decode them.
+pub const ENCRYPTED_RECORD_FLAG: u32 = 1 << 31;
+
+/// Bits of the record metadata reserved for describing how the payload is stored.
+const RESERVED_METADATA_BITS: u32 = ENCRYPTED_RECORD_FLAG | COMPRESSION_BITS;
+
 /// Result of checking if a buffer contained a valid record.
 pub enum RecordStatus {
     /// The record was able to be read from the buffer, and the checksum is valid.
@@ -130,9 +142,28 @@ impl<'a> Record<'a> {
 }
 
 impl ArchivedRecord<'_> {
+    /// Gets the record ID of this record.
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
     /// Gets the metadata of this record.
     pub fn metadata(&self) -> u32 {
-        self.metadata
+        self.metadata & !RESERVED_METADATA_BITS
+    }
+
+    /// Whether or not the payload of this record is encrypted.
+    pub fn is_encrypted(&self) -> bool {
+        self.metadata & ENCRYPTED_RECORD_FLAG != 0
+    }
+
+    /// Gets the compression algorithm the payload of this record was compressed with.
+    ///
+    /// # Errors
+    ///
+    /// If the record refers to an unknown compression algorithm, an error variant will be returned.
+    pub fn compression(&self) -> Result<DiskBufferCompression, CompressionError> {
+        compression::from_metadata(self.metadata)
     }
 
     /// Gets the payload of this record.
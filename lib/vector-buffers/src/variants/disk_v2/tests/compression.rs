@@ -0,0 +1,149 @@
+use std::{path::Path, sync::Arc};
+
+use base64::{Engine, engine::general_purpose::STANDARD};
+
+use super::{FilesystemUnderTest, create_default_buffer_v2, read_next_some};
+use crate::{
+    buffer_usage_data::BufferUsageHandle,
+    config::DiskBufferCompression,
+    test::{SizedRecord, acknowledge, with_temp_dir},
+    variants::disk_v2::{
+        Buffer, BufferReader, BufferWriter, DiskBufferConfigBuilder, Ledger, RecordCipher,
+    },
+};
+
+async fn create_buffer_v2_with_compression<P: AsRef<Path>>(
+    data_dir: P,
+    compression: DiskBufferCompression,
+    cipher: Option<RecordCipher>,
+) -> (
+    BufferWriter<SizedRecord, FilesystemUnderTest>,
+    BufferReader<SizedRecord, FilesystemUnderTest>,
+    Arc<Ledger<FilesystemUnderTest>>,
+) {
+    let mut builder = DiskBufferConfigBuilder::from_path(data_dir).compression(compression);
+    if let Some(cipher) = cipher {
+        builder = builder.cipher(cipher);
+    }
+    let config = builder.build().expect("creating buffer should not fail");
+
+    Buffer::from_config_inner(config, BufferUsageHandle::noop())
+        .await
+        .expect("should not fail to create buffer")
+}
+
+async fn buffered_size_of_record(compression: DiskBufferCompression) -> u64 {
+    with_temp_dir(|dir| {
+        let data_dir = dir.to_path_buf();
+
+        async move {
+            let (mut writer, mut reader, ledger) =
+                create_buffer_v2_with_compression(data_dir, compression, None).await;
+            writer
+                .write_record(SizedRecord::new(4096))
+                .await
+                .expect("write should not fail");
+            writer.flush().await.expect("flush should not fail");
+            let buffer_size = ledger.get_total_buffer_size();
+
+            let record = read_next_some(&mut reader).await;
+            assert_eq!(SizedRecord::new(4096), record);
+            acknowledge(record).await;
+
+            buffer_size
+        }
+    })
+    .await
+}
+
+#[tokio::test]
+async fn compressed_records_count_compressed_size_towards_buffer_size() {
+    let uncompressed = buffered_size_of_record(DiskBufferCompression::None).await;
+    let zstd = buffered_size_of_record(DiskBufferCompression::Zstd).await;
+    let lz4 = buffered_size_of_record(DiskBufferCompression::Lz4).await;
+
+    // `SizedRecord` encodes its payload as a run of 0x42 bytes, which compresses extremely well.
+    assert!(uncompressed > 4096);
+    assert!(zstd < uncompressed / 4);
+    assert!(lz4 < uncompressed / 4);
+}
+
+#[tokio::test]
+async fn records_stay_readable_when_changing_compression() {
+    with_temp_dir(|dir| {
+        let data_dir = dir.to_path_buf();
+
+        async move {
+            let (mut writer, reader, ledger) = create_default_buffer_v2(data_dir.clone()).await;
+            writer
+                .write_record(SizedRecord::new(32))
+                .await
+                .expect("write should not fail");
+            writer.flush().await.expect("flush should not fail");
+            drop(writer);
+            drop(reader);
+            drop(ledger);
+
+            let (mut writer, reader, ledger) = create_buffer_v2_with_compression(
+                data_dir.clone(),
+                DiskBufferCompression::Zstd,
+                None,
+            )
+            .await;
+            writer
+                .write_record(SizedRecord::new(1024))
+                .await
+                .expect("write should not fail");
+            writer.flush().await.expect("flush should not fail");
+            drop(writer);
+            drop(reader);
+            drop(ledger);
+
+            // The reader decompresses records based on how they were written, not on how the buffer
+            // is currently configured.
+            let (mut writer, mut reader, _ledger) =
+                create_buffer_v2_with_compression(data_dir, DiskBufferCompression::Lz4, None).await;
+            writer
+                .write_record(SizedRecord::new(2048))
+                .await
+                .expect("write should not fail");
+            writer.flush().await.expect("flush should not fail");
+
+            for expected in [32, 1024, 2048] {
+                let record = read_next_some(&mut reader).await;
+                assert_eq!(SizedRecord::new(expected), record);
+                acknowledge(record).await;
+            }
+        }
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn compressed_records_can_be_encrypted() {
+    with_temp_dir(|dir| {
+        let data_dir = dir.to_path_buf();
+
+        async move {
+            let cipher = RecordCipher::from_base64(&STANDARD.encode([1; 32]), [])
+                .expect("key should be valid");
+            let (mut writer, mut reader, _ledger) = create_buffer_v2_with_compression(
+                data_dir,
+                DiskBufferCompression::Zstd,
+                Some(cipher),
+            )
+            .await;
+
+            writer
+                .write_record(SizedRecord::new(4096))
+                .await
+                .expect("write should not fail");
+            writer.flush().await.expect("flush should not fail");
+
+            let record = read_next_some(&mut reader).await;
+            assert_eq!(SizedRecord::new(4096), record);
+            acknowledge(record).await;
+        }
+    })
+    .await;
+}
@@ -0,0 +1,162 @@
+use base64::{Engine, engine::general_purpose::STANDARD};
+
+use super::{create_buffer_v2_with_cipher, create_default_buffer_v2, read_next_some};
+use crate::{
+    buffer_usage_data::BufferUsageHandle,
+    test::{SizedRecord, acknowledge, with_temp_dir},
+    variants::disk_v2::{Buffer, DiskBufferConfigBuilder, RecordCipher},
+};
+
+fn key(byte: u8) -> String {
+    STANDARD.encode([byte; 32])
+}
+
+fn cipher(current: u8, previous: &[u8]) -> RecordCipher {
+    let previous = previous.iter().map(|byte| key(*byte)).collect::<Vec<_>>();
+    RecordCipher::from_base64(&key(current), previous.iter().map(String::as_str))
+        .expect("keys should be valid")
+}
+
+#[tokio::test]
+async fn encrypted_records_are_not_written_in_plaintext() {
+    with_temp_dir(|dir| {
+        let data_dir = dir.to_path_buf();
+
+        async move {
+            let (mut writer, mut reader, ledger) =
+                create_buffer_v2_with_cipher(data_dir, cipher(1, &[])).await;
+
+            // `SizedRecord` encodes its payload as a run of 0x42 bytes, which would be plainly
+            // visible in the data file if the record wasn't encrypted.
+            writer
+                .write_record(SizedRecord::new(4096))
+                .await
+                .expect("write should not fail");
+            writer.flush().await.expect("flush should not fail");
+
+            let data_file = tokio::fs::read(ledger.get_current_writer_data_file_path())
+                .await
+                .expect("should not fail to read data file");
+            assert!(!data_file.windows(64).any(|window| window == [0x42; 64]));
+
+            let record = read_next_some(&mut reader).await;
+            assert_eq!(SizedRecord::new(4096), record);
+            acknowledge(record).await;
+        }
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn previous_keys_decrypt_records_written_before_rotation() {
+    with_temp_dir(|dir| {
+        let data_dir = dir.to_path_buf();
+
+        async move {
+            let (mut writer, reader, ledger) =
+                create_buffer_v2_with_cipher(data_dir.clone(), cipher(1, &[])).await;
+            writer
+                .write_record(SizedRecord::new(32))
+                .await
+                .expect("write should not fail");
+            writer.flush().await.expect("flush should not fail");
+            drop(writer);
+            drop(reader);
+            drop(ledger);
+
+            // Rotate to a new key, keeping the old one around to read what's already buffered.
+            let (mut writer, mut reader, _ledger) =
+                create_buffer_v2_with_cipher(data_dir, cipher(2, &[1])).await;
+            writer
+                .write_record(SizedRecord::new(64))
+                .await
+                .expect("write should not fail");
+            writer.flush().await.expect("flush should not fail");
+
+            let first = read_next_some(&mut reader).await;
+            assert_eq!(SizedRecord::new(32), first);
+            acknowledge(first).await;
+
+            let second = read_next_some(&mut reader).await;
+            assert_eq!(SizedRecord::new(64), second);
+            acknowledge(second).await;
+        }
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn plaintext_records_are_readable_after_enabling_encryption() {
+    with_temp_dir(|dir| {
+        let data_dir = dir.to_path_buf();
+
+        async move {
+            let (mut writer, reader, ledger) = create_default_buffer_v2(data_dir.clone()).await;
+            writer
+                .write_record(SizedRecord::new(32))
+                .await
+                .expect("write should not fail");
+            writer.flush().await.expect("flush should not fail");
+            drop(writer);
+            drop(reader);
+            drop(ledger);
+
+            let (mut writer, mut reader, _ledger) =
+                create_buffer_v2_with_cipher(data_dir, cipher(1, &[])).await;
+            writer
+                .write_record(SizedRecord::new(64))
+                .await
+                .expect("write should not fail");
+            writer.flush().await.expect("flush should not fail");
+
+            let first = read_next_some(&mut reader).await;
+            assert_eq!(SizedRecord::new(32), first);
+            acknowledge(first).await;
+
+            let second = read_next_some(&mut reader).await;
+            assert_eq!(SizedRecord::new(64), second);
+            acknowledge(second).await;
+        }
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn buffer_fails_to_load_without_the_key_of_buffered_records() {
+    with_temp_dir(|dir| {
+        let data_dir = dir.to_path_buf();
+
+        async move {
+            let (mut writer, reader, ledger) =
+                create_buffer_v2_with_cipher(data_dir.clone(), cipher(1, &[])).await;
+            writer
+                .write_record(SizedRecord::new(32))
+                .await
+                .expect("write should not fail");
+            writer.flush().await.expect("flush should not fail");
+            drop(writer);
+            drop(reader);
+            drop(ledger);
+
+            // Rotating without keeping the old key around means the writer can't validate the
+            // last record it wrote, so the buffer refuses to load rather than silently dropping
+            // everything that was buffered.
+            let config = DiskBufferConfigBuilder::from_path(data_dir)
+                .cipher(cipher(2, &[]))
+                .build()
+                .expect("creating buffer should not fail");
+            let result =
+                Buffer::<SizedRecord>::from_config_inner(config, BufferUsageHandle::noop()).await;
+            assert!(result.is_err());
+        }
+    })
+    .await;
+}
+
+#[test]
+fn keys_must_be_32_bytes_of_base64() {
+    assert!(RecordCipher::from_base64("not base64!", []).is_err());
+    assert!(RecordCipher::from_base64(&STANDARD.encode([1; 16]), []).is_err());
+    assert!(RecordCipher::from_base64(&key(1), ["not base64!"]).is_err());
+    assert!(RecordCipher::from_base64(&key(1), [key(2).as_str()]).is_ok());
+}
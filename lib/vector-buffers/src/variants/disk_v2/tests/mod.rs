@@ -10,7 +10,7 @@ use tokio::{
 };
 
 use super::{
-    Buffer, BufferReader, BufferWriter, DiskBufferConfigBuilder, Filesystem, Ledger,
+    Buffer, BufferReader, BufferWriter, DiskBufferConfigBuilder, Filesystem, Ledger, RecordCipher,
     io::{AsyncFile, Metadata, ProductionFilesystem, ReadableMemoryMap, WritableMemoryMap},
     ledger::LEDGER_LEN,
     record::RECORD_HEADER_LEN,
@@ -24,16 +24,24 @@ type FilesystemUnderTest = ProductionFilesystem;
 
 mod acknowledgements;
 mod basic;
+mod compression;
+mod encryption;
 mod initialization;
 mod invariants;
 mod known_errors;
 mod model;
+mod offline;
+mod pool;
 mod record;
+mod retention;
 mod size_limits;
 
 impl AsyncFile for DuplexStream {
     async fn metadata(&self) -> io::Result<Metadata> {
-        Ok(Metadata { len: 0 })
+        Ok(Metadata {
+            len: 0,
+            modified: None,
+        })
     }
 
     async fn sync_all(&self) -> io::Result<()> {
@@ -43,7 +51,10 @@ impl AsyncFile for DuplexStream {
 
 impl AsyncFile for Cursor<Vec<u8>> {
     async fn metadata(&self) -> io::Result<Metadata> {
-        Ok(Metadata { len: 0 })
+        Ok(Metadata {
+            len: 0,
+            modified: None,
+        })
     }
 
     async fn sync_all(&self) -> io::Result<()> {
@@ -283,6 +294,30 @@ where
         .expect("should not fail to create buffer")
 }
 
+/// Creates a disk v2 buffer that encrypts records with the given cipher.
+pub(crate) async fn create_buffer_v2_with_cipher<P, R>(
+    data_dir: P,
+    cipher: RecordCipher,
+) -> (
+    BufferWriter<R, FilesystemUnderTest>,
+    BufferReader<R, FilesystemUnderTest>,
+    Arc<Ledger<FilesystemUnderTest>>,
+)
+where
+    P: AsRef<Path>,
+    R: Bufferable,
+{
+    let config = DiskBufferConfigBuilder::from_path(data_dir)
+        .cipher(cipher)
+        .build()
+        .expect("creating buffer should not fail");
+    let usage_handle = BufferUsageHandle::noop();
+
+    Buffer::from_config_inner(config, usage_handle)
+        .await
+        .expect("should not fail to create buffer")
+}
+
 /// Creates a disk v2 buffer with the specified maximum record size.
 pub(crate) async fn create_buffer_v2_with_max_record_size<P, R>(
     data_dir: P,
@@ -0,0 +1,206 @@
+use super::{create_default_buffer_v2, read_next, read_next_some, set_file_length};
+use crate::{
+    test::{SizedRecord, acknowledge, with_temp_dir},
+    variants::disk_v2::{
+        ReaderError,
+        offline::{OfflineBuffer, OfflineError, get_buffer_data_dir, move_buffer},
+    },
+};
+
+#[tokio::test]
+async fn offline_buffer_reads_ledger_and_records() {
+    with_temp_dir(|dir| {
+        let data_dir = dir.to_path_buf();
+
+        async move {
+            let (mut writer, mut reader, ledger) = create_default_buffer_v2(data_dir.clone()).await;
+
+            let records = (32..35).map(SizedRecord::new).collect::<Vec<_>>();
+            for record in records.clone() {
+                writer
+                    .write_record(record)
+                    .await
+                    .expect("write should not fail");
+            }
+            writer.flush().await.expect("flush should not fail");
+
+            // Read and acknowledge the first record, so that the ledger shows it as read.  The
+            // acknowledgement is only recorded in the ledger once the reader is asked for the next
+            // record, which we hold on to so that it stays unacknowledged.
+            let first_read = read_next_some(&mut reader).await;
+            acknowledge(first_read).await;
+            let _second_read = read_next_some(&mut reader).await;
+
+            drop(writer);
+            drop(reader);
+            drop(ledger);
+
+            let buffer = OfflineBuffer::open(&data_dir)
+                .await
+                .expect("should not fail to open buffer");
+            assert_eq!(buffer.ledger().writer_next_record_id, 4);
+            assert_eq!(buffer.ledger().reader_last_record_id, 1);
+            assert_eq!(buffer.ledger().unread_events(), 2);
+
+            let data_files = buffer
+                .data_files()
+                .await
+                .expect("should not fail to list data files");
+            assert_eq!(data_files.len(), 1);
+
+            let mut read_records = Vec::new();
+            let scan = buffer
+                .read_data_file(&data_files[0], |record, result| {
+                    read_records.push((
+                        record.id,
+                        buffer.ledger().is_acknowledged(record.id),
+                        result.expect("record should decode"),
+                    ));
+                })
+                .await
+                .expect("should not fail to read data file");
+            assert!(!scan.is_corrupted());
+            assert_eq!(scan.records, 3);
+            assert_eq!(scan.valid_len, data_files[0].size);
+            assert_eq!(
+                read_records,
+                vec![
+                    (1, true, records[0].clone()),
+                    (2, false, records[1].clone()),
+                    (3, false, records[2].clone()),
+                ]
+            );
+        }
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn offline_buffer_cannot_be_opened_while_in_use() {
+    with_temp_dir(|dir| {
+        let data_dir = dir.to_path_buf();
+
+        async move {
+            let (_writer, _reader, _ledger) =
+                create_default_buffer_v2::<_, SizedRecord>(data_dir.clone()).await;
+
+            let result = OfflineBuffer::open(&data_dir).await;
+            assert!(matches!(result, Err(OfflineError::BufferInUse)));
+        }
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn truncating_corrupted_tail_keeps_valid_records() {
+    with_temp_dir(|dir| {
+        let data_dir = dir.to_path_buf();
+
+        async move {
+            let (mut writer, reader, ledger) = create_default_buffer_v2(data_dir.clone()).await;
+
+            let first_bytes_written = writer
+                .write_record(SizedRecord::new(32))
+                .await
+                .expect("write should not fail");
+            let second_bytes_written = writer
+                .write_record(SizedRecord::new(33))
+                .await
+                .expect("write should not fail");
+            writer.flush().await.expect("flush should not fail");
+            let data_file_path = ledger.get_current_writer_data_file_path();
+
+            drop(writer);
+            drop(reader);
+            drop(ledger);
+
+            // Cut the second record in half, as if the process had crashed while writing it.
+            let initial_len = (first_bytes_written + second_bytes_written) as u64;
+            let target_len = (first_bytes_written + second_bytes_written / 2) as u64;
+            set_file_length(&data_file_path, initial_len, target_len)
+                .await
+                .expect("should not fail to truncate data file");
+
+            let buffer = OfflineBuffer::open(&data_dir)
+                .await
+                .expect("should not fail to open buffer");
+            let data_files = buffer
+                .data_files()
+                .await
+                .expect("should not fail to list data files");
+            let scan = buffer
+                .verify_data_file::<SizedRecord>(&data_files[0])
+                .await
+                .expect("should not fail to verify data file");
+            assert_eq!(scan.records, 1);
+            assert_eq!(scan.valid_len, first_bytes_written as u64);
+            assert!(matches!(scan.error, Some(ReaderError::PartialWrite)));
+
+            buffer
+                .truncate_data_file(&data_files[0], scan.valid_len)
+                .await
+                .expect("should not fail to truncate data file");
+            let data_files = buffer
+                .data_files()
+                .await
+                .expect("should not fail to list data files");
+            assert_eq!(data_files[0].size, first_bytes_written as u64);
+            let scan = buffer
+                .verify_data_file::<SizedRecord>(&data_files[0])
+                .await
+                .expect("should not fail to verify data file");
+            assert!(!scan.is_corrupted());
+            drop(buffer);
+
+            // The buffer can be loaded again, and the first record is still there.
+            let (mut writer, mut reader, _ledger) =
+                create_default_buffer_v2::<_, SizedRecord>(data_dir).await;
+            writer.close();
+
+            let first_read = read_next_some(&mut reader).await;
+            assert_eq!(first_read, SizedRecord::new(32));
+            acknowledge(first_read).await;
+            assert_eq!(read_next(&mut reader).await, None);
+        }
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn buffers_can_be_moved_to_another_id() {
+    with_temp_dir(|dir| {
+        let data_dir = dir.to_path_buf();
+
+        async move {
+            for buffer_id in ["a", "b"] {
+                let (mut writer, _reader, _ledger) =
+                    create_default_buffer_v2(get_buffer_data_dir(&data_dir, buffer_id)).await;
+                writer
+                    .write_record(SizedRecord::new(32))
+                    .await
+                    .expect("write should not fail");
+                writer.flush().await.expect("flush should not fail");
+            }
+
+            // Buffers can't be moved on top of an existing buffer.
+            let result = move_buffer(&data_dir, "a", "b").await;
+            assert!(matches!(
+                result,
+                Err(OfflineError::BufferAlreadyExists { .. })
+            ));
+
+            move_buffer(&data_dir, "a", "c")
+                .await
+                .expect("should not fail to move buffer");
+            assert!(!get_buffer_data_dir(&data_dir, "a").exists());
+
+            let (mut writer, mut reader, _ledger) =
+                create_default_buffer_v2::<_, SizedRecord>(get_buffer_data_dir(&data_dir, "c"))
+                    .await;
+            writer.close();
+            let first_read = read_next_some(&mut reader).await;
+            assert_eq!(first_read, SizedRecord::new(32));
+        }
+    })
+    .await;
+}
@@ -0,0 +1,101 @@
+use std::{path::Path, sync::Arc, time::Duration};
+
+use tokio::time::{sleep, timeout};
+use tokio_test::{assert_pending, task::spawn};
+
+use super::{FilesystemUnderTest, read_next, read_next_some};
+use crate::{
+    assert_buffer_is_empty, assert_buffer_size,
+    buffer_usage_data::BufferUsageHandle,
+    config::BufferPoolQuota,
+    pool::BufferPool,
+    test::{SizedRecord, acknowledge, with_temp_dir},
+    variants::disk_v2::{Buffer, BufferReader, BufferWriter, DiskBufferConfigBuilder, Ledger},
+};
+
+/// Creates a disk v2 buffer that draws its space from the given buffer pool.
+async fn create_buffer_v2_with_pool<P>(
+    data_dir: P,
+    pool: &BufferPool,
+    quota: BufferPoolQuota,
+) -> (
+    BufferWriter<SizedRecord, FilesystemUnderTest>,
+    BufferReader<SizedRecord, FilesystemUnderTest>,
+    Arc<Ledger<FilesystemUnderTest>>,
+)
+where
+    P: AsRef<Path>,
+{
+    let config = DiskBufferConfigBuilder::from_path(data_dir)
+        .pool(pool.register("test", quota))
+        .build()
+        .expect("creating buffer should not fail");
+    let usage_handle = BufferUsageHandle::noop();
+
+    Buffer::from_config_inner(config, usage_handle)
+        .await
+        .expect("should not fail to create buffer")
+}
+
+#[tokio::test]
+async fn writer_waits_for_space_in_pool() {
+    with_temp_dir(|dir| {
+        let data_dir = dir.to_path_buf();
+
+        async move {
+            let pool = BufferPool::new(u64::MAX);
+            let (mut writer_a, mut reader_a, ledger_a) =
+                create_buffer_v2_with_pool(data_dir.join("a"), &pool, BufferPoolQuota::default())
+                    .await;
+            let (mut writer_b, _reader_b, ledger_b) =
+                create_buffer_v2_with_pool(data_dir.join("b"), &pool, BufferPoolQuota::default())
+                    .await;
+
+            let record = SizedRecord::new(64);
+            let bytes_written_a = writer_a
+                .write_record(record.clone())
+                .await
+                .expect("write should not fail");
+            writer_a.flush().await.expect("flush should not fail");
+            let bytes_written_b = writer_b
+                .write_record(record.clone())
+                .await
+                .expect("write should not fail");
+            writer_b.flush().await.expect("flush should not fail");
+            assert_eq!(pool.usage(), (bytes_written_a + bytes_written_b) as u64);
+
+            // Shrink the pool down to what's already in use, so neither buffer can grow any further
+            // even though they both have plenty of room themselves.
+            pool.set_max_size(pool.usage());
+
+            let mut blocked_write = spawn(async {
+                writer_b
+                    .write_record(record.clone())
+                    .await
+                    .expect("write should not fail")
+            });
+            assert_pending!(blocked_write.poll());
+            sleep(Duration::from_millis(50)).await;
+            assert_pending!(blocked_write.poll());
+
+            // Reading and acknowledging the record in the other buffer frees up its space in the
+            // pool, which lets the blocked write go through.
+            let read = read_next_some(&mut reader_a).await;
+            assert_eq!(read, record);
+            acknowledge(read).await;
+            writer_a.close();
+            assert_eq!(read_next(&mut reader_a).await, None);
+            assert_buffer_is_empty!(ledger_a);
+
+            let bytes_written = timeout(Duration::from_secs(5), blocked_write)
+                .await
+                .expect("blocked write should complete once space is freed up");
+            assert_eq!(bytes_written, bytes_written_b);
+
+            writer_b.flush().await.expect("flush should not fail");
+            assert_buffer_size!(ledger_b, 2, bytes_written_b * 2);
+            assert_eq!(pool.usage(), (bytes_written_b * 2) as u64);
+        }
+    })
+    .await;
+}
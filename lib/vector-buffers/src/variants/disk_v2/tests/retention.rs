@@ -0,0 +1,169 @@
+use std::{path::Path, sync::Arc, time::Duration};
+
+use tokio::time::sleep;
+
+use super::{
+    FilesystemUnderTest, get_minimum_data_file_size_for_record_payload, read_next, read_next_some,
+};
+use crate::{
+    assert_buffer_is_empty, assert_buffer_size, assert_reader_last_writer_next_positions,
+    assert_reader_writer_v2_file_positions,
+    buffer_usage_data::BufferUsageHandle,
+    test::{SizedRecord, acknowledge, with_temp_dir},
+    variants::disk_v2::{
+        Buffer, BufferReader, BufferWriter, DiskBufferConfigBuilder, Ledger, ledger::LEDGER_LEN,
+    },
+};
+
+/// Creates a disk v2 buffer limited to the given number of data files, which drops the oldest data
+/// files when full if `drop_oldest` is set.
+async fn create_buffer_v2_with_retention<P>(
+    data_dir: P,
+    max_data_file_size: u64,
+    data_file_count_limit: u64,
+    drop_oldest: bool,
+) -> (
+    BufferWriter<SizedRecord, FilesystemUnderTest>,
+    BufferReader<SizedRecord, FilesystemUnderTest>,
+    Arc<Ledger<FilesystemUnderTest>>,
+    BufferUsageHandle,
+)
+where
+    P: AsRef<Path>,
+{
+    let ledger_len: u64 = LEDGER_LEN.try_into().unwrap();
+    let max_buffer_size = max_data_file_size * data_file_count_limit + ledger_len;
+
+    let config = DiskBufferConfigBuilder::from_path(data_dir)
+        .max_record_size(usize::try_from(max_data_file_size).unwrap())
+        .max_data_file_size(max_data_file_size)
+        .max_buffer_size(max_buffer_size)
+        .drop_oldest(drop_oldest)
+        .build()
+        .expect("creating buffer should not fail");
+    let usage_handle = BufferUsageHandle::noop();
+    let (writer, reader, ledger) = Buffer::from_config_inner(config, usage_handle.clone())
+        .await
+        .expect("should not fail to create buffer");
+
+    (writer, reader, ledger, usage_handle)
+}
+
+#[tokio::test]
+async fn drop_oldest_drops_unread_data_file_when_full() {
+    with_temp_dir(|dir| {
+        let data_dir = dir.to_path_buf();
+
+        async move {
+            // Every record fills up an entire data file, and the buffer can only hold four of them,
+            // so the fifth write has to drop a data file to make room.
+            let records = (64..69).map(SizedRecord::new).collect::<Vec<_>>();
+            let max_data_file_size = get_minimum_data_file_size_for_record_payload(&records[4]);
+            let (mut writer, mut reader, ledger, usage) =
+                create_buffer_v2_with_retention(data_dir, max_data_file_size, 4, true).await;
+            assert_buffer_is_empty!(ledger);
+
+            let mut bytes_written = Vec::new();
+            for record in records.iter().take(4).cloned() {
+                let written = writer
+                    .write_record(record)
+                    .await
+                    .expect("write should not fail");
+                bytes_written.push(written);
+            }
+            writer.flush().await.expect("flush should not fail");
+            assert_buffer_size!(ledger, 4, bytes_written.iter().sum::<usize>());
+            assert_reader_writer_v2_file_positions!(ledger, 0, 3);
+
+            // The reader hasn't opened any data file yet, so the oldest data file that can be
+            // dropped is the second one, as the first one is where the reader will start.
+            let written = writer
+                .write_record(records[4].clone())
+                .await
+                .expect("write should not fail");
+            writer.flush().await.expect("flush should not fail");
+            bytes_written[1] = written;
+            assert_reader_writer_v2_file_positions!(ledger, 0, 4);
+
+            // Records are counted by their IDs, so the dropped record is only accounted for once
+            // the reader skips over it, while its bytes are freed up immediately.
+            assert_buffer_size!(ledger, 5, bytes_written.iter().sum::<usize>());
+
+            let snapshot = usage.snapshot();
+            assert_eq!(snapshot.dropped_event_count_intentional, 1);
+            assert_eq!(snapshot.dropped_event_count, 0);
+
+            // The reader skips over the dropped data file, and the events in it aren't reported as
+            // dropped a second time.
+            for expected in [&records[0], &records[2], &records[3], &records[4]] {
+                let record = read_next_some(&mut reader).await;
+                assert_eq!(&record, expected);
+                acknowledge(record).await;
+            }
+
+            writer.close();
+            assert_eq!(read_next(&mut reader).await, None);
+            assert_buffer_is_empty!(ledger);
+            assert_reader_last_writer_next_positions!(ledger, 5, 6);
+
+            let snapshot = usage.snapshot();
+            assert_eq!(snapshot.dropped_event_count_intentional, 1);
+            assert_eq!(snapshot.dropped_event_count, 0);
+        }
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn max_age_drops_unread_data_files_older_than_limit() {
+    with_temp_dir(|dir| {
+        let data_dir = dir.to_path_buf();
+
+        async move {
+            let records = (64..68).map(SizedRecord::new).collect::<Vec<_>>();
+            let max_data_file_size = get_minimum_data_file_size_for_record_payload(&records[3]);
+            let (mut writer, mut reader, ledger, usage) =
+                create_buffer_v2_with_retention(data_dir, max_data_file_size, 8, false).await;
+
+            let mut bytes_written = Vec::new();
+            for record in records.iter().cloned() {
+                let written = writer
+                    .write_record(record)
+                    .await
+                    .expect("write should not fail");
+                bytes_written.push(written);
+            }
+            writer.flush().await.expect("flush should not fail");
+            assert_reader_writer_v2_file_positions!(ledger, 0, 3);
+
+            // Nothing is old enough to be dropped yet.
+            writer
+                .expire_data_files_older_than(Duration::from_secs(3600))
+                .await
+                .expect("expiring should not fail");
+            assert_buffer_size!(ledger, 4, bytes_written.iter().sum::<usize>());
+
+            // Everything is now old enough to be dropped, but the data files the reader and writer
+            // are on are left alone.
+            sleep(Duration::from_millis(50)).await;
+            writer
+                .expire_data_files_older_than(Duration::from_millis(10))
+                .await
+                .expect("expiring should not fail");
+            assert_buffer_size!(ledger, 4, bytes_written[0] + bytes_written[3]);
+            assert_eq!(usage.snapshot().dropped_event_count_intentional, 2);
+
+            for expected in [&records[0], &records[3]] {
+                let record = read_next_some(&mut reader).await;
+                assert_eq!(&record, expected);
+                acknowledge(record).await;
+            }
+
+            writer.close();
+            assert_eq!(read_next(&mut reader).await, None);
+            assert_buffer_is_empty!(ledger);
+            assert_eq!(usage.snapshot().dropped_event_count, 0);
+        }
+    })
+    .await;
+}
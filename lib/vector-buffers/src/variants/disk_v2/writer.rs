@@ -6,6 +6,7 @@ use std::{
     marker::PhantomData,
     num::NonZeroUsize,
     sync::Arc,
+    time::Duration,
 };
 
 use bytes::BufMut;
@@ -24,17 +25,20 @@ use snafu::{ResultExt, Snafu};
 use tokio::io::{AsyncWrite, AsyncWriteExt};
 
 use super::{
-    common::{DiskBufferConfig, create_crc32c_hasher},
+    common::{DiskBufferConfig, MAX_FILE_ID, create_crc32c_hasher},
+    compression::{self, CompressionError},
+    encryption::{ENCRYPTION_OVERHEAD, EncryptionError, RecordCipher},
     io::Filesystem,
     ledger::Ledger,
-    record::{Record, RecordStatus, validate_record_archive},
+    record::{ENCRYPTED_RECORD_FLAG, Record, RecordStatus, validate_record_archive},
 };
 use crate::{
     Bufferable,
+    config::DiskBufferCompression,
     encoding::{AsMetadata, Encodable},
     variants::disk_v2::{
         io::AsyncFile,
-        reader::decode_record_payload,
+        reader::{RecordReader, decode_record_payload},
         record::{RECORD_HEADER_LEN, try_as_record_archive},
     },
 };
@@ -95,6 +99,14 @@ where
         source: <T as Encodable>::EncodeError,
     },
 
+    /// The writer failed to compress the encoded record.
+    #[snafu(display("failed to compress record: {}", source))]
+    FailedToCompress { source: CompressionError },
+
+    /// The writer failed to encrypt the encoded record.
+    #[snafu(display("failed to encrypt record: {}", source))]
+    FailedToEncrypt { source: EncryptionError },
+
     /// The writer failed to serialize the record.
     ///
     /// As records are encoded and then wrapped in a container which carries metadata about the size
@@ -208,6 +220,8 @@ where
 pub(super) struct WriteToken {
     event_count: usize,
     serialized_len: usize,
+    encoded_len: usize,
+    stored_len: usize,
 }
 
 impl WriteToken {
@@ -218,6 +232,16 @@ impl WriteToken {
     pub fn serialized_len(&self) -> usize {
         self.serialized_len
     }
+
+    /// Length of the encoded record before compression.
+    pub fn encoded_len(&self) -> usize {
+        self.encoded_len
+    }
+
+    /// Length of the encoded record as stored, after compression.
+    pub fn stored_len(&self) -> usize {
+        self.stored_len
+    }
 }
 
 #[derive(Debug, Default, PartialEq)]
@@ -360,9 +384,13 @@ impl<W: fmt::Debug> fmt::Debug for TrackingBufWriter<W> {
 pub(super) struct RecordWriter<W, T> {
     writer: TrackingBufWriter<W>,
     encode_buf: Vec<u8>,
+    compress_buf: Vec<u8>,
+    encrypt_buf: Vec<u8>,
     ser_buf: AlignedVec,
     ser_scratch: AlignedVec,
     checksummer: Hasher,
+    compression: DiskBufferCompression,
+    cipher: Option<RecordCipher>,
     max_record_size: usize,
     current_data_file_size: u64,
     max_data_file_size: u64,
@@ -410,9 +438,13 @@ where
         Self {
             writer: TrackingBufWriter::with_capacity(write_buffer_size, writer),
             encode_buf: Vec::with_capacity(16_384),
+            compress_buf: Vec::new(),
+            encrypt_buf: Vec::new(),
             ser_buf: AlignedVec::with_capacity(16_384),
             ser_scratch: AlignedVec::with_capacity(16_384),
             checksummer: create_crc32c_hasher(),
+            compression: DiskBufferCompression::None,
+            cipher: None,
             max_record_size,
             current_data_file_size,
             max_data_file_size,
@@ -420,6 +452,24 @@ where
         }
     }
 
+    /// Sets the algorithm used to compress records before they are written.
+    pub fn with_compression(mut self, compression: DiskBufferCompression) -> Self {
+        self.compression = compression;
+        self
+    }
+
+    /// Sets the cipher used to encrypt records before they are written.
+    ///
+    /// As encryption adds a fixed overhead to each record, this also lowers the limit on the
+    /// encoded size of a record accordingly.
+    pub fn with_cipher(mut self, cipher: Option<RecordCipher>) -> Self {
+        if cipher.is_some() && self.cipher.is_none() {
+            self.max_record_size = self.max_record_size.saturating_sub(ENCRYPTION_OVERHEAD);
+        }
+        self.cipher = cipher;
+        self
+    }
+
     /// Gets a reference to the underlying writer.
     #[cfg(test)]
     pub fn get_ref(&self) -> &W {
@@ -481,8 +531,33 @@ where
         }
 
         let metadata = T::get_metadata().into_u32();
+        let mut flags = 0;
+
+        // Compress the encoded record, but only keep the compressed form if it's actually smaller,
+        // which saves us from spending time decompressing it later for no gain.
+        let mut payload = &self.encode_buf[..];
+        if self.compression != DiskBufferCompression::None {
+            compression::compress(self.compression, payload, &mut self.compress_buf)
+                .context(FailedToCompressSnafu)?;
+            if self.compress_buf.len() < payload.len() {
+                payload = &self.compress_buf[..];
+                flags |= compression::metadata_bits(self.compression);
+            }
+        }
+        let stored_len = payload.len();
+
+        if let Some(cipher) = &self.cipher {
+            // The checksum covers the encrypted payload, so corruption is still detected without
+            // needing the key.
+            cipher
+                .encrypt(id, metadata, payload, &mut self.encrypt_buf)
+                .context(FailedToEncryptSnafu)?;
+            payload = &self.encrypt_buf[..];
+            flags |= ENCRYPTED_RECORD_FLAG;
+        }
+
         let wrapped_record =
-            Record::with_checksum(id, metadata, &self.encode_buf, &self.checksummer);
+            Record::with_checksum(id, metadata | flags, payload, &self.checksummer);
 
         // Push 8 dummy bytes where our length delimiter will sit.  We'll fix this up after
         // serialization.  Notably, `AlignedSerializer` will report the serializer position as
@@ -560,6 +635,8 @@ where
         Ok(WriteToken {
             event_count,
             serialized_len,
+            encoded_len,
+            stored_len,
         })
     }
 
@@ -665,17 +742,10 @@ where
             }
         })?;
 
-        // Now we can actually decode it as `T`.
-        let record_metadata = T::Metadata::from_u32(wrapped_record.metadata()).ok_or(
-            WriterError::InconsistentState {
-                reason: "failed to decode record metadata immediately after encoding it"
-                    .to_string(),
-            },
-        )?;
-
-        T::decode(record_metadata, wrapped_record.payload()).map_err(|_| {
+        // Now we can actually decode it as `T`, decrypting and decompressing it along the way.
+        decode_record_payload(&wrapped_record, self.cipher.as_ref()).map_err(|e| {
             WriterError::InconsistentState {
-                reason: "failed to decode record immediately after encoding it".to_string(),
+                reason: format!("failed to decode record immediately after encoding it: {e}"),
             }
         })
     }
@@ -795,7 +865,13 @@ where
         let potential_write_len =
             u64::try_from(amount).expect("Vector only supports 64-bit architectures.");
 
-        self.can_write() && total_buffer_size + potential_write_len <= self.config.max_buffer_size
+        self.can_write()
+            && total_buffer_size + potential_write_len <= self.config.max_buffer_size
+            && self
+                .config
+                .pool
+                .as_ref()
+                .is_none_or(|pool| pool.try_reserve(self.unflushed_bytes + potential_write_len))
     }
 
     #[instrument(skip(self), level = "debug")]
@@ -882,11 +958,10 @@ where
                 // next writer record ID should be.
                 let record = try_as_record_archive(data_file_mmap.as_ref())
                     .expect("record was already validated");
-                let item = decode_record_payload::<T>(record).map_err(|e| {
-                    WriterError::FailedToValidate {
+                let item = decode_record_payload::<T>(record, self.config.cipher.as_ref())
+                    .map_err(|e| WriterError::FailedToValidate {
                         reason: e.to_string(),
-                    }
-                })?;
+                    })?;
 
                 // Since we have a valid record, checksum and all, see if the writer record ID
                 // in the ledger lines up with the record ID we have here.  Specifically, the record
@@ -1059,6 +1134,15 @@ where
             // iteration is the happy path, trying to create the new file.  If we can't create it,
             // this may be because it already exists and we're just picking up where we left off
             // from last time, but it could also be a data file that a reader hasn't completed yet.
+            //
+            // When data files can be expired, the reader may also have skipped over data files that
+            // no longer exist, but whose file IDs it still has to process, so we wait on those, too.
+            if should_open_next && self.is_next_data_file_pending_reader() {
+                debug!("Target data file is still pending for the reader. Waiting for reader.");
+                self.ledger.wait_for_reader().await;
+                continue;
+            }
+
             let data_file_path = if should_open_next {
                 self.ledger.get_next_writer_data_file_path()
             } else {
@@ -1123,13 +1207,17 @@ where
                 // Make sure the file is flushed to disk, especially if we just created it.
                 data_file.sync_all().await?;
 
-                self.writer = Some(RecordWriter::new(
-                    data_file,
-                    data_file_size,
-                    self.config.write_buffer_size,
-                    self.config.max_data_file_size,
-                    self.config.max_record_size,
-                ));
+                self.writer = Some(
+                    RecordWriter::new(
+                        data_file,
+                        data_file_size,
+                        self.config.write_buffer_size,
+                        self.config.max_data_file_size,
+                        self.config.max_record_size,
+                    )
+                    .with_compression(self.config.compression)
+                    .with_cipher(self.config.cipher.clone()),
+                );
                 self.data_file_size = data_file_size;
 
                 // If we opened the "next" data file, we need to increment the current writer
@@ -1154,6 +1242,157 @@ where
         }
     }
 
+    /// Returns `true` if the next data file is one the reader has moved past but not yet deleted.
+    ///
+    /// This can only happen when data files are expired, as otherwise the next data file would
+    /// still exist on disk until the reader deletes it.
+    fn is_next_data_file_pending_reader(&self) -> bool {
+        self.config.expires_data_files()
+            && self
+                .ledger
+                .is_unacked_reader_data_file(self.ledger.get_next_writer_file_id())
+    }
+
+    /// Gets the ID of the first record in the given data file, if any.
+    async fn get_first_record_id(&self, file_id: u16) -> io::Result<Option<u64>> {
+        let data_file_path = self.ledger.get_data_file_path(file_id);
+        let data_file = match self
+            .ledger
+            .filesystem()
+            .open_file_readable(&data_file_path)
+            .await
+        {
+            Ok(data_file) => data_file,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e),
+        };
+
+        // The record ID is stored in the clear, so we don't need to decrypt the record to get at
+        // it, and a record we can't read is left for the reader to deal with.
+        let mut reader = RecordReader::<FS::File, T>::new(data_file);
+        Ok(reader
+            .try_next_record(true)
+            .await
+            .ok()
+            .flatten()
+            .map(|token| token.record_id()))
+    }
+
+    /// Gets the number of events in the given data file.
+    ///
+    /// This is the difference between the ID of the first record in the data file and the ID of
+    /// the first record that follows it, in either a later data file or the next write.  If either
+    /// can't be determined, zero is returned, and the events are instead treated as lost when the
+    /// reader skips over them.
+    async fn get_data_file_event_count(
+        &self,
+        file_id: u16,
+        writer_file_id: u16,
+    ) -> io::Result<u64> {
+        let Some(first_record_id) = self.get_first_record_id(file_id).await? else {
+            return Ok(0);
+        };
+
+        let mut next_file_id = file_id;
+        let next_record_id = loop {
+            next_file_id = (next_file_id + 1) % MAX_FILE_ID;
+            if let Some(record_id) = self.get_first_record_id(next_file_id).await? {
+                break record_id;
+            }
+            if next_file_id == writer_file_id {
+                // All writes before the current data file have been flushed, so if there's no
+                // record in it yet, the next record ID follows directly on from this data file.
+                break self.next_record_id;
+            }
+        };
+
+        Ok(next_record_id.wrapping_sub(first_record_id))
+    }
+
+    /// Drops the oldest data file that the reader has not yet opened.
+    ///
+    /// If `older_than` is given, the data file is only dropped if it was last written to at least
+    /// that long ago.  Data files that are currently being read from or written to are never
+    /// dropped.
+    ///
+    /// Returns `true` if a data file was dropped.
+    async fn expire_data_file(&mut self, older_than: Option<Duration>) -> io::Result<bool> {
+        let _data_files_guard = self.ledger.lock_data_files().await;
+
+        // If the reader is on our current data file, or is waiting for our next one after having
+        // skipped past a bad record, there's nothing between us to drop.
+        let (reader_file_id, writer_file_id) = self.ledger.get_current_reader_writer_file_id();
+        if reader_file_id == writer_file_id
+            || reader_file_id == self.ledger.get_next_writer_file_id()
+        {
+            return Ok(false);
+        }
+
+        // Find the oldest data file after the reader's current data file, skipping over any that
+        // have already been dropped.
+        let mut file_id = (reader_file_id + 1) % MAX_FILE_ID;
+        while file_id != writer_file_id {
+            let data_file_path = self.ledger.get_data_file_path(file_id);
+            let metadata = match self
+                .ledger
+                .filesystem()
+                .open_file_readable(&data_file_path)
+                .await
+            {
+                Ok(data_file) => data_file.metadata().await?,
+                Err(e) if e.kind() == ErrorKind::NotFound => {
+                    file_id = (file_id + 1) % MAX_FILE_ID;
+                    continue;
+                }
+                Err(e) => return Err(e),
+            };
+
+            if let Some(older_than) = older_than {
+                let age = metadata
+                    .modified()
+                    .and_then(|modified| modified.elapsed().ok());
+                if age.is_none_or(|age| age < older_than) {
+                    return Ok(false);
+                }
+            }
+
+            let event_count = self
+                .get_data_file_event_count(file_id, writer_file_id)
+                .await?;
+            self.ledger
+                .filesystem()
+                .delete_file(&data_file_path)
+                .await?;
+            self.ledger.track_expired(event_count, metadata.len());
+
+            debug!(
+                data_file_path = data_file_path.to_string_lossy().as_ref(),
+                event_count,
+                data_file_size = metadata.len(),
+                "Dropped unread data file."
+            );
+
+            return Ok(true);
+        }
+
+        Ok(false)
+    }
+
+    /// Drops all data files that the reader has not yet opened, and that were last written to at
+    /// least `max_age` ago.
+    ///
+    /// # Errors
+    ///
+    /// If an error occurred while inspecting or deleting a data file, an error variant will be
+    /// returned describing the error.
+    pub(super) async fn expire_data_files_older_than(
+        &mut self,
+        max_age: Duration,
+    ) -> io::Result<()> {
+        while self.expire_data_file(Some(max_age)).await? {}
+        Ok(())
+    }
+
     /// Attempts to write a record.
     ///
     /// If the buffer is currently full, the original record will be immediately returned.
@@ -1235,6 +1474,11 @@ where
             .expect("writer should exist after `ensure_ready_for_write`");
 
         let (bytes_written, flush_result) = if can_write_record {
+            if self.config.compression != DiskBufferCompression::None {
+                self.ledger
+                    .track_compression(token.encoded_len() as u64, token.stored_len() as u64);
+            }
+
             // We always return errors here because flushing the record won't return a recoverable error like
             // `DataFileFull`, as that gets checked during archiving.
             writer.flush_record(token).await?
@@ -1289,7 +1533,19 @@ where
                 Ok(bytes_written) => return Ok(bytes_written),
                 Err(old_record) => {
                     record = old_record;
-                    self.ledger.wait_for_reader().await;
+
+                    // Make room by dropping the oldest data file if we're configured to, and
+                    // otherwise, or if there's nothing we can drop, wait for the reader.  When the
+                    // buffer itself still has room, it's the buffer pool that is full, so we wait
+                    // for any member of the pool to free up space instead.
+                    if !self.config.drop_oldest
+                        || !self.expire_data_file(None).await.context(IoSnafu)?
+                    {
+                        match self.config.pool.as_ref() {
+                            Some(pool) if !self.is_buffer_full() => pool.wait_for_space().await,
+                            _ => self.ledger.wait_for_reader().await,
+                        }
+                    }
                 }
             }
         }
@@ -1,4 +1,12 @@
+pub const BZIP2_MAGIC: &[u8] = b"BZh";
+/// Magic of a bzip2 compressed block, following the stream header and its block size digit.
+/// See: <https://github.com/dsnet/compress/blob/master/doc/bzip2-format.pdf>
+pub const BZIP2_BLOCK_MAGIC: &[u8] = &[0x31, 0x41, 0x59, 0x26, 0x53, 0x59];
+/// Magic of the end of a bzip2 stream, which directly follows the stream header of empty streams.
+/// See: <https://github.com/dsnet/compress/blob/master/doc/bzip2-format.pdf>
+pub const BZIP2_EOS_MAGIC: &[u8] = &[0x17, 0x72, 0x45, 0x38, 0x50, 0x90];
 pub const GZIP_MAGIC: &[u8] = &[0x1f, 0x8b];
+pub const XZ_MAGIC: &[u8] = &[0xFD, b'7', b'z', b'X', b'Z', 0x00];
 pub const ZLIB_MAGIC: &[u8] = &[0x78];
 pub const ZSTD_MAGIC: &[u8] = &[0x28, 0xB5, 0x2F, 0xFD];
 
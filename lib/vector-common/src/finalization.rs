@@ -3,7 +3,14 @@
 //! as it flows through transforms, being duplicated and merged, and
 //! then report its status when the last copy is delivered or dropped.
 
-use std::{cmp, future::Future, mem, pin::Pin, sync::Arc, task::Poll};
+use std::{
+    cmp,
+    future::Future,
+    mem,
+    pin::Pin,
+    sync::{Arc, OnceLock},
+    task::Poll,
+};
 
 use crossbeam_utils::atomic::AtomicCell;
 use futures::future::FutureExt;
@@ -86,6 +93,17 @@ impl EventFinalizers {
         }
     }
 
+    /// Updates the status of all event finalizers in the collection, recording why delivery failed.
+    ///
+    /// The failure is recorded on the underlying batches, where it can be retrieved through
+    /// [`BatchStatusReceiver::with_failure`]. Only the first failure recorded on a batch is kept.
+    pub fn update_status_with_failure(&self, status: EventStatus, failure: &DeliveryFailure) {
+        for finalizer in &self.0 {
+            finalizer.update_status(status);
+            finalizer.batch.record_failure(failure);
+        }
+    }
+
     /// Consumes all event finalizers and updates their underlying batches immediately.
     pub fn update_sources(&mut self) {
         let finalizers = mem::take(&mut self.0);
@@ -160,14 +178,14 @@ impl Drop for EventFinalizer {
 /// A convenience newtype wrapper for the one-shot receiver for an
 /// individual batch status.
 #[pin_project::pin_project]
-pub struct BatchStatusReceiver(oneshot::Receiver<BatchStatus>);
+pub struct BatchStatusReceiver(oneshot::Receiver<(BatchStatus, Option<DeliveryFailure>)>);
 
 impl Future for BatchStatusReceiver {
     type Output = BatchStatus;
     fn poll(mut self: Pin<&mut Self>, ctx: &mut std::task::Context<'_>) -> Poll<Self::Output> {
         match self.0.poll_unpin(ctx) {
             Poll::Pending => Poll::Pending,
-            Poll::Ready(Ok(status)) => Poll::Ready(status),
+            Poll::Ready(Ok((status, _))) => Poll::Ready(status),
             Poll::Ready(Err(error)) => {
                 error!(%error, "Batch status receiver dropped before sending.");
                 Poll::Ready(BatchStatus::Errored)
@@ -184,7 +202,16 @@ impl BatchStatusReceiver {
     /// - `TryRecvError::Empty` if no value has been sent yet.
     /// - `TryRecvError::Closed` if the sender has dropped without sending a value.
     pub fn try_recv(&mut self) -> Result<BatchStatus, oneshot::error::TryRecvError> {
-        self.0.try_recv()
+        self.0.try_recv().map(|(status, _)| status)
+    }
+
+    /// Waits for the status of the batch, along with the reason that its delivery failed, if one
+    /// was recorded.
+    pub async fn with_failure(self) -> (BatchStatus, Option<DeliveryFailure>) {
+        self.0.await.unwrap_or_else(|error| {
+            error!(%error, "Batch status receiver dropped before sending.");
+            (BatchStatus::Errored, None)
+        })
     }
 }
 
@@ -201,6 +228,7 @@ impl BatchNotifier {
         let (sender, receiver) = oneshot::channel();
         let notifier = OwnedBatchNotifier {
             status: AtomicCell::new(BatchStatus::Delivered),
+            failure: OnceLock::new(),
             notifier: Some(sender),
         };
         (Self(Arc::new(notifier)), BatchStatusReceiver(receiver))
@@ -250,13 +278,19 @@ impl BatchNotifier {
                 .unwrap_or_else(|_| unreachable!());
         }
     }
+
+    /// Records why delivery of the batch failed, unless a failure was already recorded.
+    fn record_failure(&self, failure: &DeliveryFailure) {
+        self.0.failure.get_or_init(|| failure.clone());
+    }
 }
 
 /// The non-shared data underlying the shared `BatchNotifier`
 #[derive(Debug)]
 pub struct OwnedBatchNotifier {
     status: AtomicCell<BatchStatus>,
-    notifier: Option<oneshot::Sender<BatchStatus>>,
+    failure: OnceLock<DeliveryFailure>,
+    notifier: Option<oneshot::Sender<(BatchStatus, Option<DeliveryFailure>)>>,
 }
 
 impl OwnedBatchNotifier {
@@ -264,9 +298,14 @@ impl OwnedBatchNotifier {
     fn send_status(&mut self) {
         if let Some(notifier) = self.notifier.take() {
             let status = self.status.load();
+            // The failure is only passed along when the batch itself failed.
+            let failure = self
+                .failure
+                .take()
+                .filter(|_| status != BatchStatus::Delivered);
             // Ignore the error case, as it will happen during normal
             // source shutdown and we can't detect that here.
-            _ = notifier.send(status);
+            _ = notifier.send((status, failure));
         }
     }
 }
@@ -277,6 +316,33 @@ impl Drop for OwnedBatchNotifier {
     }
 }
 
+/// Details about why the delivery of events failed.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct DeliveryFailure {
+    /// A description of the failure.
+    pub message: String,
+
+    /// The HTTP status code of the response that rejected the events, if any.
+    pub http_status: Option<u16>,
+}
+
+impl DeliveryFailure {
+    /// Creates a new `DeliveryFailure` with the given message.
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            http_status: None,
+        }
+    }
+
+    /// Sets the HTTP status code of the response that rejected the events.
+    #[must_use]
+    pub fn with_http_status(mut self, http_status: u16) -> Self {
+        self.http_status = Some(http_status);
+        self
+    }
+}
+
 /// The status of an individual batch.
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
 #[repr(u8)]
@@ -420,6 +486,27 @@ mod tests {
         assert_eq!(receiver.try_recv(), Ok(BatchStatus::Rejected));
     }
 
+    #[test]
+    fn records_failure() {
+        let (fin, receiver) = make_finalizer();
+        fin.update_status_with_failure(
+            EventStatus::Rejected,
+            &DeliveryFailure::new("bad request").with_http_status(400),
+        );
+        fin.update_status_with_failure(EventStatus::Errored, &DeliveryFailure::new("timed out"));
+        drop(fin);
+
+        let (status, failure) = receiver
+            .with_failure()
+            .now_or_never()
+            .expect("status should have been sent");
+        assert_eq!(status, BatchStatus::Rejected);
+        assert_eq!(
+            failure,
+            Some(DeliveryFailure::new("bad request").with_http_status(400))
+        );
+    }
+
     #[test]
     fn clone_events() {
         let (fin1, mut receiver) = make_finalizer();
@@ -21,6 +21,8 @@ pub enum CounterName {
     BufferDiscardedEventsTotal,
     BufferDiscardedBytesTotal,
     BufferErrorsTotal,
+    BufferUncompressedBytesTotal,
+    BufferCompressedBytesTotal,
     // Internal events from src/internal_events/
     AggregateEventsRecordedTotal,
     AggregateFailedUpdates,
@@ -47,6 +49,7 @@ pub enum CounterName {
     FilesDeletedTotal,
     FilesResumedTotal,
     FilesUnwatchedTotal,
+    InotifyFallbacksTotal,
     GrpcServerMessagesReceivedTotal,
     GrpcServerMessagesSentTotal,
     HttpClientErrorsTotal,
@@ -100,6 +103,10 @@ pub enum CounterName {
     MemoryEnrichmentTableReadsTotal,
     MemoryEnrichmentTableTtlExpirations,
     ComponentCpuUsageNsTotal,
+    FanoutDetachedTotal,
+    FanoutDiscardedEventsTotal,
+    TracesSampledTotal,
+    TracesEvictedTotal,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Display, AsRefStr, EnumIter)]
@@ -130,6 +137,7 @@ pub enum HistogramName {
     HttpClientErrorRttSeconds,
     SourceBufferUtilization,
     TransformBufferUtilization,
+    FanoutSendDurationSeconds,
 }
 
 impl HistogramName {
@@ -165,6 +173,7 @@ impl HistogramName {
             Self::HttpClientErrorRttSeconds => "http_client_error_rtt_seconds",
             Self::SourceBufferUtilization => "source_buffer_utilization",
             Self::TransformBufferUtilization => "transform_buffer_utilization",
+            Self::FanoutSendDurationSeconds => "fanout_send_duration_seconds",
         }
     }
 }
@@ -193,9 +202,11 @@ pub enum GaugeName {
     BufferSizeEvents,
     BufferSizeBytes,
     BufferByteSize,
+    BufferCompressionRatio,
     Utilization,
     ComponentAllocatedBytes,
     OpenFiles,
+    InotifyWatches,
     UptimeSeconds,
     BuildInfo,
     KafkaQueueMessages,
@@ -209,6 +220,8 @@ pub enum GaugeName {
     MemoryEnrichmentTableObjectsCount,
     MemoryEnrichmentTableByteSize,
     TagCardinalityTrackedKeys,
+    BufferedTraces,
+    BufferedSpans,
 }
 
 impl GaugeName {
@@ -236,9 +249,11 @@ impl GaugeName {
             Self::BufferSizeEvents => "buffer_size_events",
             Self::BufferSizeBytes => "buffer_size_bytes",
             Self::BufferByteSize => "buffer_byte_size",
+            Self::BufferCompressionRatio => "buffer_compression_ratio",
             Self::Utilization => "utilization",
             Self::ComponentAllocatedBytes => "component_allocated_bytes",
             Self::OpenFiles => "open_files",
+            Self::InotifyWatches => "inotify_watches",
             Self::UptimeSeconds => "uptime_seconds",
             Self::BuildInfo => "build_info",
             Self::KafkaQueueMessages => "kafka_queue_messages",
@@ -252,6 +267,8 @@ impl GaugeName {
             Self::MemoryEnrichmentTableObjectsCount => "memory_enrichment_table_objects_count",
             Self::MemoryEnrichmentTableByteSize => "memory_enrichment_table_byte_size",
             Self::TagCardinalityTrackedKeys => "tag_cardinality_tracked_keys",
+            Self::BufferedTraces => "buffered_traces",
+            Self::BufferedSpans => "buffered_spans",
         }
     }
 }
@@ -278,6 +295,8 @@ impl CounterName {
             Self::BufferDiscardedEventsTotal => "buffer_discarded_events_total",
             Self::BufferDiscardedBytesTotal => "buffer_discarded_bytes_total",
             Self::BufferErrorsTotal => "buffer_errors_total",
+            Self::BufferUncompressedBytesTotal => "buffer_uncompressed_bytes_total",
+            Self::BufferCompressedBytesTotal => "buffer_compressed_bytes_total",
             Self::AggregateEventsRecordedTotal => "aggregate_events_recorded_total",
             Self::AggregateFailedUpdates => "aggregate_failed_updates",
             Self::AggregateFlushesTotal => "aggregate_flushes_total",
@@ -307,6 +326,7 @@ impl CounterName {
             Self::FilesDeletedTotal => "files_deleted_total",
             Self::FilesResumedTotal => "files_resumed_total",
             Self::FilesUnwatchedTotal => "files_unwatched_total",
+            Self::InotifyFallbacksTotal => "inotify_fallbacks_total",
             Self::GrpcServerMessagesReceivedTotal => "grpc_server_messages_received_total",
             Self::GrpcServerMessagesSentTotal => "grpc_server_messages_sent_total",
             Self::HttpClientErrorsTotal => "http_client_errors_total",
@@ -366,6 +386,10 @@ impl CounterName {
             Self::MemoryEnrichmentTableReadsTotal => "memory_enrichment_table_reads_total",
             Self::MemoryEnrichmentTableTtlExpirations => "memory_enrichment_table_ttl_expirations",
             Self::ComponentCpuUsageNsTotal => "component_cpu_usage_ns_total",
+            Self::FanoutDetachedTotal => "fanout_detached_total",
+            Self::FanoutDiscardedEventsTotal => "fanout_discarded_events_total",
+            Self::TracesSampledTotal => "traces_sampled_total",
+            Self::TracesEvictedTotal => "traces_evicted_total",
         }
     }
 }
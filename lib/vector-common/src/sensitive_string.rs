@@ -1,9 +1,19 @@
+use std::cell::Cell;
+
+use serde::{Serialize, Serializer};
 use vector_config::{ConfigurableString, configurable_component};
 
+/// The value that sensitive strings are displayed and redacted as.
+pub const REDACTED: &str = "**REDACTED**";
+
+thread_local! {
+    static REDACT_ON_SERIALIZE: Cell<bool> = const { Cell::new(false) };
+}
+
 /// Wrapper for sensitive strings containing credentials
 #[configurable_component(no_deser, no_ser)]
-#[derive(::serde::Deserialize, ::serde::Serialize)]
-#[serde(from = "String", into = "String")]
+#[derive(::serde::Deserialize)]
+#[serde(from = "String")]
 #[configurable(metadata(sensitive))]
 #[derive(Clone, Default, PartialEq, Eq)]
 pub struct SensitiveString(String);
@@ -24,14 +34,14 @@ impl ConfigurableString for SensitiveString {}
 
 impl std::fmt::Display for SensitiveString {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "**REDACTED**")
+        write!(f, "{REDACTED}")
     }
 }
 
 impl std::fmt::Debug for SensitiveString {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         // we keep the double quotes here to keep the String behavior
-        write!(f, "\"**REDACTED**\"")
+        write!(f, "\"{REDACTED}\"")
     }
 }
 
@@ -40,6 +50,33 @@ impl SensitiveString {
     pub fn inner(&self) -> &str {
         self.0.as_str()
     }
+
+    /// Runs `f` with sensitive strings serialized as `**REDACTED**` on the current thread.
+    ///
+    /// Outside of this scope, sensitive strings serialize to their plaintext value so that
+    /// configurations can round-trip.
+    pub fn redacted<T>(f: impl FnOnce() -> T) -> T {
+        struct Reset(bool);
+
+        impl Drop for Reset {
+            fn drop(&mut self) {
+                REDACT_ON_SERIALIZE.set(self.0);
+            }
+        }
+
+        let _reset = Reset(REDACT_ON_SERIALIZE.replace(true));
+        f()
+    }
+}
+
+impl Serialize for SensitiveString {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if REDACT_ON_SERIALIZE.get() {
+            serializer.serialize_str(REDACTED)
+        } else {
+            serializer.serialize_str(&self.0)
+        }
+    }
 }
 
 #[cfg(test)]
@@ -54,6 +91,14 @@ mod tests {
         assert_eq!(result, json_value);
     }
 
+    #[test]
+    fn redacted_serialization() {
+        let value = SensitiveString("hello world".to_string());
+        let result = SensitiveString::redacted(|| serde_json::to_string(&value).unwrap());
+        assert_eq!(result, "\"**REDACTED**\"");
+        assert_eq!(serde_json::to_string(&value).unwrap(), "\"hello world\"");
+    }
+
     #[test]
     fn hide_content() {
         let value = SensitiveString("hello world".to_string());
@@ -1,6 +1,7 @@
 use std::{fs::DirBuilder, path::PathBuf, time::Duration};
 
 use snafu::{ResultExt, Snafu};
+use vector_buffers::BufferPoolConfig;
 use vector_common::TimeZone;
 use vector_config::{configurable_component, impl_generate_config_from_default};
 
@@ -174,6 +175,15 @@ pub struct GlobalOptions {
     #[configurable(metadata(docs::advanced))]
     pub buffer_utilization_ewma_half_life_seconds: Option<f64>,
 
+    /// A pool of disk space shared by all disk buffers.
+    ///
+    /// When configured, disk buffers draw their space from this pool, and their own `max_size` acts
+    /// as a cap on how much of the pool they can use. Space in the pool can be reserved for a disk
+    /// buffer, and prioritized between disk buffers, with the buffer's `pool` option.
+    #[serde(default, skip_serializing_if = "crate::serde::is_default")]
+    #[configurable(metadata(docs::advanced))]
+    pub buffer_pool: Option<BufferPoolConfig>,
+
     /// The alpha value for the exponential weighted moving average (EWMA) of transform latency
     /// metrics.
     ///
@@ -298,6 +308,10 @@ impl GlobalOptions {
             errors.push("conflicting values for 'expire_metrics_secs' found".to_owned());
         }
 
+        if conflicts(self.buffer_pool.as_ref(), with.buffer_pool.as_ref()) {
+            errors.push("conflicting values for 'buffer_pool' found".to_owned());
+        }
+
         let data_dir = if self.data_dir.is_none() || self.data_dir == default_data_dir() {
             with.data_dir
         } else if with.data_dir != default_data_dir() && self.data_dir != with.data_dir {
@@ -344,6 +358,7 @@ impl GlobalOptions {
                 buffer_utilization_ewma_half_life_seconds: self
                     .buffer_utilization_ewma_half_life_seconds
                     .or(with.buffer_utilization_ewma_half_life_seconds),
+                buffer_pool: self.buffer_pool.clone().or(with.buffer_pool),
                 latency_ewma_alpha: self.latency_ewma_alpha.or(with.latency_ewma_alpha),
                 metrics_storage_refresh_period: self
                     .metrics_storage_refresh_period
@@ -509,6 +524,24 @@ mod tests {
         );
     }
 
+    #[test]
+    fn merges_buffer_pool() {
+        let merge = |a, b| {
+            merge("buffer_pool.max_size", a, b, |result| {
+                result.buffer_pool.map(|pool| pool.max_size.get())
+            })
+        };
+
+        assert_eq!(merge(None::<u64>, None), Ok(None));
+        assert_eq!(merge(Some(1024), None), Ok(Some(1024)));
+        assert_eq!(merge(None, Some(2048)), Ok(Some(2048)));
+        assert_eq!(merge(Some(1024), Some(1024)), Ok(Some(1024)));
+        assert_eq!(
+            merge(Some(1024), Some(2048)),
+            Err(vec!["conflicting values for 'buffer_pool' found".into()])
+        );
+    }
+
     #[test]
     fn diff_detects_changed_keys() {
         let old = GlobalOptions {
@@ -37,6 +37,11 @@ impl Discriminant {
             .collect();
         Self { values }
     }
+
+    /// The values of the discriminant fields, in the order the fields were given.
+    pub fn values(&self) -> &[Option<Value>] {
+        &self.values
+    }
 }
 
 impl PartialEq for Discriminant {
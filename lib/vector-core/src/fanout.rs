@@ -1,20 +1,81 @@
-use std::{collections::HashMap, fmt, task::Poll, time::Instant};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+    num::NonZeroU64,
+    task::Poll,
+    time::{Duration, Instant},
+};
 
 use futures::{Stream, StreamExt};
 use futures_util::{pending, poll};
 use indexmap::IndexMap;
-use tokio::sync::mpsc;
+use metrics::{Counter, Histogram};
+use tokio::{sync::mpsc, task::JoinHandle};
 use tokio_util::sync::ReusableBoxFuture;
+use tracing::Span;
 use vector_buffers::topology::channel::BufferSender;
+use vector_common::{
+    counter, histogram,
+    internal_event::{self, ComponentEventsDropped, CounterName, HistogramName, INTENTIONAL},
+};
+use vector_config::configurable_component;
 
 use crate::{
     config::ComponentKey,
     event::{EventArray, EventContainer},
 };
 
+const fn default_detach_timeout_secs() -> NonZeroU64 {
+    NonZeroU64::new(5).unwrap()
+}
+
+/// How a fanout treats a downstream component that isn't keeping up with the events sent to it.
+///
+/// By default, the fanout waits for every downstream component to accept the events before sending
+/// the next ones, so a single slow component slows down all of the other components fed by the same
+/// upstream component. Isolating a component stops it from holding up the others, at the cost of
+/// dropping some of the events sent to it.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case", tag = "mode")]
+#[configurable(metadata(docs::enum_tag_description = "The isolation mode."))]
+pub enum Isolation {
+    /// Wait for the component to accept the events.
+    #[default]
+    Block,
+
+    /// Drop the events for this component only, if it can't accept them right away.
+    ///
+    /// Events that the component started accepting are still delivered to it in the background,
+    /// unless the component is removed first.
+    Drop,
+
+    /// Wait for the component to accept the events for up to `timeout_secs`, then detach it.
+    ///
+    /// While detached, events for the component are dropped, until it accepts the events it was
+    /// waiting on.
+    Detach {
+        /// How long to wait for the component before detaching it, in seconds.
+        #[serde(default = "default_detach_timeout_secs")]
+        #[configurable(metadata(docs::type_unit = "seconds"))]
+        timeout_secs: NonZeroU64,
+    },
+}
+
+impl Isolation {
+    /// How long to wait for the component before giving up on it, if it's isolated at all.
+    fn timeout(self) -> Option<Duration> {
+        match self {
+            Self::Block => None,
+            Self::Drop => Some(Duration::ZERO),
+            Self::Detach { timeout_secs } => Some(Duration::from_secs(timeout_secs.get())),
+        }
+    }
+}
+
 pub enum ControlMessage {
     /// Adds a new sink to the fanout.
-    Add(ComponentKey, BufferSender<EventArray>),
+    Add(ComponentKey, BufferSender<EventArray>, Isolation),
 
     /// Removes a sink from the fanout.
     Remove(ComponentKey),
@@ -26,17 +87,17 @@ pub enum ControlMessage {
     Pause(ComponentKey),
 
     /// Replaces a paused sink with its new sender.
-    Replace(ComponentKey, BufferSender<EventArray>),
+    Replace(ComponentKey, BufferSender<EventArray>, Isolation),
 }
 
 impl fmt::Debug for ControlMessage {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "ControlMessage::")?;
         match self {
-            Self::Add(id, _) => write!(f, "Add({id:?})"),
+            Self::Add(id, _, isolation) => write!(f, "Add({id:?}, {isolation:?})"),
             Self::Remove(id) => write!(f, "Remove({id:?})"),
             Self::Pause(id) => write!(f, "Pause({id:?})"),
-            Self::Replace(id, _) => write!(f, "Replace({id:?})"),
+            Self::Replace(id, _, isolation) => write!(f, "Replace({id:?}, {isolation:?})"),
         }
     }
 }
@@ -69,29 +130,34 @@ impl Fanout {
     /// # Panics
     ///
     /// Function will panic if a sink with the same ID is already present.
-    pub fn add(&mut self, id: ComponentKey, sink: BufferSender<EventArray>) {
+    pub fn add(&mut self, id: ComponentKey, sink: BufferSender<EventArray>, isolation: Isolation) {
         assert!(
             !self.senders.contains_key(&id),
             "Adding duplicate output id to fanout: {id}"
         );
-        self.senders.insert(id, Some(Sender::new(sink)));
+        let sender = Sender::new(&id, sink, isolation);
+        self.senders.insert(id, Some(sender));
     }
 
     fn remove(&mut self, id: &ComponentKey) {
+        let sender = self.senders.shift_remove(id);
         assert!(
-            self.senders.shift_remove(id).is_some(),
+            sender.is_some(),
             "Removing nonexistent sink from fanout: {id}"
         );
+        if let Some(sender) = sender.flatten() {
+            sender.cancel_detached();
+        }
     }
 
-    fn replace(&mut self, id: &ComponentKey, sink: BufferSender<EventArray>) {
+    fn replace(&mut self, id: &ComponentKey, sink: BufferSender<EventArray>, isolation: Isolation) {
         match self.senders.get_mut(id) {
             Some(sender) => {
                 // While a sink must be _known_ to be replaced, it must also be empty (previously
                 // paused or consumed when the `SendGroup` was created), otherwise an invalid
                 // sequence of control operations has been applied.
                 assert!(
-                    sender.replace(Sender::new(sink)).is_none(),
+                    sender.replace(Sender::new(id, sink, isolation)).is_none(),
                     "Replacing existing sink is not valid: {id}"
                 );
             }
@@ -134,10 +200,10 @@ impl Fanout {
         trace!("Processing control message outside of send: {:?}", message);
 
         match message {
-            ControlMessage::Add(id, sink) => self.add(id, sink),
+            ControlMessage::Add(id, sink, isolation) => self.add(id, sink, isolation),
             ControlMessage::Remove(id) => self.remove(&id),
             ControlMessage::Pause(id) => self.pause(&id),
-            ControlMessage::Replace(id, sink) => self.replace(&id, sink),
+            ControlMessage::Replace(id, sink, isolation) => self.replace(&id, sink, isolation),
         }
     }
 
@@ -277,8 +343,8 @@ impl Fanout {
                     // During a send operation, control messages must be applied via the
                     // `SendGroup`, since it has exclusive access to the senders.
                     match maybe_msg {
-                        Some(ControlMessage::Add(id, sink)) => {
-                            send_group.add(id, sink);
+                        Some(ControlMessage::Add(id, sink, isolation)) => {
+                            send_group.add(id, sink, isolation);
                         },
                         Some(ControlMessage::Remove(id)) => {
                             send_group.remove(&id);
@@ -286,8 +352,9 @@ impl Fanout {
                         Some(ControlMessage::Pause(id)) => {
                             send_group.pause(&id);
                         },
-                        Some(ControlMessage::Replace(id, sink)) => {
-                            send_group.replace(&id, Sender::new(sink));
+                        Some(ControlMessage::Replace(id, sink, isolation)) => {
+                            let sender = Sender::new(&id, sink, isolation);
+                            send_group.replace(&id, sender);
                         },
                         None => {
                             // Control channel is closed, which means Vector is shutting down.
@@ -313,6 +380,8 @@ impl Fanout {
 struct SendGroup<'a> {
     senders: &'a mut IndexMap<ComponentKey, Option<Sender>>,
     sends: HashMap<ComponentKey, ReusableBoxFuture<'static, crate::Result<Sender>>>,
+    /// The components of the sends that are isolated, which are cancelled when they're removed.
+    isolated: HashSet<ComponentKey>,
 }
 
 impl<'a> SendGroup<'a> {
@@ -331,10 +400,14 @@ impl<'a> SendGroup<'a> {
         // We generate a send future for each sender we have, which arms them with the events to
         // send but also takes ownership of the sender itself, which we give back when the sender completes.
         let mut sends = HashMap::new();
+        let mut isolated = HashSet::new();
         for (i, (key, sender)) in senders.iter_mut().enumerate() {
             let mut sender = sender
                 .take()
                 .expect("sender must be present to initialize SendGroup");
+            if sender.isolation != Isolation::Block {
+                isolated.insert(key.clone());
+            }
 
             // First, arm each sender with the item to actually send.
             if i == last_sender_idx {
@@ -353,7 +426,11 @@ impl<'a> SendGroup<'a> {
             sends.insert(key.clone(), ReusableBoxFuture::new(send));
         }
 
-        Self { senders, sends }
+        Self {
+            senders,
+            sends,
+            isolated,
+        }
     }
 
     fn try_detach_send(&mut self, id: &ComponentKey) -> bool {
@@ -377,13 +454,12 @@ impl<'a> SendGroup<'a> {
     }
 
     #[allow(clippy::needless_pass_by_value)]
-    fn add(&mut self, id: ComponentKey, sink: BufferSender<EventArray>) {
+    fn add(&mut self, id: ComponentKey, sink: BufferSender<EventArray>, isolation: Isolation) {
         // When we're in the middle of a send, we can only keep track of the new sink, but can't
         // actually send to it, as we don't have the item to send... so only add it to `senders`.
+        let sender = Sender::new(&id, sink, isolation);
         assert!(
-            self.senders
-                .insert(id.clone(), Some(Sender::new(sink)))
-                .is_none(),
+            self.senders.insert(id.clone(), Some(sender)).is_none(),
             "Adding duplicate output id to fanout: {id}"
         );
     }
@@ -392,16 +468,25 @@ impl<'a> SendGroup<'a> {
         // We may or may not be removing a sender that we're try to drive a send against, so we have
         // to also detach the send future for the sender if it exists, otherwise we'd be hanging
         // around still trying to send to it.
+        let sender = self.senders.shift_remove(id);
         assert!(
-            self.senders.shift_remove(id).is_some(),
+            sender.is_some(),
             "Removing nonexistent sink from fanout: {id}"
         );
+        if let Some(sender) = sender.flatten() {
+            sender.cancel_detached();
+        }
 
         // Now try and detach the in-flight send, if it exists.
         //
         // We don't ensure that a send was or wasn't detached because this could be called either
-        // during an in-flight send _or_ after the send has completed.
-        self.try_detach_send(id);
+        // during an in-flight send _or_ after the send has completed. The in-flight send of an
+        // isolated component is dropped instead, since the events sent to it may be dropped anyway.
+        if self.isolated.contains(id) {
+            self.sends.remove(id);
+        } else {
+            self.try_detach_send(id);
+        }
     }
 
     fn replace(&mut self, id: &ComponentKey, sink: Sender) {
@@ -476,35 +561,172 @@ impl<'a> SendGroup<'a> {
     }
 }
 
+/// The send of an isolated component, which owns the buffer sender of the component until it
+/// completes.
+type IsolatedSend = ReusableBoxFuture<'static, crate::Result<BufferSender<EventArray>>>;
+
 struct Sender {
-    inner: BufferSender<EventArray>,
+    id: ComponentKey,
+    /// Only missing while an isolated component is detached, as its send owns it until then.
+    inner: Option<BufferSender<EventArray>>,
     input: Option<EventArray>,
     send_reference: Option<Instant>,
+    isolation: Isolation,
+    /// The allocation reused by every send of an isolated component.
+    isolated_send: Option<IsolatedSend>,
+    /// The send that the component was detached on, if it hasn't been attached again yet.
+    detached: Option<JoinHandle<crate::Result<(BufferSender<EventArray>, IsolatedSend)>>>,
+    /// Only recorded for isolated components, as the others never drop events.
+    send_duration: Option<Histogram>,
+    detached_total: Counter,
+    discarded_events: Counter,
+    /// The span of the downstream component, which the events dropped for it are reported in.
+    span: Span,
 }
 
 impl Sender {
-    fn new(inner: BufferSender<EventArray>) -> Self {
+    fn new(id: &ComponentKey, inner: BufferSender<EventArray>, isolation: Isolation) -> Self {
+        let consumer_id = id.to_string();
         Self {
-            inner,
+            id: id.clone(),
+            inner: Some(inner),
             input: None,
             send_reference: None,
+            isolation,
+            isolated_send: None,
+            detached: None,
+            send_duration: isolation.timeout().map(|_| {
+                histogram!(
+                    HistogramName::FanoutSendDurationSeconds,
+                    "consumer_id" => consumer_id.clone(),
+                )
+            }),
+            detached_total: counter!(
+                CounterName::FanoutDetachedTotal,
+                "consumer_id" => consumer_id.clone(),
+            ),
+            discarded_events: counter!(
+                CounterName::FanoutDiscardedEventsTotal,
+                "consumer_id" => consumer_id,
+            ),
+            // Only sinks can be isolated. The span has no parent, so that the events dropped for the
+            // sink aren't tagged with the upstream component the fanout belongs to.
+            span: tracing::error_span!(
+                parent: None,
+                "sink",
+                component_kind = "sink",
+                component_id = %id.id(),
+            ),
         }
     }
 
     async fn flush(&mut self) -> crate::Result<()> {
         let send_reference = self.send_reference.take();
-        if let Some(input) = self.input.take() {
-            self.inner.send(input, send_reference).await?;
-            self.inner.flush().await?;
+        let Some(input) = self.input.take() else {
+            return Ok(());
+        };
+
+        let Some(timeout) = self.isolation.timeout() else {
+            let inner = self
+                .inner
+                .as_mut()
+                .expect("only isolated components are detached");
+            inner.send(input, send_reference).await?;
+            inner.flush().await?;
+            return Ok(());
+        };
+
+        if let Some(detached) = self.detached.take() {
+            if !detached.is_finished() {
+                self.detached = Some(detached);
+                self.discard(input);
+                return Ok(());
+            }
+            // The component caught up, so it's attached again, unless its buffer went away.
+            let (inner, isolated_send) = detached.await??;
+            self.inner = Some(inner);
+            self.isolated_send = Some(isolated_send);
         }
 
-        Ok(())
+        // The send is polled in place first, so that a component that keeps up costs neither a
+        // timer nor a task. It's only cancelled when the component is removed, as that isn't safe
+        // for every type of buffer. Instead, it's moved to the background along with the buffer
+        // sender if it takes too long, and the component stays detached until it completes.
+        let started = Instant::now();
+        let mut inner = self
+            .inner
+            .take()
+            .expect("attached components have a sender");
+        let send = async move {
+            inner.send(input, send_reference).await?;
+            inner.flush().await?;
+            Ok::<_, crate::Error>(inner)
+        };
+        let mut send = match self.isolated_send.take() {
+            Some(mut isolated_send) => {
+                isolated_send.set(send);
+                isolated_send
+            }
+            None => ReusableBoxFuture::new(send),
+        };
+        let result = match poll!(send.get_pin()) {
+            Poll::Ready(result) => Some(result),
+            Poll::Pending if timeout.is_zero() => None,
+            Poll::Pending => tokio::time::timeout(timeout, &mut send).await.ok(),
+        };
+        if let Some(send_duration) = &self.send_duration {
+            send_duration.record(started.elapsed());
+        }
+        match result {
+            Some(result) => {
+                self.inner = Some(result?);
+                self.isolated_send = Some(send);
+                Ok(())
+            }
+            None => {
+                if !matches!(self.isolation, Isolation::Drop) {
+                    warn!(
+                        message = "Downstream component is not keeping up, detaching it until it does.",
+                        consumer_id = %self.id,
+                        timeout_secs = timeout.as_secs(),
+                    );
+                }
+                self.detached_total.increment(1);
+                self.detached = Some(tokio::spawn(async move {
+                    let inner = (&mut send).await?;
+                    Ok::<_, crate::Error>((inner, send))
+                }));
+                Ok(())
+            }
+        }
+    }
+
+    /// Cancels the send that the component was detached on, as the component is being removed.
+    fn cancel_detached(&self) {
+        if let Some(detached) = &self.detached {
+            detached.abort();
+        }
+    }
+
+    fn discard(&self, events: EventArray) {
+        let count = events.len();
+        self.discarded_events.increment(count as u64);
+        self.span.in_scope(|| {
+            internal_event::emit(ComponentEventsDropped::<INTENTIONAL> {
+                count,
+                reason: "Downstream component is not keeping up.",
+            });
+        });
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use std::{mem, num::NonZeroUsize};
+    use std::{
+        mem,
+        num::{NonZeroU64, NonZeroUsize},
+        time::Duration,
+    };
 
     use futures::poll;
     use tokio::sync::mpsc::UnboundedSender;
@@ -519,10 +741,11 @@ mod tests {
     };
     use vrl::value::Value;
 
-    use super::{ControlMessage, Fanout};
+    use super::{ControlMessage, Fanout, Isolation};
     use crate::{
         config::ComponentKey,
-        event::{Event, EventArray, EventContainer, LogEvent},
+        event::{Event, EventArray, EventContainer, LogEvent, MetricValue},
+        metrics::{self, Controller},
         test_util::{collect_ready, collect_ready_events},
     };
 
@@ -560,7 +783,7 @@ mod tests {
 
         let mut receivers = Vec::new();
         for (i, (sender, receiver)) in pairs.into_iter().enumerate() {
-            fanout.add(ComponentKey::from(i.to_string()), sender);
+            fanout.add(ComponentKey::from(i.to_string()), sender, Isolation::Block);
             receivers.push(receiver);
         }
 
@@ -576,7 +799,11 @@ mod tests {
         let (sender, receiver) = build_sender_pair(capacity);
         receivers.push(receiver);
 
-        fanout.add(ComponentKey::from(sender_id.to_string()), sender);
+        fanout.add(
+            ComponentKey::from(sender_id.to_string()),
+            sender,
+            Isolation::Block,
+        );
     }
 
     fn remove_sender_from_fanout(control: &UnboundedSender<ControlMessage>, sender_id: usize) {
@@ -606,6 +833,7 @@ mod tests {
             .send(ControlMessage::Replace(
                 ComponentKey::from(sender_id.to_string()),
                 sender,
+                Isolation::Block,
             ))
             .expect("sending control message should not fail");
 
@@ -639,6 +867,7 @@ mod tests {
             .send(ControlMessage::Replace(
                 ComponentKey::from(sender_id.to_string()),
                 sender,
+                Isolation::Block,
             ))
             .expect("sending control message should not fail");
     }
@@ -964,6 +1193,209 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn fanout_drop_isolates_slow_sender() {
+        let (mut fanout, _control) = Fanout::new(ComponentKey::from("test_upstream"));
+        let (fast_sender, mut fast_receiver) = build_sender_pair(4);
+        let (slow_sender, mut slow_receiver) = build_sender_pair(1);
+        fanout.add(ComponentKey::from("fast"), fast_sender, Isolation::Block);
+        fanout.add(ComponentKey::from("slow"), slow_sender, Isolation::Drop);
+        let events = make_events(4);
+
+        // The slow sender only has room for the first event. The second one is still delivered to
+        // it in the background, and the third one is dropped for it while it's detached, but none
+        // of them hold up the fast sender.
+        for event in &events[..3] {
+            fanout
+                .send(event.clone().into(), None)
+                .await
+                .expect("should not fail");
+        }
+        for event in &events[..3] {
+            assert_eq!(Some(event.clone().into()), fast_receiver.next().await);
+        }
+        assert_eq!(Some(events[0].clone().into()), slow_receiver.next().await);
+        assert_eq!(Some(events[1].clone().into()), slow_receiver.next().await);
+
+        // Now that the slow sender caught up, it's attached again.
+        fanout
+            .send(events[3].clone().into(), None)
+            .await
+            .expect("should not fail");
+        assert_eq!(Some(events[3].clone().into()), fast_receiver.next().await);
+        assert_eq!(Some(events[3].clone().into()), slow_receiver.next().await);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn fanout_detach_isolates_slow_sender_after_timeout() {
+        let (mut fanout, _control) = Fanout::new(ComponentKey::from("test_upstream"));
+        let (fast_sender, mut fast_receiver) = build_sender_pair(4);
+        let (slow_sender, mut slow_receiver) = build_sender_pair(1);
+        fanout.add(ComponentKey::from("fast"), fast_sender, Isolation::Block);
+        fanout.add(
+            ComponentKey::from("slow"),
+            slow_sender,
+            Isolation::Detach {
+                timeout_secs: NonZeroU64::new(5).unwrap(),
+            },
+        );
+        let events = make_events(4);
+
+        // The first event fits in the slow sender, the second one waits for the timeout before the
+        // slow sender is detached, and the third one is dropped for it right away.
+        let started = tokio::time::Instant::now();
+        fanout
+            .send(events[0].clone().into(), None)
+            .await
+            .expect("should not fail");
+        assert!(started.elapsed() < Duration::from_secs(5));
+        fanout
+            .send(events[1].clone().into(), None)
+            .await
+            .expect("should not fail");
+        assert!(started.elapsed() >= Duration::from_secs(5));
+        let detached = tokio::time::Instant::now();
+        fanout
+            .send(events[2].clone().into(), None)
+            .await
+            .expect("should not fail");
+        assert!(detached.elapsed() < Duration::from_secs(5));
+
+        for event in &events[..3] {
+            assert_eq!(Some(event.clone().into()), fast_receiver.next().await);
+        }
+        assert_eq!(Some(events[0].clone().into()), slow_receiver.next().await);
+        assert_eq!(Some(events[1].clone().into()), slow_receiver.next().await);
+
+        // Now that the slow sender caught up, it's attached again.
+        fanout
+            .send(events[3].clone().into(), None)
+            .await
+            .expect("should not fail");
+        assert_eq!(Some(events[3].clone().into()), fast_receiver.next().await);
+        assert_eq!(Some(events[3].clone().into()), slow_receiver.next().await);
+    }
+
+    #[tokio::test]
+    async fn fanout_drop_counts_dropped_events() {
+        metrics::init_test();
+        let (mut fanout, _control) = Fanout::new(ComponentKey::from("test_upstream"));
+        let (fast_sender, mut fast_receiver) = build_sender_pair(4);
+        let (slow_sender, mut slow_receiver) = build_sender_pair(1);
+        fanout.add(ComponentKey::from("fast"), fast_sender, Isolation::Block);
+        fanout.add(ComponentKey::from("dropping"), slow_sender, Isolation::Drop);
+        let events = make_events(4);
+
+        // Only the third and fourth events are dropped, as the slow sender is detached on the
+        // second one and never catches up in between.
+        for event in &events {
+            fanout
+                .send(event.clone().into(), None)
+                .await
+                .expect("should not fail");
+        }
+        for event in &events {
+            assert_eq!(Some(event.clone().into()), fast_receiver.next().await);
+        }
+        assert_eq!(Some(events[0].clone().into()), slow_receiver.next().await);
+        assert_eq!(Some(events[1].clone().into()), slow_receiver.next().await);
+
+        assert_eq!(
+            counter_value("fanout_discarded_events_total", "dropping"),
+            2.0
+        );
+        assert_eq!(counter_value("fanout_detached_total", "dropping"), 1.0);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn fanout_detach_recovers_after_timeout() {
+        metrics::init_test();
+        let (mut fanout, _control) = Fanout::new(ComponentKey::from("test_upstream"));
+        let (slow_sender, mut slow_receiver) = build_sender_pair(1);
+        fanout.add(
+            ComponentKey::from("detaching"),
+            slow_sender,
+            Isolation::Detach {
+                timeout_secs: NonZeroU64::new(5).unwrap(),
+            },
+        );
+        let events = make_events(5);
+
+        // The second event times out, the third one is dropped while the slow sender is detached.
+        for event in &events[..3] {
+            fanout
+                .send(event.clone().into(), None)
+                .await
+                .expect("should not fail");
+        }
+        assert_eq!(Some(events[0].clone().into()), slow_receiver.next().await);
+        assert_eq!(Some(events[1].clone().into()), slow_receiver.next().await);
+
+        // Once attached again, the slow sender gets every event that it keeps up with.
+        for event in &events[3..] {
+            fanout
+                .send(event.clone().into(), None)
+                .await
+                .expect("should not fail");
+            assert_eq!(Some(event.clone().into()), slow_receiver.next().await);
+        }
+
+        assert_eq!(
+            counter_value("fanout_discarded_events_total", "detaching"),
+            1.0
+        );
+        assert_eq!(counter_value("fanout_detached_total", "detaching"), 1.0);
+    }
+
+    #[tokio::test]
+    async fn fanout_remove_cancels_detached_send() {
+        let (mut fanout, control) = Fanout::new(ComponentKey::from("test_upstream"));
+        let (fast_sender, mut fast_receiver) = build_sender_pair(4);
+        let (slow_sender, mut slow_receiver) = build_sender_pair(1);
+        fanout.add(ComponentKey::from("0"), fast_sender, Isolation::Block);
+        fanout.add(ComponentKey::from("1"), slow_sender, Isolation::Drop);
+        let events = make_events(3);
+
+        // The second event is still waiting to be delivered to the slow sender when it's removed.
+        for event in &events[..2] {
+            fanout
+                .send(event.clone().into(), None)
+                .await
+                .expect("should not fail");
+        }
+        remove_sender_from_fanout(&control, 1);
+        fanout
+            .send(events[2].clone().into(), None)
+            .await
+            .expect("should not fail");
+
+        for event in &events {
+            assert_eq!(Some(event.clone().into()), fast_receiver.next().await);
+        }
+
+        // Cancelling the detached send drops the last buffer sender, so the slow receiver ends
+        // without ever getting the second event.
+        assert_eq!(Some(events[0].clone().into()), slow_receiver.next().await);
+        assert_eq!(None, slow_receiver.next().await);
+    }
+
+    fn counter_value(name: &str, consumer_id: &str) -> f64 {
+        let counters = Controller::get()
+            .expect("There must be a controller")
+            .capture_metrics()
+            .into_iter()
+            .filter(|metric| {
+                metric.name() == name
+                    && metric.tag_value("consumer_id").as_deref() == Some(consumer_id)
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(counters.len(), 1);
+        match counters[0].value() {
+            MetricValue::Counter { value } => *value,
+            value => panic!("{name} should be a counter, got {value:?}"),
+        }
+    }
+
     fn make_events_inner(count: usize) -> impl Iterator<Item = LogEvent> {
         (0..count).map(|i| LogEvent::from(format!("line {i}")))
     }
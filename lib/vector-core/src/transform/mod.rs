@@ -1,4 +1,4 @@
-use std::{collections::HashMap, pin::Pin, sync::Arc};
+use std::{collections::HashMap, pin::Pin, sync::Arc, time::Duration};
 
 use futures::{Stream, StreamExt};
 
@@ -142,6 +142,24 @@ pub trait SyncTransform: Send + dyn_clone::DynClone + Sync {
             self.transform(event, output);
         }
     }
+
+    /// The interval at which [`SyncTransform::flush`] is called, if any.
+    ///
+    /// Transforms that hold on to events between calls, such as those correlating events within a
+    /// time window, use this to write out events that are due even when no new events are received.
+    /// Transforms that return an interval can't enable concurrency, and fail to build if they do.
+    fn flush_interval(&self) -> Option<Duration> {
+        None
+    }
+
+    /// Writes out the events held by the transform that are due.
+    fn flush(&mut self, _output: &mut TransformOutputsBuf) {}
+
+    /// Writes out all of the events still held by the transform once its input has ended.
+    ///
+    /// This is only called for transforms that don't enable concurrency, which includes every
+    /// transform that returns a [`SyncTransform::flush_interval`].
+    fn flush_all(&mut self, _output: &mut TransformOutputsBuf) {}
 }
 
 dyn_clone::clone_trait_object!(SyncTransform);
@@ -11,7 +11,7 @@ use vector_common::{
     },
     request_metadata::{GroupedCountByteSize, MetaDescriptive},
 };
-use vector_core::event::{EventFinalizers, EventStatus, Finalizable};
+use vector_core::event::{DeliveryFailure, EventFinalizers, EventStatus, Finalizable};
 
 use super::FuturesUnorderedCount;
 
@@ -25,6 +25,14 @@ pub trait DriverResponse {
     fn bytes_sent(&self) -> Option<usize> {
         None
     }
+
+    /// Describes why the request that returned this response failed.
+    ///
+    /// This is only called when `event_status` is `Errored` or `Rejected`, and is passed along to
+    /// the events' finalizers so that undelivered events can be annotated with the reason.
+    fn delivery_failure(&self) -> DeliveryFailure {
+        DeliveryFailure::new("Response failed.")
+    }
 }
 
 /// Drives the interaction between a stream of items and a service which processes them
@@ -206,12 +214,18 @@ where
     ) {
         match result {
             Err(error) => {
+                let failure = DeliveryFailure::new(format!("{error:?}"));
                 Self::emit_call_error(Some(error), request_id, event_count);
-                finalizers.update_status(EventStatus::Rejected);
+                finalizers.update_status_with_failure(EventStatus::Rejected, &failure);
             }
             Ok(response) => {
                 trace!(message = "Service call succeeded.", request_id);
-                finalizers.update_status(response.event_status());
+                match response.event_status() {
+                    status @ (EventStatus::Errored | EventStatus::Rejected) => {
+                        finalizers.update_status_with_failure(status, &response.delivery_failure());
+                    }
+                    status => finalizers.update_status(status),
+                }
                 if response.event_status() == EventStatus::Delivered {
                     if let Some(bytes_sent) = bytes_sent
                         && let Some(byte_size) = response.bytes_sent()
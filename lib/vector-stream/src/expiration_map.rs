@@ -17,9 +17,21 @@ impl<T> Emitter<T> {
     }
 }
 
+impl<T> IntoIterator for Emitter<T> {
+    type Item = T;
+    type IntoIter = std::vec::IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.values.into_iter()
+    }
+}
+
 /// Similar to `stream.filter_map(..).flatten(..)` but also allows checking for expired events
 /// and flushing when the input stream ends.
-pub fn map_with_expiration<S, T, M, E, F>(
+///
+/// The flush takes the state by value and is awaited before the stream ends, so that it can finish
+/// any work, such as writing the state to disk, before the component shuts down.
+pub fn map_with_expiration<S, T, M, E, F, Fut>(
     initial_state: S,
     input: impl Stream<Item = T> + 'static,
     expiration_interval: Duration,
@@ -28,12 +40,13 @@ pub fn map_with_expiration<S, T, M, E, F>(
     // called periodically to allow expiring internal state
     mut expiration_fn: E,
     // called once at the end of the input stream
-    mut flush_fn: F,
+    flush_fn: F,
 ) -> impl Stream<Item = T>
 where
     M: FnMut(&mut S, T, &mut Emitter<T>),
     E: FnMut(&mut S, &mut Emitter<T>),
-    F: FnMut(&mut S, &mut Emitter<T>),
+    F: FnOnce(S) -> Fut,
+    Fut: Future<Output = Emitter<T>>,
 {
     let mut state = initial_state;
     let mut flush_stream = tokio::time::interval(expiration_interval);
@@ -49,10 +62,7 @@ where
                     }
                     maybe_event = input.next() => {
                       match maybe_event {
-                        None => {
-                            flush_fn(&mut state, &mut emitter);
-                            true
-                        }
+                        None => true,
                         Some(event) => {
                             map_fn(&mut state, event, &mut emitter);
                             false
@@ -60,10 +70,12 @@ where
                       }
                     }
                 };
-                yield futures::stream::iter(emitter.values.into_iter());
                 if done { break }
+                yield futures::stream::iter(emitter.values.into_iter());
               }
 
+        let emitter = flush_fn(state).await;
+        yield futures::stream::iter(emitter.values.into_iter());
     })
     .flatten()
 }
@@ -83,8 +95,10 @@ mod test {
         let expiration_fn = |_state: &mut i32, _emitter: &mut Emitter<i32>| {
             // do nothing
         };
-        let flush_fn = |state: &mut i32, emitter: &mut Emitter<i32>| {
-            emitter.emit(*state);
+        let flush_fn = |state: i32| async move {
+            let mut emitter = Emitter::new();
+            emitter.emit(state);
+            emitter
         };
         let stream: Vec<i32> = map_with_expiration(
             0_i32,
@@ -113,8 +127,9 @@ mod test {
         let expiration_fn = |state: &mut i32, emitter: &mut Emitter<i32>| {
             emitter.emit(*state);
         };
-        let flush_fn = |_state: &mut i32, _emitter: &mut Emitter<i32>| {
+        let flush_fn = |_state: i32| async {
             // do nothing
+            Emitter::new()
         };
         let stream: Vec<i32> = map_with_expiration(
             0_i32,
@@ -0,0 +1,93 @@
+//! Capture files record tapped events so they can be played back by the `replay` source.
+//!
+//! A capture file is a sequence of frames, each holding one event in Vector's native protobuf
+//! encoding, prefixed with its length as a 4-byte big-endian integer. This is what the `native`
+//! codec produces with `length_delimited` framing, so capture files can also be read by any source
+//! configured with them. The component each event was tapped from, and when, are recorded in its
+//! metadata.
+
+use chrono::{DateTime, Utc};
+use vector_core::event::Event;
+
+/// Metadata path of the ID of the component a captured event was tapped from.
+pub const COMPONENT_ID_PATH: &str = "vector.tap.component_id";
+
+/// Metadata path of the time a captured event was tapped.
+pub const TAPPED_AT_PATH: &str = "vector.tap.tapped_at";
+
+/// Records the component `event` was tapped from, and when, in its metadata.
+pub fn annotate(event: &mut Event, component_id: &str, tapped_at: DateTime<Utc>) {
+    let metadata = event.metadata_mut().value_mut();
+    metadata.insert(COMPONENT_ID_PATH, component_id);
+    metadata.insert(TAPPED_AT_PATH, tapped_at);
+}
+
+/// Returns when a captured event was tapped.
+pub fn tapped_at(event: &Event) -> Option<DateTime<Utc>> {
+    event
+        .metadata()
+        .value()
+        .get(TAPPED_AT_PATH)
+        .and_then(|value| value.as_timestamp())
+        .copied()
+}
+
+#[cfg(feature = "api")]
+pub use self::encoder::CaptureEncoder;
+
+#[cfg(feature = "api")]
+mod encoder {
+    use bytes::{Bytes, BytesMut};
+    use chrono::{DateTime, Utc};
+    use codecs::{LengthDelimitedEncoder, NativeSerializer};
+    use prost::Message;
+    use tokio_util::codec::Encoder;
+    use vector_api_client::proto::TappedEvent;
+    use vector_core::event::Event;
+
+    /// Encodes tapped events into capture file frames.
+    #[derive(Debug, Default)]
+    pub struct CaptureEncoder {
+        framer: LengthDelimitedEncoder,
+        frame: BytesMut,
+    }
+
+    impl CaptureEncoder {
+        /// Appends the frame of `tapped` to `buffer`. Tapped events without event data are
+        /// skipped.
+        pub fn encode(
+            &mut self,
+            tapped: &TappedEvent,
+            buffer: &mut BytesMut,
+        ) -> Result<(), String> {
+            let Some(event_wrapper) = &tapped.event else {
+                return Ok(());
+            };
+
+            // Round-trip through protobuf bytes, as `vector_api_client` compiles `event.proto`
+            // independently of `vector_core`. See `TapRunner::serialize_event`.
+            let core_event_wrapper = vector_core::event::proto::EventWrapper::decode(Bytes::from(
+                event_wrapper.encode_to_vec(),
+            ))
+            .map_err(|error| format!("Failed to decode event: {error}"))?;
+            let mut event = Event::from(core_event_wrapper);
+
+            let tapped_at = tapped
+                .tapped_at
+                .as_ref()
+                .and_then(|ts| DateTime::from_timestamp(ts.seconds, ts.nanos as u32))
+                .unwrap_or_else(Utc::now);
+            super::annotate(&mut event, &tapped.component_id, tapped_at);
+
+            self.frame.clear();
+            NativeSerializer
+                .encode(event, &mut self.frame)
+                .map_err(|error| format!("Failed to encode event: {error}"))?;
+            self.framer
+                .encode((), &mut self.frame)
+                .map_err(|error| format!("Failed to frame event: {error}"))?;
+            buffer.extend_from_slice(&self.frame);
+            Ok(())
+        }
+    }
+}
@@ -1,9 +1,15 @@
 use std::{
     collections::{HashMap, HashSet},
+    fmt,
     num::NonZeroUsize,
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    },
 };
 
 use futures::{FutureExt, future::try_join_all};
+use rand::{Rng, SeedableRng as _, rngs::SmallRng};
 use tokio::sync::{
     mpsc as tokio_mpsc,
     mpsc::error::{SendError, TrySendError},
@@ -14,12 +20,12 @@ use uuid::Uuid;
 use vector_buffers::{WhenFull, topology::builder::TopologyBuilder};
 use vector_common::config::ComponentKey;
 use vector_core::{
-    event::{EventArray, LogArray, MetricArray, TraceArray},
+    event::{Event, EventArray, EventContainer, LogArray, MetricArray, TraceArray},
     fanout,
 };
 
 use crate::{
-    notification::{InvalidMatch, Matched, NotMatched, Notification},
+    notification::{EventsDropped, InvalidMatch, Matched, NotMatched, Notification},
     topology::{TapOutput, TapResource, WatchRx},
 };
 
@@ -100,6 +106,81 @@ impl TapPatterns {
     }
 }
 
+/// A condition evaluated against each tapped event. Like `Condition::check`, it takes the event
+/// by value and hands it back along with the result.
+pub type TapCondition = Arc<dyn Fn(Event) -> (bool, Event) + Send + Sync>;
+
+/// Server-side selection of tapped events.
+///
+/// The filter is applied by the task that forwards events from each matched output to the tap
+/// receiver, so it runs off the hot path of the tapped component, and events it rejects never
+/// take up room in the channel to the client.
+#[derive(Clone, Default)]
+pub struct TapFilter {
+    condition: Option<TapCondition>,
+    sample_ratio: Option<f64>,
+}
+
+impl TapFilter {
+    /// Only forward events matching the given condition.
+    pub fn with_condition(mut self, condition: TapCondition) -> Self {
+        self.condition = Some(condition);
+        self
+    }
+
+    /// Only forward each event with the given probability, which must be within `0.0..=1.0`.
+    pub fn with_sample_ratio(mut self, sample_ratio: f64) -> Self {
+        self.sample_ratio = Some(sample_ratio);
+        self
+    }
+
+    /// Returns the events of `events` that pass the filter, or `None` if none do.
+    fn apply(&self, events: EventArray, rng: &mut SmallRng) -> Option<EventArray> {
+        if self.condition.is_none() && self.sample_ratio.is_none() {
+            return Some(events);
+        }
+
+        let events = match events {
+            EventArray::Logs(logs) => EventArray::Logs(self.retain(logs, rng, Event::into_log)),
+            EventArray::Metrics(metrics) => {
+                EventArray::Metrics(self.retain(metrics, rng, Event::into_metric))
+            }
+            EventArray::Traces(traces) => {
+                EventArray::Traces(self.retain(traces, rng, Event::into_trace))
+            }
+        };
+        events.iter_events().next().is_some().then_some(events)
+    }
+
+    fn retain<T: Into<Event>>(
+        &self,
+        items: Vec<T>,
+        rng: &mut SmallRng,
+        from_event: fn(Event) -> T,
+    ) -> Vec<T> {
+        items
+            .into_iter()
+            .filter(|_| self.sample_ratio.is_none_or(|ratio| rng.random_bool(ratio)))
+            .filter_map(|item| match &self.condition {
+                Some(condition) => {
+                    let (matched, event) = condition(item.into());
+                    matched.then(|| from_event(event))
+                }
+                None => Some(item),
+            })
+            .collect()
+    }
+}
+
+impl fmt::Debug for TapFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TapFilter")
+            .field("condition", &self.condition.is_some())
+            .field("sample_ratio", &self.sample_ratio)
+            .finish()
+    }
+}
+
 /// A tap payload contains events or notifications that alert users about the
 /// status of the tap request.
 #[derive(Debug)]
@@ -121,6 +202,11 @@ impl TapPayload {
         Self::Notification(Notification::NotMatched(NotMatched::new(pattern.into())))
     }
 
+    /// Raise an `events_dropped` event for the given number of events.
+    pub fn events_dropped(count: usize) -> Self {
+        Self::Notification(Notification::EventsDropped(EventsDropped::new(count)))
+    }
+
     /// Raise an `invalid_match` event against the provided input pattern.
     pub fn invalid_input_pattern_match<T: Into<String>>(
         pattern: T,
@@ -159,26 +245,42 @@ impl TapPayload {
 pub struct TapTransformer {
     tap_tx: TapSender,
     output: TapOutput,
+    dropped_events: Arc<AtomicUsize>,
 }
 
 impl TapTransformer {
-    pub const fn new(tap_tx: TapSender, output: TapOutput) -> Self {
-        Self { tap_tx, output }
+    pub const fn new(
+        tap_tx: TapSender,
+        output: TapOutput,
+        dropped_events: Arc<AtomicUsize>,
+    ) -> Self {
+        Self {
+            tap_tx,
+            output,
+            dropped_events,
+        }
     }
 
     pub fn try_send(&mut self, events: EventArray) {
+        let count = events.len();
         let payload = match events {
             EventArray::Logs(logs) => TapPayload::Log(self.output.clone(), logs),
             EventArray::Metrics(metrics) => TapPayload::Metric(self.output.clone(), metrics),
             EventArray::Traces(traces) => TapPayload::Trace(self.output.clone(), traces),
         };
 
-        if let Err(TrySendError::Closed(payload)) = self.tap_tx.try_send(payload) {
-            debug!(
-                message = "Couldn't send event.",
-                payload = ?payload,
-                component_id = ?self.output.output_id,
-            );
+        match self.tap_tx.try_send(payload) {
+            Ok(()) => {}
+            Err(TrySendError::Full(_)) => {
+                self.dropped_events.fetch_add(count, Ordering::Relaxed);
+            }
+            Err(TrySendError::Closed(payload)) => {
+                debug!(
+                    message = "Couldn't send event.",
+                    payload = ?payload,
+                    component_id = ?self.output.output_id,
+                );
+            }
         }
     }
 }
@@ -188,17 +290,33 @@ impl TapTransformer {
 #[derive(Debug)]
 pub struct TapController {
     _shutdown: ShutdownTx,
+    dropped_events: Arc<AtomicUsize>,
 }
 
 impl TapController {
     /// Creates a new tap sink, and spawns a handler for watching for topology changes
     /// and a separate inner handler for events. Uses a oneshot channel to trigger shutdown
-    /// of handlers when the `TapSink` drops out of scope.
-    pub fn new(watch_rx: WatchRx, tap_tx: TapSender, patterns: TapPatterns) -> Self {
+    /// of handlers when the `TapSink` drops out of scope. Only events passing `filter` are
+    /// sent to `tap_tx`.
+    pub fn new(
+        watch_rx: WatchRx,
+        tap_tx: TapSender,
+        patterns: TapPatterns,
+        filter: TapFilter,
+    ) -> Self {
         let (_shutdown, shutdown_rx) = oneshot::channel();
+        let dropped_events = Arc::new(AtomicUsize::new(0));
 
         tokio::spawn(
-            tap_handler(patterns, tap_tx, watch_rx, shutdown_rx).instrument(error_span!(
+            tap_handler(
+                patterns,
+                filter,
+                tap_tx,
+                Arc::clone(&dropped_events),
+                watch_rx,
+                shutdown_rx,
+            )
+            .instrument(error_span!(
                 "tap_handler",
                 component_kind = "sink",
                 component_id = "_tap", // It isn't clear what the component_id should be here other than "_tap"
@@ -206,7 +324,16 @@ impl TapController {
             )),
         );
 
-        Self { _shutdown }
+        Self {
+            _shutdown,
+            dropped_events,
+        }
+    }
+
+    /// Returns the number of tapped events dropped because the tap receiver was full since the
+    /// last call.
+    pub fn take_dropped_events(&self) -> usize {
+        self.dropped_events.swap(0, Ordering::Relaxed)
     }
 }
 
@@ -273,7 +400,9 @@ async fn send_invalid_output_pattern_match(
 /// `LogEvent`s` when a component matches one or more of the provided patterns.
 async fn tap_handler(
     patterns: TapPatterns,
+    filter: TapFilter,
     tx: TapSender,
+    dropped_events: Arc<AtomicUsize>,
     mut watch_rx: WatchRx,
     mut shutdown_rx: ShutdownRx,
 ) {
@@ -364,11 +493,15 @@ async fn tap_handler(
                                 None,
                                 None,
                             );
-                            let mut tap_transformer = TapTransformer::new(tx.clone(), output.clone());
+                            let mut tap_transformer = TapTransformer::new(tx.clone(), output.clone(), Arc::clone(&dropped_events));
+                            let filter = filter.clone();
 
                             vector_common::spawn_in_current_span(async move {
+                                let mut rng = SmallRng::from_rng(&mut rand::rng());
                                 while let Some(events) = tap_buffer_rx.next().await {
-                                    tap_transformer.try_send(events);
+                                    if let Some(events) = filter.apply(events, &mut rng) {
+                                        tap_transformer.try_send(events);
+                                    }
                                 }
                             });
 
@@ -379,7 +512,7 @@ async fn tap_handler(
                             // this point.
                             let sink_id = Uuid::new_v4().to_string();
                             match control_tx
-                                .send(fanout::ControlMessage::Add(ComponentKey::from(sink_id.as_str()), tap_buffer_tx))
+                                .send(fanout::ControlMessage::Add(ComponentKey::from(sink_id.as_str()), tap_buffer_tx, fanout::Isolation::Block))
                             {
                                 Ok(_) => {
                                     debug!(
@@ -481,4 +614,45 @@ mod tests {
             assert!(!patterns.iter().any(|p| p.to_string().matches_glob(id)));
         }
     }
+
+    #[test]
+    /// Filters should apply the condition and sample ratio to each event.
+    fn filter_events() {
+        use std::sync::Arc;
+
+        use rand::{SeedableRng as _, rngs::SmallRng};
+        use vector_core::event::{EventArray, LogEvent};
+
+        use super::TapFilter;
+
+        let mut rng = SmallRng::seed_from_u64(0);
+        let events = || {
+            EventArray::from(vec![
+                LogEvent::from("keep"),
+                LogEvent::from("drop"),
+                LogEvent::from("keep"),
+            ])
+        };
+
+        let filter = TapFilter::default().with_condition(Arc::new(|event| {
+            let matched = event
+                .as_log()
+                .get("message")
+                .is_some_and(|message| message.to_string_lossy() == "keep");
+            (matched, event)
+        }));
+        let filtered = filter
+            .apply(events(), &mut rng)
+            .expect("events should match");
+        assert_eq!(filtered.iter_events().count(), 2);
+
+        let filter = filter.with_sample_ratio(0.0);
+        assert!(filter.apply(events(), &mut rng).is_none());
+
+        let filter = TapFilter::default().with_sample_ratio(1.0);
+        let filtered = filter
+            .apply(events(), &mut rng)
+            .expect("events should be kept");
+        assert_eq!(filtered.iter_events().count(), 3);
+    }
 }
@@ -3,6 +3,7 @@
 #[macro_use]
 extern crate tracing;
 
+pub mod capture;
 pub mod controller;
 pub mod notification;
 pub mod topology;
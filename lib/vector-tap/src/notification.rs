@@ -55,12 +55,32 @@ impl InvalidMatch {
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Tapped events were dropped rather than sent to the client
+pub struct EventsDropped {
+    message: String,
+    /// Number of events dropped since the last notification
+    pub count: usize,
+}
+
+impl EventsDropped {
+    pub fn new(count: usize) -> Self {
+        Self {
+            message: format!(
+                "[tap] Dropped {count} events: the tapped components produced events faster than they could be sent."
+            ),
+            count,
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 /// A specific kind of notification with additional details
 pub enum Notification {
     Matched(Matched),
     NotMatched(NotMatched),
     InvalidMatch(InvalidMatch),
+    EventsDropped(EventsDropped),
 }
 
 impl Notification {
@@ -69,6 +89,7 @@ impl Notification {
             Notification::Matched(n) => n.message.as_ref(),
             Notification::NotMatched(n) => n.message.as_ref(),
             Notification::InvalidMatch(n) => n.message.as_ref(),
+            Notification::EventsDropped(n) => n.message.as_ref(),
         }
     }
 }
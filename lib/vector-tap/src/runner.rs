@@ -139,6 +139,10 @@ pub struct TapRunner<'a> {
     input_patterns: Vec<String>,
     output_patterns: Vec<String>,
     output_channel: &'a OutputChannel,
+    filter: Option<String>,
+    sample_ratio: Option<f64>,
+    max_events_per_second: Option<u32>,
+    unsampled: bool,
 }
 
 impl<'a> TapRunner<'a> {
@@ -153,9 +157,39 @@ impl<'a> TapRunner<'a> {
             input_patterns,
             output_patterns,
             output_channel,
+            filter: None,
+            sample_ratio: None,
+            max_events_per_second: None,
+            unsampled: false,
         }
     }
 
+    /// Only stream events matching the given VRL boolean expression. The expression is
+    /// evaluated by the server.
+    pub fn with_filter(mut self, filter: Option<String>) -> Self {
+        self.filter = filter;
+        self
+    }
+
+    /// Only stream each matching event with the given probability, within `(0, 1]`.
+    pub fn with_sample_ratio(mut self, sample_ratio: Option<f64>) -> Self {
+        self.sample_ratio = sample_ratio;
+        self
+    }
+
+    /// Cap the number of events streamed each second.
+    pub fn with_max_events_per_second(mut self, max_events_per_second: Option<u32>) -> Self {
+        self.max_events_per_second = max_events_per_second;
+        self
+    }
+
+    /// Stream every tapped event rather than a sample of `limit` events per interval. Events that
+    /// still have to be dropped are reported in notifications.
+    pub fn with_unsampled(mut self, unsampled: bool) -> Self {
+        self.unsampled = unsampled;
+        self
+    }
+
     pub async fn run_tap(
         &self,
         interval: i64,
@@ -189,6 +223,10 @@ impl<'a> TapRunner<'a> {
             inputs_patterns: self.input_patterns.clone(),
             limit: limit as i32,
             interval_ms: interval as i32,
+            filter: self.filter.clone(),
+            sample_ratio: self.sample_ratio,
+            max_events_per_second: self.max_events_per_second,
+            unsampled: self.unsampled,
         };
 
         let mut stream = client.stream_output_events(request).await?;
@@ -22,10 +22,12 @@ use ratatui::{
 };
 use tokio::sync::oneshot;
 use unit_prefix::NumberPrefix;
+use vector_common::internal_event::DEFAULT_OUTPUT;
 
 use crate::{
+    graph,
     input::{InputMode, handle_input},
-    state::{ComponentRow, FilterColumn, FilterMenuState, SortColumn},
+    state::{ComponentRow, FilterColumn, FilterMenuState, GraphPane, SortColumn, View},
 };
 
 use super::{
@@ -43,6 +45,12 @@ macro_rules! row_comparator {
     };
 }
 
+macro_rules! row_float_comparator {
+    ($field:ident) => {
+        |l: &ComponentRow, r: &ComponentRow| l.$field.total_cmp(&r.$field)
+    };
+}
+
 /// Format metrics, with thousands separation
 trait ThousandsFormatter {
     fn thousands_format(&self) -> String;
@@ -137,6 +145,15 @@ fn format_metric_bytes(total: i64, throughput: i64, human_metrics: bool) -> Stri
     }
 }
 
+/// Format a ratio as a percentage, returning `--` if zero
+fn format_percentage(ratio: f64) -> String {
+    if ratio == 0.0 {
+        "--".into()
+    } else {
+        format!("{:.1}%", ratio * 100.0)
+    }
+}
+
 const NUM_COLUMNS: usize = if is_allocation_tracing_enabled() {
     10
 } else {
@@ -157,6 +174,9 @@ pub mod columns {
     pub const BYTES_OUT: &str = "Bytes Out";
     pub const BYTES_OUT_TOTAL: &str = "Bytes Out Total";
     pub const ERRORS: &str = "Errors";
+    pub const CPU: &str = "CPU";
+    pub const UTILIZATION: &str = "Utilization";
+    pub const BUFFER_UTILIZATION: &str = "Buffer Usage";
     #[cfg(feature = "allocation-tracing")]
     pub const MEMORY_USED: &str = "Memory Used";
 }
@@ -175,6 +195,24 @@ static HEADER: [&str; NUM_COLUMNS] = [
     columns::ERRORS,
 ];
 
+const NUM_RESOURCES_COLUMNS: usize = if is_allocation_tracing_enabled() {
+    8
+} else {
+    7
+};
+
+static RESOURCES_HEADER: [&str; NUM_RESOURCES_COLUMNS] = [
+    columns::ID,
+    columns::KIND,
+    columns::TYPE,
+    columns::CPU,
+    columns::UTILIZATION,
+    columns::BUFFER_UTILIZATION,
+    #[cfg(feature = "allocation-tracing")]
+    columns::MEMORY_USED,
+    columns::ERRORS,
+];
+
 struct Widgets<'a> {
     constraints: Vec<Constraint>,
     url_string: &'a str,
@@ -240,7 +278,11 @@ impl<'a> Widgets<'a> {
     /// statistics pulled from `ComponentsState`,
     fn components_table(&self, f: &mut Frame, state: &state::State, area: Rect) {
         // Header columns
-        let header = HEADER
+        let header_names: &[&str] = match state.ui.view {
+            View::Throughput => &HEADER,
+            View::Resources => &RESOURCES_HEADER,
+        };
+        let header = header_names
             .iter()
             .map(|s| {
                 let mut content_line = Line::from(*s);
@@ -286,6 +328,9 @@ impl<'a> Widgets<'a> {
                 SortColumn::BytesOut => row_comparator!(sent_bytes_throughput_sec),
                 SortColumn::BytesOutTotal => row_comparator!(sent_bytes_total),
                 SortColumn::Errors => row_comparator!(errors),
+                SortColumn::Cpu => row_float_comparator!(cpu_usage),
+                SortColumn::Utilization => row_float_comparator!(utilization),
+                SortColumn::BufferUtilization => row_float_comparator!(buffer_utilization),
                 #[cfg(feature = "allocation-tracing")]
                 SortColumn::MemoryUsed => row_comparator!(allocated_bytes),
             };
@@ -315,6 +360,32 @@ impl<'a> Widgets<'a> {
                 true
             }
         }) {
+            let errors = if self.human_metrics {
+                r.errors.human_format()
+            } else {
+                r.errors.thousands_format()
+            };
+
+            if state.ui.view == View::Resources {
+                let data = vec![
+                    r.key.id().to_string(),
+                    r.kind.clone(),
+                    r.component_type.clone(),
+                    format_percentage(r.cpu_usage),
+                    format_percentage(r.utilization),
+                    format_percentage(r.buffer_utilization),
+                    #[cfg(feature = "allocation-tracing")]
+                    if state.allocation_tracing_active {
+                        r.allocated_bytes.human_format_bytes()
+                    } else {
+                        "disabled".to_string()
+                    },
+                    errors,
+                ];
+                items.push(Row::new(data).style(Style::default()));
+                continue;
+            }
+
             let mut data = vec![
                 r.key.id().to_string(),
                 if !r.has_displayable_outputs() {
@@ -354,11 +425,7 @@ impl<'a> Widgets<'a> {
                 } else {
                     "disabled".to_string()
                 },
-                if self.human_metrics {
-                    r.errors.human_format()
-                } else {
-                    r.errors.thousands_format()
-                },
+                errors,
             ];
 
             data.extend_from_slice(&formatted_metrics);
@@ -383,8 +450,27 @@ impl<'a> Widgets<'a> {
             }
         }
 
-        let widths: &[Constraint] = if is_allocation_tracing_enabled() {
-            &[
+        let widths: &[Constraint] = match (state.ui.view, is_allocation_tracing_enabled()) {
+            (View::Resources, true) => &[
+                Constraint::Percentage(20), // ID
+                Constraint::Percentage(10), // Kind
+                Constraint::Percentage(14), // Type
+                Constraint::Percentage(10), // CPU
+                Constraint::Percentage(12), // Utilization
+                Constraint::Percentage(12), // Buffer Usage
+                Constraint::Percentage(14), // Memory Used
+                Constraint::Percentage(8),  // Errors
+            ],
+            (View::Resources, false) => &[
+                Constraint::Percentage(24), // ID
+                Constraint::Percentage(12), // Kind
+                Constraint::Percentage(16), // Type
+                Constraint::Percentage(12), // CPU
+                Constraint::Percentage(14), // Utilization
+                Constraint::Percentage(14), // Buffer Usage
+                Constraint::Percentage(8),  // Errors
+            ],
+            (View::Throughput, true) => &[
                 Constraint::Percentage(13), // ID
                 Constraint::Percentage(8),  // Output
                 Constraint::Percentage(5),  // Kind
@@ -395,9 +481,8 @@ impl<'a> Widgets<'a> {
                 Constraint::Percentage(12), // Bytes Out
                 Constraint::Percentage(14), // Memory Used
                 Constraint::Percentage(7),  // Errors
-            ]
-        } else {
-            &[
+            ],
+            (View::Throughput, false) => &[
                 Constraint::Percentage(13), // ID
                 Constraint::Percentage(12), // Output
                 Constraint::Percentage(10), // Kind
@@ -407,7 +492,7 @@ impl<'a> Widgets<'a> {
                 Constraint::Percentage(12), // Events Out
                 Constraint::Percentage(14), // Bytes Out
                 Constraint::Percentage(7),  // Errors
-            ]
+            ],
         };
         let w = Table::new(items, widths)
             .header(Row::new(header).bottom_margin(1))
@@ -443,6 +528,102 @@ impl<'a> Widgets<'a> {
         );
     }
 
+    /// Renders the graph view, showing the components the focused component receives events
+    /// from and sends events to, with the events per second sent over each edge.
+    fn graph_view(&self, f: &mut Frame, state: &state::State, area: Rect) {
+        let graph_state = &state.ui.graph;
+        let Some(row) = graph_state
+            .focused(&state.components)
+            .and_then(|focus| state.components.get(focus))
+        else {
+            let block = Block::default().borders(Borders::ALL).title("Graph");
+            f.render_widget(Paragraph::new("No components").block(block), area);
+            return;
+        };
+
+        let [summary_area, trees_area] =
+            Layout::vertical([Constraint::Length(3), Constraint::Min(0)]).areas(area);
+        let [upstream_area, downstream_area] =
+            Layout::horizontal([Constraint::Percentage(50), Constraint::Percentage(50)])
+                .areas(trees_area);
+
+        let mut summary = vec![
+            Span::styled(row.key.id(), Style::default().add_modifier(Modifier::BOLD)),
+            Span::from(format!(
+                " {} ({}) | Events In: {}/s | Events Out: {}/s | Buffer Usage: ",
+                row.kind,
+                row.component_type,
+                row.received_events_throughput_sec.human_format(),
+                row.sent_events_throughput_sec.human_format(),
+            )),
+        ];
+        let buffer_style = if graph::is_stalled(row) {
+            Style::default().fg(Color::Red)
+        } else {
+            Style::default()
+        };
+        summary.push(Span::styled(
+            format_percentage(row.buffer_utilization),
+            buffer_style,
+        ));
+        let block = Block::default().borders(Borders::ALL).title("Graph");
+        f.render_widget(
+            Paragraph::new(Line::from(summary)).block(block),
+            summary_area,
+        );
+
+        for (pane, area, title) in [
+            (GraphPane::Upstream, upstream_area, "Receives from"),
+            (GraphPane::Downstream, downstream_area, "Sends to"),
+        ] {
+            let lines = graph_state.lines(&state.components, pane);
+            let items = lines.iter().map(|line| {
+                let port = line
+                    .outputs
+                    .iter()
+                    .filter(|output| *output != DEFAULT_OUTPUT)
+                    .map(|output| format!(".{output}"))
+                    .collect::<Vec<_>>()
+                    .join(",");
+                let text = match pane {
+                    GraphPane::Upstream => format!(
+                        "{}◀ {}{port} ({}/s)",
+                        "  ".repeat(line.depth),
+                        line.key.id(),
+                        line.events_per_sec.human_format(),
+                    ),
+                    GraphPane::Downstream => format!(
+                        "{}{port}▶ {} ({}/s)",
+                        "  ".repeat(line.depth),
+                        line.key.id(),
+                        line.events_per_sec.human_format(),
+                    ),
+                };
+                if line.stalled {
+                    ListItem::new(format!("{text} backpressure")).fg(Color::Red)
+                } else {
+                    ListItem::new(text)
+                }
+            });
+
+            let active = pane == graph_state.pane;
+            let block = Block::default()
+                .borders(Borders::ALL)
+                .border_style(if active {
+                    Style::default().fg(Color::Yellow)
+                } else {
+                    Style::default()
+                })
+                .title(title);
+            let w = List::new(items)
+                .block(block)
+                .highlight_style(Style::new().reversed());
+            let mut list_state =
+                ListState::default().with_selected(active.then_some(graph_state.selection));
+            f.render_stateful_widget(w, area, &mut list_state);
+        }
+    }
+
     /// Alerts the user to resize the window to view columns
     fn components_resize_window(&self, f: &mut Frame, area: Rect) {
         let block = Block::default().borders(Borders::ALL).title("Components");
@@ -465,7 +646,8 @@ impl<'a> Widgets<'a> {
             Line::from("End, G => scroll to bottom"),
             Line::from("Home, g => scroll to top"),
             Line::from("F1, ? => toggle this help window"),
-            Line::from("1-9 => sort by column"),
+            Line::from("1-9 => sort by column of the current view"),
+            Line::from("F5, v => switch view (throughput, resources, graph)"),
             Line::from("F6, s => toggle sort menu"),
             Line::from("F7, r => toggle ascending/descending sort"),
             Line::from("F4, f, / => toggle filter menu"),
@@ -481,6 +663,13 @@ impl<'a> Widgets<'a> {
             Line::from("↓, Tab => move filter column selection down"),
             Line::from("Enter => confirm filter selection"),
             Line::from("F4 => toggle sort menu"),
+            Line::default(),
+            Line::from("Graph view").bold(),
+            Line::from("↑, k / ↓, j => move selection up / down"),
+            Line::from("←, h / →, l, Tab => switch between inputs and outputs"),
+            Line::from("Enter => focus selected component"),
+            Line::from("Backspace => focus previous component"),
+            Line::from("n / p => focus next / previous component"),
         ];
 
         let block = Block::default()
@@ -579,7 +768,11 @@ impl<'a> Widgets<'a> {
 
         // Require a minimum of 80 chars of line width to display the table
         if size.width >= 80 {
-            self.components_table(f, &state, rects[1]);
+            if state.ui.view == View::Graph {
+                self.graph_view(f, &state, rects[1]);
+            } else {
+                self.components_table(f, &state, rects[1]);
+            }
         } else {
             self.components_resize_window(f, rects[1]);
         }
@@ -591,7 +784,7 @@ impl<'a> Widgets<'a> {
             let [area] = Layout::horizontal([Constraint::Length(64)])
                 .flex(Flex::Center)
                 .areas(size);
-            let [area] = Layout::vertical([Constraint::Length(32)])
+            let [area] = Layout::vertical([Constraint::Length(40)])
                 .flex(Flex::Center)
                 .areas(area);
             self.help_box(f, area);
@@ -669,6 +862,8 @@ pub async fn init_dashboard<'a>(
                     input_mode = InputMode::SortMenu;
                 } else if state.ui.help_visible {
                     input_mode = InputMode::HelpMenu;
+                } else if state.ui.view == View::Graph {
+                    input_mode = InputMode::Graph;
                 } else {
                     input_mode = InputMode::Top;
                 }
@@ -794,4 +989,12 @@ mod tests {
         assert_eq!((N * (N * (N * N))).human_format_bytes(), "1.00 TiB");
         assert_eq!((N * (N * (N * (N * N)))).human_format_bytes(), "1.00 PiB");
     }
+
+    #[test]
+    /// Should format ratios as percentages
+    fn format_percentages() {
+        assert_eq!(format_percentage(0.0), "--");
+        assert_eq!(format_percentage(0.0123), "1.2%");
+        assert_eq!(format_percentage(1.5), "150.0%");
+    }
 }
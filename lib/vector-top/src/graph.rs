@@ -0,0 +1,275 @@
+//! The topology graph rendered by the graph view.
+//!
+//! Like `vector graph`, the graph has one node per component and one edge per input of each
+//! component. The components upstream and downstream of the focused component are listed breadth
+//! first, each once, at its distance from the focused component.
+
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+
+use vector_common::{config::ComponentKey, internal_event::DEFAULT_OUTPUT};
+
+use crate::state::ComponentRow;
+
+/// Buffer usage at which a component is considered to be applying backpressure.
+pub const STALLED_BUFFER_UTILIZATION: f64 = 0.9;
+
+/// A component reached from the focused component.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GraphLine {
+    /// Number of edges between this component and the focused component, minus one
+    pub depth: usize,
+    /// The component
+    pub key: ComponentKey,
+    /// The outputs of the upstream components of the edges joining this component to the
+    /// previous layer
+    pub outputs: Vec<String>,
+    /// Events per second sent over the edges joining this component to the previous layer
+    pub events_per_sec: i64,
+    /// Whether the downstream component of any of those edges is applying backpressure
+    pub stalled: bool,
+}
+
+/// An edge of the topology, from an output of one component into another.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+struct Edge<'a> {
+    from: &'a ComponentKey,
+    output: &'a str,
+    to: &'a ComponentKey,
+}
+
+/// The direction edges are followed in, away from the focused component.
+#[derive(Clone, Copy)]
+enum Direction {
+    Upstream,
+    Downstream,
+}
+
+/// The topology DAG built from the inputs of each component.
+pub struct Graph<'a> {
+    components: &'a BTreeMap<ComponentKey, ComponentRow>,
+    edges: BTreeSet<Edge<'a>>,
+}
+
+/// Returns whether `row` has a buffer full enough to be applying backpressure.
+pub fn is_stalled(row: &ComponentRow) -> bool {
+    row.buffer_utilization >= STALLED_BUFFER_UTILIZATION
+}
+
+/// Returns the events per second `row` sends from its `output`.
+fn events_per_sec(row: &ComponentRow, output: &str) -> i64 {
+    if row.has_displayable_outputs() {
+        row.outputs
+            .get(output)
+            .map_or(0, |metrics| metrics.sent_events_throughput_sec)
+    } else if output == DEFAULT_OUTPUT {
+        row.sent_events_throughput_sec
+    } else {
+        0
+    }
+}
+
+impl<'a> Graph<'a> {
+    pub fn new(components: &'a BTreeMap<ComponentKey, ComponentRow>) -> Self {
+        let edges = components
+            .iter()
+            .flat_map(|(key, row)| {
+                row.inputs
+                    .iter()
+                    .filter(|input| components.contains_key(&input.key))
+                    .map(move |input| Edge {
+                        from: &input.key,
+                        output: &input.output,
+                        to: key,
+                    })
+            })
+            .collect();
+        Self { components, edges }
+    }
+
+    /// Returns the components `focus` receives events from, breadth first.
+    pub fn upstream(&self, focus: &ComponentKey) -> Vec<GraphLine> {
+        self.walk(focus, Direction::Upstream)
+    }
+
+    /// Returns the components `focus` sends events to, breadth first.
+    pub fn downstream(&self, focus: &ComponentKey) -> Vec<GraphLine> {
+        self.walk(focus, Direction::Downstream)
+    }
+
+    fn walk(&self, focus: &ComponentKey, direction: Direction) -> Vec<GraphLine> {
+        let mut seen = HashSet::from([focus]);
+        let mut layer = HashSet::from([focus]);
+        let mut lines = Vec::new();
+
+        for depth in 0.. {
+            let mut next = BTreeMap::<&ComponentKey, GraphLine>::new();
+            for edge in &self.edges {
+                let (near, far) = match direction {
+                    Direction::Upstream => (edge.to, edge.from),
+                    Direction::Downstream => (edge.from, edge.to),
+                };
+                if !layer.contains(near) || seen.contains(far) {
+                    continue;
+                }
+                let line = next.entry(far).or_insert_with(|| GraphLine {
+                    depth,
+                    key: far.clone(),
+                    outputs: Vec::new(),
+                    events_per_sec: 0,
+                    stalled: false,
+                });
+                if !line.outputs.iter().any(|output| output == edge.output) {
+                    line.outputs.push(edge.output.to_string());
+                }
+                line.events_per_sec += self
+                    .components
+                    .get(edge.from)
+                    .map_or(0, |row| events_per_sec(row, edge.output));
+                line.stalled |= self.components.get(edge.to).is_some_and(is_stalled);
+            }
+            if next.is_empty() {
+                break;
+            }
+            seen.extend(next.keys().copied());
+            layer = next.keys().copied().collect();
+            lines.extend(next.into_values());
+        }
+        lines
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::state::{ComponentInput, OutputMetrics};
+
+    fn row(id: &str, inputs: &[(&str, &str)], sent_events_throughput_sec: i64) -> ComponentRow {
+        ComponentRow {
+            key: ComponentKey::from(id),
+            kind: "transform".to_string(),
+            component_type: "remap".to_string(),
+            inputs: inputs
+                .iter()
+                .map(|(key, output)| ComponentInput {
+                    key: ComponentKey::from(*key),
+                    output: output.to_string(),
+                })
+                .collect(),
+            outputs: HashMap::from([(DEFAULT_OUTPUT.to_string(), OutputMetrics::from(0))]),
+            received_bytes_total: 0,
+            received_bytes_throughput_sec: 0,
+            received_events_total: 0,
+            received_events_throughput_sec: 0,
+            sent_bytes_total: 0,
+            sent_bytes_throughput_sec: 0,
+            sent_events_total: 0,
+            sent_events_throughput_sec,
+            cpu_usage: 0.0,
+            utilization: 0.0,
+            buffer_utilization: 0.0,
+            #[cfg(feature = "allocation-tracing")]
+            allocated_bytes: 0,
+            errors: 0,
+        }
+    }
+
+    fn topology(rows: Vec<ComponentRow>) -> BTreeMap<ComponentKey, ComponentRow> {
+        rows.into_iter().map(|row| (row.key.clone(), row)).collect()
+    }
+
+    /// source -> parse -> sink, with source also feeding sink directly
+    fn fan_in() -> BTreeMap<ComponentKey, ComponentRow> {
+        topology(vec![
+            row("source", &[], 100),
+            row("parse", &[("source", DEFAULT_OUTPUT)], 90),
+            row(
+                "sink",
+                &[("parse", DEFAULT_OUTPUT), ("source", DEFAULT_OUTPUT)],
+                0,
+            ),
+        ])
+    }
+
+    fn summary(lines: &[GraphLine]) -> Vec<(usize, &str, i64)> {
+        lines
+            .iter()
+            .map(|line| (line.depth, line.key.id(), line.events_per_sec))
+            .collect()
+    }
+
+    #[test]
+    fn walks_upstream() {
+        let components = fan_in();
+        let graph = Graph::new(&components);
+        assert_eq!(
+            summary(&graph.upstream(&ComponentKey::from("sink"))),
+            [(0, "parse", 90), (0, "source", 100)]
+        );
+        assert_eq!(
+            summary(&graph.upstream(&ComponentKey::from("parse"))),
+            [(0, "source", 100)]
+        );
+    }
+
+    #[test]
+    fn walks_downstream() {
+        let components = fan_in();
+        let graph = Graph::new(&components);
+        assert_eq!(
+            summary(&graph.downstream(&ComponentKey::from("source"))),
+            [(0, "parse", 100), (0, "sink", 100)]
+        );
+        assert_eq!(
+            summary(&graph.downstream(&ComponentKey::from("parse"))),
+            [(0, "sink", 90)]
+        );
+    }
+
+    #[test]
+    fn lists_components_reached_over_several_paths_once() {
+        // source -> left, right -> sink, with `right` reading a named output of `source`
+        let components = topology(vec![
+            row("source", &[], 100),
+            row("left", &[("source", DEFAULT_OUTPUT)], 40),
+            row("right", &[("source", "errors")], 60),
+            row(
+                "sink",
+                &[("left", DEFAULT_OUTPUT), ("right", DEFAULT_OUTPUT)],
+                0,
+            ),
+        ]);
+        let graph = Graph::new(&components);
+
+        let lines = graph.upstream(&ComponentKey::from("sink"));
+        assert_eq!(
+            summary(&lines),
+            [(0, "left", 40), (0, "right", 60), (1, "source", 100)]
+        );
+        assert_eq!(lines[2].outputs, [DEFAULT_OUTPUT, "errors"]);
+
+        assert_eq!(
+            summary(&graph.downstream(&ComponentKey::from("source"))),
+            [(0, "left", 100), (0, "right", 0), (1, "sink", 100)]
+        );
+    }
+
+    #[test]
+    fn marks_edges_into_stalled_components() {
+        let mut components = fan_in();
+        components
+            .get_mut(&ComponentKey::from("sink"))
+            .unwrap()
+            .buffer_utilization = 0.95;
+        let graph = Graph::new(&components);
+
+        let lines = graph.upstream(&ComponentKey::from("sink"));
+        let stalled = lines.iter().map(|line| line.stalled).collect::<Vec<_>>();
+        assert_eq!(stalled, [true, true]);
+
+        let lines = graph.downstream(&ComponentKey::from("source"));
+        let stalled = lines.iter().map(|line| line.stalled).collect::<Vec<_>>();
+        assert_eq!(stalled, [false, true]);
+    }
+}
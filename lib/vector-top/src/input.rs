@@ -1,7 +1,7 @@
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use ratatui::{Terminal, prelude::Backend};
 
-use crate::state::{self, SortColumn, UiEventType};
+use crate::state::{self, UiEventType};
 
 #[derive(Debug, Clone, Copy)]
 pub(crate) enum InputMode {
@@ -9,6 +9,7 @@ pub(crate) enum InputMode {
     HelpMenu,
     FilterInput,
     SortMenu,
+    Graph,
 }
 
 /// Handles keyboard input for top
@@ -25,6 +26,7 @@ pub(crate) async fn handle_input<B: Backend>(
         InputMode::HelpMenu => handle_help_input(key_event, event_tx, terminal).await,
         InputMode::FilterInput => handle_filter_input(key_event, event_tx, terminal).await,
         InputMode::SortMenu => handle_sort_input(key_event, event_tx, terminal).await,
+        InputMode::Graph => handle_graph_input(key_event, event_tx, terminal).await,
     }
 }
 
@@ -98,6 +100,9 @@ async fn handle_top_input<B: Backend>(
         KeyCode::Char('?') | KeyCode::F(1) => {
             let _ = event_tx.send(UiEventType::ToggleHelp).await;
         }
+        KeyCode::Char('v') | KeyCode::F(5) => {
+            let _ = event_tx.send(UiEventType::NextView).await;
+        }
         KeyCode::Char('s') | KeyCode::F(6) => {
             let _ = event_tx.send(UiEventType::ToggleSortMenu).await;
         }
@@ -105,20 +110,8 @@ async fn handle_top_input<B: Backend>(
             let _ = event_tx.send(UiEventType::ToggleSortDirection).await;
         }
         KeyCode::Char(d) if d.is_ascii_digit() => {
-            let col = match d {
-                '1' => SortColumn::Id,
-                '3' => SortColumn::Kind,
-                '4' => SortColumn::Type,
-                '5' => SortColumn::EventsInTotal,
-                '6' => SortColumn::BytesInTotal,
-                '7' => SortColumn::EventsOutTotal,
-                '8' => SortColumn::BytesOutTotal,
-                '9' => SortColumn::Errors,
-                #[cfg(feature = "allocation-tracing")]
-                '0' => SortColumn::MemoryUsed,
-                _ => return false,
-            };
-            let _ = event_tx.send(UiEventType::SortByColumn(col)).await;
+            // The column each digit sorts by depends on the view, which the state keeps track of.
+            let _ = event_tx.send(UiEventType::SortByShortcut(d)).await;
         }
         KeyCode::F(4) | KeyCode::Char('f') | KeyCode::Char('/') => {
             let _ = event_tx.send(UiEventType::ToggleFilterMenu).await;
@@ -193,3 +186,35 @@ async fn handle_filter_input<B: Backend>(
     }
     false
 }
+
+async fn handle_graph_input<B: Backend>(
+    key_event: KeyEvent,
+    event_tx: &state::UiEventTx,
+    terminal: &Terminal<B>,
+) -> bool {
+    match key_event.code {
+        KeyCode::Up | KeyCode::Char('k') => {
+            let _ = event_tx.send(UiEventType::GraphSelection(-1)).await;
+        }
+        KeyCode::Down | KeyCode::Char('j') => {
+            let _ = event_tx.send(UiEventType::GraphSelection(1)).await;
+        }
+        KeyCode::Left | KeyCode::Right | KeyCode::Tab | KeyCode::Char('h') | KeyCode::Char('l') => {
+            let _ = event_tx.send(UiEventType::GraphSwitchPane).await;
+        }
+        KeyCode::Enter => {
+            let _ = event_tx.send(UiEventType::GraphFocusSelection).await;
+        }
+        KeyCode::Backspace => {
+            let _ = event_tx.send(UiEventType::GraphBack).await;
+        }
+        KeyCode::Char('n') => {
+            let _ = event_tx.send(UiEventType::GraphFocusNext(1)).await;
+        }
+        KeyCode::Char('p') => {
+            let _ = event_tx.send(UiEventType::GraphFocusNext(-1)).await;
+        }
+        _ => return handle_top_input(key_event, event_tx, terminal).await,
+    }
+    false
+}
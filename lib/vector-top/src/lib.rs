@@ -1,6 +1,7 @@
 //! Top subcommand
 pub mod dashboard;
 pub mod events;
+pub mod graph;
 mod input;
 pub mod metrics;
 pub mod state;
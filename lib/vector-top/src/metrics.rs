@@ -1,5 +1,5 @@
 use std::{
-    collections::{BTreeMap, HashSet},
+    collections::{BTreeMap, HashMap, HashSet},
     sync::Arc,
     time::Duration,
 };
@@ -13,7 +13,7 @@ use vector_api_client::{
     proto::{Component, ComponentType, MetricName, stream_component_metrics_response::Value},
 };
 
-use crate::state::{self, OutputMetrics, SentEventsMetric};
+use crate::state::{self, ComponentInput, OutputMetrics, SentEventsMetric};
 use vector_common::config::ComponentKey;
 
 fn component_matches_patterns(component_id: &str, components_patterns: &[Pattern]) -> bool {
@@ -28,7 +28,8 @@ fn component_matches_patterns(component_id: &str, components_patterns: &[Pattern
 
 /// Component polling task
 ///
-/// Polls for component changes every interval and diffs to detect adds/removes.
+/// Polls for component changes every interval and diffs to detect adds/removes, as well as
+/// changes to the inputs of existing components.
 async fn poll_components(
     mut client: Client,
     tx: state::EventTx,
@@ -37,6 +38,7 @@ async fn poll_components(
     initial_components: HashSet<String>,
 ) {
     let mut known_components = initial_components;
+    let mut known_inputs = HashMap::new();
     let poll_interval = Duration::from_millis(interval_ms as u64);
     let mut consecutive_errors = 0;
     const MAX_CONSECUTIVE_ERRORS: u32 = 3;
@@ -72,6 +74,27 @@ async fn poll_components(
             }
         }
 
+        // Detect components whose inputs changed
+        for component in &response.components {
+            let component_id = &component.component_id;
+            if !component_matches_patterns(component_id, &components_patterns) {
+                continue;
+            }
+            let inputs = component_inputs(component);
+            if known_inputs.get(component_id) != Some(&inputs) {
+                if known_components.contains(component_id) {
+                    let key = ComponentKey::from(component_id.as_str());
+                    _ = tx
+                        .send(state::EventType::ComponentInputsChanged(
+                            key,
+                            inputs.clone(),
+                        ))
+                        .await;
+                }
+                known_inputs.insert(component_id.clone(), inputs);
+            }
+        }
+
         // Detect removed components
         for old_id in &known_components {
             if !current_components.contains(old_id)
@@ -82,10 +105,22 @@ async fn poll_components(
             }
         }
 
+        known_inputs.retain(|component_id, _| current_components.contains(component_id));
         known_components = current_components;
     }
 }
 
+fn component_inputs(component: &Component) -> Vec<ComponentInput> {
+    component
+        .inputs
+        .iter()
+        .map(|input| ComponentInput {
+            key: ComponentKey::from(input.component_id.as_str()),
+            output: input.output_id.clone(),
+        })
+        .collect()
+}
+
 fn component_to_row(component: &Component) -> state::ComponentRow {
     let key = ComponentKey::from(component.component_id.as_str());
     let metrics = component.metrics.as_ref();
@@ -100,6 +135,7 @@ fn component_to_row(component: &Component) -> state::ComponentRow {
         }
         .to_string(),
         component_type: component.on_type.clone(), // actual plugin type e.g. "demo_logs", "kafka"
+        inputs: component_inputs(component),
         outputs: component
             .outputs
             .iter()
@@ -118,6 +154,9 @@ fn component_to_row(component: &Component) -> state::ComponentRow {
         sent_bytes_throughput_sec: 0,
         sent_events_total: metrics.and_then(|m| m.sent_events_total).unwrap_or(0),
         sent_events_throughput_sec: 0,
+        cpu_usage: 0.0,
+        utilization: 0.0,
+        buffer_utilization: 0.0,
         #[cfg(feature = "allocation-tracing")]
         allocated_bytes: 0,
         errors: 0,
@@ -424,6 +463,94 @@ async fn errors_totals(
     }
 }
 
+/// CPU usage per component, converted from CPU nanoseconds per second to cores
+async fn cpu_usages(
+    mut client: Client,
+    tx: state::EventTx,
+    interval: i64,
+    components_patterns: Arc<Vec<Pattern>>,
+) {
+    let Ok(mut stream) = client
+        .stream_component_metrics(MetricName::CpuUsageThroughput, interval as i32)
+        .await
+    else {
+        return;
+    };
+
+    while let Some(Ok(response)) = stream.next().await {
+        let component_id = &response.component_id;
+        if !component_matches_patterns(component_id, &components_patterns) {
+            continue;
+        }
+        if let Some(Value::Throughput(tp)) = response.value {
+            _ = tx
+                .send(state::EventType::CpuUsages(vec![(
+                    ComponentKey::from(component_id.as_str()),
+                    tp.value / 1e9,
+                )]))
+                .await;
+        }
+    }
+}
+
+async fn utilizations(
+    mut client: Client,
+    tx: state::EventTx,
+    interval: i64,
+    components_patterns: Arc<Vec<Pattern>>,
+) {
+    let Ok(mut stream) = client
+        .stream_component_metrics(MetricName::Utilization, interval as i32)
+        .await
+    else {
+        return;
+    };
+
+    while let Some(Ok(response)) = stream.next().await {
+        let component_id = &response.component_id;
+        if !component_matches_patterns(component_id, &components_patterns) {
+            continue;
+        }
+        if let Some(Value::Gauge(gauge)) = response.value {
+            _ = tx
+                .send(state::EventType::Utilizations(vec![(
+                    ComponentKey::from(component_id.as_str()),
+                    gauge.value,
+                )]))
+                .await;
+        }
+    }
+}
+
+async fn buffer_utilizations(
+    mut client: Client,
+    tx: state::EventTx,
+    interval: i64,
+    components_patterns: Arc<Vec<Pattern>>,
+) {
+    let Ok(mut stream) = client
+        .stream_component_metrics(MetricName::BufferUtilization, interval as i32)
+        .await
+    else {
+        return;
+    };
+
+    while let Some(Ok(response)) = stream.next().await {
+        let component_id = &response.component_id;
+        if !component_matches_patterns(component_id, &components_patterns) {
+            continue;
+        }
+        if let Some(Value::Gauge(gauge)) = response.value {
+            _ = tx
+                .send(state::EventType::BufferUtilizations(vec![(
+                    ComponentKey::from(component_id.as_str()),
+                    gauge.value,
+                )]))
+                .await;
+        }
+    }
+}
+
 async fn uptime_changed(mut client: Client, tx: state::EventTx, interval: i64) {
     let Ok(mut stream) = client.stream_uptime(interval as i32).await else {
         return;
@@ -528,6 +655,24 @@ pub async fn subscribe(
             interval,
             Arc::clone(&components_patterns),
         )),
+        tokio::spawn(cpu_usages(
+            client.clone(),
+            tx.clone(),
+            interval,
+            Arc::clone(&components_patterns),
+        )),
+        tokio::spawn(utilizations(
+            client.clone(),
+            tx.clone(),
+            interval,
+            Arc::clone(&components_patterns),
+        )),
+        tokio::spawn(buffer_utilizations(
+            client.clone(),
+            tx.clone(),
+            interval,
+            Arc::clone(&components_patterns),
+        )),
         tokio::spawn(uptime_changed(client.clone(), tx.clone(), interval)),
     ];
 
@@ -17,9 +17,13 @@ use vector_common::internal_event::DEFAULT_OUTPUT;
 
 use vector_common::config::ComponentKey;
 
-use crate::dashboard::columns;
+use crate::{
+    dashboard::columns,
+    graph::{Graph, GraphLine},
+};
 
 type IdentifiedMetric = (ComponentKey, i64);
+type IdentifiedGauge = (ComponentKey, f64);
 
 #[derive(Debug, Clone)]
 pub struct SentEventsMetric {
@@ -46,10 +50,16 @@ pub enum EventType {
     /// Throughput values already normalized to per-second by the server
     SentEventsThroughputs(Vec<SentEventsMetric>),
     ErrorsTotals(Vec<IdentifiedMetric>),
+    /// CPU usage, in cores (CPU seconds per second)
+    CpuUsages(Vec<IdentifiedGauge>),
+    Utilizations(Vec<IdentifiedGauge>),
+    BufferUtilizations(Vec<IdentifiedGauge>),
     #[cfg(feature = "allocation-tracing")]
     AllocatedBytes(Vec<IdentifiedMetric>),
     ComponentAdded(ComponentRow),
     ComponentRemoved(ComponentKey),
+    /// Inputs of a component changed, after a config reload
+    ComponentInputsChanged(ComponentKey, Vec<ComponentInput>),
     ConnectionUpdated(ConnectionStatus),
 }
 
@@ -61,14 +71,26 @@ pub enum UiEventType {
     ScrollPage(isize, Size),
     // Toggles help window. Also closes other windows.
     ToggleHelp,
+    // Switches to the next view.
+    NextView,
+    // Change graph view selection up (-) or down (+).
+    GraphSelection(isize),
+    // Switches the graph view selection between the upstream and downstream panes.
+    GraphSwitchPane,
+    // Focuses the graph view on the selected component.
+    GraphFocusSelection,
+    // Focuses the graph view on the previous (-) or next (+) component.
+    GraphFocusNext(isize),
+    // Focuses the graph view back on the previously focused component.
+    GraphBack,
     // Toggles sort menu. Also closes other windows.
     ToggleSortMenu,
     // Toggles sort direction.
     ToggleSortDirection,
     // Change sort selection up (-) or down (+).
     SortSelection(isize),
-    // Change sort selection to a specific column.
-    SortByColumn(SortColumn),
+    // Change sort selection to the column of the current view with the given shortcut key.
+    SortByShortcut(char),
     // Confirms current sort selection.
     SortConfirmation,
     // Toggles filter menu. Also closes other windows.
@@ -141,8 +163,11 @@ pub enum SortColumn {
     BytesOut = 9,
     BytesOutTotal = 10,
     Errors = 11,
+    Cpu = 12,
+    Utilization = 13,
+    BufferUtilization = 14,
     #[cfg(feature = "allocation-tracing")]
-    MemoryUsed = 12,
+    MemoryUsed = 15,
 }
 
 #[derive(Debug, Default, Clone, Copy)]
@@ -164,6 +189,9 @@ impl SortColumn {
             SortColumn::EventsOut | SortColumn::EventsOutTotal => header == columns::EVENTS_OUT,
             SortColumn::BytesOut | SortColumn::BytesOutTotal => header == columns::BYTES_OUT,
             SortColumn::Errors => header == columns::ERRORS,
+            SortColumn::Cpu => header == columns::CPU,
+            SortColumn::Utilization => header == columns::UTILIZATION,
+            SortColumn::BufferUtilization => header == columns::BUFFER_UTILIZATION,
             #[cfg(feature = "allocation-tracing")]
             SortColumn::MemoryUsed => header == columns::MEMORY_USED,
         }
@@ -183,6 +211,9 @@ impl SortColumn {
             columns::BYTES_OUT,
             columns::BYTES_OUT_TOTAL,
             columns::ERRORS,
+            columns::CPU,
+            columns::UTILIZATION,
+            columns::BUFFER_UTILIZATION,
             #[cfg(feature = "allocation-tracing")]
             columns::MEMORY_USED,
         ]
@@ -217,8 +248,11 @@ impl From<usize> for SortColumn {
             9 => SortColumn::BytesOut,
             10 => SortColumn::BytesOutTotal,
             11 => SortColumn::Errors,
+            12 => SortColumn::Cpu,
+            13 => SortColumn::Utilization,
+            14 => SortColumn::BufferUtilization,
             #[cfg(feature = "allocation-tracing")]
-            12 => SortColumn::MemoryUsed,
+            15 => SortColumn::MemoryUsed,
             _ => SortColumn::Id,
         }
     }
@@ -278,8 +312,109 @@ pub struct FilterState {
     pub pattern: Option<Regex>,
 }
 
+/// The set of metrics shown in the components table.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum View {
+    /// Events and bytes flowing through each component.
+    #[default]
+    Throughput,
+    /// CPU, utilization, buffer and memory usage of each component.
+    Resources,
+    /// Components a single component receives events from and sends events to.
+    Graph,
+}
+
+impl View {
+    pub const fn next(self) -> Self {
+        match self {
+            Self::Throughput => Self::Resources,
+            Self::Resources => Self::Graph,
+            Self::Graph => Self::Throughput,
+        }
+    }
+
+    /// Returns the column sorted by the given shortcut key in this view.
+    ///
+    /// Digits sort by the column at that position in the table, and `0` sorts by memory usage.
+    pub const fn sort_column(self, key: char) -> Option<SortColumn> {
+        match (self, key) {
+            (Self::Throughput | Self::Resources, '1') => Some(SortColumn::Id),
+            (Self::Throughput, '3') | (Self::Resources, '2') => Some(SortColumn::Kind),
+            (Self::Throughput, '4') | (Self::Resources, '3') => Some(SortColumn::Type),
+            (Self::Throughput, '5') => Some(SortColumn::EventsInTotal),
+            (Self::Throughput, '6') => Some(SortColumn::BytesInTotal),
+            (Self::Throughput, '7') => Some(SortColumn::EventsOutTotal),
+            (Self::Throughput, '8') => Some(SortColumn::BytesOutTotal),
+            (Self::Throughput, '9') | (Self::Resources, '7') => Some(SortColumn::Errors),
+            (Self::Resources, '4') => Some(SortColumn::Cpu),
+            (Self::Resources, '5') => Some(SortColumn::Utilization),
+            (Self::Resources, '6') => Some(SortColumn::BufferUtilization),
+            #[cfg(feature = "allocation-tracing")]
+            (Self::Throughput | Self::Resources, '0') => Some(SortColumn::MemoryUsed),
+            _ => None,
+        }
+    }
+}
+
+/// The pane of the graph view the selection is in.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum GraphPane {
+    #[default]
+    Upstream,
+    Downstream,
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct GraphState {
+    /// The component the graph view is focused on
+    pub focus: Option<ComponentKey>,
+    /// Previously focused components, most recent last
+    pub history: Vec<ComponentKey>,
+    pub pane: GraphPane,
+    pub selection: usize,
+}
+
+impl GraphState {
+    /// Returns the focused component, falling back to the first component if none is focused
+    /// or the focused component was removed.
+    pub fn focused<'a>(
+        &'a self,
+        components: &'a BTreeMap<ComponentKey, ComponentRow>,
+    ) -> Option<&'a ComponentKey> {
+        self.focus
+            .as_ref()
+            .filter(|key| components.contains_key(*key))
+            .or_else(|| components.keys().next())
+    }
+
+    /// Returns the components listed in `pane`.
+    pub fn lines(
+        &self,
+        components: &BTreeMap<ComponentKey, ComponentRow>,
+        pane: GraphPane,
+    ) -> Vec<GraphLine> {
+        match self.focused(components) {
+            Some(focus) if pane == GraphPane::Upstream => Graph::new(components).upstream(focus),
+            Some(focus) => Graph::new(components).downstream(focus),
+            None => Vec::new(),
+        }
+    }
+
+    fn focus_on(&mut self, components: &BTreeMap<ComponentKey, ComponentRow>, key: ComponentKey) {
+        let previous = self.focused(components).cloned();
+        if previous.as_ref() == Some(&key) {
+            return;
+        }
+        self.history.extend(previous);
+        self.focus = Some(key);
+        self.selection = 0;
+    }
+}
+
 #[derive(Debug, Default, Clone)]
 pub struct UiState {
+    pub view: View,
+    pub graph: GraphState,
     pub scroll: usize,
     pub help_visible: bool,
     pub sort_visible: bool,
@@ -392,11 +527,19 @@ impl From<i64> for OutputMetrics {
     }
 }
 
+/// An output of another component that a component receives events from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ComponentInput {
+    pub key: ComponentKey,
+    pub output: String,
+}
+
 #[derive(Debug, Clone)]
 pub struct ComponentRow {
     pub key: ComponentKey,
     pub kind: String,
     pub component_type: String,
+    pub inputs: Vec<ComponentInput>,
     pub outputs: HashMap<String, OutputMetrics>,
     pub received_bytes_total: i64,
     pub received_bytes_throughput_sec: i64,
@@ -406,6 +549,12 @@ pub struct ComponentRow {
     pub sent_bytes_throughput_sec: i64,
     pub sent_events_total: i64,
     pub sent_events_throughput_sec: i64,
+    /// CPU usage, in cores (CPU seconds per second)
+    pub cpu_usage: f64,
+    /// Fraction of time spent busy, between 0 and 1
+    pub utilization: f64,
+    /// Fill level of the fullest buffer, between 0 and 1
+    pub buffer_utilization: f64,
     #[cfg(feature = "allocation-tracing")]
     pub allocated_bytes: i64,
     pub errors: i64,
@@ -527,6 +676,27 @@ pub async fn updater(
                                     }
                                 }
                             }
+                            EventType::CpuUsages(rows) => {
+                                for (key, v) in rows {
+                                    if let Some(r) = state.components.get_mut(&key) {
+                                        r.cpu_usage = v;
+                                    }
+                                }
+                            }
+                            EventType::Utilizations(rows) => {
+                                for (key, v) in rows {
+                                    if let Some(r) = state.components.get_mut(&key) {
+                                        r.utilization = v;
+                                    }
+                                }
+                            }
+                            EventType::BufferUtilizations(rows) => {
+                                for (key, v) in rows {
+                                    if let Some(r) = state.components.get_mut(&key) {
+                                        r.buffer_utilization = v;
+                                    }
+                                }
+                            }
                             #[cfg(feature = "allocation-tracing")]
                             EventType::AllocatedBytes(rows) => {
                                 for (key, v) in rows {
@@ -541,6 +711,11 @@ pub async fn updater(
                             EventType::ComponentRemoved(key) => {
                                 _ = state.components.remove(&key);
                             }
+                            EventType::ComponentInputsChanged(key, inputs) => {
+                                if let Some(r) = state.components.get_mut(&key) {
+                                    r.inputs = inputs;
+                                }
+                            }
                             EventType::ConnectionUpdated(status) => {
                                 state.connection_status = status;
                             }
@@ -578,6 +753,56 @@ fn handle_ui_event(event: UiEventType, state: &mut State) {
                 state.ui.filter_visible = false;
             }
         }
+        UiEventType::NextView => {
+            state.ui.view = state.ui.view.next();
+        }
+        UiEventType::GraphSelection(diff) => {
+            let graph = &mut state.ui.graph;
+            let lines = graph.lines(&state.components, graph.pane).len();
+            graph.selection = graph
+                .selection
+                .saturating_add_signed(diff)
+                .min(lines.saturating_sub(1));
+        }
+        UiEventType::GraphSwitchPane => {
+            let graph = &mut state.ui.graph;
+            graph.pane = match graph.pane {
+                GraphPane::Upstream => GraphPane::Downstream,
+                GraphPane::Downstream => GraphPane::Upstream,
+            };
+            graph.selection = 0;
+        }
+        UiEventType::GraphFocusSelection => {
+            let graph = &mut state.ui.graph;
+            if let Some(line) = graph
+                .lines(&state.components, graph.pane)
+                .into_iter()
+                .nth(graph.selection)
+            {
+                graph.focus_on(&state.components, line.key);
+            }
+        }
+        UiEventType::GraphFocusNext(diff) => {
+            let graph = &mut state.ui.graph;
+            let keys = state.components.keys().collect::<Vec<_>>();
+            let current = graph
+                .focused(&state.components)
+                .and_then(|focus| keys.iter().position(|key| *key == focus))
+                .unwrap_or_default();
+            let next = current
+                .saturating_add_signed(diff)
+                .min(keys.len().saturating_sub(1));
+            if let Some(key) = keys.get(next).map(|key| (*key).clone()) {
+                graph.focus_on(&state.components, key);
+            }
+        }
+        UiEventType::GraphBack => {
+            let graph = &mut state.ui.graph;
+            if let Some(previous) = graph.history.pop() {
+                graph.focus = Some(previous);
+                graph.selection = 0;
+            }
+        }
         UiEventType::ToggleSortMenu => {
             state.ui.sort_visible = !state.ui.sort_visible;
             if state.ui.sort_visible {
@@ -594,7 +819,11 @@ fn handle_ui_event(event: UiEventType, state: &mut State) {
             });
             state.ui.sort_menu_state.select(Some(next));
         }
-        UiEventType::SortByColumn(col) => state.sort_state.column = Some(col),
+        UiEventType::SortByShortcut(key) => {
+            if let Some(col) = state.ui.view.sort_column(key) {
+                state.sort_state.column = Some(col);
+            }
+        }
         UiEventType::SortConfirmation => {
             if let Some(selected) = state.ui.sort_menu_state.selected() {
                 state.sort_state.column = Some(selected.into())
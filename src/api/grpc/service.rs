@@ -5,28 +5,39 @@ use std::pin::Pin;
 // cheaper std mutex is correct here. tokio::sync::Mutex is only needed when the
 // critical section itself contains .await.
 use std::sync::{Arc, Mutex};
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use futures::{StreamExt as FuturesStreamExt, stream};
-use rand::{Rng, SeedableRng as _, rngs::SmallRng};
+use rand::{Rng, SeedableRng as _, rngs::SmallRng, seq::SliceRandom};
 use tokio::select;
-use tokio::sync::mpsc;
+use tokio::sync::{mpsc, oneshot};
 use tokio::time::{self, interval};
 use tokio_stream::{
     Stream,
     wrappers::{IntervalStream, ReceiverStream},
 };
 use tonic::{Request, Response, Status};
-use vector_lib::tap::{
-    controller::{TapController, TapPatterns, TapPayload},
-    topology::WatchRx,
+use vector_lib::{
+    config::LogNamespace,
+    sensitive_string::SensitiveString,
+    tap::{
+        controller::{TapController, TapFilter, TapPatterns, TapPayload},
+        topology::WatchRx,
+    },
 };
 
-use crate::event::{Metric, MetricValue};
+use crate::conditions::{ConditionConfig, VrlConfig};
+use crate::config::{
+    self, BufferAction as ConfigBufferAction, ComponentKey, ConfigDiff, Difference,
+};
+use crate::event::{Event, EventContainer, Metric, MetricValue, array::events_into_arrays};
 use crate::metrics::Controller;
 use crate::proto::observability::{
     self, Component as ProtoComponent, ComponentType, EventNotification, TappedEvent, *,
 };
+use crate::topology::control::{
+    self, ControlCommand, ControlError, ControlRequest, ControlTx, DiffReport, ReloadReport,
+};
 
 type BoxStream<T> = Pin<Box<dyn Stream<Item = Result<T, Status>> + Send>>;
 
@@ -107,6 +118,53 @@ fn extract_component_metrics(metrics: &[Metric]) -> HashMap<String, ComponentMet
     result
 }
 
+/// Buffer size and capacity metrics, paired by unit. Sink buffers report their size directly,
+/// while source and transform buffers report the level of their in-memory channel, in the unit of
+/// whichever limit they were configured with.
+const BUFFER_FILL_METRICS: &[(&str, &[&str])] = &[
+    ("buffer_size_events", &["buffer_max_size_events"]),
+    ("buffer_size_bytes", &["buffer_max_size_bytes"]),
+    (
+        "source_buffer_utilization_level",
+        &[
+            "source_buffer_max_size_events",
+            "source_buffer_max_size_bytes",
+        ],
+    ),
+    (
+        "transform_buffer_utilization_level",
+        &[
+            "transform_buffer_max_size_events",
+            "transform_buffer_max_size_bytes",
+        ],
+    ),
+];
+
+/// Calculates how full the fullest buffer of each component is, between 0 and 1.
+fn buffer_utilization(metrics: &[Metric]) -> HashMap<String, f64> {
+    let mut result = HashMap::<String, f64>::new();
+
+    for (size_name, max_names) in BUFFER_FILL_METRICS {
+        let sizes = filter_and_group_metrics(metrics, size_name);
+        let mut max_sizes = HashMap::<String, f64>::new();
+        for max_name in *max_names {
+            for (component_id, max_size) in filter_and_group_metrics(metrics, max_name) {
+                *max_sizes.entry(component_id).or_insert(0.0) += max_size;
+            }
+        }
+
+        for (component_id, size) in sizes {
+            if let Some(max_size) = max_sizes.get(&component_id).filter(|max| **max > 0.0) {
+                let fill = (size / max_size).clamp(0.0, 1.0);
+                let entry = result.entry(component_id).or_insert(0.0);
+                *entry = entry.max(fill);
+            }
+        }
+    }
+
+    result
+}
+
 /// Helper function to calculate throughput by comparing current and previous values
 fn calculate_throughput(
     current: &HashMap<String, f64>,
@@ -171,6 +229,35 @@ fn validate_interval_ms(interval_ms: i32) -> Result<u64, Status> {
     Ok(interval_ms as u64)
 }
 
+/// Builds the server-side filter of a tap request from its VRL condition and sample ratio.
+fn build_tap_filter(
+    filter: Option<String>,
+    sample_ratio: Option<f64>,
+) -> Result<TapFilter, Status> {
+    let mut tap_filter = TapFilter::default();
+
+    if let Some(source) = filter {
+        let condition = ConditionConfig::Vrl(VrlConfig {
+            source,
+            runtime: Default::default(),
+        })
+        .build(&Default::default(), &Default::default())
+        .map_err(|error| Status::invalid_argument(format!("Invalid filter: {error}")))?;
+        tap_filter = tap_filter.with_condition(Arc::new(move |event| condition.check(event)));
+    }
+
+    if let Some(sample_ratio) = sample_ratio {
+        if !(sample_ratio > 0.0 && sample_ratio <= 1.0) {
+            return Err(Status::invalid_argument(
+                "sample_ratio must be > 0 and <= 1",
+            ));
+        }
+        tap_filter = tap_filter.with_sample_ratio(sample_ratio);
+    }
+
+    Ok(tap_filter)
+}
+
 fn get_controller() -> Result<&'static Controller, Status> {
     Controller::get().map_err(|_| Status::internal("Metrics system not initialized"))
 }
@@ -248,6 +335,34 @@ fn metric_throughput_stream(
     ))
 }
 
+/// Builds a stream that emits the per-component gauge values calculated by `values` every
+/// `duration`.
+fn metric_gauge_stream(
+    duration: Duration,
+    values: fn(&[Metric]) -> HashMap<String, f64>,
+) -> Result<BoxStream<StreamComponentMetricsResponse>, Status> {
+    let controller = get_controller()?;
+    Ok(Box::pin(
+        tokio_stream::StreamExt::map(IntervalStream::new(interval(duration)), move |_| {
+            let metrics = controller.capture_metrics();
+            tokio_stream::iter(
+                values(&metrics)
+                    .into_iter()
+                    .map(|(component_id, value)| {
+                        Ok(StreamComponentMetricsResponse {
+                            component_id,
+                            value: Some(stream_component_metrics_response::Value::Gauge(
+                                GaugeMetric { value },
+                            )),
+                        })
+                    })
+                    .collect::<Vec<_>>(),
+            )
+        })
+        .flatten(),
+    ))
+}
+
 /// Builds a stream that emits per-component sent_events totals with per-output breakdown.
 fn sent_events_totals_stream(
     duration: Duration,
@@ -393,14 +508,225 @@ fn ports_to_proto_outputs(
         .collect()
 }
 
+/// Compares two byte strings in time that only depends on their lengths.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+/// Parses the component ID of a control-plane request.
+fn parse_component_id(component_id: String) -> Result<ComponentKey, Status> {
+    if component_id.is_empty() {
+        return Err(Status::invalid_argument("component_id must be specified"));
+    }
+    Ok(ComponentKey::from(component_id))
+}
+
+const fn component_state_to_proto(state: control::ComponentState) -> ComponentState {
+    match state {
+        control::ComponentState::Running => ComponentState::Running,
+        control::ComponentState::Paused => ComponentState::Paused,
+        control::ComponentState::Drained => ComponentState::Drained,
+    }
+}
+
+/// Decodes the events of an injection request, tagging them as injected.
+fn decode_injected_events(
+    request: InjectEventsRequest,
+    log_namespace: LogNamespace,
+) -> Result<Vec<Event>, Status> {
+    let encoding = request.encoding();
+    let mut events = request
+        .encoded_events
+        .iter()
+        .enumerate()
+        .map(|(index, encoded)| {
+            let event = match encoding {
+                InjectEncoding::Unspecified | InjectEncoding::Json => serde_json::from_str(encoded)
+                    .map_err(|error| error.to_string())
+                    .and_then(|value| {
+                        Event::from_json_value(value, log_namespace)
+                            .map_err(|error| error.to_string())
+                    }),
+                InjectEncoding::NativeJson => {
+                    serde_json::from_str::<Event>(encoded).map_err(|error| error.to_string())
+                }
+            };
+            event.map_err(|error| {
+                Status::invalid_argument(format!("Invalid event at index {index}: {error}"))
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    events.extend(request.native_events.into_iter().map(Event::from));
+
+    for event in &mut events {
+        event
+            .metadata_mut()
+            .value_mut()
+            .insert("vector.injected", true);
+    }
+    Ok(events)
+}
+
+const fn reload_status_to_proto(status: control::ReloadStatus) -> ReloadStatus {
+    match status {
+        control::ReloadStatus::Applied => ReloadStatus::Applied,
+        control::ReloadStatus::RolledBack => ReloadStatus::RolledBack,
+        control::ReloadStatus::Rejected => ReloadStatus::Rejected,
+        control::ReloadStatus::Failed => ReloadStatus::Failed,
+    }
+}
+
+/// Converts the changes to one kind of component, sorting IDs so that responses are stable.
+fn difference_to_proto(difference: &Difference) -> ComponentDiff {
+    let sorted = |keys: &HashSet<ComponentKey>| {
+        let mut ids = keys.iter().map(ToString::to_string).collect::<Vec<_>>();
+        ids.sort();
+        ids
+    };
+
+    ComponentDiff {
+        added: sorted(&difference.to_add),
+        changed: sorted(&difference.to_change),
+        removed: sorted(&difference.to_remove),
+    }
+}
+
+fn config_diff_to_proto(diff: &ConfigDiff) -> TopologyDiff {
+    TopologyDiff {
+        sources: Some(difference_to_proto(&diff.sources)),
+        transforms: Some(difference_to_proto(&diff.transforms)),
+        sinks: Some(difference_to_proto(&diff.sinks)),
+        enrichment_tables: Some(difference_to_proto(&diff.enrichment_tables)),
+    }
+}
+
+fn reload_report_to_proto(report: ReloadReport) -> ReloadConfigResponse {
+    ReloadConfigResponse {
+        status: reload_status_to_proto(report.status) as i32,
+        diff: report.diff.as_ref().map(config_diff_to_proto),
+        errors: report.errors,
+    }
+}
+
+const fn buffer_action_to_proto(action: ConfigBufferAction) -> BufferAction {
+    match action {
+        ConfigBufferAction::Reused => BufferAction::Reused,
+        ConfigBufferAction::Rebuilt => BufferAction::Rebuilt,
+        ConfigBufferAction::Dropped => BufferAction::Dropped,
+    }
+}
+
+fn diff_report_to_proto(report: DiffReport) -> DiffConfigResponse {
+    DiffConfigResponse {
+        diff: report.diff.as_ref().map(config_diff_to_proto),
+        buffers: report
+            .buffers
+            .into_iter()
+            .map(|(key, action)| BufferChange {
+                component_id: key.to_string(),
+                action: buffer_action_to_proto(action) as i32,
+            })
+            .collect(),
+        errors: report.errors,
+    }
+}
+
+const fn config_format_from_proto(format: ConfigFormat) -> config::Format {
+    match format {
+        ConfigFormat::Unspecified | ConfigFormat::Json => config::Format::Json,
+        ConfigFormat::Yaml => config::Format::Yaml,
+        ConfigFormat::Toml => config::Format::Toml,
+    }
+}
+
+fn control_error_to_status(error: ControlError) -> Status {
+    let message = error.to_string();
+    match error {
+        ControlError::NotFound { .. } => Status::not_found(message),
+        ControlError::Unsupported { .. } => Status::invalid_argument(message),
+        ControlError::AlreadyStopped { .. }
+        | ControlError::NotStopped { .. }
+        | ControlError::LastSource { .. } => Status::failed_precondition(message),
+        ControlError::StopFailed { .. } | ControlError::BuildFailed { .. } => {
+            Status::internal(message)
+        }
+        ControlError::ShuttingDown => Status::unavailable(message),
+    }
+}
+
 /// gRPC observability service implementation.
 pub struct ObservabilityService {
     watch_rx: WatchRx,
+    control_tx: ControlTx,
+    control_token: Option<SensitiveString>,
 }
 
 impl ObservabilityService {
-    pub const fn new(watch_rx: WatchRx) -> Self {
-        Self { watch_rx }
+    pub const fn new(
+        watch_rx: WatchRx,
+        control_tx: ControlTx,
+        control_token: Option<SensitiveString>,
+    ) -> Self {
+        Self {
+            watch_rx,
+            control_tx,
+            control_token,
+        }
+    }
+
+    /// Checks that a control-plane request carries the configured bearer token.
+    fn authorize<T>(&self, request: &Request<T>) -> Result<(), Status> {
+        let Some(expected) = &self.control_token else {
+            return Err(Status::permission_denied(
+                "Control-plane calls are disabled. Set `api.control_token` to enable them.",
+            ));
+        };
+
+        let provided = request
+            .metadata()
+            .get("authorization")
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .ok_or_else(|| Status::unauthenticated("Missing bearer token"))?;
+
+        if constant_time_eq(provided.as_bytes(), expected.inner().as_bytes()) {
+            Ok(())
+        } else {
+            Err(Status::unauthenticated("Invalid bearer token"))
+        }
+    }
+
+    /// Sends a request to the application's main loop and waits for its outcome.
+    async fn send_control<T>(
+        &self,
+        request: ControlRequest,
+        outcome: oneshot::Receiver<T>,
+    ) -> Result<T, Status> {
+        self.control_tx
+            .send(request)
+            .await
+            .map_err(|_| control_error_to_status(ControlError::ShuttingDown))?;
+        outcome
+            .await
+            .map_err(|_| control_error_to_status(ControlError::ShuttingDown))
+    }
+
+    /// Sends a control command to the topology and waits for it to be applied.
+    async fn control(
+        &self,
+        command: ControlCommand,
+    ) -> Result<Response<ControlComponentResponse>, Status> {
+        let component_id = command.component_key().to_string();
+        let (request, outcome) = ControlRequest::component(command);
+        let state = self
+            .send_control(request, outcome)
+            .await?
+            .map_err(control_error_to_status)?;
+
+        Ok(Response::new(ControlComponentResponse {
+            component_id,
+            state: component_state_to_proto(state) as i32,
+        }))
     }
 }
 
@@ -482,11 +808,12 @@ impl observability::Service for ObservabilityService {
                 on_type,
                 outputs: ports_to_proto_outputs(ports, &key_str, &sent_events_by_output),
                 metrics: component_metrics_map.get(&key_str).cloned(),
+                inputs: vec![],
             });
         }
 
         // Transforms and sinks: present in `inputs` (full snapshot)
-        for component_key in tap_resource.inputs.keys() {
+        for (component_key, inputs) in &tap_resource.inputs {
             let key_str = component_key.to_string();
             let (component_type, outputs) = if let Some(ports) = output_ports.get(component_key) {
                 (
@@ -506,6 +833,13 @@ impl observability::Service for ObservabilityService {
                 on_type,
                 outputs,
                 metrics: component_metrics_map.get(&key_str).cloned(),
+                inputs: inputs
+                    .iter()
+                    .map(|input| Input {
+                        component_id: input.component.to_string(),
+                        output_id: input.port.as_deref().unwrap_or("_default").to_string(),
+                    })
+                    .collect(),
             });
         }
 
@@ -631,6 +965,13 @@ impl observability::Service for ObservabilityService {
             MetricName::SentBytesThroughput => {
                 metric_throughput_stream(duration, "component_sent_bytes_total")?
             }
+            MetricName::CpuUsageThroughput => {
+                metric_throughput_stream(duration, "component_cpu_usage_ns_total")?
+            }
+            MetricName::Utilization => metric_gauge_stream(duration, |metrics| {
+                filter_and_group_metrics(metrics, "utilization")
+            })?,
+            MetricName::BufferUtilization => metric_gauge_stream(duration, buffer_utilization)?,
         };
 
         Ok(Response::new(stream))
@@ -663,6 +1004,15 @@ impl observability::Service for ObservabilityService {
         let interval_ms = validate_interval_ms(req.interval_ms)?;
         let limit = req.limit as usize;
 
+        if req.max_events_per_second == Some(0) {
+            return Err(Status::invalid_argument(
+                "max_events_per_second must be >= 1",
+            ));
+        }
+        let rate_limit = req.max_events_per_second.map(RateLimit::new);
+        let unsampled = req.unsampled;
+        let filter = build_tap_filter(req.filter, req.sample_ratio)?;
+
         let patterns = TapPatterns {
             for_outputs: req.outputs_patterns.into_iter().collect(),
             for_inputs: req.inputs_patterns.into_iter().collect(),
@@ -677,10 +1027,10 @@ impl observability::Service for ObservabilityService {
         let watch_rx = self.watch_rx.clone();
 
         crate::spawn_in_current_span(async move {
-            let _tap_controller = TapController::new(watch_rx, tap_tx, patterns);
+            let tap_controller = TapController::new(watch_rx, tap_tx, patterns, filter);
             let mut tap_rx = ReceiverStream::new(tap_rx);
             let mut interval = time::interval(time::Duration::from_millis(interval_ms));
-            let mut reservoir = Reservoir::new(limit);
+            let mut reservoir = Reservoir::new(limit, rate_limit, unsampled);
 
             loop {
                 select! {
@@ -690,6 +1040,7 @@ impl observability::Service for ObservabilityService {
                         }
                     }
                     _ = interval.tick() => {
+                        reservoir.record_dropped(tap_controller.take_dropped_events());
                         if event_tx.is_closed() || reservoir.flush(&event_tx).await.is_err() {
                             break;
                         }
@@ -704,23 +1055,174 @@ impl observability::Service for ObservabilityService {
 
         Ok(Response::new(Box::pin(stream)))
     }
+
+    // ========== Control Plane ==========
+
+    async fn pause_component(
+        &self,
+        request: Request<PauseComponentRequest>,
+    ) -> Result<Response<ControlComponentResponse>, Status> {
+        self.authorize(&request)?;
+        let key = parse_component_id(request.into_inner().component_id)?;
+        self.control(ControlCommand::Pause(key)).await
+    }
+
+    async fn resume_component(
+        &self,
+        request: Request<ResumeComponentRequest>,
+    ) -> Result<Response<ControlComponentResponse>, Status> {
+        self.authorize(&request)?;
+        let key = parse_component_id(request.into_inner().component_id)?;
+        self.control(ControlCommand::Resume(key)).await
+    }
+
+    async fn drain_component(
+        &self,
+        request: Request<DrainComponentRequest>,
+    ) -> Result<Response<ControlComponentResponse>, Status> {
+        self.authorize(&request)?;
+        let key = parse_component_id(request.into_inner().component_id)?;
+        self.control(ControlCommand::Drain(key)).await
+    }
+
+    async fn inject_events(
+        &self,
+        request: Request<InjectEventsRequest>,
+    ) -> Result<Response<InjectEventsResponse>, Status> {
+        self.authorize(&request)?;
+        let request = request.into_inner();
+        let key = parse_component_id(request.component_id.clone())?;
+
+        let (control_request, outcome) = ControlRequest::component_input(key);
+        let mut input = self
+            .send_control(control_request, outcome)
+            .await?
+            .map_err(control_error_to_status)?;
+
+        let events = decode_injected_events(request, input.log_namespace)?;
+        let total = events.len();
+        // Events of the same type are sent together, so the events of a request are only split
+        // when it mixes logs, metrics and traces.
+        let mut injected_events = 0;
+        for array in events_into_arrays(events, None) {
+            let count = array.len();
+            input
+                .sender
+                .send(array, Some(Instant::now()))
+                .await
+                .map_err(|error| {
+                    Status::unavailable(format!(
+                        "Failed to inject events after injecting {injected_events} of {total}: {error}"
+                    ))
+                })?;
+            injected_events += count;
+        }
+
+        Ok(Response::new(InjectEventsResponse {
+            injected_events: injected_events as i64,
+        }))
+    }
+
+    async fn reload_config(
+        &self,
+        request: Request<ReloadConfigRequest>,
+    ) -> Result<Response<ReloadConfigResponse>, Status> {
+        self.authorize(&request)?;
+        let (request, outcome) = ControlRequest::reload();
+        let report = self.send_control(request, outcome).await?;
+        Ok(Response::new(reload_report_to_proto(report)))
+    }
+
+    async fn diff_config(
+        &self,
+        request: Request<DiffConfigRequest>,
+    ) -> Result<Response<DiffConfigResponse>, Status> {
+        self.authorize(&request)?;
+        let request = request.into_inner();
+        let format = config_format_from_proto(request.format());
+        let config = request.config.map(|config| (config, format));
+        let (request, outcome) = ControlRequest::diff_config(config);
+        let report = self.send_control(request, outcome).await?;
+        Ok(Response::new(diff_report_to_proto(report)))
+    }
+
+    async fn get_effective_config(
+        &self,
+        request: Request<GetEffectiveConfigRequest>,
+    ) -> Result<Response<GetEffectiveConfigResponse>, Status> {
+        // The configuration is redacted, but it still describes the whole deployment, so it's
+        // restricted to control-plane clients.
+        self.authorize(&request)?;
+        let format = config_format_from_proto(request.into_inner().format());
+        let (request, outcome) = ControlRequest::effective_config(format);
+        let config = self
+            .send_control(request, outcome)
+            .await?
+            .map_err(Status::internal)?;
+        Ok(Response::new(GetEffectiveConfigResponse { config }))
+    }
+}
+
+/// Caps the number of tapped events sent to a client in each one-second window.
+struct RateLimit {
+    max_events: usize,
+    window_start: Instant,
+    sent: usize,
+}
+
+impl RateLimit {
+    fn new(max_events_per_second: u32) -> Self {
+        Self {
+            max_events: max_events_per_second as usize,
+            window_start: Instant::now(),
+            sent: 0,
+        }
+    }
+
+    /// Returns how many of `requested` events can be sent now, and counts them as sent.
+    fn take(&mut self, requested: usize) -> usize {
+        if self.window_start.elapsed() >= Duration::from_secs(1) {
+            self.window_start = Instant::now();
+            self.sent = 0;
+        }
+        let allowed = requested.min(self.max_events - self.sent);
+        self.sent += allowed;
+        allowed
+    }
 }
 
 /// Reservoir sampler for tap events, batched and flushed on an interval.
+///
+/// When unsampled, every event is kept instead, and batches are also flushed as soon as they reach
+/// the limit. The events that are dropped regardless are reported to the client.
 struct Reservoir {
     events: Vec<(usize, StreamOutputEventsResponse)>,
     rng: SmallRng,
     batch: usize,
     limit: usize,
+    rate_limit: Option<RateLimit>,
+    unsampled: bool,
+    dropped: usize,
 }
 
 impl Reservoir {
-    fn new(limit: usize) -> Self {
+    fn new(limit: usize, rate_limit: Option<RateLimit>, unsampled: bool) -> Self {
         Self {
             events: Vec::with_capacity(limit),
             rng: SmallRng::from_rng(&mut rand::rng()),
             batch: 0,
             limit,
+            rate_limit,
+            unsampled,
+            dropped: 0,
+        }
+    }
+
+    /// Counts events dropped before reaching the reservoir, to report them on the next flush.
+    /// Sampled taps are lossy by design, so their drops aren't reported.
+    const fn record_dropped(&mut self, count: usize) {
+        if self.unsampled {
+            self.dropped += count;
         }
     }
 
@@ -739,6 +1241,12 @@ impl Reservoir {
                 tx.send(vec![event]).await.map_err(|err| {
                     debug!(message = "Couldn't send notification.", error = ?err);
                 })?;
+            } else if self.unsampled {
+                self.events.push((self.batch, event));
+                self.batch += 1;
+                if self.events.len() >= self.limit {
+                    self.flush(tx).await?;
+                }
             } else {
                 // Reservoir sampling (Algorithm R).
                 // Draw from 0..=batch (inclusive) so that event i has
@@ -759,15 +1267,32 @@ impl Reservoir {
         Ok(())
     }
 
-    /// Flush sampled events to the client, sorted by arrival order.
+    /// Flush sampled events to the client, sorted by arrival order. If the rate limit doesn't
+    /// allow sending all of them, a random subset is sent and the rest are dropped.
     async fn flush(
         &mut self,
         tx: &mpsc::Sender<Vec<StreamOutputEventsResponse>>,
     ) -> Result<(), ()> {
+        if let Some(rate_limit) = &mut self.rate_limit {
+            let allowed = rate_limit.take(self.events.len());
+            if allowed < self.events.len() {
+                self.record_dropped(self.events.len() - allowed);
+                self.events.shuffle(&mut self.rng);
+                self.events.truncate(allowed);
+            }
+        }
+        if self.dropped > 0 {
+            let notification =
+                tap_payload_to_output_events(TapPayload::events_dropped(self.dropped));
+            self.dropped = 0;
+            tx.send(notification).await.map_err(|err| {
+                debug!(message = "Couldn't send notification.", error = ?err);
+            })?;
+        }
+        self.batch = 0;
         if self.events.is_empty() {
             return Ok(());
         }
-        self.batch = 0;
         self.events.sort_by_key(|(batch, _)| *batch);
         let events = self.events.drain(..).map(|(_, e)| e).collect();
         tx.send(events).await.map_err(|err| {
@@ -780,6 +1305,12 @@ impl Reservoir {
 fn tap_payload_to_output_events(payload: TapPayload) -> Vec<StreamOutputEventsResponse> {
     use crate::event::proto::{Event, EventWrapper};
 
+    let now = chrono::Utc::now();
+    let tapped_at = Some(prost_types::Timestamp {
+        seconds: now.timestamp(),
+        nanos: now.timestamp_subsec_nanos() as i32,
+    });
+
     match payload {
         TapPayload::Log(output, log_array) => log_array
             .into_iter()
@@ -797,6 +1328,7 @@ fn tap_payload_to_output_events(payload: TapPayload) -> Vec<StreamOutputEventsRe
                             component_type: output.component_type.to_string(),
                             component_kind: output.component_kind.to_string(),
                             event: event_wrapper,
+                            tapped_at: tapped_at.clone(),
                         },
                     )),
                 }
@@ -818,6 +1350,7 @@ fn tap_payload_to_output_events(payload: TapPayload) -> Vec<StreamOutputEventsRe
                             component_type: output.component_type.to_string(),
                             component_kind: output.component_kind.to_string(),
                             event: event_wrapper,
+                            tapped_at: tapped_at.clone(),
                         },
                     )),
                 }
@@ -839,6 +1372,7 @@ fn tap_payload_to_output_events(payload: TapPayload) -> Vec<StreamOutputEventsRe
                             component_type: output.component_type.to_string(),
                             component_kind: output.component_kind.to_string(),
                             event: event_wrapper,
+                            tapped_at: tapped_at.clone(),
                         },
                     )),
                 }
@@ -20,7 +20,10 @@ use tonic_health::server::{HealthReporter, health_reporter};
 use vector_lib::tap::topology::WatchRx;
 
 use super::grpc::ObservabilityService;
-use crate::{config::Config, proto::observability::Server as ObservabilityServer};
+use crate::{
+    config::Config, proto::observability::Server as ObservabilityServer,
+    topology::control::ControlTx,
+};
 
 /// Shared flag backing the HTTP `/health` endpoint. Mirrors the gRPC
 /// `HealthReporter` serving status so HTTP and gRPC probes agree.
@@ -41,8 +44,14 @@ impl GrpcServer {
     /// it in the background. The server will shut down gracefully when this struct
     /// is dropped.
     ///
+    /// Control-plane calls are forwarded to the application through `control_tx`.
+    ///
     /// Returns an error if the server fails to bind to the configured address.
-    pub async fn start(config: &Config, watch_rx: WatchRx) -> crate::Result<Self> {
+    pub async fn start(
+        config: &Config,
+        watch_rx: WatchRx,
+        control_tx: ControlTx,
+    ) -> crate::Result<Self> {
         let addr = config.api.address.ok_or_else(|| {
             crate::Error::from("API address not configured in config.api.address")
         })?;
@@ -59,7 +68,8 @@ impl GrpcServer {
 
         info!("GRPC API server bound to {}.", actual_addr);
 
-        let service = ObservabilityService::new(watch_rx);
+        let service =
+            ObservabilityService::new(watch_rx, control_tx, config.api.control_token.clone());
 
         // Create the standard gRPC health service (grpc.health.v1.Health).
         // The empty service ("") is registered as SERVING by default.
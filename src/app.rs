@@ -4,6 +4,7 @@ use std::os::unix::process::ExitStatusExt;
 #[cfg(windows)]
 use std::os::windows::process::ExitStatusExt;
 use std::{
+    collections::HashSet,
     num::{NonZeroU64, NonZeroUsize},
     path::PathBuf,
     process::ExitStatus,
@@ -35,6 +36,7 @@ use crate::{
     topology::{
         ReloadOutcome, RunningTopology, SharedTopologyController, ShutdownErrorReceiver,
         TopologyController,
+        control::{self, ControlRequest, ControlRx, DiffReport, ReloadReport, ReloadStatus},
     },
     trace,
 };
@@ -101,7 +103,7 @@ impl ApplicationConfig {
         extra_context: ExtraContext,
     ) -> Result<Self, ExitCode> {
         #[cfg(feature = "api")]
-        let api = config.api;
+        let api = config.api.clone();
 
         let (topology, graceful_crash_receiver) =
             RunningTopology::start_init_validated(config, extra_context.clone())
@@ -135,12 +137,17 @@ impl ApplicationConfig {
 
     /// Configure the gRPC API server, if applicable
     #[cfg(feature = "api")]
-    pub fn setup_api(&self, handle: &Handle) -> Option<api::GrpcServer> {
+    pub fn setup_api(
+        &self,
+        handle: &Handle,
+        control_tx: control::ControlTx,
+    ) -> Option<api::GrpcServer> {
         if self.api.enabled {
             // Start gRPC server
             let api_server = handle.block_on(api::GrpcServer::start(
                 self.topology.config(),
                 self.topology.watch(),
+                control_tx,
             ));
             match api_server {
                 Ok(server) => {
@@ -268,12 +275,19 @@ impl Application {
             signals,
         } = self;
 
+        // Control requests are only issued through the API, so without it the sender is dropped
+        // right away and the receiver never yields anything.
+        #[cfg_attr(not(feature = "api"), allow(unused_variables))]
+        let (control_tx, control_rx) = control::control_channel();
+
         #[cfg(feature = "api")]
-        let api_server = config.setup_api(handle);
+        let api_server = config.setup_api(handle, control_tx.clone());
 
         let topology_controller = SharedTopologyController::new(TopologyController {
             #[cfg(feature = "api")]
             api_server,
+            #[cfg(feature = "api")]
+            control_tx,
             topology: config.topology,
             config_paths: config.config_paths.clone(),
             require_healthy: root_opts.require_healthy,
@@ -285,6 +299,7 @@ impl Application {
             internal_topologies: config.internal_topologies,
             graceful_crash_receiver: config.graceful_crash_receiver,
             signals,
+            control_rx,
             topology_controller,
             allow_empty_config: root_opts.allow_empty_config,
             interpolate_env: !root_opts.disable_env_var_interpolation,
@@ -297,6 +312,7 @@ pub struct StartedApplication {
     pub internal_topologies: Vec<RunningTopology>,
     pub graceful_crash_receiver: ShutdownErrorReceiver,
     pub signals: SignalPair,
+    pub control_rx: ControlRx,
     pub topology_controller: SharedTopologyController,
     pub allow_empty_config: bool,
     pub interpolate_env: bool,
@@ -312,6 +328,7 @@ impl StartedApplication {
             config_paths,
             graceful_crash_receiver,
             signals,
+            mut control_rx,
             topology_controller,
             internal_topologies,
             allow_empty_config,
@@ -336,6 +353,16 @@ impl StartedApplication {
                 ).await {
                     break signal;
                 },
+                Some(request) = control_rx.recv() => if let Some(signal) = handle_control_request(
+                    request,
+                    &topology_controller,
+                    &config_paths,
+                    &mut signal_handler,
+                    allow_empty_config,
+                    interpolate_env,
+                ).await {
+                    break signal;
+                },
                 // Trigger graceful shutdown if a component crashed, or all sources have ended.
                 error = graceful_crash.next() => break SignalTo::Shutdown(error),
                 _ = TopologyController::sources_finished(topology_controller.clone()), if has_sources => {
@@ -392,35 +419,16 @@ async fn handle_signal(
         }
         Ok(SignalTo::ReloadFromDisk) => {
             let mut topology_controller = topology_controller.lock().await;
-
-            // Reload paths
-            if let Some(paths) = config::process_paths(config_paths) {
-                topology_controller.config_paths = paths;
-            }
-
-            // Reload config
-            let new_config = config::load_from_paths_with_provider_and_secrets(
-                &topology_controller.config_paths,
+            let new_config = load_config_from_disk(
+                &mut topology_controller,
+                config_paths,
                 signal_handler,
                 allow_empty_config,
                 interpolate_env,
             )
             .await;
-
             if let Ok(ref config) = new_config {
-                // Find all transforms that have external files to watch
-                let transform_keys_to_reload = config.transform_keys_with_external_files();
-
-                // Add these transforms to reload set
-                if !transform_keys_to_reload.is_empty() {
-                    info!(
-                        message = "Reloading transforms with external files.",
-                        count = transform_keys_to_reload.len()
-                    );
-                    topology_controller
-                        .topology
-                        .extend_reload_set(transform_keys_to_reload);
-                }
+                reload_transforms_with_external_files(&mut topology_controller, config);
             }
 
             reload_config_from_result(topology_controller, new_config).await
@@ -443,6 +451,178 @@ async fn handle_signal(
     }
 }
 
+/// Loads the configuration from disk, as done when reloading in response to a signal.
+async fn load_config_from_disk(
+    topology_controller: &mut TopologyController,
+    config_paths: &[ConfigPath],
+    signal_handler: &mut SignalHandler,
+    allow_empty_config: bool,
+    interpolate_env: bool,
+) -> Result<Config, Vec<String>> {
+    // Reload paths
+    if let Some(paths) = config::process_paths(config_paths) {
+        topology_controller.config_paths = paths;
+    }
+
+    // Reload config
+    config::load_from_paths_with_provider_and_secrets(
+        &topology_controller.config_paths,
+        signal_handler,
+        allow_empty_config,
+        interpolate_env,
+    )
+    .await
+}
+
+/// Schedules the transforms of the given configuration that depend on external files for reload,
+/// so that changes to those files are picked up.
+fn reload_transforms_with_external_files(
+    topology_controller: &mut TopologyController,
+    config: &Config,
+) {
+    // Find all transforms that have external files to watch
+    let transform_keys_to_reload = config.transform_keys_with_external_files();
+
+    // Add these transforms to reload set
+    if !transform_keys_to_reload.is_empty() {
+        info!(
+            message = "Reloading transforms with external files.",
+            count = transform_keys_to_reload.len()
+        );
+        topology_controller
+            .topology
+            .extend_reload_set(transform_keys_to_reload);
+    }
+}
+
+async fn handle_control_request(
+    request: ControlRequest,
+    topology_controller: &SharedTopologyController,
+    config_paths: &[ConfigPath],
+    signal_handler: &mut SignalHandler,
+    allow_empty_config: bool,
+    interpolate_env: bool,
+) -> Option<SignalTo> {
+    // In all cases, the caller may have gone away while the request was being handled.
+    match request {
+        ControlRequest::Component { command, reply } => {
+            let operation = command.operation();
+            let component_id = command.component_key().clone();
+
+            let result = topology_controller.lock().await.control(command).await;
+            if let Err(error) = &result {
+                warn!(
+                    message = "Component control request failed.",
+                    operation,
+                    component_id = %component_id,
+                    %error,
+                );
+            }
+
+            _ = reply.send(result);
+            None
+        }
+        ControlRequest::ComponentInput { key, reply } => {
+            let result = topology_controller
+                .lock()
+                .await
+                .topology
+                .component_input(&key);
+            _ = reply.send(result);
+            None
+        }
+        ControlRequest::Reload { reply } => {
+            let mut topology_controller = topology_controller.lock().await;
+            let new_config = load_config_from_disk(
+                &mut topology_controller,
+                config_paths,
+                signal_handler,
+                allow_empty_config,
+                interpolate_env,
+            )
+            .await;
+
+            let new_config = match new_config {
+                Ok(new_config) => new_config,
+                Err(errors) => {
+                    handle_config_errors(errors.clone());
+                    emit!(VectorConfigLoadError);
+                    _ = reply.send(ReloadReport::rejected(errors));
+                    return None;
+                }
+            };
+
+            reload_transforms_with_external_files(&mut topology_controller, &new_config);
+            let diff = topology_controller
+                .topology
+                .config_diff(&new_config, HashSet::new());
+            let (status, errors, signal) = match topology_controller.reload(new_config).await {
+                ReloadOutcome::Success => (ReloadStatus::Applied, Vec::new(), None),
+                ReloadOutcome::RolledBack(errors) => (ReloadStatus::RolledBack, errors, None),
+                ReloadOutcome::MissingApiKey => (
+                    ReloadStatus::RolledBack,
+                    vec!["missing API key".to_string()],
+                    None,
+                ),
+                ReloadOutcome::FatalError(error) => (
+                    ReloadStatus::Failed,
+                    vec![error.to_string()],
+                    Some(SignalTo::Shutdown(Some(error))),
+                ),
+            };
+
+            _ = reply.send(ReloadReport {
+                status,
+                diff: Some(diff),
+                errors,
+            });
+            signal
+        }
+        ControlRequest::DiffConfig { config, reply } => {
+            let mut topology_controller = topology_controller.lock().await;
+            let new_config = match config {
+                // Supplied configurations are interpolated by the caller, but secrets are
+                // resolved here so that they compare equal to the running ones.
+                Some((input, format)) => {
+                    config::load_from_str_with_secrets(
+                        &input,
+                        format,
+                        signal_handler,
+                        allow_empty_config,
+                        false,
+                    )
+                    .await
+                }
+                None => {
+                    load_config_from_disk(
+                        &mut topology_controller,
+                        config_paths,
+                        signal_handler,
+                        allow_empty_config,
+                        interpolate_env,
+                    )
+                    .await
+                }
+            };
+
+            let report = match new_config {
+                Ok(new_config) => topology_controller.diff_config(&new_config),
+                Err(errors) => DiffReport {
+                    errors,
+                    ..Default::default()
+                },
+            };
+            _ = reply.send(report);
+            None
+        }
+        ControlRequest::EffectiveConfig { format, reply } => {
+            let result = topology_controller.lock().await.effective_config(format);
+            _ = reply.send(result);
+            None
+        }
+    }
+}
+
 async fn reload_config_from_result(
     mut topology_controller: MutexGuard<'_, TopologyController>,
     config: Result<Config, Vec<String>>,
@@ -0,0 +1,381 @@
+#![allow(missing_docs)]
+use std::path::PathBuf;
+
+use clap::{Parser, Subcommand};
+use vector_lib::{
+    buffers::{
+        BufferConfig, BufferType, DiskBufferEncryption,
+        offline::{self, DataFile, OfflineBuffer},
+    },
+    event::EventArray,
+};
+
+use crate::config::{self, ComponentKey};
+
+#[derive(Parser, Debug)]
+#[command(rename_all = "kebab-case")]
+pub struct Opts {
+    /// The ID of the sink component whose disk buffer to manage.
+    ///
+    /// The sink doesn't need to be in the configuration anymore, such as when recovering the buffer
+    /// of a sink that was removed. The priority lane of a sink's buffer is managed with the ID
+    /// `<sink_id>.priority`.
+    #[arg(long)]
+    component: ComponentKey,
+
+    #[command(subcommand)]
+    command: Command,
+
+    /// The data directory that the disk buffer is in. Defaults to the `data_dir` of the
+    /// configuration.
+    #[arg(long)]
+    data_dir: Option<PathBuf>,
+
+    /// Read configuration from one or more files. Wildcard paths are supported.
+    /// File format is detected from the file name.
+    /// If zero files are specified the default config path
+    /// `/etc/vector/vector.yaml` will be targeted.
+    #[arg(
+        id = "config",
+        short,
+        long,
+        env = "VECTOR_CONFIG",
+        value_delimiter(',')
+    )]
+    paths: Vec<PathBuf>,
+
+    /// Vector config files in TOML format.
+    #[arg(id = "config-toml", long, value_delimiter(','))]
+    paths_toml: Vec<PathBuf>,
+
+    /// Vector config files in JSON format.
+    #[arg(id = "config-json", long, value_delimiter(','))]
+    paths_json: Vec<PathBuf>,
+
+    /// Vector config files in YAML format.
+    #[arg(id = "config-yaml", long, value_delimiter(','))]
+    paths_yaml: Vec<PathBuf>,
+
+    /// Read configuration from files in one or more directories.
+    /// File format is detected from the file name.
+    ///
+    /// Files not ending in .toml, .json, .yaml, or .yml will be ignored.
+    #[arg(
+        id = "config-dir",
+        short = 'C',
+        long,
+        env = "VECTOR_CONFIG_DIR",
+        value_delimiter(',')
+    )]
+    pub config_dirs: Vec<PathBuf>,
+
+    /// Disable interpolation of environment variables in configuration files.
+    #[arg(
+        long,
+        env = "VECTOR_DISABLE_ENV_VAR_INTERPOLATION",
+        default_value = "false"
+    )]
+    pub disable_env_var_interpolation: bool,
+}
+
+#[derive(Subcommand, Debug)]
+#[command(rename_all = "kebab-case")]
+enum Command {
+    /// Show the state of the buffer, and the records in each of its data files.
+    Info,
+
+    /// Output the events in the buffer as JSON, one event per line.
+    Dump {
+        /// Also output the events that were already acknowledged, but whose data file hasn't
+        /// been deleted yet.
+        #[arg(long)]
+        all: bool,
+    },
+
+    /// Verify the checksums of all records in the buffer.
+    ///
+    /// Exits with an error if any data file has a corrupted tail.
+    Verify,
+
+    /// Cut off the corrupted tails of data files, so that the records before them can still be
+    /// read.
+    Truncate,
+
+    /// Move the buffer so that it's used by another sink.
+    ///
+    /// The priority lane of the buffer, if any, is moved along with it.
+    Rehome {
+        /// The ID of the sink to move the buffer to. The sink must not have a disk buffer already.
+        #[arg(long)]
+        to: ComponentKey,
+    },
+}
+
+impl Opts {
+    fn paths_with_formats(&self) -> Vec<config::ConfigPath> {
+        config::merge_path_lists(vec![
+            (&self.paths, None),
+            (&self.paths_toml, Some(config::Format::Toml)),
+            (&self.paths_json, Some(config::Format::Json)),
+            (&self.paths_yaml, Some(config::Format::Yaml)),
+        ])
+        .map(|(path, hint)| config::ConfigPath::File(path, hint))
+        .chain(
+            self.config_dirs
+                .iter()
+                .map(|dir| config::ConfigPath::Dir(dir.to_path_buf())),
+        )
+        .collect()
+    }
+}
+
+/// The sink component whose disk buffer is managed.
+struct Component {
+    data_dir: PathBuf,
+    encryption: Option<DiskBufferEncryption>,
+}
+
+impl Component {
+    fn load(opts: &Opts) -> Result<Self, Vec<String>> {
+        let paths = config::process_paths(&opts.paths_with_formats())
+            .ok_or_else(|| vec!["Unable to find configuration files.".to_owned()])?;
+        let config = config::load_from_paths(&paths, !opts.disable_env_var_interpolation)?;
+
+        let data_dir = config
+            .global
+            .resolve_and_validate_data_dir(opts.data_dir.as_ref())
+            .map_err(|e| vec![e.to_string()])?;
+
+        // Records can only be decrypted with the keys of the sink's disk buffer, if it's still
+        // configured. The priority lane of the buffer uses the same keys.
+        let sink = config.sink(&opts.component).or_else(|| {
+            config
+                .sinks()
+                .find(|(id, _)| BufferConfig::priority_lane_id(id.id()) == opts.component.id())
+                .map(|(_, sink)| sink)
+        });
+        let encryption = sink.and_then(|sink| {
+            sink.buffer.stages().iter().find_map(|stage| match stage {
+                BufferType::DiskV2 { encryption, .. } => encryption.clone(),
+                BufferType::Memory { .. } => None,
+            })
+        });
+
+        Ok(Self {
+            data_dir,
+            encryption,
+        })
+    }
+
+    async fn open(&self, id: &ComponentKey) -> Result<OfflineBuffer, String> {
+        let buffer_dir = offline::get_buffer_data_dir(&self.data_dir, id.id());
+        OfflineBuffer::open(&buffer_dir)
+            .await
+            .and_then(|buffer| buffer.with_encryption(self.encryption.as_ref()))
+            .map_err(|error| format!("Unable to open disk buffer of {:?}: {error}", id.id()))
+    }
+}
+
+pub async fn cmd(opts: &Opts) -> exitcode::ExitCode {
+    let component = match Component::load(opts) {
+        Ok(component) => component,
+        Err(errors) => {
+            #[allow(clippy::print_stderr)]
+            for error in errors {
+                eprintln!("{error}");
+            }
+            return exitcode::CONFIG;
+        }
+    };
+
+    match run(opts, &component).await {
+        Ok(()) => exitcode::OK,
+        Err(error) => {
+            #[allow(clippy::print_stderr)]
+            {
+                eprintln!("{error}");
+            }
+            exitcode::SOFTWARE
+        }
+    }
+}
+
+fn file_name(file: &DataFile) -> String {
+    file.path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+#[allow(clippy::print_stdout, clippy::print_stderr)]
+async fn run(opts: &Opts, component: &Component) -> Result<(), String> {
+    if let Command::Rehome { to } = &opts.command {
+        let move_error = |error| format!("Unable to move disk buffer: {error}");
+        let from_lane = BufferConfig::priority_lane_id(opts.component.id());
+        let to_lane = BufferConfig::priority_lane_id(to.id());
+
+        // The target of the priority lane is checked before anything is moved, so that the buffer
+        // isn't left split between the two sinks.
+        let has_lane = offline::buffer_exists(&component.data_dir, &from_lane)
+            .await
+            .map_err(move_error)?;
+        if has_lane
+            && offline::buffer_exists(&component.data_dir, &to_lane)
+                .await
+                .map_err(move_error)?
+        {
+            return Err(format!(
+                "Unable to move disk buffer: {:?} already has a priority lane.",
+                to.id()
+            ));
+        }
+
+        offline::move_buffer(&component.data_dir, opts.component.id(), to.id())
+            .await
+            .map_err(move_error)?;
+        println!(
+            "Moved the disk buffer of {:?} to {:?}.",
+            opts.component.id(),
+            to.id()
+        );
+
+        if has_lane {
+            offline::move_buffer(&component.data_dir, &from_lane, &to_lane)
+                .await
+                .map_err(|error| format!("Unable to move the priority lane: {error}"))?;
+            println!(
+                "Moved the priority lane of {:?} to {:?}.",
+                opts.component.id(),
+                to.id()
+            );
+        }
+        return Ok(());
+    }
+
+    let buffer = component.open(&opts.component).await?;
+    let ledger = *buffer.ledger();
+    let data_files = buffer
+        .data_files()
+        .await
+        .map_err(|error| format!("Unable to list data files: {error}"))?;
+    let scan_error = |file: &DataFile, error| format!("Unable to read {:?}: {error}", file.path);
+
+    match &opts.command {
+        Command::Info => {
+            println!("Buffer: {}", buffer.data_dir().display());
+            println!(
+                "Writer: next record ID {}, data file {}",
+                ledger.writer_next_record_id, ledger.writer_current_data_file
+            );
+            println!(
+                "Reader: last acknowledged record ID {}, data file {}",
+                ledger.reader_last_record_id, ledger.reader_current_data_file
+            );
+            println!("Unread events: {}", ledger.unread_events());
+            println!("Data files: {}", data_files.len());
+            for file in &data_files {
+                let scan = buffer
+                    .verify_data_file::<EventArray>(file)
+                    .await
+                    .map_err(|error| scan_error(file, error))?;
+                let status = match &scan.error {
+                    None => "ok".to_owned(),
+                    Some(error) => format!("corrupted after {} bytes: {error}", scan.valid_len),
+                };
+                println!(
+                    "  {}: {} bytes, {} records, {status}",
+                    file_name(file),
+                    file.size,
+                    scan.records
+                );
+            }
+        }
+        Command::Dump { all } => {
+            for file in &data_files {
+                let data_file = file_name(file);
+                let scan = buffer
+                    .read_data_file::<EventArray, _>(file, |record, result| {
+                        if !all && ledger.is_acknowledged(record.id) {
+                            return;
+                        }
+                        match result {
+                            Ok(events) => {
+                                for event in events.into_events() {
+                                    let line = serde_json::json!({
+                                        "data_file": data_file,
+                                        "record_id": record.id,
+                                        "event": event,
+                                    });
+                                    println!("{line}");
+                                }
+                            }
+                            Err(error) => eprintln!(
+                                "Unable to decode record {} in {data_file}: {error}",
+                                record.id
+                            ),
+                        }
+                    })
+                    .await
+                    .map_err(|error| scan_error(file, error))?;
+                if let Some(error) = scan.error {
+                    eprintln!(
+                        "Skipped the rest of {data_file} after {} bytes: {error}",
+                        scan.valid_len
+                    );
+                }
+            }
+        }
+        Command::Verify => {
+            let mut corrupted = 0;
+            for file in &data_files {
+                let scan = buffer
+                    .verify_data_file::<EventArray>(file)
+                    .await
+                    .map_err(|error| scan_error(file, error))?;
+                match scan.error {
+                    None => println!("{}: {} records, ok", file_name(file), scan.records),
+                    Some(error) => {
+                        corrupted += 1;
+                        println!(
+                            "{}: {} records, corrupted after {} of {} bytes: {error}",
+                            file_name(file),
+                            scan.records,
+                            scan.valid_len,
+                            file.size
+                        );
+                    }
+                }
+            }
+            if corrupted > 0 {
+                return Err(format!(
+                    "Found {corrupted} corrupted data file(s). Use `vector buffer truncate` to cut \
+                     off their corrupted tails."
+                ));
+            }
+        }
+        Command::Truncate => {
+            for file in &data_files {
+                let scan = buffer
+                    .verify_data_file::<EventArray>(file)
+                    .await
+                    .map_err(|error| scan_error(file, error))?;
+                if scan.is_corrupted() {
+                    buffer
+                        .truncate_data_file(file, scan.valid_len)
+                        .await
+                        .map_err(|error| format!("Unable to truncate {:?}: {error}", file.path))?;
+                    println!(
+                        "{}: truncated from {} to {} bytes, keeping {} records",
+                        file_name(file),
+                        file.size,
+                        scan.valid_len,
+                        scan.records
+                    );
+                }
+            }
+        }
+        Command::Rehome { .. } => unreachable!("handled above"),
+    }
+
+    Ok(())
+}
@@ -0,0 +1,485 @@
+#![allow(missing_docs)]
+use std::{
+    fs::File,
+    io::{self, Read, Seek, SeekFrom},
+    path::{Path, PathBuf},
+};
+
+use clap::{Args, Parser, Subcommand};
+use vector_lib::file_source_common::{
+    CheckpointsLock, ContentChecksum, FileFingerprint, FilePosition, FingerprintStrategy,
+    Fingerprinter, checkpointer::Checkpointer,
+};
+
+use crate::{
+    config::{self, ComponentKey},
+    sources::file::FileConfig,
+};
+
+#[derive(Parser, Debug)]
+#[command(rename_all = "kebab-case")]
+pub struct Opts {
+    /// The ID of the `file` source component whose checkpoints to manage.
+    #[arg(long)]
+    component: ComponentKey,
+
+    #[command(subcommand)]
+    command: Command,
+
+    /// Read configuration from one or more files. Wildcard paths are supported.
+    /// File format is detected from the file name.
+    /// If zero files are specified the default config path
+    /// `/etc/vector/vector.yaml` will be targeted.
+    #[arg(
+        id = "config",
+        short,
+        long,
+        env = "VECTOR_CONFIG",
+        value_delimiter(',')
+    )]
+    paths: Vec<PathBuf>,
+
+    /// Vector config files in TOML format.
+    #[arg(id = "config-toml", long, value_delimiter(','))]
+    paths_toml: Vec<PathBuf>,
+
+    /// Vector config files in JSON format.
+    #[arg(id = "config-json", long, value_delimiter(','))]
+    paths_json: Vec<PathBuf>,
+
+    /// Vector config files in YAML format.
+    #[arg(id = "config-yaml", long, value_delimiter(','))]
+    paths_yaml: Vec<PathBuf>,
+
+    /// Read configuration from files in one or more directories.
+    /// File format is detected from the file name.
+    ///
+    /// Files not ending in .toml, .json, .yaml, or .yml will be ignored.
+    #[arg(
+        id = "config-dir",
+        short = 'C',
+        long,
+        env = "VECTOR_CONFIG_DIR",
+        value_delimiter(',')
+    )]
+    pub config_dirs: Vec<PathBuf>,
+
+    /// Disable interpolation of environment variables in configuration files.
+    #[arg(
+        long,
+        env = "VECTOR_DISABLE_ENV_VAR_INTERPOLATION",
+        default_value = "false"
+    )]
+    pub disable_env_var_interpolation: bool,
+}
+
+#[derive(Subcommand, Debug)]
+#[command(rename_all = "kebab-case")]
+enum Command {
+    /// List the checkpoints of the component.
+    List {
+        /// Output the checkpoints as JSON, in the format used by `export`.
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Export the checkpoints of the component, such as to move them to another node.
+    Export {
+        /// The file to write the checkpoints to. Defaults to stdout.
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+
+    /// Import checkpoints written by `export`, replacing the checkpoints of the same files.
+    Import {
+        /// The file to read the checkpoints from, or `-` for stdin.
+        input: PathBuf,
+
+        /// Remove all existing checkpoints before importing.
+        #[arg(long)]
+        replace: bool,
+    },
+
+    /// Reset checkpoints, so that files are read according to `read_from` when next found.
+    Reset {
+        #[command(flatten)]
+        target: Target,
+
+        /// Reset the checkpoints of all files.
+        #[arg(long, conflicts_with_all = ["file", "fingerprint"])]
+        all: bool,
+    },
+
+    /// Set the position that reading a file resumes from.
+    Edit {
+        #[command(flatten)]
+        target: Target,
+
+        /// The position, in bytes from the beginning of the file, to resume reading from.
+        #[arg(long)]
+        position: u64,
+    },
+}
+
+impl Command {
+    /// Returns whether the command rewrites the checkpoints.
+    const fn writes(&self) -> bool {
+        matches!(
+            self,
+            Self::Import { .. } | Self::Reset { .. } | Self::Edit { .. }
+        )
+    }
+}
+
+#[derive(Args, Debug)]
+#[group(multiple = false)]
+struct Target {
+    /// The path of the file whose checkpoint to change. The file is fingerprinted the same way
+    /// the component does.
+    #[arg(long)]
+    file: Option<PathBuf>,
+
+    /// The fingerprint of the file whose checkpoint to change, as shown by `list`.
+    #[arg(long)]
+    fingerprint: Option<FileFingerprint>,
+}
+
+impl Opts {
+    fn paths_with_formats(&self) -> Vec<config::ConfigPath> {
+        config::merge_path_lists(vec![
+            (&self.paths, None),
+            (&self.paths_toml, Some(config::Format::Toml)),
+            (&self.paths_json, Some(config::Format::Json)),
+            (&self.paths_yaml, Some(config::Format::Yaml)),
+        ])
+        .map(|(path, hint)| config::ConfigPath::File(path, hint))
+        .chain(
+            self.config_dirs
+                .iter()
+                .map(|dir| config::ConfigPath::Dir(dir.to_path_buf())),
+        )
+        .collect()
+    }
+}
+
+/// The `file` source component that checkpoints are managed for.
+struct Component {
+    config: FileConfig,
+    data_dir: PathBuf,
+}
+
+impl Component {
+    fn load(opts: &Opts) -> Result<Self, Vec<String>> {
+        let paths = config::process_paths(&opts.paths_with_formats())
+            .ok_or_else(|| vec!["Unable to find configuration files.".to_owned()])?;
+        let config = config::load_from_paths(&paths, !opts.disable_env_var_interpolation)?;
+
+        let source = config
+            .source(&opts.component)
+            .ok_or_else(|| vec![format!("Source {:?} not found.", opts.component.id())])?;
+        if source.inner.get_component_name() != "file" {
+            return Err(vec![format!(
+                "Source {:?} is a `{}` source, checkpoints can only be managed for `file` sources.",
+                opts.component.id(),
+                source.inner.get_component_name()
+            )]);
+        }
+
+        // Sources are only available as trait objects, so round trip the configuration through
+        // its serialized form to get at the `file` source's options.
+        let mut value = serde_json::to_value(&source.inner).map_err(|e| vec![e.to_string()])?;
+        if let Some(object) = value.as_object_mut() {
+            object.remove("type");
+        }
+        let file_config: FileConfig =
+            serde_json::from_value(value).map_err(|e| vec![e.to_string()])?;
+
+        let data_dir = config
+            .global
+            .resolve_and_validate_data_dir(file_config.data_dir.as_ref())
+            .map_err(|e| vec![e.to_string()])?
+            .join(opts.component.id());
+
+        Ok(Self {
+            config: file_config,
+            data_dir,
+        })
+    }
+
+    async fn checkpointer(&self) -> Checkpointer {
+        let mut checkpointer = Checkpointer::new(&self.data_dir);
+        checkpointer.read_checkpoints(None).await;
+        checkpointer
+    }
+
+    async fn fingerprint(&self, target: &Target) -> Result<Option<FileFingerprint>, String> {
+        match (&target.file, target.fingerprint) {
+            (Some(path), _) => {
+                let strategy = FingerprintStrategy::from(self.config.fingerprint.clone());
+                let mut fingerprinter =
+                    Fingerprinter::new(strategy, self.config.max_line_bytes, false);
+                fingerprinter
+                    .fingerprint(path)
+                    .await
+                    .map(Some)
+                    .map_err(|error| format!("Unable to fingerprint {path:?}: {error}"))
+            }
+            (None, fingerprint) => Ok(fingerprint),
+        }
+    }
+
+    /// Computes the checksum of the data just before `position` in the target file, which the
+    /// component uses to check that the file still holds the data read up to its checkpoint.
+    ///
+    /// Returns `None` if the target is only given by its fingerprint, or if the file doesn't hold
+    /// any data before `position`.
+    fn content_checksum(
+        &self,
+        target: &Target,
+        position: FilePosition,
+    ) -> Result<Option<ContentChecksum>, String> {
+        let Some(path) = &target.file else {
+            return Ok(None);
+        };
+        let read = || -> io::Result<Option<ContentChecksum>> {
+            let mut file = File::open(path)?;
+            if file.metadata()?.len() < position {
+                return Ok(None);
+            }
+            let start = position.saturating_sub(ContentChecksum::MAX_LEN as u64);
+            let mut data = vec![0; (position - start) as usize];
+            file.seek(SeekFrom::Start(start))?;
+            file.read_exact(&mut data)?;
+            Ok((!data.is_empty()).then(|| ContentChecksum::new(position, &data)))
+        };
+        read().map_err(|error| format!("Unable to read {path:?}: {error}"))
+    }
+
+    /// Locks the checkpoints, failing if the component is running in a Vector process, as it would
+    /// overwrite any change to them.
+    fn lock(&self) -> Result<CheckpointsLock, String> {
+        std::fs::create_dir_all(&self.data_dir)
+            .and_then(|()| CheckpointsLock::try_lock(&self.data_dir))
+            .map_err(|error| {
+                format!(
+                    "Unable to lock the checkpoints in {:?}: {error}",
+                    self.data_dir
+                )
+            })?
+            .ok_or_else(|| {
+                format!(
+                    "The checkpoints in {:?} are locked; is a Vector process running this component?",
+                    self.data_dir
+                )
+            })
+    }
+
+    async fn save(&self, checkpointer: &Checkpointer) -> io::Result<usize> {
+        checkpointer.write_checkpoints().await
+    }
+}
+
+pub async fn cmd(opts: &Opts) -> exitcode::ExitCode {
+    let component = match Component::load(opts) {
+        Ok(component) => component,
+        Err(errors) => {
+            #[allow(clippy::print_stderr)]
+            for error in errors {
+                eprintln!("{error}");
+            }
+            return exitcode::CONFIG;
+        }
+    };
+
+    match run(&opts.command, &component).await {
+        Ok(()) => exitcode::OK,
+        Err(error) => {
+            #[allow(clippy::print_stderr)]
+            {
+                eprintln!("{error}");
+            }
+            exitcode::SOFTWARE
+        }
+    }
+}
+
+#[allow(clippy::print_stdout)]
+async fn run(command: &Command, component: &Component) -> Result<(), String> {
+    // Held until the changed checkpoints are written.
+    let _lock = command.writes().then(|| component.lock()).transpose()?;
+    let checkpointer = component.checkpointer().await;
+    let checkpoints = checkpointer.view();
+    let save_error = |error: io::Error| {
+        format!(
+            "Unable to write checkpoints to {:?}: {error}",
+            component.data_dir
+        )
+    };
+
+    match command {
+        Command::List { json: true } | Command::Export { output: None } => {
+            let exported = checkpointer.export().map_err(|error| error.to_string())?;
+            println!("{}", String::from_utf8_lossy(&exported));
+        }
+        Command::List { json: false } => {
+            println!("{:<48} {:>20} MODIFIED", "FINGERPRINT", "POSITION");
+            for checkpoint in checkpoints.list() {
+                println!(
+                    "{:<48} {:>20} {}",
+                    checkpoint.fingerprint.to_string(),
+                    checkpoint.position,
+                    checkpoint.modified.to_rfc3339()
+                );
+            }
+        }
+        Command::Export {
+            output: Some(output),
+        } => {
+            let exported = checkpointer.export().map_err(|error| error.to_string())?;
+            std::fs::write(output, exported)
+                .map_err(|error| format!("Unable to write {output:?}: {error}"))?;
+        }
+        Command::Import { input, replace } => {
+            let data =
+                read_input(input).map_err(|error| format!("Unable to read {input:?}: {error}"))?;
+            if *replace {
+                for checkpoint in checkpoints.list() {
+                    checkpoints.remove(checkpoint.fingerprint);
+                }
+            }
+            let count = checkpointer
+                .import(&data)
+                .map_err(|error| format!("Unable to import {input:?}: {error}"))?;
+            component.save(&checkpointer).await.map_err(save_error)?;
+            println!("Imported {count} checkpoints.");
+        }
+        Command::Reset { target, all } => {
+            let fingerprint = component.fingerprint(target).await?;
+            match fingerprint {
+                Some(fingerprint) => {
+                    if checkpoints.remove(fingerprint).is_none() {
+                        return Err(format!("No checkpoint found for {fingerprint}."));
+                    }
+                }
+                None if *all => {
+                    for checkpoint in checkpoints.list() {
+                        checkpoints.remove(checkpoint.fingerprint);
+                    }
+                }
+                None => {
+                    return Err("One of `--file`, `--fingerprint` or `--all` is required.".into());
+                }
+            }
+            let count = component.save(&checkpointer).await.map_err(save_error)?;
+            println!("Reset checkpoints, {count} remaining.");
+        }
+        Command::Edit { target, position } => {
+            let fingerprint = component
+                .fingerprint(target)
+                .await?
+                .ok_or("One of `--file` or `--fingerprint` is required.")?;
+            // The checksum of the previous position no longer applies, and would make the component
+            // read the file from the beginning, so replace it or leave the new position unverified.
+            match component.content_checksum(target, *position)? {
+                Some(content_checksum) => {
+                    checkpoints.update_content_checksum(fingerprint, content_checksum)
+                }
+                None => checkpoints.remove_content_checksum(fingerprint),
+            }
+            checkpoints.update(fingerprint, *position);
+            component.save(&checkpointer).await.map_err(save_error)?;
+            println!("Set checkpoint of {fingerprint} to position {position}.");
+        }
+    }
+
+    Ok(())
+}
+
+fn read_input(input: &Path) -> io::Result<Vec<u8>> {
+    let mut data = Vec::new();
+    if input == Path::new("-") {
+        io::stdin().read_to_end(&mut data)?;
+    } else {
+        data = std::fs::read(input)?;
+    }
+    Ok(data)
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::tempdir;
+
+    use super::*;
+
+    fn component(data_dir: &Path) -> Component {
+        Component {
+            config: FileConfig::default(),
+            data_dir: data_dir.to_path_buf(),
+        }
+    }
+
+    #[tokio::test]
+    async fn edit_is_refused_while_checkpoints_are_locked() {
+        let dir = tempdir().unwrap();
+        let component = component(dir.path());
+        let fingerprint = FileFingerprint::FirstLinesChecksum(1);
+        let command = Command::Edit {
+            target: Target {
+                file: None,
+                fingerprint: Some(fingerprint),
+            },
+            position: 10,
+        };
+
+        let lock = CheckpointsLock::lock(dir.path()).unwrap();
+        let error = run(&command, &component).await.unwrap_err();
+        assert!(error.contains("are locked"), "{error}");
+        assert_eq!(
+            component.checkpointer().await.get_checkpoint(fingerprint),
+            None
+        );
+
+        drop(lock);
+        run(&command, &component).await.unwrap();
+        assert_eq!(
+            component.checkpointer().await.get_checkpoint(fingerprint),
+            Some(10)
+        );
+    }
+
+    #[tokio::test]
+    async fn edit_replaces_content_checksum() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("file.log");
+        std::fs::write(&path, "first line\nsecond line\n").unwrap();
+        let component = component(dir.path());
+        let target = Target {
+            file: Some(path.clone()),
+            fingerprint: None,
+        };
+        let fingerprint = component.fingerprint(&target).await.unwrap().unwrap();
+
+        let checkpointer = component.checkpointer().await;
+        checkpointer.view().update(fingerprint, 23);
+        checkpointer
+            .view()
+            .update_content_checksum(fingerprint, ContentChecksum::new(23, b"second line\n"));
+        checkpointer.write_checkpoints().await.unwrap();
+
+        run(
+            &Command::Edit {
+                target,
+                position: 11,
+            },
+            &component,
+        )
+        .await
+        .unwrap();
+
+        let checkpoints = component.checkpointer().await.view();
+        assert_eq!(checkpoints.get(fingerprint), Some(11));
+        let content_checksum = checkpoints.get_content_checksum(fingerprint).unwrap();
+        assert_eq!(content_checksum, ContentChecksum::new(11, b"first line\n"));
+        assert!(content_checksum.matches(&path).await.unwrap());
+    }
+}
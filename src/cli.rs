@@ -4,6 +4,8 @@ use std::{num::NonZeroU64, path::PathBuf};
 
 use clap::{ArgAction, CommandFactory, FromArgMatches, Parser};
 
+#[cfg(feature = "sources-file")]
+use crate::checkpoints;
 #[cfg(windows)]
 use crate::service;
 #[cfg(feature = "api-client")]
@@ -12,8 +14,8 @@ use crate::tap;
 use crate::top;
 
 use crate::{
-    completion, config, convert_config, generate, generate_schema, get_version, graph, list,
-    signal, unit_test, validate,
+    buffer, completion, config, convert_config, generate, generate_schema, get_version, graph,
+    list, signal, unit_test, validate,
 };
 
 #[derive(Parser, Debug)]
@@ -440,6 +442,18 @@ pub enum SubCommand {
     #[cfg(windows)]
     Service(service::Opts),
 
+    /// List, export, import, reset, and edit the checkpoints of a `file` source.
+    ///
+    /// Vector must not be running the source while its checkpoints are changed, as it would
+    /// overwrite them.
+    #[cfg(feature = "sources-file")]
+    Checkpoints(checkpoints::Opts),
+
+    /// Inspect, verify, and recover the disk buffer of a sink.
+    ///
+    /// Vector must not be running the sink while its buffer is managed.
+    Buffer(buffer::Opts),
+
     /// Vector Remap Language CLI
     Vrl(vrl::cli::Opts),
 }
@@ -451,6 +465,9 @@ impl SubCommand {
         color: bool,
     ) -> exitcode::ExitCode {
         match self {
+            Self::Buffer(b) => buffer::cmd(b).await,
+            #[cfg(feature = "sources-file")]
+            Self::Checkpoints(c) => checkpoints::cmd(c).await,
             Self::Completion(s) => completion::cmd(s),
             Self::Config(c) => config::cmd(c),
             Self::ConvertConfig(opts) => convert_config::cmd(opts),
@@ -1,4 +1,4 @@
-use std::{path::Path, time::Duration};
+use std::{collections::HashSet, path::Path, time::Duration};
 
 use indexmap::IndexMap;
 use vector_lib::{config::GlobalOptions, configurable::configurable_component};
@@ -80,6 +80,12 @@ pub struct ConfigBuilder {
     #[serde(default, skip)]
     #[doc(hidden)]
     pub allow_empty: bool,
+
+    /// The values interpolated into the configuration from environment variables and secret
+    /// backends, redacted when the configuration is serialized back out.
+    #[serde(default, skip)]
+    #[doc(hidden)]
+    pub interpolated_values: HashSet<String>,
 }
 
 impl From<Config> for ConfigBuilder {
@@ -97,6 +103,7 @@ impl From<Config> for ConfigBuilder {
             tests,
             secret,
             graceful_shutdown_duration,
+            interpolated_values,
         } = config;
 
         let transforms = transforms
@@ -131,6 +138,7 @@ impl From<Config> for ConfigBuilder {
             secret,
             graceful_shutdown_duration,
             allow_empty: false,
+            interpolated_values,
         }
     }
 }
@@ -272,6 +280,7 @@ impl ConfigBuilder {
         self.transforms.extend(with.transforms);
         self.tests.extend(with.tests);
         self.secret.extend(with.secret);
+        self.interpolated_values.extend(with.interpolated_values);
 
         Ok(())
     }
@@ -40,6 +40,10 @@ pub fn compile(mut builder: ConfigBuilder) -> Result<(Config, Vec<String>), Vec<
         errors.extend(alpha_errors);
     }
 
+    if let Err(pool_errors) = validation::check_buffer_pool(&builder) {
+        errors.extend(pool_errors);
+    }
+
     let ConfigBuilder {
         global,
         #[cfg(feature = "api")]
@@ -55,6 +59,7 @@ pub fn compile(mut builder: ConfigBuilder) -> Result<(Config, Vec<String>), Vec<
         secret,
         graceful_shutdown_duration,
         allow_empty: _,
+        interpolated_values,
     } = builder;
     let all_sinks = sinks
         .clone()
@@ -97,6 +102,10 @@ pub fn compile(mut builder: ConfigBuilder) -> Result<(Config, Vec<String>), Vec<
         errors.push(e);
     }
 
+    if let Err(dead_letter_errors) = validation::check_dead_letters(&sources, &sinks, &graph) {
+        errors.extend(dead_letter_errors);
+    }
+
     // Inputs are resolved from string into OutputIds as part of graph construction, so update them
     // here before adding to the final config (the types require this).
     let sinks = sinks
@@ -139,6 +148,7 @@ pub fn compile(mut builder: ConfigBuilder) -> Result<(Config, Vec<String>), Vec<
             tests,
             secret,
             graceful_shutdown_duration,
+            interpolated_values,
         };
 
         config.propagate_acknowledgements()?;
@@ -66,6 +66,81 @@ impl ConfigDiff {
             || self.sinks.is_removed(key)
             || self.enrichment_tables.contains(key)
     }
+
+    /// Checks whether the buffer of a changed sink is handed over to its new version, which is
+    /// the case when the sink isn't forcibly reloaded and its buffer configuration, including its
+    /// buffer priority, is unchanged.
+    pub fn reuses_sink_buffer(&self, old: &Config, new: &Config, key: &ComponentKey) -> bool {
+        // Conditions can't be compared directly, so their serialized forms are compared instead.
+        let buffer_config = |config: &Config| {
+            config.sink(key).map(|sink| {
+                (
+                    &sink.buffer,
+                    serde_json::to_value(&sink.buffer_priority).ok(),
+                )
+            })
+        };
+
+        self.sinks.is_changed(key)
+            && !self.components_to_reload.contains(key)
+            && buffer_config(old) == buffer_config(new)
+    }
+
+    /// Determines what happens to the buffer of every changed or removed sink, including sinks
+    /// of enrichment tables, when this differential is applied. Sinks are sorted by key.
+    pub fn sink_buffer_changes(
+        &self,
+        old: &Config,
+        new: &Config,
+    ) -> Vec<(ComponentKey, BufferAction)> {
+        let is_table_sink = |config: &Config, key: &ComponentKey| {
+            config
+                .enrichment_table(key)
+                .and_then(|table| table.as_sink(key))
+                .is_some()
+        };
+
+        let removed = self.sinks.to_remove.iter().chain(
+            self.enrichment_tables
+                .to_remove
+                .iter()
+                .filter(|key| is_table_sink(old, key)),
+        );
+        let changed = self.sinks.to_change.iter().chain(
+            self.enrichment_tables
+                .to_change
+                .iter()
+                .filter(|key| is_table_sink(old, key)),
+        );
+
+        let mut changes = removed
+            .map(|key| (key.clone(), BufferAction::Dropped))
+            .chain(changed.map(|key| {
+                let action = if self.reuses_sink_buffer(old, new, key) {
+                    BufferAction::Reused
+                } else {
+                    BufferAction::Rebuilt
+                };
+                (key.clone(), action)
+            }))
+            .collect::<Vec<_>>();
+        changes.sort_by(|(a, _), (b, _)| a.cmp(b));
+        changes
+    }
+}
+
+/// What happens to the buffer of a changed or removed sink during a reload.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum BufferAction {
+    /// The buffer, along with any events in it, is handed over to the new version of the sink.
+    Reused,
+
+    /// The old sink drains its buffer and shuts down, and the new version of the sink starts with
+    /// a new buffer. Disk buffers reopen their existing data.
+    Rebuilt,
+
+    /// The sink drains its buffer and shuts down, and the buffer is discarded.
+    Dropped,
 }
 
 #[derive(Debug)]
@@ -317,4 +392,70 @@ mod tests {
             HashSet::from_iter(["memory_table".into(), "memory_table_source".into()])
         );
     }
+
+    #[test]
+    fn sink_buffer_changes() {
+        let old_config: Config = serde_yaml::from_str::<ConfigBuilder>(indoc! {r#"
+            sources:
+              test:
+                type: "test_basic"
+
+            sinks:
+              reused:
+                type: "test_basic"
+                inputs: ["test"]
+              rebuilt:
+                type: "test_basic"
+                inputs: ["test"]
+              dropped:
+                type: "test_basic"
+                inputs: ["test"]
+              unchanged:
+                type: "test_basic"
+                inputs: ["test"]
+        "#})
+        .unwrap()
+        .build()
+        .unwrap();
+
+        let new_config: Config = serde_yaml::from_str::<ConfigBuilder>(indoc! {r#"
+            sources:
+              test:
+                type: "test_basic"
+
+            sinks:
+              reused:
+                type: "test_basic"
+                inputs: ["test"]
+                data: "changed"
+              rebuilt:
+                type: "test_basic"
+                inputs: ["test"]
+                buffer:
+                  type: memory
+                  max_events: 10
+              unchanged:
+                type: "test_basic"
+                inputs: ["test"]
+        "#})
+        .unwrap()
+        .build()
+        .unwrap();
+
+        let diff = ConfigDiff::new(&old_config, &new_config, HashSet::new());
+        assert_eq!(
+            diff.sink_buffer_changes(&old_config, &new_config),
+            vec![
+                ("dropped".into(), BufferAction::Dropped),
+                ("rebuilt".into(), BufferAction::Rebuilt),
+                ("reused".into(), BufferAction::Reused),
+            ]
+        );
+
+        let diff = ConfigDiff::new(&old_config, &new_config, HashSet::from(["reused".into()]));
+        assert_eq!(
+            diff.sink_buffer_changes(&old_config, &new_config)[2],
+            ("reused".into(), BufferAction::Rebuilt)
+        );
+    }
 }
@@ -67,7 +67,10 @@ where
                     healthcheck_uri: None,
                     healthcheck: Default::default(),
                     buffer: Default::default(),
+                    buffer_priority: None,
                     proxy: Default::default(),
+                    isolation: Default::default(),
+                    reroute_failed: false,
                     inner: sink,
                 },
             )
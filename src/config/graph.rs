@@ -380,6 +380,21 @@ impl Graph {
             .collect()
     }
 
+    /// Gets the components that receive events from the given root node, directly or through
+    /// other components.
+    pub fn reachable_from(&self, root: &ComponentKey) -> HashSet<ComponentKey> {
+        let mut reachable = HashSet::new();
+        let mut traversal = vec![root];
+        while let Some(n) = traversal.pop() {
+            for edge in self.edges.iter().filter(|e| &e.from.component == n) {
+                if reachable.insert(edge.to.clone()) {
+                    traversal.push(&edge.to);
+                }
+            }
+        }
+        reachable
+    }
+
     /// From a given root node, get all paths from the root node to leaf nodes
     /// where the leaf node must be a sink. This is useful for determining which
     /// components are relevant in a Vector unit test.
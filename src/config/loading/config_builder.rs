@@ -3,7 +3,9 @@ use std::{collections::HashMap, io::Read};
 use indexmap::IndexMap;
 use toml::value::Table;
 
-use super::{ComponentHint, Process, deserialize_table, loader, prepare_input, secret};
+use super::{
+    ComponentHint, Process, deserialize_table, loader, prepare_input_with_interpolated, secret,
+};
 use crate::config::{
     ComponentKey, ConfigBuilder, EnrichmentTableOuter, SinkOuter, SourceOuter, TestDefinition,
     TransformOuter,
@@ -68,11 +70,19 @@ impl Default for ConfigBuilderLoader {
 impl Process for ConfigBuilderLoader {
     /// Prepares input for a `ConfigBuilder` by interpolating environment variables.
     fn prepare<R: Read>(&mut self, input: R) -> Result<String, Vec<String>> {
-        let prepared_input = prepare_input(input, self.interpolate_env)?;
+        let prepared_input = prepare_input_with_interpolated(
+            input,
+            self.interpolate_env,
+            &mut self.builder.interpolated_values,
+        )?;
         Ok(if self.secrets.is_empty() {
             prepared_input
         } else {
-            secret::interpolate(&prepared_input, &self.secrets)?
+            let interpolated = secret::interpolate(&prepared_input, &self.secrets)?;
+            self.builder.interpolated_values.extend(
+                secret::interpolated_values(&prepared_input, &self.secrets).map(String::from),
+            );
+            interpolated
         })
     }
 
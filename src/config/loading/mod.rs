@@ -4,7 +4,7 @@ mod secret;
 mod source;
 
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     fmt::Debug,
     fs::{File, ReadDir},
     path::{Path, PathBuf},
@@ -307,8 +307,18 @@ fn load_from_inputs(
 }
 
 pub fn prepare_input<R: std::io::Read>(
+    input: R,
+    interpolate_env: bool,
+) -> Result<String, Vec<String>> {
+    prepare_input_with_interpolated(input, interpolate_env, &mut HashSet::new())
+}
+
+/// Like `prepare_input`, also recording the values of the environment variables interpolated into
+/// the input.
+pub fn prepare_input_with_interpolated<R: std::io::Read>(
     mut input: R,
     interpolate_env: bool,
+    interpolated: &mut HashSet<String>,
 ) -> Result<String, Vec<String>> {
     let mut source_string = String::new();
     input
@@ -328,6 +338,7 @@ pub fn prepare_input<R: std::io::Read>(
         {
             vars.insert("HOSTNAME".into(), hostname);
         }
+        interpolated.extend(vars::interpolated_values(&source_string, &vars).map(String::from));
         vars::interpolate(&source_string, &vars)
     } else {
         Ok(source_string)
@@ -170,13 +170,25 @@ pub fn interpolate(input: &str, secrets: &HashMap<String, String>) -> Result<Str
     }
 }
 
+/// Returns the non-empty secrets referenced by the input, which `interpolate` substitutes into it.
+pub fn interpolated_values<'a>(
+    input: &'a str,
+    secrets: &'a HashMap<String, String>,
+) -> impl Iterator<Item = &'a str> + 'a {
+    COLLECTOR
+        .captures_iter(input)
+        .filter_map(|caps| secrets.get(&format!("{}.{}", &caps[1], &caps[2])))
+        .map(String::as_str)
+        .filter(|value| !value.is_empty())
+}
+
 #[cfg(test)]
 mod tests {
     use std::collections::HashMap;
 
     use indoc::indoc;
 
-    use super::{collect_secret_keys, interpolate};
+    use super::{collect_secret_keys, interpolate, interpolated_values};
 
     #[test]
     fn replacement() {
@@ -226,6 +238,22 @@ mod tests {
         );
     }
 
+    #[test]
+    fn interpolated_secrets() {
+        let secrets: HashMap<String, String> = vec![
+            ("a.secret.key".into(), "value".into()),
+            ("a.unused".into(), "unused_value".into()),
+        ]
+        .into_iter()
+        .collect();
+
+        assert_eq!(
+            interpolated_values("xxxSECRET[a.secret.key]yyy SECRET[a.missing]", &secrets)
+                .collect::<Vec<_>>(),
+            vec!["value"]
+        );
+    }
+
     #[test]
     fn collection() {
         let mut keys = HashMap::new();
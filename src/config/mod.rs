@@ -12,6 +12,7 @@ use std::{
 use indexmap::IndexMap;
 use serde::Serialize;
 use vector_config::configurable_component;
+use vector_lib::sensitive_string::{REDACTED, SensitiveString};
 pub use vector_lib::{
     config::{
         AcknowledgementsConfig, DataType, GlobalOptions, Input, LogNamespace,
@@ -50,7 +51,7 @@ pub mod watcher;
 
 pub use builder::ConfigBuilder;
 pub use cmd::{Opts, cmd};
-pub use diff::ConfigDiff;
+pub use diff::{BufferAction, ConfigDiff, Difference};
 pub use enrichment_table::{EnrichmentTableConfig, EnrichmentTableOuter};
 pub use format::{Format, FormatHint};
 pub use loading::{
@@ -63,7 +64,8 @@ pub use secret::SecretBackend;
 pub use sink::{BoxedSink, SinkConfig, SinkContext, SinkHealthcheckOptions, SinkOuter};
 pub use source::{BoxedSource, SourceConfig, SourceContext, SourceOuter};
 pub use transform::{
-    BoxedTransform, TransformConfig, TransformContext, TransformOuter, get_transform_output_ids,
+    BoxedTransform, Retirement, TransformConfig, TransformContext, TransformOuter,
+    get_transform_output_ids,
 };
 pub use unit_test::{UnitTestResult, build_unit_tests, build_unit_tests_main};
 pub use validation::warnings;
@@ -158,6 +160,8 @@ pub struct Config {
     tests: Vec<TestDefinition>,
     secret: IndexMap<ComponentKey, SecretBackends>,
     pub graceful_shutdown_duration: Option<Duration>,
+    #[serde(skip)]
+    interpolated_values: HashSet<String>,
 }
 
 impl Config {
@@ -255,6 +259,17 @@ impl Config {
         }
     }
 
+    /// Serializes the configuration in the given format, with sensitive options redacted.
+    ///
+    /// Options holding values interpolated from environment variables or secret backends are
+    /// redacted as well, as they may hold secrets in plain strings.
+    pub fn serialize_redacted(&self, format: Format) -> Result<String, String> {
+        let mut value = SensitiveString::redacted(|| serde_json::to_value(self))
+            .map_err(|error| error.to_string())?;
+        redact_interpolated(&mut value, &self.interpolated_values, format);
+        format::serialize(&value, format)
+    }
+
     pub fn transform_keys_with_external_files(&self) -> HashSet<ComponentKey> {
         self.transforms
             .iter()
@@ -269,6 +284,59 @@ impl Config {
     }
 }
 
+/// The minimum length of an interpolated value for strings merely containing it to be redacted.
+///
+/// Shorter values, such as `1`, `true` or `info`, are common in unrelated options, so strings are
+/// only redacted when they match them exactly.
+const MIN_CONTAINED_REDACTED_LEN: usize = 8;
+
+/// Returns whether the string holds any of the interpolated values.
+fn holds_interpolated(string: &str, interpolated: &HashSet<String>) -> bool {
+    interpolated.iter().any(|interpolated| {
+        string == interpolated
+            || (interpolated.len() >= MIN_CONTAINED_REDACTED_LEN
+                && string.contains(interpolated.as_str()))
+    })
+}
+
+/// Redacts the strings and map keys of the value that hold any of the interpolated values.
+///
+/// TOML has no null values, so the unset options are also removed when serializing to TOML.
+fn redact_interpolated(
+    value: &mut serde_json::Value,
+    interpolated: &HashSet<String>,
+    format: Format,
+) {
+    match value {
+        serde_json::Value::String(string) if holds_interpolated(string, interpolated) => {
+            *string = REDACTED.to_owned();
+        }
+        serde_json::Value::Array(values) => values
+            .iter_mut()
+            .for_each(|value| redact_interpolated(value, interpolated, format)),
+        serde_json::Value::Object(fields) => {
+            if format == Format::Toml {
+                fields.retain(|_, value| !value.is_null());
+            }
+            let mut redacted = 0;
+            *fields = std::mem::take(fields)
+                .into_iter()
+                .map(|(key, mut value)| {
+                    redact_interpolated(&mut value, interpolated, format);
+                    if holds_interpolated(&key, interpolated) {
+                        // Keep redacted keys distinct, so their values aren't merged.
+                        redacted += 1;
+                        (format!("{REDACTED}{redacted}"), value)
+                    } else {
+                        (key, value)
+                    }
+                })
+                .collect();
+        }
+        _ => {}
+    }
+}
+
 /// Healthcheck options.
 #[configurable_component]
 #[derive(Clone, Copy, Debug)]
@@ -556,7 +624,7 @@ pub struct TestInput {
 
     /// The type of the input event.
     ///
-    /// Can be either `raw`, `vrl`, `log`, or `metric.
+    /// Can be either `raw`, `vrl`, `log`, `metric`, or `capture`.
     #[serde(default = "default_test_input_type", rename = "type")]
     pub type_str: String,
 
@@ -580,6 +648,12 @@ pub struct TestInput {
     ///
     /// Only relevant when `type` is `metric`.
     pub metric: Option<Metric>,
+
+    /// The path of a capture file, written by `vector tap --capture`, whose events are all used as
+    /// input events.
+    ///
+    /// Only relevant when `type` is `capture`.
+    pub path: Option<PathBuf>,
 }
 
 fn default_test_input_type() -> String {
@@ -1025,6 +1099,77 @@ mod tests {
         assert_eq!(config.tests.len(), 1);
     }
 
+    #[test]
+    fn serialize_redacted_redacts_interpolated_values() {
+        let mut config: ConfigBuilder = format::deserialize(
+            indoc! {r#"
+                [sources.in]
+                  type = "test_basic"
+
+                [transforms.foo]
+                  type = "test_basic"
+                  inputs = [ "in" ]
+                  suffix = "token=hunter2hunter2"
+                  increase = 1.25
+
+                [sinks.hunter2hunter2]
+                  type = "test_basic"
+                  inputs = ["foo"]
+            "#},
+            Format::Toml,
+        )
+        .unwrap();
+        config
+            .interpolated_values
+            .insert("hunter2hunter2".to_owned());
+        let config = config.build().unwrap();
+
+        let json = config.serialize_redacted(Format::Json).unwrap();
+        let json: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(json["transforms"]["foo"]["suffix"], "**REDACTED**");
+        assert_eq!(json["transforms"]["foo"]["type"], "test_basic");
+        assert_eq!(json["sinks"]["**REDACTED**1"]["type"], "test_basic");
+
+        let toml = config.serialize_redacted(Format::Toml).unwrap();
+        assert!(!toml.contains("hunter2"));
+    }
+
+    #[test]
+    fn serialize_redacted_keeps_options_holding_short_interpolated_values() {
+        let mut config: ConfigBuilder = format::deserialize(
+            indoc! {r#"
+                [sources.in]
+                  type = "test_basic"
+
+                [transforms.v1]
+                  type = "test_basic"
+                  inputs = [ "in" ]
+                  suffix = "-v1-true"
+
+                [sinks.out]
+                  type = "test_basic"
+                  inputs = ["v1"]
+                  data = "true"
+            "#},
+            Format::Toml,
+        )
+        .unwrap();
+        config
+            .interpolated_values
+            .extend(["1", "true"].map(String::from));
+        let config = config.build().unwrap();
+
+        let json = config.serialize_redacted(Format::Json).unwrap();
+        let json: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(json["transforms"]["v1"]["suffix"], "-v1-true");
+        assert_eq!(
+            json["transforms"]["v1"]["inputs"],
+            serde_json::json!(["in"])
+        );
+        assert_eq!(json["sinks"]["out"]["inputs"], serde_json::json!(["v1"]));
+        assert_eq!(json["sinks"]["out"]["data"], "**REDACTED**");
+    }
+
     #[test]
     fn config_append_collisions() {
         let mut config: ConfigBuilder = format::deserialize(
@@ -1101,6 +1246,73 @@ mod tests {
         assert!(source.proxy.no_proxy.matches("localhost"));
     }
 
+    #[test]
+    fn buffer_pool_reservations_must_fit() {
+        let errors = load_from_str(
+            indoc! {r#"
+                [buffer_pool]
+                  max_size = 536870912
+
+                [sources.in]
+                  type = "test_basic"
+
+                [sinks.out1]
+                  type = "test_basic"
+                  inputs = ["in"]
+                  buffer.type = "disk"
+                  buffer.max_size = 268435488
+                  buffer.pool.min_size = 268435488
+
+                [sinks.out2]
+                  type = "test_basic"
+                  inputs = ["in"]
+                  buffer.type = "disk"
+                  buffer.max_size = 268435488
+                  buffer.pool.min_size = 300000000
+            "#},
+            Format::Toml,
+        )
+        .unwrap_err();
+
+        assert_eq!(
+            errors,
+            vec![
+                "Sink out2 reserves more space in the buffer pool (pool.min_size = 300000000) than its buffer can hold (max_size = 268435488).",
+                "Disk buffers reserve more space in the buffer pool (568435488 bytes) than the pool can hold (buffer_pool.max_size = 536870912).",
+            ]
+        );
+    }
+
+    #[test]
+    fn buffer_pool_reservations_count_priority_lanes() {
+        let errors = load_from_str(
+            indoc! {r#"
+                [buffer_pool]
+                  max_size = 536870912
+
+                [sources.in]
+                  type = "test_basic"
+
+                [sinks.out]
+                  type = "test_basic"
+                  inputs = ["in"]
+                  buffer.type = "disk"
+                  buffer.max_size = 268435488
+                  buffer.pool.min_size = 268435488
+                  buffer_priority = ".audit == true"
+            "#},
+            Format::Toml,
+        )
+        .unwrap_err();
+
+        assert_eq!(
+            errors,
+            vec![
+                "Disk buffers reserve more space in the buffer pool (536870976 bytes) than the pool can hold (buffer_pool.max_size = 536870912).",
+            ]
+        );
+    }
+
     #[test]
     fn with_partial_global_proxy() {
         let config: ConfigBuilder = format::deserialize(
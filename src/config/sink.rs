@@ -13,6 +13,7 @@ use vector_lib::{
         configurable_component,
         schema::{SchemaGenerator, SchemaObject},
     },
+    fanout::Isolation,
     id::Inputs,
     sink::VectorSink,
 };
@@ -20,6 +21,7 @@ use vector_vrl_metrics::MetricsStorage;
 
 use super::{ComponentKey, ProxyConfig, Resource, dot_graph::GraphConfig, schema};
 use crate::{
+    conditions::AnyCondition,
     extra_context::ExtraContext,
     sinks::{Healthcheck, util::UriSerde},
 };
@@ -82,10 +84,41 @@ where
     #[serde(default, skip_serializing_if = "vector_lib::serde::is_default")]
     pub buffer: BufferConfig,
 
+    /// A condition that determines which events are high priority in the sink's buffer.
+    ///
+    /// When set, the buffer gets a priority lane, which is a second buffer with the same
+    /// configuration that the matching events are sent to instead. When the sink falls behind,
+    /// the events waiting in the priority lane are sent to the sink before any of the other
+    /// buffered events. Events are sent in the order they were received within each lane.
+    ///
+    /// The priority lane has its own `max_size`, and with disk buffers it reserves its own
+    /// `pool.min_size` from the buffer pool, so the buffer can take up twice the configured size.
+    #[configurable(metadata(docs::advanced))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub buffer_priority: Option<AnyCondition>,
+
     #[configurable(derived)]
     #[serde(default, skip_serializing_if = "vector_lib::serde::is_default")]
     pub proxy: ProxyConfig,
 
+    #[configurable(derived, metadata(docs::advanced))]
+    #[serde(default, skip_serializing_if = "vector_lib::serde::is_default")]
+    pub isolation: Isolation,
+
+    /// Reroutes events that the sink fails to deliver to `dead_letter` sources.
+    ///
+    /// By default, events that are rejected by the downstream service, or that fail to be sent
+    /// after all retries are exhausted, are dropped and reported back to the source as failed. In
+    /// some cases, it may be desirable to keep the events around for further analysis, debugging,
+    /// or retrying.
+    ///
+    /// In these cases, `reroute_failed` can be set to `true`, which forwards the original events to
+    /// any `dead_letter` source that subscribes to this sink. The events are annotated with
+    /// additional fields describing why their delivery failed.
+    #[serde(default, skip_serializing_if = "vector_lib::serde::is_default")]
+    #[configurable(metadata(docs::advanced, docs::human_name = "Reroute Failed Events"))]
+    pub reroute_failed: bool,
+
     #[serde(flatten)]
     #[configurable(metadata(docs::hidden))]
     pub inner: BoxedSink,
@@ -103,10 +136,13 @@ where
         SinkOuter {
             inputs: Inputs::from_iter(inputs),
             buffer: Default::default(),
+            buffer_priority: None,
             healthcheck: SinkHealthcheckOptions::default(),
             healthcheck_uri: None,
             inner: inner.into(),
             proxy: Default::default(),
+            isolation: Default::default(),
+            reroute_failed: false,
             graph: Default::default(),
         }
     }
@@ -116,7 +152,14 @@ where
         for stage in self.buffer.stages() {
             match stage {
                 BufferType::Memory { .. } => {}
-                BufferType::DiskV2 { .. } => resources.push(Resource::DiskBuffer(id.to_string())),
+                BufferType::DiskV2 { .. } => {
+                    resources.push(Resource::DiskBuffer(id.to_string()));
+                    if self.buffer_priority.is_some() {
+                        resources.push(Resource::DiskBuffer(BufferConfig::priority_lane_id(
+                            &id.to_string(),
+                        )));
+                    }
+                }
             }
         }
         resources
@@ -163,9 +206,12 @@ where
             inputs: Inputs::from_iter(inputs),
             inner: self.inner,
             buffer: self.buffer,
+            buffer_priority: self.buffer_priority,
             healthcheck: self.healthcheck,
             healthcheck_uri: self.healthcheck_uri,
             proxy: self.proxy,
+            isolation: self.isolation,
+            reroute_failed: self.reroute_failed,
             graph: self.graph,
         }
     }
@@ -18,7 +18,10 @@ use vector_lib::{
 use vector_vrl_metrics::MetricsStorage;
 
 use super::{ComponentKey, ProxyConfig, Resource, dot_graph::GraphConfig, schema};
-use crate::{SourceSender, extra_context::ExtraContext, shutdown::ShutdownSignal};
+use crate::{
+    SourceSender, extra_context::ExtraContext, shutdown::ShutdownSignal,
+    sinks::util::dead_letter::DeadLetterRegistry,
+};
 
 pub type BoxedSource = Box<dyn SourceConfig>;
 
@@ -127,6 +130,14 @@ pub trait SourceConfig: DynClone + NamedComponent + core::fmt::Debug + Send + Sy
     fn send_timeout(&self) -> Option<Duration> {
         None
     }
+
+    /// Gets the sinks whose failed events this source receives, if it receives any.
+    ///
+    /// An empty list stands for every sink that has `reroute_failed` enabled. This is used to check
+    /// that failed events can't be rerouted back into the sinks they failed in.
+    fn dead_letter_sinks(&self) -> Option<Vec<ComponentKey>> {
+        None
+    }
 }
 
 dyn_clone::clone_trait_object!(SourceConfig);
@@ -151,6 +162,9 @@ pub struct SourceContext {
     /// Extra context data provided by the running app and shared across all components. This can be
     /// used to pass shared settings or other data from outside the components.
     pub extra_context: ExtraContext,
+
+    /// Subscriptions to the events that the sinks of the topology failed to deliver.
+    pub dead_letters: DeadLetterRegistry,
 }
 
 impl SourceContext {
@@ -174,6 +188,7 @@ impl SourceContext {
                 schema_definitions: HashMap::default(),
                 schema: Default::default(),
                 extra_context: Default::default(),
+                dead_letters: Default::default(),
             },
             shutdown,
         )
@@ -196,6 +211,7 @@ impl SourceContext {
             schema_definitions: schema_definitions.unwrap_or_default(),
             schema: Default::default(),
             extra_context: Default::default(),
+            dead_letters: Default::default(),
         }
     }
 
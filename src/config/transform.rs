@@ -2,6 +2,10 @@ use std::{
     cell::RefCell,
     collections::{HashMap, HashSet},
     path::PathBuf,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
 };
 
 use async_trait::async_trait;
@@ -162,6 +166,9 @@ pub struct TransformContext {
     /// (e.g. `aws_ec2_metadata`, `throttle`) clone this and pass it to [`crate::cpu_time::spawn_timed`] so
     /// their CPU is attributed to the component alongside the main transform task.
     pub cpu_ns: Option<Counter>,
+
+    /// Signals the transform that a reload is retiring it, rather than the whole topology stopping.
+    pub retirement: Retirement,
 }
 
 impl Default for TransformContext {
@@ -176,10 +183,30 @@ impl Default for TransformContext {
             schema: SchemaOptions::default(),
             extra_context: Default::default(),
             cpu_ns: None,
+            retirement: Default::default(),
         }
     }
 }
 
+/// Signals a transform that a reload is retiring it, because it's removed or replaced by a new
+/// instance, rather than the whole topology stopping.
+///
+/// Cloning the signal is cheap, and all clones refer to the same signal.
+#[derive(Clone, Debug, Default)]
+pub struct Retirement(Arc<AtomicBool>);
+
+impl Retirement {
+    /// Marks the transform as retired.
+    pub fn retire(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    /// Whether the transform has been retired.
+    pub fn is_retired(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
 impl TransformContext {
     // clippy allow avoids an issue where vrl is flagged off and `globals` is
     // the sole field in the struct
@@ -254,6 +281,19 @@ pub trait TransformConfig: DynClone + NamedComponent + core::fmt::Debug + Send +
         Ok(())
     }
 
+    /// Validates the configuration of the transform against its inputs.
+    ///
+    /// This is called with the inputs of the transform after any wildcards have been expanded, for
+    /// transforms whose configuration refers to some of their inputs.
+    ///
+    /// # Errors
+    ///
+    /// If validation does not succeed, an error variant containing a list of all validation errors
+    /// is returned.
+    fn validate_inputs(&self, _inputs: &[String]) -> Result<(), Vec<String>> {
+        Ok(())
+    }
+
     /// Whether or not concurrency should be enabled for this transform.
     ///
     /// When enabled, this transform may be run in parallel in order to attempt to maximize
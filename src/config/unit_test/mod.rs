@@ -13,16 +13,23 @@ mod unit_test_components;
 
 use std::{
     collections::{BTreeMap, HashMap, HashSet},
+    path::Path,
     sync::Arc,
 };
 
+use bytes::BytesMut;
 use futures_util::{StreamExt, stream::FuturesUnordered};
 use indexmap::IndexMap;
 use tokio::sync::{
     Mutex,
     oneshot::{self, Receiver},
 };
+use tokio_util::codec::Decoder as _;
 use uuid::Uuid;
+use vector_lib::{
+    codecs::{LengthDelimitedDecoder, NativeDeserializer, decoding::format::Deserializer as _},
+    config::LogNamespace,
+};
 use vrl::{
     compiler::{Context, TargetValue, TimeZone, state::RuntimeState},
     diagnostic::Formatter,
@@ -544,14 +551,12 @@ fn build_and_validate_inputs(
 
     for (index, input) in test_inputs.iter().enumerate() {
         if available_insert_targets.contains(&input.insert_at) {
-            match build_input_event(input) {
-                Ok(input_event) => {
+            match build_input_events(input) {
+                Ok(input_events) => {
                     inputs
                         .entry(input.insert_at.clone())
-                        .and_modify(|events: &mut Vec<Event>| {
-                            events.push(input_event.clone());
-                        })
-                        .or_insert_with(|| vec![input_event]);
+                        .or_insert_with(Vec::new)
+                        .extend(input_events);
                 }
                 Err(error) => errors.push(error),
             }
@@ -649,6 +654,36 @@ fn build_outputs(
     }
 }
 
+fn build_input_events(input: &TestInput) -> Result<Vec<Event>, String> {
+    match input.type_str.as_ref() {
+        "capture" => match &input.path {
+            Some(path) => read_capture_file(path),
+            None => Err("input type 'capture' requires the field 'path'".to_string()),
+        },
+        _ => build_input_event(input).map(|event| vec![event]),
+    }
+}
+
+/// Reads the events of a capture file written by `vector tap --capture`.
+fn read_capture_file(path: &Path) -> Result<Vec<Event>, String> {
+    let mut bytes = std::fs::read(path)
+        .map(|bytes| BytesMut::from(bytes.as_slice()))
+        .map_err(|error| format!("failed to read capture file {path:?}: {error}"))?;
+
+    let mut framer = LengthDelimitedDecoder::default();
+    let mut events = Vec::new();
+    while let Some(frame) = framer
+        .decode_eof(&mut bytes)
+        .map_err(|error| format!("invalid capture file {path:?}: {error}"))?
+    {
+        let frame_events = NativeDeserializer
+            .parse(frame, LogNamespace::Legacy)
+            .map_err(|error| format!("invalid event in capture file {path:?}: {error}"))?;
+        events.extend(frame_events);
+    }
+    Ok(events)
+}
+
 fn build_input_event(input: &TestInput) -> Result<Event, String> {
     match input.type_str.as_ref() {
         "raw" => match input.value.as_ref() {
@@ -705,7 +740,7 @@ fn build_input_event(input: &TestInput) -> Result<Event, String> {
             }
         }
         _ => Err(format!(
-            "unrecognized input type '{}', expected one of: 'raw', 'log' or 'metric'",
+            "unrecognized input type '{}', expected one of: 'raw', 'log', 'metric' or 'capture'",
             input.type_str
         )),
     }
@@ -353,7 +353,7 @@ async fn parse_bad_input_event() {
         vec![
             indoc! {r"
             Failed to build test 'broken test':
-              unrecognized input type 'nah', expected one of: 'raw', 'log' or 'metric'"}
+              unrecognized input type 'nah', expected one of: 'raw', 'log', 'metric' or 'capture'"}
             .to_owned(),
         ]
     );
@@ -886,6 +886,65 @@ async fn test_metric_input() {
     assert!(tests.remove(0).run().await.errors.is_empty());
 }
 
+#[tokio::test]
+async fn test_capture_input() {
+    use bytes::BytesMut;
+    use tokio_util::codec::Encoder;
+    use vector_lib::{
+        codecs::{LengthDelimitedEncoder, NativeSerializer},
+        tap::capture,
+    };
+
+    crate::test_util::trace_init();
+
+    let mut buffer = BytesMut::new();
+    for message in ["first", "second"] {
+        let mut event = Event::from(LogEvent::from(message));
+        capture::annotate(&mut event, "demo", chrono::Utc::now());
+        let mut frame = BytesMut::new();
+        NativeSerializer.encode(event, &mut frame).unwrap();
+        LengthDelimitedEncoder::default()
+            .encode((), &mut frame)
+            .unwrap();
+        buffer.extend_from_slice(&frame);
+    }
+    let path = crate::test_util::temp_file();
+    std::fs::write(&path, buffer).unwrap();
+
+    let config: ConfigBuilder = toml::from_str(&indoc::formatdoc! { r#"
+          [transforms.foo]
+            inputs = ["ignored"]
+            type = "remap"
+            source = '''
+            .new_field = "string value"
+            '''
+
+          [[tests]]
+            name = "successful test with captured events"
+
+            [tests.input]
+              insert_at = "foo"
+              type = "capture"
+              path = "{}"
+
+            [[tests.outputs]]
+              extract_from = "foo"
+              expected_event_count = 2
+              [[tests.outputs.conditions]]
+                type = "vrl"
+                source = """
+                    assert_eq!(.new_field, "string value")
+                    assert_eq!(%vector.tap.component_id, "demo")
+                """
+      "#,
+        path.display()
+    })
+    .unwrap();
+
+    let mut tests = build_unit_tests(config).await.unwrap();
+    assert!(tests.remove(0).run().await.errors.is_empty());
+}
+
 #[tokio::test]
 async fn test_success_over_gap() {
     crate::test_util::trace_init();
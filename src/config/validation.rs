@@ -3,11 +3,14 @@ use std::{collections::HashMap, path::PathBuf};
 use futures_util::{FutureExt, StreamExt, TryFutureExt, TryStreamExt, stream};
 use heim::{disk::Partition, units::information::byte};
 use indexmap::IndexMap;
-use vector_lib::{buffers::config::DiskUsage, internal_event::DEFAULT_OUTPUT};
+use vector_lib::{
+    buffers::{BufferConfig, BufferType, config::DiskUsage},
+    internal_event::DEFAULT_OUTPUT,
+};
 
 use super::{
-    ComponentKey, Config, OutputId, Resource, builder::ConfigBuilder,
-    transform::get_transform_output_ids,
+    ComponentKey, Config, OutputId, Resource, SinkOuter, SourceOuter, builder::ConfigBuilder,
+    graph::Graph, transform::get_transform_output_ids,
 };
 use crate::config::schema;
 
@@ -216,6 +219,97 @@ pub fn check_values(config: &ConfigBuilder) -> Result<(), Vec<String>> {
     }
 }
 
+/// Checks that the space reserved in the disk buffer pool fits within the pool, and within the
+/// buffers it is reserved for.
+///
+/// The priority lane of a sink's buffer has the same configuration as the buffer, so it reserves
+/// the same space in the pool again.
+pub fn check_buffer_pool(config: &ConfigBuilder) -> Result<(), Vec<String>> {
+    let Some(buffer_pool) = config.global.buffer_pool.as_ref() else {
+        return Ok(());
+    };
+
+    let mut errors = Vec::new();
+    let mut total_min_size = 0u64;
+    for (key, sink) in config.sinks.iter() {
+        let lanes = if sink.buffer_priority.is_some() { 2 } else { 1 };
+        for stage in sink.buffer.stages() {
+            if let BufferType::DiskV2 {
+                max_size,
+                pool: Some(quota),
+                ..
+            } = stage
+            {
+                if quota.min_size > max_size.get() {
+                    errors.push(format!(
+                        "Sink {key} reserves more space in the buffer pool (pool.min_size = {}) than its buffer can hold (max_size = {max_size}).",
+                        quota.min_size
+                    ));
+                }
+                total_min_size =
+                    total_min_size.saturating_add(quota.min_size.saturating_mul(lanes));
+            }
+        }
+    }
+
+    if total_min_size > buffer_pool.max_size.get() {
+        errors.push(format!(
+            "Disk buffers reserve more space in the buffer pool ({total_min_size} bytes) than the pool can hold (buffer_pool.max_size = {}).",
+            buffer_pool.max_size
+        ));
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+/// Checks that the sinks that sources receive the failed events of exist and reroute their failed
+/// events, and that those events can't be sent back into the sinks they failed in.
+pub fn check_dead_letters(
+    sources: &IndexMap<ComponentKey, SourceOuter>,
+    sinks: &IndexMap<ComponentKey, SinkOuter<String>>,
+    graph: &Graph,
+) -> Result<(), Vec<String>> {
+    let mut errors = Vec::new();
+    for (key, source) in sources {
+        let Some(subscribed) = source.inner.dead_letter_sinks() else {
+            continue;
+        };
+
+        for sink_key in &subscribed {
+            match sinks.get(sink_key) {
+                None => errors.push(format!(
+                    "Source \"{key}\" receives the failed events of sink \"{sink_key}\", which doesn't exist."
+                )),
+                Some(sink) if !sink.reroute_failed => errors.push(format!(
+                    "Source \"{key}\" receives the failed events of sink \"{sink_key}\", which doesn't have `reroute_failed` enabled."
+                )),
+                Some(_) => {}
+            }
+        }
+
+        let reachable = graph.reachable_from(key);
+        for (sink_key, _) in sinks.iter().filter(|(sink_key, sink)| {
+            sink.reroute_failed
+                && (subscribed.is_empty() || subscribed.contains(*sink_key))
+                && reachable.contains(*sink_key)
+        }) {
+            errors.push(format!(
+                "Source \"{key}\" receives the failed events of sink \"{sink_key}\" and sends events to it, so events that keep failing would be rerouted forever."
+            ));
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
 /// To avoid collisions between `output` metric tags, check that a component
 /// does not have a named output with the name [`DEFAULT_OUTPUT`]
 pub fn check_outputs(config: &ConfigBuilder) -> Result<(), Vec<String>> {
@@ -241,6 +335,10 @@ pub fn check_outputs(config: &ConfigBuilder) -> Result<(), Vec<String>> {
             errors.extend(errs.into_iter().map(|msg| format!("Transform {key} {msg}")));
         }
 
+        if let Err(errs) = transform.inner.validate_inputs(&transform.inputs) {
+            errors.extend(errs.into_iter().map(|msg| format!("Transform {key} {msg}")));
+        }
+
         if get_transform_output_ids(
             transform.inner.as_ref(),
             key.clone(),
@@ -273,15 +371,27 @@ pub async fn check_buffer_preconditions(config: &Config) -> Result<(), Vec<Strin
     // Notably, this does *not* cover other data usage by Vector on the same mountpoint because we
     // don't always know the upper bound of that usage i.e. file checkpoint state.
 
-    // Grab all configured disk buffers, and if none are present, simply return early.
+    // Grab all configured disk buffers, and if none are present, simply return early. The priority
+    // lane of a buffer is a second buffer with the same configuration, so it can take up the same
+    // space again.
     let global_data_dir = config.global.data_dir.clone();
     let configured_disk_buffers = config
         .sinks()
         .flat_map(|(id, sink)| {
-            sink.buffer
-                .stages()
-                .iter()
-                .filter_map(|stage| stage.disk_usage(global_data_dir.clone(), id))
+            let lane_id = sink
+                .buffer_priority
+                .is_some()
+                .then(|| ComponentKey::from(BufferConfig::priority_lane_id(id.id())));
+            let global_data_dir = global_data_dir.clone();
+            sink.buffer.stages().iter().flat_map(move |stage| {
+                let lane = lane_id
+                    .as_ref()
+                    .and_then(|lane_id| stage.disk_usage(global_data_dir.clone(), lane_id));
+                stage
+                    .disk_usage(global_data_dir.clone(), id)
+                    .into_iter()
+                    .chain(lane)
+            })
         })
         .collect::<Vec<_>>();
 
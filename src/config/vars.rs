@@ -84,9 +84,23 @@ pub fn interpolate(input: &str, vars: &HashMap<String, String>) -> Result<String
     }
 }
 
+/// Returns the non-empty values of the variables referenced by the input, which `interpolate`
+/// substitutes into it.
+pub fn interpolated_values<'a>(
+    input: &'a str,
+    vars: &'a HashMap<String, String>,
+) -> impl Iterator<Item = &'a str> + 'a {
+    ENVIRONMENT_VARIABLE_INTERPOLATION_REGEX
+        .captures_iter(input)
+        .filter_map(|caps| caps.get(1).or_else(|| caps.get(2)))
+        .filter_map(|name| vars.get(name.as_str()))
+        .map(String::as_str)
+        .filter(|value| !value.is_empty())
+}
+
 #[cfg(test)]
 mod test {
-    use super::interpolate;
+    use super::{interpolate, interpolated_values};
     #[test]
     fn interpolation() {
         let vars = vec![
@@ -130,6 +144,21 @@ mod test {
         assert!(interpolate("${EMPTY:?error cats}", &vars).is_err());
     }
 
+    #[test]
+    fn interpolated_values_of_input() {
+        let vars = vec![
+            ("FOO".into(), "dogs".into()),
+            ("EMPTY".into(), "".into()),
+            ("UNUSED".into(), "cats".into()),
+        ]
+        .into_iter()
+        .collect();
+
+        let values = interpolated_values("$FOO ${EMPTY} ${NOT:-x} $$UNUSED", &vars);
+
+        assert_eq!(values.collect::<Vec<_>>(), vec!["dogs"]);
+    }
+
     #[test]
     fn test_multiline_expansion_prevented() {
         let vars = vec![
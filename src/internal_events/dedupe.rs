@@ -1,6 +1,10 @@
+use std::path::PathBuf;
+
 use vector_lib::{
-    NamedInternalEvent,
-    internal_event::{ComponentEventsDropped, INTENTIONAL, InternalEvent},
+    NamedInternalEvent, counter,
+    internal_event::{
+        ComponentEventsDropped, CounterName, INTENTIONAL, InternalEvent, error_stage, error_type,
+    },
 };
 
 #[derive(Debug, NamedInternalEvent)]
@@ -16,3 +20,51 @@ impl InternalEvent for DedupeEventsDropped {
         });
     }
 }
+
+#[derive(Debug, NamedInternalEvent)]
+pub struct DedupeCacheRestoreError {
+    pub error: std::io::Error,
+    pub path: PathBuf,
+}
+
+impl InternalEvent for DedupeCacheRestoreError {
+    fn emit(self) {
+        error!(
+            message = "Unable to restore the persisted deduplication cache, starting with an empty cache.",
+            path = ?self.path,
+            error = %self.error,
+            error_type = error_type::IO_FAILED,
+            stage = error_stage::PROCESSING,
+        );
+        counter!(
+            CounterName::ComponentErrorsTotal,
+            "stage" => error_stage::PROCESSING,
+            "error_type" => error_type::IO_FAILED,
+        )
+        .increment(1);
+    }
+}
+
+#[derive(Debug, NamedInternalEvent)]
+pub struct DedupeCachePersistError {
+    pub error: std::io::Error,
+    pub path: PathBuf,
+}
+
+impl InternalEvent for DedupeCachePersistError {
+    fn emit(self) {
+        error!(
+            message = "Unable to persist the deduplication cache.",
+            path = ?self.path,
+            error = %self.error,
+            error_type = error_type::IO_FAILED,
+            stage = error_stage::PROCESSING,
+        );
+        counter!(
+            CounterName::ComponentErrorsTotal,
+            "stage" => error_stage::PROCESSING,
+            "error_type" => error_type::IO_FAILED,
+        )
+        .increment(1);
+    }
+}
@@ -114,8 +114,9 @@ mod source {
     use vector_lib::{
         NamedInternalEvent, counter, emit,
         file_source_common::internal_events::FileSourceInternalEvents,
+        gauge,
         internal_event::{
-            ComponentEventsDropped, CounterName, INTENTIONAL, error_stage, error_type,
+            ComponentEventsDropped, CounterName, GaugeName, INTENTIONAL, error_stage, error_type,
         },
         json_size::JsonSize,
     };
@@ -497,6 +498,32 @@ mod source {
         }
     }
 
+    #[derive(Debug, NamedInternalEvent)]
+    pub struct FileInotifyWatches {
+        pub count: usize,
+    }
+
+    impl InternalEvent for FileInotifyWatches {
+        fn emit(self) {
+            gauge!(GaugeName::InotifyWatches).set(self.count as f64);
+        }
+    }
+
+    #[derive(Debug, NamedInternalEvent)]
+    pub struct FileInotifyFallback {
+        pub error: Error,
+    }
+
+    impl InternalEvent for FileInotifyFallback {
+        fn emit(self) {
+            warn!(
+                message = "Unable to use inotify, falling back to polling for file changes.",
+                error = %self.error,
+            );
+            counter!(CounterName::InotifyFallbacksTotal).increment(1);
+        }
+    }
+
     #[derive(Debug, NamedInternalEvent)]
     pub struct FileLineTooBigError<'a> {
         pub truncated_bytes: &'a BytesMut,
@@ -611,6 +638,14 @@ mod source {
             emit!(PathGlobbingError { path, error });
         }
 
+        fn emit_inotify_watches(&self, count: usize) {
+            emit!(FileInotifyWatches { count });
+        }
+
+        fn emit_inotify_fallback(&self, error: Error) {
+            emit!(FileInotifyFallback { error });
+        }
+
         fn emit_file_line_too_long(
             &self,
             truncated_bytes: &bytes::BytesMut,
@@ -0,0 +1,18 @@
+use vector_lib::{
+    NamedInternalEvent,
+    internal_event::{ComponentEventsDropped, INTENTIONAL, InternalEvent},
+};
+
+#[derive(Debug, NamedInternalEvent)]
+pub struct JoinEventsDropped {
+    pub count: usize,
+}
+
+impl InternalEvent for JoinEventsDropped {
+    fn emit(self) {
+        emit!(ComponentEventsDropped::<INTENTIONAL> {
+            count: self.count,
+            reason: "Unmatched events were evicted to stay within the maximum number of pending events.",
+        });
+    }
+}
@@ -0,0 +1,54 @@
+use std::path::PathBuf;
+
+use vector_lib::{
+    NamedInternalEvent, counter,
+    internal_event::{CounterName, InternalEvent, error_stage, error_type},
+};
+
+#[derive(Debug, NamedInternalEvent)]
+pub struct TransformStateRestoreError {
+    pub error: std::io::Error,
+    pub path: PathBuf,
+}
+
+impl InternalEvent for TransformStateRestoreError {
+    fn emit(self) {
+        error!(
+            message = "Unable to restore the transform state snapshot, leaving out the state that can't be read.",
+            path = ?self.path,
+            error = %self.error,
+            error_type = error_type::IO_FAILED,
+            stage = error_stage::PROCESSING,
+        );
+        counter!(
+            CounterName::ComponentErrorsTotal,
+            "stage" => error_stage::PROCESSING,
+            "error_type" => error_type::IO_FAILED,
+        )
+        .increment(1);
+    }
+}
+
+#[derive(Debug, NamedInternalEvent)]
+pub struct TransformStatePersistError {
+    pub error: std::io::Error,
+    pub path: PathBuf,
+}
+
+impl InternalEvent for TransformStatePersistError {
+    fn emit(self) {
+        error!(
+            message = "Unable to write the transform state snapshot.",
+            path = ?self.path,
+            error = %self.error,
+            error_type = error_type::IO_FAILED,
+            stage = error_stage::PROCESSING,
+        );
+        counter!(
+            CounterName::ComponentErrorsTotal,
+            "stage" => error_stage::PROCESSING,
+            "error_type" => error_type::IO_FAILED,
+        )
+        .increment(1);
+    }
+}
@@ -0,0 +1,79 @@
+use vector_lib::{
+    NamedInternalEvent, counter, gauge,
+    internal_event::{ComponentEventsDropped, CounterName, GaugeName, INTENTIONAL, InternalEvent},
+};
+
+#[derive(Debug, NamedInternalEvent)]
+pub struct TailSampleTraceSampled {
+    /// The policy the trace was kept by, or `None` if it was dropped.
+    pub policy: Option<&'static str>,
+    pub spans: usize,
+}
+
+impl InternalEvent for TailSampleTraceSampled {
+    fn emit(self) {
+        match self.policy {
+            Some(policy) => {
+                counter!(
+                    CounterName::TracesSampledTotal,
+                    "decision" => "kept",
+                    "policy" => policy,
+                )
+                .increment(1);
+            }
+            None => {
+                counter!(
+                    CounterName::TracesSampledTotal,
+                    "decision" => "dropped",
+                    "policy" => "none",
+                )
+                .increment(1);
+                emit!(TailSampleSpansDiscarded { count: self.spans });
+            }
+        }
+    }
+}
+
+#[derive(Debug, NamedInternalEvent)]
+pub struct TailSampleSpansDiscarded {
+    pub count: usize,
+}
+
+impl InternalEvent for TailSampleSpansDiscarded {
+    fn emit(self) {
+        emit!(ComponentEventsDropped::<INTENTIONAL> {
+            count: self.count,
+            reason: "Trace not sampled."
+        })
+    }
+}
+
+#[derive(Debug, NamedInternalEvent)]
+pub struct TailSampleTraceEvicted {
+    pub max_traces: usize,
+    pub max_spans: usize,
+}
+
+impl InternalEvent for TailSampleTraceEvicted {
+    fn emit(self) {
+        debug!(
+            message = "Sampling trace before it completed to stay within memory limits.",
+            max_traces = self.max_traces,
+            max_spans = self.max_spans,
+        );
+        counter!(CounterName::TracesEvictedTotal).increment(1);
+    }
+}
+
+#[derive(Debug, NamedInternalEvent)]
+pub struct TailSampleBuffered {
+    pub traces: usize,
+    pub spans: usize,
+}
+
+impl InternalEvent for TailSampleBuffered {
+    fn emit(self) {
+        gauge!(GaugeName::BufferedTraces).set(self.traces as f64);
+        gauge!(GaugeName::BufferedSpans).set(self.spans as f64);
+    }
+}
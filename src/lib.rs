@@ -78,6 +78,9 @@ pub mod app;
 pub mod async_read;
 #[cfg(feature = "aws-config")]
 pub mod aws;
+pub(crate) mod buffer;
+#[cfg(feature = "sources-file")]
+pub(crate) mod checkpoints;
 pub mod common;
 pub mod completion;
 mod convert_config;
@@ -25,7 +25,7 @@ use similar_asserts::assert_eq;
 use tempfile::TempDir;
 use tokio_stream::StreamExt;
 use vector_lib::{
-    buffers::{BufferConfig, BufferType, WhenFull},
+    buffers::{BufferConfig, BufferType, DiskBufferCompression, WhenFull},
     codecs::{TextSerializerConfig, encoding::FramingConfig},
     config::{ComponentKey, proxy::ProxyConfig},
     event::{BatchNotifier, BatchStatus, BatchStatusReceiver, Event, EventArray, LogEvent},
@@ -590,6 +590,10 @@ async fn s3_disk_buffer_reload_delivers_all_events() {
     old_config.sinks[&sink_key].buffer = BufferConfig::Single(BufferType::DiskV2 {
         max_size: NonZeroU64::new(268435488).unwrap(),
         when_full: WhenFull::Block,
+        compression: DiskBufferCompression::None,
+        encryption: None,
+        max_age_secs: None,
+        pool: None,
     });
 
     // Clone config before building so we can create the reload config.
@@ -597,6 +601,10 @@ async fn s3_disk_buffer_reload_delivers_all_events() {
     new_config.sinks[&sink_key].buffer = BufferConfig::Single(BufferType::DiskV2 {
         max_size: NonZeroU64::new(536870912).unwrap(),
         when_full: WhenFull::Block,
+        compression: DiskBufferCompression::None,
+        encryption: None,
+        max_age_secs: None,
+        pool: None,
     });
 
     // 1. Start topology with initial disk buffer config.
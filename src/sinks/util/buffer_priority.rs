@@ -0,0 +1,101 @@
+//! Routing of high-priority events to the priority lane of a sink's buffer.
+//!
+//! When a sink has `buffer_priority` configured, the topology builds its buffer with a priority
+//! lane, and every event array sent to the buffer is split by a [`BufferPriorityCondition`]. Events
+//! matching the condition go to the lane, and are handed to the sink ahead of the events that were
+//! already waiting in the buffer.
+
+use vector_lib::buffers::BufferPriority;
+
+use crate::{
+    conditions::Condition,
+    event::{Event, EventArray},
+};
+
+/// Splits event arrays into the events matching a condition and the rest.
+#[derive(Clone, Debug)]
+pub struct BufferPriorityCondition {
+    condition: Condition,
+}
+
+impl BufferPriorityCondition {
+    pub const fn new(condition: Condition) -> Self {
+        Self { condition }
+    }
+
+    fn partition<E>(
+        &self,
+        events: Vec<E>,
+        from_event: fn(Event) -> E,
+        into_array: fn(Vec<E>) -> EventArray,
+    ) -> (Option<EventArray>, Option<EventArray>)
+    where
+        E: Into<Event>,
+    {
+        let mut high = Vec::new();
+        let mut rest = Vec::new();
+        for event in events {
+            let (matched, event) = self.condition.check(event.into());
+            if matched {
+                high.push(from_event(event));
+            } else {
+                rest.push(from_event(event));
+            }
+        }
+
+        let non_empty = |events: Vec<E>| (!events.is_empty()).then(|| into_array(events));
+        (non_empty(high), non_empty(rest))
+    }
+}
+
+impl BufferPriority<EventArray> for BufferPriorityCondition {
+    fn split(&self, events: EventArray) -> (Option<EventArray>, Option<EventArray>) {
+        match events {
+            EventArray::Logs(logs) => self.partition(logs, Event::into_log, EventArray::Logs),
+            EventArray::Metrics(metrics) => {
+                self.partition(metrics, Event::into_metric, EventArray::Metrics)
+            }
+            EventArray::Traces(traces) => {
+                self.partition(traces, Event::into_trace, EventArray::Traces)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{conditions::AnyCondition, event::LogEvent};
+
+    #[test]
+    fn splits_matching_events_in_order() {
+        let condition = AnyCondition::String(r#".level == "error""#.to_owned())
+            .build(&Default::default(), &Default::default())
+            .unwrap();
+        let priority = BufferPriorityCondition::new(condition);
+
+        let logs = [("a", "info"), ("b", "error"), ("c", "info"), ("d", "error")]
+            .into_iter()
+            .map(|(message, level)| {
+                let mut log = LogEvent::from(message);
+                log.insert("level", level);
+                log
+            })
+            .collect::<Vec<_>>();
+
+        let messages = |events: Option<EventArray>| {
+            events
+                .unwrap()
+                .iter_events()
+                .map(|event| event.as_log()["message"].to_string_lossy().into_owned())
+                .collect::<Vec<_>>()
+        };
+        let (high, rest) = priority.split(EventArray::Logs(logs));
+        assert_eq!(messages(high), ["b", "d"]);
+        assert_eq!(messages(rest), ["a", "c"]);
+
+        let (high, rest) = priority.split(EventArray::from(LogEvent::from("e")));
+        assert!(high.is_none());
+        assert_eq!(messages(rest), ["e"]);
+    }
+}
@@ -0,0 +1,539 @@
+//! Rerouting of the events that sinks fail to deliver to `dead_letter` sources.
+//!
+//! When a sink has `reroute_failed` enabled, the topology passes every event array through a
+//! [`DeadLetterRouter`] before handing it to the sink. The router keeps a copy of the events, along
+//! with their original finalizers, until the sink reports the outcome of the delivery of each
+//! event. Log and trace events share their fields with the copy until the sink modifies them.
+//! Events that were delivered are acknowledged as usual, while events that failed are annotated
+//! with the reason of the failure and published to the [`DeadLetterSubscription`]s of the sink.
+//! The number of event arrays a sink can have in flight is bounded, so that the copies don't grow
+//! without limit when the sink falls behind.
+//!
+//! Subscriptions are registered in the [`DeadLetterRegistry`] of the topology, which is shared by
+//! the sinks and sources of the topology across reloads.
+
+use std::{
+    iter,
+    sync::{Arc, Mutex},
+};
+
+use futures::{StreamExt, future, stream::FuturesUnordered};
+use tokio::sync::mpsc;
+use vector_lib::{
+    config::LogNamespace,
+    finalization::AddBatchNotifier,
+    lookup::{PathPrefix, metadata_path},
+};
+use vrl::path;
+
+use crate::{
+    config::{ComponentKey, log_schema},
+    event::{
+        BatchNotifier, BatchStatus, BatchStatusReceiver, DeliveryFailure, Event, EventArray,
+        EventContainer, EventFinalizer, EventMutRef, EventStatus, Finalizable,
+        array::events_into_arrays,
+    },
+};
+
+/// The number of event arrays that can be queued for each subscription before sinks wait for the
+/// subscriber to catch up.
+const SUBSCRIPTION_CAPACITY: usize = 100;
+
+struct Subscriber {
+    id: usize,
+    sinks: Vec<ComponentKey>,
+    tx: mpsc::Sender<EventArray>,
+}
+
+impl Subscriber {
+    fn wants(&self, sink: &ComponentKey) -> bool {
+        self.sinks.is_empty() || self.sinks.contains(sink)
+    }
+}
+
+#[derive(Default)]
+struct Subscribers {
+    next_id: usize,
+    subscribers: Vec<Subscriber>,
+}
+
+/// The subscriptions to the failed events of the sinks of a topology.
+#[derive(Clone, Default)]
+pub struct DeadLetterRegistry(Arc<Mutex<Subscribers>>);
+
+impl std::fmt::Debug for DeadLetterRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DeadLetterRegistry").finish_non_exhaustive()
+    }
+}
+
+impl DeadLetterRegistry {
+    /// Subscribes to the failed events of the given sinks, or of all sinks if `sinks` is empty.
+    pub fn subscribe(&self, sinks: Vec<ComponentKey>) -> DeadLetterSubscription {
+        let (tx, rx) = mpsc::channel(SUBSCRIPTION_CAPACITY);
+        let mut inner = self.0.lock().expect("poisoned lock");
+        let id = inner.next_id;
+        inner.next_id += 1;
+        inner.subscribers.push(Subscriber { id, sinks, tx });
+        DeadLetterSubscription {
+            id,
+            rx,
+            registry: self.clone(),
+        }
+    }
+
+    fn subscribers_of(&self, sink: &ComponentKey) -> Vec<mpsc::Sender<EventArray>> {
+        self.0
+            .lock()
+            .expect("poisoned lock")
+            .subscribers
+            .iter()
+            .filter(|subscriber| subscriber.wants(sink))
+            .map(|subscriber| subscriber.tx.clone())
+            .collect()
+    }
+}
+
+/// A subscription to the events that sinks failed to deliver.
+pub struct DeadLetterSubscription {
+    id: usize,
+    rx: mpsc::Receiver<EventArray>,
+    registry: DeadLetterRegistry,
+}
+
+impl DeadLetterSubscription {
+    /// Receives the next array of failed events.
+    ///
+    /// Returns `None` once no more events can be received.
+    pub async fn recv(&mut self) -> Option<EventArray> {
+        self.rx.recv().await
+    }
+}
+
+impl Drop for DeadLetterSubscription {
+    fn drop(&mut self) {
+        self.registry
+            .0
+            .lock()
+            .expect("poisoned lock")
+            .subscribers
+            .retain(|subscriber| subscriber.id != self.id);
+
+        // The events that were queued but never received are reported back to their sources as
+        // failed, so that they aren't mistaken for delivered.
+        self.rx.close();
+        while let Ok(mut events) = self.rx.try_recv() {
+            events.take_finalizers().update_status(EventStatus::Errored);
+        }
+    }
+}
+
+/// The number of event arrays whose delivery can be tracked at once for each sink. Once reached,
+/// the sink waits for earlier events to be settled before taking more events out of its buffer.
+const TRACKING_CAPACITY: usize = 100;
+
+/// An event array sent to a sink, along with the receivers of the delivery status of its events.
+type Tracked = (EventArray, Vec<BatchStatusReceiver>);
+
+/// Tracks the delivery of the events sent to a sink, and reroutes the ones that failed.
+///
+/// The outcomes of the deliveries are awaited by a single task, which runs until the router is
+/// dropped and every tracked event has been settled. The delivery of each event is tracked on its
+/// own, since sinks can split an event array across requests that fail or succeed separately.
+#[derive(Clone, Debug)]
+pub struct DeadLetterRouter {
+    tx: mpsc::Sender<Tracked>,
+}
+
+impl DeadLetterRouter {
+    pub fn new(
+        component_key: ComponentKey,
+        component_type: &'static str,
+        registry: DeadLetterRegistry,
+    ) -> Self {
+        let (tx, rx) = mpsc::channel(TRACKING_CAPACITY);
+        let settler = Settler {
+            component_key,
+            component_type,
+            registry,
+        };
+        tokio::spawn(settler.run(rx));
+        Self { tx }
+    }
+
+    /// Prepares `events` to be sent to the sink.
+    ///
+    /// The original finalizers of the events are swapped for a batch notifier per event owned by
+    /// the router, which settles them once the sink is done with the events. This waits while too
+    /// many events are waiting to be settled, so that the copies kept by the router stay bounded
+    /// and the events wait in the buffer of the sink instead.
+    pub async fn track(&self, mut events: EventArray) -> EventArray {
+        let retained = events.clone();
+        drop(events.take_finalizers());
+
+        let receivers = events
+            .iter_events_mut()
+            .map(|mut event| {
+                let (batch, receiver) = BatchNotifier::new_with_receiver();
+                event
+                    .metadata_mut()
+                    .add_finalizer(EventFinalizer::new(batch));
+                receiver
+            })
+            .collect();
+
+        // The settler runs for as long as the router exists, so this can't fail.
+        _ = self.tx.send((retained, receivers)).await;
+
+        events
+    }
+}
+
+/// Settles the events tracked by a [`DeadLetterRouter`] once their delivery is done.
+struct Settler {
+    component_key: ComponentKey,
+    component_type: &'static str,
+    registry: DeadLetterRegistry,
+}
+
+impl Settler {
+    async fn run(self, mut rx: mpsc::Receiver<Tracked>) {
+        let mut pending = FuturesUnordered::new();
+        let mut open = true;
+        while open || !pending.is_empty() {
+            tokio::select! {
+                tracked = rx.recv(), if open && pending.len() < TRACKING_CAPACITY => match tracked {
+                    Some((events, receivers)) => pending.push(async move {
+                        let outcomes = receivers.into_iter().map(BatchStatusReceiver::with_failure);
+                        (events, future::join_all(outcomes).await)
+                    }),
+                    None => open = false,
+                },
+                Some((events, outcomes)) = pending.next() => {
+                    self.settle(events, outcomes).await;
+                }
+            }
+        }
+    }
+
+    async fn settle(
+        &self,
+        events: EventArray,
+        outcomes: Vec<(BatchStatus, Option<DeliveryFailure>)>,
+    ) {
+        let mut failed = Vec::new();
+        let mut failures = Vec::new();
+        for (mut event, (status, failure)) in events.into_events().zip(outcomes) {
+            let status = match status {
+                BatchStatus::Delivered => {
+                    event
+                        .take_finalizers()
+                        .update_status(EventStatus::Delivered);
+                    continue;
+                }
+                BatchStatus::Errored => EventStatus::Errored,
+                BatchStatus::Rejected => EventStatus::Rejected,
+            };
+            let failure = failure.unwrap_or_else(|| DeliveryFailure::new("Delivery failed."));
+            failed.push(event);
+            failures.push((status, failure));
+        }
+        if failed.is_empty() {
+            return;
+        }
+
+        let subscribers = self.registry.subscribers_of(&self.component_key);
+        if subscribers.is_empty() {
+            report_failed(failed, &failures);
+            return;
+        }
+
+        for (event, (status, failure)) in failed.iter_mut().zip(&failures) {
+            self.annotate_failed(event.into(), status_reason(*status), failure);
+        }
+
+        // Each subscriber gets its own copy of the events, which all share the original finalizers.
+        let count = subscribers.len();
+        for (tx, events) in subscribers.into_iter().zip(iter::repeat_n(failed, count)) {
+            for events in events_into_arrays(events, None) {
+                if let Err(mpsc::error::SendError(events)) = tx.send(events).await {
+                    report_failed(events.into_events(), &failures);
+                }
+            }
+        }
+    }
+
+    fn failure_data(&self, reason: &str, failure: &DeliveryFailure) -> serde_json::Value {
+        serde_json::json!({
+            "reason": reason,
+            "message": failure.message,
+            "http_status": failure.http_status,
+            "component_id": self.component_key,
+            "component_type": self.component_type,
+            "component_kind": "sink",
+        })
+    }
+
+    fn annotate_failed(&self, event: EventMutRef<'_>, reason: &str, failure: &DeliveryFailure) {
+        match event {
+            EventMutRef::Log(log) => match log.namespace() {
+                LogNamespace::Legacy => {
+                    if let Some(metadata_key) = log_schema().metadata_key() {
+                        log.insert(
+                            (PathPrefix::Event, metadata_key.concat(path!("dead_letter"))),
+                            self.failure_data(reason, failure),
+                        );
+                    }
+                }
+                LogNamespace::Vector => {
+                    log.insert(
+                        metadata_path!("vector", "dead_letter"),
+                        self.failure_data(reason, failure),
+                    );
+                }
+            },
+            EventMutRef::Metric(metric) => {
+                if let Some(metadata_key) = log_schema().metadata_key() {
+                    metric.replace_tag(format!("{metadata_key}.dead_letter.reason"), reason.into());
+                    if let Some(http_status) = failure.http_status {
+                        metric.replace_tag(
+                            format!("{metadata_key}.dead_letter.http_status"),
+                            http_status.to_string(),
+                        );
+                    }
+                    metric.replace_tag(
+                        format!("{metadata_key}.dead_letter.component_id"),
+                        self.component_key.to_string(),
+                    );
+                    metric.replace_tag(
+                        format!("{metadata_key}.dead_letter.component_type"),
+                        self.component_type.into(),
+                    );
+                    metric.replace_tag(
+                        format!("{metadata_key}.dead_letter.component_kind"),
+                        "sink".into(),
+                    );
+                }
+            }
+            EventMutRef::Trace(trace) => {
+                trace.maybe_insert(log_schema().metadata_key_target_path(), || {
+                    self.failure_data(reason, failure).into()
+                });
+            }
+        }
+    }
+}
+
+/// Reports the failure of each of `events` to its source.
+fn report_failed(
+    events: impl IntoIterator<Item = Event>,
+    failures: &[(EventStatus, DeliveryFailure)],
+) {
+    for (mut event, (status, failure)) in events.into_iter().zip(failures) {
+        event
+            .take_finalizers()
+            .update_status_with_failure(*status, failure);
+    }
+}
+
+const fn status_reason(status: EventStatus) -> &'static str {
+    match status {
+        EventStatus::Rejected => "rejected",
+        _ => "errored",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use vector_lib::{finalization::AddBatchNotifier, lookup::event_path};
+
+    use super::*;
+    use crate::event::LogEvent;
+
+    fn tracked_log(message: &str) -> (EventArray, crate::event::BatchStatusReceiver) {
+        let (batch, receiver) = BatchNotifier::new_with_receiver();
+        let mut log = LogEvent::from(message);
+        log.add_batch_notifier(batch);
+        (EventArray::from(log), receiver)
+    }
+
+    #[tokio::test]
+    async fn delivered_events_are_acknowledged() {
+        let registry = DeadLetterRegistry::default();
+        let router = DeadLetterRouter::new(ComponentKey::from("sink"), "test", registry.clone());
+        let mut subscription = registry.subscribe(vec![ComponentKey::from("sink")]);
+
+        let (events, receiver) = tracked_log("hello");
+        let mut sent = router.track(events).await;
+        sent.take_finalizers().update_status(EventStatus::Delivered);
+        drop(sent);
+
+        assert_eq!(receiver.await, BatchStatus::Delivered);
+        assert!(subscription.rx.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn failed_events_are_rerouted_with_annotations() {
+        let registry = DeadLetterRegistry::default();
+        let router =
+            DeadLetterRouter::new(ComponentKey::from("failing_sink"), "test", registry.clone());
+        let mut subscription = registry.subscribe(vec![ComponentKey::from("failing_sink")]);
+        let mut other_subscription = registry.subscribe(vec![ComponentKey::from("other_sink")]);
+
+        let (events, mut receiver) = tracked_log("hello");
+        let mut sent = router.track(events).await;
+        sent.take_finalizers().update_status_with_failure(
+            EventStatus::Rejected,
+            &DeliveryFailure::new("Bad request.").with_http_status(400),
+        );
+        drop(sent);
+
+        let mut rerouted = subscription
+            .recv()
+            .await
+            .expect("events should be rerouted");
+        assert!(other_subscription.rx.try_recv().is_err());
+        // The source isn't notified until the rerouted events are finalized.
+        assert!(receiver.try_recv().is_err());
+
+        let log = rerouted.iter_events().next().unwrap().as_log();
+        assert_eq!(
+            log.get(event_path!("metadata", "dead_letter", "reason")),
+            Some(&"rejected".into())
+        );
+        assert_eq!(
+            log.get(event_path!("metadata", "dead_letter", "http_status")),
+            Some(&400.into())
+        );
+        assert_eq!(
+            log.get(event_path!("metadata", "dead_letter", "component_id")),
+            Some(&"failing_sink".into())
+        );
+
+        rerouted
+            .take_finalizers()
+            .update_status(EventStatus::Delivered);
+        drop(rerouted);
+        assert_eq!(receiver.await, BatchStatus::Delivered);
+    }
+
+    #[tokio::test]
+    async fn failed_events_without_subscribers_are_reported_to_the_source() {
+        let registry = DeadLetterRegistry::default();
+        let router = DeadLetterRouter::new(ComponentKey::from("sink"), "test", registry.clone());
+        // Subscriptions of other topologies aren't notified.
+        let mut other_topology = DeadLetterRegistry::default().subscribe(Vec::new());
+
+        let (events, receiver) = tracked_log("hello");
+        let mut sent = router.track(events).await;
+        sent.take_finalizers().update_status(EventStatus::Errored);
+        drop(sent);
+
+        assert_eq!(receiver.await, BatchStatus::Errored);
+        assert!(other_topology.rx.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn batches_are_settled_in_any_order() {
+        let registry = DeadLetterRegistry::default();
+        let router = DeadLetterRouter::new(ComponentKey::from("sink"), "test", registry.clone());
+        let mut subscription = registry.subscribe(Vec::new());
+
+        let (first, first_receiver) = tracked_log("first");
+        let (second, second_receiver) = tracked_log("second");
+        let mut first = router.track(first).await;
+        let mut second = router.track(second).await;
+        drop(router);
+
+        second.take_finalizers().update_status(EventStatus::Errored);
+        drop(second);
+        let mut rerouted = subscription
+            .recv()
+            .await
+            .expect("events should be rerouted");
+        assert_eq!(
+            rerouted.iter_events().next().unwrap().as_log()["message"],
+            "second".into()
+        );
+
+        first
+            .take_finalizers()
+            .update_status(EventStatus::Delivered);
+        drop(first);
+        assert_eq!(first_receiver.await, BatchStatus::Delivered);
+
+        rerouted
+            .take_finalizers()
+            .update_status(EventStatus::Delivered);
+        drop(rerouted);
+        assert_eq!(second_receiver.await, BatchStatus::Delivered);
+    }
+
+    #[tokio::test]
+    async fn only_failed_events_of_an_array_are_rerouted() {
+        let registry = DeadLetterRegistry::default();
+        let router = DeadLetterRouter::new(ComponentKey::from("sink"), "test", registry.clone());
+        let mut subscription = registry.subscribe(Vec::new());
+
+        let (batch, receiver) = BatchNotifier::new_with_receiver();
+        let events = EventArray::from(
+            ["delivered", "failed"]
+                .into_iter()
+                .map(|message| LogEvent::from(message).with_batch_notifier(&batch))
+                .collect::<Vec<_>>(),
+        );
+        drop(batch);
+
+        // The sink delivers the events in separate requests, and only the second one fails.
+        let sent = router.track(events).await;
+        for mut event in sent.into_events() {
+            let status = if event.as_log()["message"].to_string_lossy() == "failed" {
+                EventStatus::Errored
+            } else {
+                EventStatus::Delivered
+            };
+            event.take_finalizers().update_status(status);
+        }
+
+        let mut rerouted = subscription
+            .recv()
+            .await
+            .expect("events should be rerouted");
+        let messages = rerouted
+            .iter_events()
+            .map(|event| event.as_log()["message"].to_string_lossy().into_owned())
+            .collect::<Vec<_>>();
+        assert_eq!(messages, ["failed"]);
+
+        rerouted
+            .take_finalizers()
+            .update_status(EventStatus::Delivered);
+        drop(rerouted);
+        assert_eq!(receiver.await, BatchStatus::Delivered);
+    }
+
+    #[tokio::test]
+    async fn tracking_waits_for_events_to_be_settled() {
+        let registry = DeadLetterRegistry::default();
+        let router = DeadLetterRouter::new(ComponentKey::from("sink"), "test", registry);
+
+        // Up to the capacity of both the queue of the settler and its pending events.
+        let mut in_flight = Vec::new();
+        for _ in 0..2 * TRACKING_CAPACITY {
+            let (events, _receiver) = tracked_log("hello");
+            in_flight.push(router.track(events).await);
+        }
+
+        let (events, _receiver) = tracked_log("hello");
+        let mut track = tokio_test::task::spawn(router.track(events));
+        tokio::task::yield_now().await;
+        tokio_test::assert_pending!(track.poll());
+
+        let mut settled = in_flight.remove(0);
+        settled
+            .take_finalizers()
+            .update_status(EventStatus::Delivered);
+        drop(settled);
+        track.await;
+    }
+}
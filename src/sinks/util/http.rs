@@ -71,7 +71,7 @@ use super::{
 #[cfg(feature = "aws-core")]
 use crate::aws::sign_request;
 use crate::{
-    event::Event,
+    event::{DeliveryFailure, Event},
     http::{HttpClient, HttpError},
     internal_events::{EndpointBytesSent, SinkRequestBuildError},
     sinks::prelude::*,
@@ -549,6 +549,14 @@ impl<T: fmt::Debug> sink::Response for http::Response<T> {
     fn is_transient(&self) -> bool {
         self.status().is_server_error()
     }
+
+    fn delivery_failure(&self) -> DeliveryFailure {
+        DeliveryFailure::new(format!(
+            "HTTP request failed with status {}.",
+            self.status()
+        ))
+        .with_http_status(self.status().as_u16())
+    }
 }
 
 /// Serializes and deserializes a [`Vec<StatusCode>`]
@@ -943,6 +951,10 @@ impl DriverResponse for HttpResponse {
     fn bytes_sent(&self) -> Option<usize> {
         Some(self.raw_byte_size)
     }
+
+    fn delivery_failure(&self) -> DeliveryFailure {
+        self.http_response.delivery_failure()
+    }
 }
 
 /// Creates a `RetryLogic` for use with `HttpResponse`.
@@ -4,9 +4,11 @@ pub mod auth;
 #[allow(clippy::non_canonical_clone_impl)]
 pub mod batch;
 pub mod buffer;
+pub mod buffer_priority;
 pub mod builder;
 pub mod compressor;
 pub mod datagram;
+pub mod dead_letter;
 pub mod encoding;
 pub mod http;
 pub mod metadata;
@@ -60,7 +60,7 @@ use super::{
     buffer::{Partition, PartitionBuffer, PartitionInnerBuffer},
     service::{Map, ServiceBuilderExt},
 };
-use crate::event::EventStatus;
+use crate::event::{DeliveryFailure, EventStatus};
 
 // === BatchSink ===
 
@@ -447,7 +447,15 @@ where
             .err_into()
             .map(move |result| {
                 let status = result_status(&result);
-                finalizers.update_status(status);
+                if status == EventStatus::Delivered {
+                    finalizers.update_status(status);
+                } else {
+                    let failure = match &result {
+                        Ok(response) => response.delivery_failure(),
+                        Err(error) => DeliveryFailure::new(error.to_string()),
+                    };
+                    finalizers.update_status_with_failure(status, &failure);
+                }
                 match status {
                     EventStatus::Delivered => {
                         events_sent.emit(CountByteSize(count, json_byte_size));
@@ -560,6 +568,11 @@ pub trait Response: fmt::Debug {
     fn is_transient(&self) -> bool {
         true
     }
+
+    /// Describes why the request failed, when the response isn't successful.
+    fn delivery_failure(&self) -> DeliveryFailure {
+        DeliveryFailure::new("Response failed.")
+    }
 }
 
 impl Response for () {}
@@ -0,0 +1,207 @@
+use vector_lib::{
+    config::LogNamespace,
+    configurable::configurable_component,
+    internal_event::{CountByteSize, InternalEventHandle as _},
+    schema::Definition,
+};
+
+use crate::{
+    SourceSender,
+    config::{ComponentKey, DataType, SourceConfig, SourceContext, SourceOutput},
+    event::{EstimatedJsonEncodedSizeOf, EventContainer},
+    internal_events::{EventsReceived, StreamClosedError},
+    shutdown::ShutdownSignal,
+    sinks::util::dead_letter::DeadLetterSubscription,
+};
+
+/// Configuration for the `dead_letter` source.
+#[configurable_component(source(
+    "dead_letter",
+    "Receive the events that sinks failed to deliver."
+))]
+#[derive(Clone, Debug, Default)]
+#[serde(deny_unknown_fields)]
+pub struct DeadLetterConfig {
+    /// The IDs of the sinks to receive failed events from.
+    ///
+    /// Only sinks with `reroute_failed` enabled send their failed events to this source. If empty,
+    /// the failed events of all such sinks are received. The events received from a sink can't be
+    /// sent back to that sink, directly or through transforms.
+    #[serde(default)]
+    #[configurable(metadata(docs::examples = "my_http_sink"))]
+    sinks: Vec<String>,
+}
+
+impl_generate_config_from_default!(DeadLetterConfig);
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "dead_letter")]
+impl SourceConfig for DeadLetterConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        let subscription = cx.dead_letters.subscribe(self.subscribed_sinks());
+
+        Ok(Box::pin(run(subscription, cx.out, cx.shutdown)))
+    }
+
+    fn outputs(&self, _global_log_namespace: LogNamespace) -> Vec<SourceOutput> {
+        // The events keep the schema they had when they were sent to the sink, which isn't known
+        // here.
+        vec![SourceOutput::new_maybe_logs(
+            DataType::all_bits(),
+            Definition::any(),
+        )]
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        false
+    }
+
+    fn dead_letter_sinks(&self) -> Option<Vec<ComponentKey>> {
+        Some(self.subscribed_sinks())
+    }
+}
+
+impl DeadLetterConfig {
+    fn subscribed_sinks(&self) -> Vec<ComponentKey> {
+        self.sinks
+            .iter()
+            .map(|id| ComponentKey::from(id.as_str()))
+            .collect()
+    }
+}
+
+async fn run(
+    mut subscription: DeadLetterSubscription,
+    mut out: SourceSender,
+    mut shutdown: ShutdownSignal,
+) -> Result<(), ()> {
+    let events_received = register!(EventsReceived);
+
+    loop {
+        let events = tokio::select! {
+            events = subscription.recv() => events,
+            _ = &mut shutdown => None,
+        };
+        let Some(events) = events else { break };
+
+        let count = events.len();
+        events_received.emit(CountByteSize(
+            count,
+            events.estimated_json_encoded_size_of(),
+        ));
+
+        if out.send_event(events).await.is_err() {
+            emit!(StreamClosedError { count });
+            return Err(());
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::StreamExt;
+    use vector_lib::finalization::{AddBatchNotifier, Finalizable};
+
+    use super::*;
+    use crate::{
+        config::{ConfigBuilder, SinkOuter},
+        event::{BatchNotifier, BatchStatus, EventArray, EventStatus, LogEvent},
+        sinks::util::dead_letter::DeadLetterRouter,
+        test_util::mock::{basic_sink, basic_source},
+    };
+
+    #[test]
+    fn generates_config() {
+        crate::test_util::test_generate_config::<DeadLetterConfig>();
+    }
+
+    #[tokio::test]
+    async fn receives_failed_events_of_subscribed_sinks() {
+        let config = DeadLetterConfig {
+            sinks: vec!["dead_letter_source_sink".to_owned()],
+        };
+        let (tx, rx) = SourceSender::new_test();
+        let cx = SourceContext::new_test(tx, None);
+        let registry = cx.dead_letters.clone();
+        let source = config.build(cx).await.unwrap();
+        tokio::spawn(source);
+
+        let (batch, receiver) = BatchNotifier::new_with_receiver();
+        let mut log = LogEvent::from("hello");
+        log.add_batch_notifier(batch);
+
+        let router = DeadLetterRouter::new(
+            ComponentKey::from("dead_letter_source_sink"),
+            "test",
+            registry,
+        );
+        let mut sent = router.track(EventArray::from(log));
+        sent.take_finalizers().update_status(EventStatus::Rejected);
+        drop(sent);
+
+        let mut events = rx.take(1).collect::<Vec<_>>().await;
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].as_log()["message"], "hello".into());
+
+        // The delivery of the rerouted events decides the status reported to the source.
+        drop(events.pop());
+        assert_eq!(receiver.await, BatchStatus::Delivered);
+    }
+
+    /// Builds a topology where `in` feeds the `out` sink, which reroutes its failed events, and the
+    /// `dead_letters` source feeds the given sink.
+    fn build_errors(sinks: &[&str], dead_letters_sink: &str) -> Vec<String> {
+        let mut builder = ConfigBuilder::default();
+        builder.add_source("in", basic_source().1);
+        builder.add_source(
+            "dead_letters",
+            DeadLetterConfig {
+                sinks: sinks.iter().map(|sink| sink.to_string()).collect(),
+            },
+        );
+        let mut out = SinkOuter::new(vec!["in".to_string()], basic_sink(1).1);
+        out.reroute_failed = true;
+        builder.add_sink_outer("out", out);
+        builder.add_sink("archive", &[dead_letters_sink], basic_sink(1).1);
+        builder.build().err().unwrap_or_default()
+    }
+
+    #[test]
+    fn accepts_failed_events_sent_to_other_sinks() {
+        assert!(build_errors(&["out"], "dead_letters").is_empty());
+        assert!(build_errors(&[], "dead_letters").is_empty());
+    }
+
+    #[test]
+    fn rejects_unknown_sinks() {
+        assert_eq!(
+            build_errors(&["missing", "archive"], "dead_letters"),
+            [
+                r#"Source "dead_letters" receives the failed events of sink "missing", which doesn't exist."#,
+                r#"Source "dead_letters" receives the failed events of sink "archive", which doesn't have `reroute_failed` enabled."#,
+            ]
+        );
+    }
+
+    #[test]
+    fn rejects_rerouting_failed_events_into_their_sink() {
+        let mut builder = ConfigBuilder::default();
+        builder.add_source("in", basic_source().1);
+        builder.add_source("dead_letters", DeadLetterConfig::default());
+        let mut out = SinkOuter::new(
+            vec!["in".to_string(), "dead_letters".to_string()],
+            basic_sink(1).1,
+        );
+        out.reroute_failed = true;
+        builder.add_sink_outer("out", out);
+
+        assert_eq!(
+            builder.build().unwrap_err(),
+            [
+                r#"Source "dead_letters" receives the failed events of sink "out" and sends events to it, so events that keep failing would be rerouted forever."#
+            ]
+        );
+    }
+}
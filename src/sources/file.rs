@@ -18,7 +18,8 @@ use vector_lib::{
         paths_provider::{Glob, MatchOptions},
     },
     file_source_common::{
-        Checkpointer, FileFingerprint, FingerprintStrategy, Fingerprinter, ReadFrom, ReadFromConfig,
+        Checkpointer, CheckpointsLock, DiscoveryMethod, FileFingerprint, FingerprintStrategy,
+        Fingerprinter, ReadFrom, ReadFromConfig,
     },
     finalizer::OrderedFinalizer,
     lookup::{OwnedValuePath, lookup_v2::OptionalValuePath, owned_value_path, path},
@@ -162,6 +163,10 @@ pub struct FileConfig {
     #[configurable(metadata(docs::human_name = "Glob Minimum Cooldown"))]
     pub glob_minimum_cooldown_ms: Duration,
 
+    #[configurable(derived)]
+    #[serde(default)]
+    pub discovery: DiscoveryMethod,
+
     #[configurable(derived)]
     #[serde(alias = "fingerprinting", default)]
     fingerprint: FingerprintConfig,
@@ -290,8 +295,8 @@ pub enum FingerprintConfig {
     /// Read lines from the beginning of the file and compute a checksum over them.
     Checksum {
         /// The number of bytes to skip ahead (or ignore) when reading the data used for generating the checksum.
-        /// If the file is compressed, the number of bytes refer to the header in the uncompressed content. Only
-        /// gzip is supported at this time.
+        /// If the file is compressed, the number of bytes refer to the header in the uncompressed content. The
+        /// gzip, bzip2, zstd, and xz formats are supported.
         ///
         /// This can be helpful if all files share a common header that should be skipped.
         #[serde(default = "default_ignored_header_bytes")]
@@ -300,8 +305,8 @@ pub enum FingerprintConfig {
 
         /// The number of lines to read for generating the checksum.
         ///
-        /// The number of lines are determined from the uncompressed content if the file is compressed. Only
-        /// gzip is supported at this time.
+        /// The number of lines are determined from the uncompressed content if the file is compressed. The
+        /// gzip, bzip2, zstd, and xz formats are supported.
         ///
         /// If the file has less than this amount of lines, it won’t be read at all.
         #[serde(default = "default_lines")]
@@ -314,6 +319,36 @@ pub enum FingerprintConfig {
     /// [inode]: https://en.wikipedia.org/wiki/Inode
     #[serde(rename = "device_and_inode")]
     DevInode,
+
+    /// Combine a checksum of the first lines with the [device and inode][inode], and verify the
+    /// content of files against a checksum of the data last read from them.
+    ///
+    /// This identifies files with identical headers apart, and detects when a file is truncated or
+    /// replaced, in which case it's read from the beginning again. When a file is a copy of another
+    /// one, such as with `copytruncate` rotation, reading it resumes where the original left off.
+    /// Checkpoints made with the other strategies are migrated automatically.
+    ///
+    /// [inode]: https://en.wikipedia.org/wiki/Inode
+    ContentHash {
+        /// The number of bytes to skip ahead (or ignore) when reading the data used for generating the checksum.
+        /// If the file is compressed, the number of bytes refer to the header in the uncompressed content. The
+        /// gzip, bzip2, zstd, and xz formats are supported.
+        ///
+        /// This can be helpful if all files share a common header that should be skipped.
+        #[serde(default = "default_ignored_header_bytes")]
+        #[configurable(metadata(docs::type_unit = "bytes"))]
+        ignored_header_bytes: usize,
+
+        /// The number of lines to read for generating the checksum.
+        ///
+        /// The number of lines are determined from the uncompressed content if the file is compressed. The
+        /// gzip, bzip2, zstd, and xz formats are supported.
+        ///
+        /// If the file has less than this amount of lines, it won’t be read at all.
+        #[serde(default = "default_lines")]
+        #[configurable(metadata(docs::type_unit = "lines"))]
+        lines: usize,
+    },
 }
 
 impl Default for FingerprintConfig {
@@ -344,6 +379,13 @@ impl From<FingerprintConfig> for FingerprintStrategy {
                 lines,
             },
             FingerprintConfig::DevInode => FingerprintStrategy::DevInode,
+            FingerprintConfig::ContentHash {
+                ignored_header_bytes,
+                lines,
+            } => FingerprintStrategy::ContentHash {
+                ignored_header_bytes,
+                lines,
+            },
         }
     }
 }
@@ -371,6 +413,7 @@ impl Default for FileConfig {
             offset_key: None,
             data_dir: None,
             glob_minimum_cooldown_ms: default_glob_minimum_cooldown_ms(),
+            discovery: DiscoveryMethod::default(),
             message_start_indicator: None,
             multi_line_timeout: default_multi_line_timeout(), // millis
             multiline: None,
@@ -532,6 +575,7 @@ pub fn file_source(
     };
 
     let checkpointer = Checkpointer::new(&data_dir);
+    let lock_dir = data_dir.clone();
     let strategy = config.fingerprint.clone().into();
 
     let file_server = FileServer {
@@ -549,6 +593,7 @@ pub fn file_source(
         remove_after: config.remove_after_secs.map(Duration::from_secs),
         emitter,
         rotate_wait: config.rotate_wait,
+        discovery: config.discovery,
     };
 
     let event_metadata = EventMetadata {
@@ -685,6 +730,17 @@ pub fn file_source(
         let span = info_span!("file_server");
         tokio::task::spawn_blocking(move || {
             let _enter = span.enter();
+            // Held until the file server stops. On reload, this waits for the previous instance of
+            // the source to release it.
+            let _lock = CheckpointsLock::lock(&lock_dir)
+                .map_err(|error| {
+                    error!(
+                        message = "Unable to lock checkpoints.",
+                        %error,
+                        internal_log_rate_limit = false
+                    )
+                })
+                .ok();
             let rt = tokio::runtime::Handle::current();
             let result =
                 rt.block_on(file_server.run(tx, shutdown, shutdown_checkpointer, checkpointer));
@@ -917,6 +973,23 @@ mod tests {
         .unwrap();
         assert_eq!(config.fingerprint, FingerprintConfig::DevInode);
 
+        let config: FileConfig = toml::from_str(
+            r#"
+        include = [ "/var/log/**/*.log" ]
+        [fingerprint]
+        strategy = "content_hash"
+        lines = 2
+        "#,
+        )
+        .unwrap();
+        assert_eq!(
+            config.fingerprint,
+            FingerprintConfig::ContentHash {
+                ignored_header_bytes: 0,
+                lines: 2
+            }
+        );
+
         let config: FileConfig = toml::from_str(
             r#"
         include = [ "/var/log/**/*.log" ]
@@ -1207,6 +1280,90 @@ mod tests {
         assert_eq!(goodbye_i, n);
     }
 
+    #[cfg(target_os = "linux")]
+    #[tokio::test]
+    async fn file_inotify_discovery() {
+        let dir = tempdir().unwrap();
+        fs::create_dir(dir.path().join("existing")).unwrap();
+        let config = file::FileConfig {
+            include: vec![dir.path().join("*/*.log")],
+            // Make sure files are only found through inotify.
+            glob_minimum_cooldown_ms: Duration::from_secs(3600),
+            discovery: DiscoveryMethod::Inotify,
+            ..test_default_file_config(&dir)
+        };
+
+        let path1 = dir.path().join("existing/file1.log");
+        let path2 = dir.path().join("created/file2.log");
+
+        let received = run_file_source(&config, false, NoAcks, LogNamespace::Legacy, None, async {
+            sleep_500_millis().await;
+
+            let mut file1 = File::create(&path1).unwrap();
+            writeln!(&mut file1, "hello").unwrap();
+            file1.flush().unwrap();
+            sleep_500_millis().await;
+
+            fs::create_dir(path2.parent().unwrap()).unwrap();
+            sleep_500_millis().await;
+            let mut file2 = File::create(&path2).unwrap();
+            writeln!(&mut file2, "goodbye").unwrap();
+            file2.flush().unwrap();
+            sleep_500_millis().await;
+
+            writeln!(&mut file1, "hello again").unwrap();
+            file1.flush().unwrap();
+            sleep_500_millis().await;
+        })
+        .await;
+
+        let lines = received
+            .into_iter()
+            .map(|event| {
+                event.as_log()[log_schema().message_key().unwrap().to_string()].to_string_lossy()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(lines, vec!["hello", "goodbye", "hello again"]);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[tokio::test]
+    async fn file_inotify_discovery_in_created_dir() {
+        let dir = tempdir().unwrap();
+        let config = file::FileConfig {
+            include: vec![dir.path().join("app/nested/*.log")],
+            // Make sure files are only found through inotify.
+            glob_minimum_cooldown_ms: Duration::from_secs(3600),
+            discovery: DiscoveryMethod::Inotify,
+            ..test_default_file_config(&dir)
+        };
+
+        let path = dir.path().join("app/nested/file.log");
+
+        let received = run_file_source(&config, false, NoAcks, LogNamespace::Legacy, None, async {
+            sleep_500_millis().await;
+
+            fs::create_dir(dir.path().join("app")).unwrap();
+            sleep_500_millis().await;
+            fs::create_dir(path.parent().unwrap()).unwrap();
+            sleep_500_millis().await;
+
+            let mut file = File::create(&path).unwrap();
+            writeln!(&mut file, "hello").unwrap();
+            file.flush().unwrap();
+            sleep_500_millis().await;
+        })
+        .await;
+
+        let lines = received
+            .into_iter()
+            .map(|event| {
+                event.as_log()[log_schema().message_key().unwrap().to_string()].to_string_lossy()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(lines, vec!["hello"]);
+    }
+
     // https://github.com/vectordotdev/vector/issues/8363
     #[tokio::test]
     async fn file_read_empty_lines() {
@@ -1297,6 +1454,59 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn file_copy_truncate_with_content_hash() {
+        let n = 5;
+
+        let dir = tempdir().unwrap();
+        let config = file::FileConfig {
+            include: vec![dir.path().join("*")],
+            fingerprint: FingerprintConfig::ContentHash {
+                ignored_header_bytes: 0,
+                lines: 1,
+            },
+            ..test_default_file_config(&dir)
+        };
+
+        let path = dir.path().join("file");
+        let archive_path = dir.path().join("file.1");
+        let received = run_file_source(&config, false, NoAcks, LogNamespace::Legacy, None, async {
+            let mut file = File::create(&path).unwrap();
+
+            writeln!(&mut file, "header").unwrap();
+            for i in 0..n {
+                writeln!(&mut file, "precopy {i}").unwrap();
+            }
+
+            file.flush().unwrap();
+            sleep_500_millis().await; // The writes must be observed before rotating
+
+            fs::copy(&path, &archive_path).expect("could not copy");
+            file.set_len(0).unwrap();
+            file.seek(std::io::SeekFrom::Start(0)).unwrap();
+
+            file.sync_all().unwrap();
+            sleep_500_millis().await; // The rotation must be observed before writing again
+
+            writeln!(&mut file, "header").unwrap();
+            for i in 0..n {
+                writeln!(&mut file, "postcopy {i}").unwrap();
+            }
+
+            file.flush().unwrap();
+            sleep_500_millis().await;
+        })
+        .await;
+
+        let received = extract_messages_string(received);
+
+        let mut expected = vec!["header".to_owned()];
+        expected.extend((0..n).map(|i| format!("precopy {i}")));
+        expected.push("header".to_owned());
+        expected.extend((0..n).map(|i| format!("postcopy {i}")));
+        assert_eq!(received, expected);
+    }
+
     #[tokio::test]
     async fn file_rotate() {
         let n = 5;
@@ -2310,6 +2520,43 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_zstd_compressed_file() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("compressed.log.zst");
+        let compressed = zstd::encode_all(
+            &b"this is a simple file\ni have been compressed with zstd\n"[..],
+            0,
+        )
+        .unwrap();
+        std::fs::write(&path, compressed).unwrap();
+
+        let config = file::FileConfig {
+            include: vec![path],
+            ..test_default_file_config(&dir)
+        };
+
+        let received = run_file_source(
+            &config,
+            false,
+            NoAcks,
+            LogNamespace::Legacy,
+            None,
+            sleep_500_millis(),
+        )
+        .await;
+
+        let received = extract_messages_value(received);
+
+        assert_eq!(
+            received,
+            vec![
+                "this is a simple file".into(),
+                "i have been compressed with zstd".into(),
+            ]
+        );
+    }
+
     #[tokio::test]
     async fn test_non_utf8_encoded_file() {
         let dir = tempdir().unwrap();
@@ -30,7 +30,7 @@ use vector_lib::{
         FileServer, Line, Shutdown as FileServerShutdown, calculate_ignore_before,
     },
     file_source_common::{
-        Checkpointer, FingerprintStrategy, Fingerprinter, ReadFrom, ReadFromConfig,
+        Checkpointer, DiscoveryMethod, FingerprintStrategy, Fingerprinter, ReadFrom, ReadFromConfig,
     },
     internal_event::{ByteSize, BytesReceived, InternalEventHandle as _, Protocol},
     lookup::{OwnedTargetPath, lookup_v2::OptionalTargetPath, owned_value_path, path},
@@ -870,6 +870,9 @@ impl Source {
             },
             // A handle to the current tokio runtime
             rotate_wait,
+            // Paths come from the k8s API rather than the file system, so they can't be discovered
+            // through file system events.
+            discovery: DiscoveryMethod::Poll,
         };
 
         let (file_source_tx, file_source_rx) = futures::channel::mpsc::channel::<Vec<Line>>(2);
@@ -203,9 +203,11 @@ fn merge_partial_events_with_custom_expiration(
             // check for expired events
             state.emit_expired_events(emitter)
         },
-        |state: &mut PartialEventMergeState, emitter: &mut Emitter<LogEvent>| {
+        |mut state: PartialEventMergeState| async move {
             // the source is ending, flush all pending events
-            state.flush_events(emitter);
+            let mut emitter = Emitter::new();
+            state.flush_events(&mut emitter);
+            emitter
         },
     )
     // LogEvent -> Event
@@ -10,7 +10,7 @@ use vector_lib::{
         file_server::{FileServer, Line, Shutdown as FileServerShutdown},
         paths_provider::PathsProvider,
     },
-    file_source_common::{Checkpointer, FileSourceInternalEvents},
+    file_source_common::{Checkpointer, CheckpointsLock, FileSourceInternalEvents},
 };
 
 /// A tiny wrapper around a [`FileServer`] that runs it as a [`spawn_blocking`]
@@ -31,6 +31,7 @@ where
     <<PP as PathsProvider>::IntoIter as IntoIterator>::IntoIter: Send,
 {
     let span = info_span!("file_server");
+    let lock_dir = file_server.data_dir.clone();
 
     // spawn_blocking shouldn't be needed: https://github.com/vectordotdev/vector/issues/23743
     let join_handle = tokio::task::spawn_blocking(move || {
@@ -40,6 +41,19 @@ where
         let shutdown2 = shutdown.clone();
         let _enter = span.enter();
 
+        // Held until the file server stops, so that the checkpoints aren't rewritten by another
+        // process while they're in use. On reload, this waits for the previous instance of the
+        // source to release it.
+        let _lock = CheckpointsLock::lock(&lock_dir)
+            .map_err(|error| {
+                error!(
+                    message = "Unable to lock checkpoints.",
+                    %error,
+                    internal_log_rate_limit = false
+                )
+            })
+            .ok();
+
         let rt = tokio::runtime::Handle::current();
         let result = rt.block_on(file_server.run(chans, shutdown, shutdown2, checkpointer));
         result.expect("file server exited with an error")
@@ -15,6 +15,8 @@ pub mod aws_s3;
 pub mod aws_sqs;
 #[cfg(feature = "sources-datadog_agent")]
 pub mod datadog_agent;
+#[cfg(feature = "sources-dead_letter")]
+pub mod dead_letter;
 #[cfg(feature = "sources-demo_logs")]
 pub mod demo_logs;
 #[cfg(all(unix, feature = "sources-dnstap"))]
@@ -80,6 +82,8 @@ pub mod prometheus;
 pub mod pulsar;
 #[cfg(feature = "sources-redis")]
 pub mod redis;
+#[cfg(feature = "sources-replay")]
+pub mod replay;
 #[cfg(feature = "sources-socket")]
 pub mod socket;
 #[cfg(feature = "sources-splunk_hec")]
@@ -0,0 +1,253 @@
+use std::path::PathBuf;
+
+use futures::StreamExt;
+use snafu::Snafu;
+use tokio::{
+    fs::File,
+    time::{self, Instant},
+};
+use vector_lib::{
+    EstimatedJsonEncodedSizeOf,
+    codecs::{
+        DecoderFramedRead, NativeDeserializerConfig, StreamDecodingError,
+        decoding::{DeserializerConfig, FramingConfig},
+    },
+    config::{DataType, LogNamespace},
+    configurable::configurable_component,
+    internal_event::{ByteSize, BytesReceived, CountByteSize, InternalEventHandle as _, Protocol},
+    tap::capture,
+};
+
+use crate::{
+    SourceSender,
+    codecs::{Decoder, DecodingConfig},
+    config::{SourceConfig, SourceContext, SourceOutput},
+    internal_events::{EventsReceived, StreamClosedError},
+    shutdown::ShutdownSignal,
+};
+
+/// Configuration for the `replay` source.
+#[configurable_component(source(
+    "replay",
+    "Play back events recorded to a capture file by `vector tap --capture`."
+))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct ReplayConfig {
+    /// The path of the capture file to play back.
+    #[configurable(metadata(docs::examples = "/var/lib/vector/capture.bin"))]
+    pub path: PathBuf,
+
+    /// How fast to play back events, relative to the timing they were captured with.
+    ///
+    /// For example, `2.0` plays events back twice as fast as they were captured. To play events
+    /// back as quickly as possible, set `speed` to `0.0`.
+    #[serde(default = "default_speed")]
+    #[configurable(metadata(docs::examples = 1.0, docs::examples = 10.0, docs::examples = 0.0))]
+    pub speed: f64,
+}
+
+const fn default_speed() -> f64 {
+    1.0
+}
+
+impl Default for ReplayConfig {
+    fn default() -> Self {
+        Self {
+            path: PathBuf::from("/var/lib/vector/capture.bin"),
+            speed: default_speed(),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Snafu)]
+pub enum ReplayConfigError {
+    #[snafu(display("`speed` must be a number >= 0, got {speed}"))]
+    InvalidSpeed { speed: f64 },
+}
+
+impl_generate_config_from_default!(ReplayConfig);
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "replay")]
+impl SourceConfig for ReplayConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        if !(self.speed >= 0.0 && self.speed.is_finite()) {
+            return Err(ReplayConfigError::InvalidSpeed { speed: self.speed }.into());
+        }
+
+        let decoder = DecodingConfig::new(
+            FramingConfig::LengthDelimited(Default::default()),
+            DeserializerConfig::Native,
+            LogNamespace::Legacy,
+        )
+        .build()?;
+        let file = File::open(&self.path)
+            .await
+            .map_err(|error| format!("Failed to open capture file {:?}: {error}", self.path))?;
+
+        Ok(Box::pin(replay_source(
+            file,
+            self.speed,
+            decoder,
+            cx.shutdown,
+            cx.out,
+        )))
+    }
+
+    fn outputs(&self, global_log_namespace: LogNamespace) -> Vec<SourceOutput> {
+        // Captured events are played back as they were tapped, so they keep the namespace they
+        // were captured with.
+        let schema_definition = NativeDeserializerConfig.schema_definition(global_log_namespace);
+
+        vec![SourceOutput::new_maybe_logs(
+            DataType::all_bits(),
+            schema_definition,
+        )]
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        false
+    }
+}
+
+async fn replay_source(
+    file: File,
+    speed: f64,
+    decoder: Decoder,
+    mut shutdown: ShutdownSignal,
+    mut out: SourceSender,
+) -> Result<(), ()> {
+    let bytes_received = register!(BytesReceived::from(Protocol::from("file")));
+    let events_received = register!(EventsReceived);
+
+    // The playback start, and the capture time of the first event played back.
+    let mut origin = None;
+
+    let mut stream = DecoderFramedRead::new(file, decoder).take_until(shutdown.clone());
+    while let Some(next) = stream.next().await {
+        let (events, byte_size) = match next {
+            Ok(frame) => frame,
+            Err(error) => {
+                // Error is logged by `crate::codecs::Decoder`, no further handling is needed here.
+                if error.can_continue() {
+                    continue;
+                }
+                break;
+            }
+        };
+        bytes_received.emit(ByteSize(byte_size));
+
+        if speed > 0.0
+            && let Some(tapped_at) = events.first().and_then(capture::tapped_at)
+        {
+            let (start, first_tapped_at) = *origin.get_or_insert((Instant::now(), tapped_at));
+            let offset = (tapped_at - first_tapped_at).to_std().unwrap_or_default();
+            tokio::select! {
+                _ = time::sleep_until(start + offset.div_f64(speed)) => {}
+                _ = &mut shutdown => break,
+            }
+        }
+
+        let count = events.len();
+        events_received.emit(CountByteSize(
+            count,
+            events.estimated_json_encoded_size_of(),
+        ));
+        out.send_batch(events).await.map_err(|_| {
+            emit!(StreamClosedError { count });
+        })?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::BytesMut;
+    use chrono::{TimeZone, Utc};
+    use futures::StreamExt;
+    use tokio::time::Duration;
+    use tokio_util::codec::Encoder;
+    use vector_lib::codecs::{LengthDelimitedEncoder, NativeSerializer};
+
+    use super::*;
+    use crate::{
+        event::{Event, LogEvent},
+        test_util::{
+            components::{SOURCE_TAGS, assert_source_compliance},
+            temp_file,
+        },
+    };
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<ReplayConfig>();
+    }
+
+    /// Writes a capture file holding `messages`, tapped one second apart.
+    fn write_capture(messages: &[&str]) -> PathBuf {
+        let mut buffer = BytesMut::new();
+        for (index, message) in messages.iter().enumerate() {
+            let mut event = Event::from(LogEvent::from(*message));
+            let tapped_at = Utc.timestamp_opt(1_700_000_000 + index as i64, 0).unwrap();
+            capture::annotate(&mut event, "demo", tapped_at);
+
+            let mut frame = BytesMut::new();
+            NativeSerializer.encode(event, &mut frame).unwrap();
+            LengthDelimitedEncoder::default()
+                .encode((), &mut frame)
+                .unwrap();
+            buffer.extend_from_slice(&frame);
+        }
+
+        let path = temp_file();
+        std::fs::write(&path, buffer).unwrap();
+        path
+    }
+
+    async fn replay(path: PathBuf, speed: f64) -> Vec<Event> {
+        assert_source_compliance(&SOURCE_TAGS, async {
+            let (tx, rx) = SourceSender::new_test();
+            let config = ReplayConfig { path, speed };
+            let source = config
+                .build(SourceContext::new_test(tx, None))
+                .await
+                .unwrap();
+            source.await.unwrap();
+            rx.collect().await
+        })
+        .await
+    }
+
+    #[tokio::test]
+    async fn replays_events_in_order() {
+        let path = write_capture(&["one", "two", "three"]);
+
+        let events = replay(path, 0.0).await;
+
+        let messages = events
+            .iter()
+            .map(|event| event.as_log()["message"].to_string_lossy().into_owned())
+            .collect::<Vec<_>>();
+        assert_eq!(messages, ["one", "two", "three"]);
+        assert_eq!(
+            events[0].metadata().value().get(capture::COMPONENT_ID_PATH),
+            Some(&"demo".into())
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn replays_with_original_timing() {
+        let path = write_capture(&["one", "two", "three"]);
+
+        let start = Instant::now();
+        replay(path.clone(), 1.0).await;
+        assert!(start.elapsed() >= Duration::from_secs(2));
+
+        let start = Instant::now();
+        replay(path, 4.0).await;
+        let elapsed = start.elapsed();
+        assert!(elapsed >= Duration::from_millis(500) && elapsed < Duration::from_secs(2));
+    }
+}
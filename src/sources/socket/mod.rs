@@ -1042,6 +1042,7 @@ mod test {
                 schema_definitions: HashMap::default(),
                 extra_context: Default::default(),
                 metrics_storage: Default::default(),
+                dead_letters: Default::default(),
             })
             .await
             .unwrap();
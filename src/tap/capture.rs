@@ -0,0 +1,91 @@
+use std::path::PathBuf;
+
+use bytes::BytesMut;
+use tokio::{fs::File, io::AsyncWriteExt, sync::mpsc, task::JoinHandle};
+use vector_lib::{
+    api_client::proto::{StreamOutputEventsResponse, stream_output_events_response::Event},
+    tap::{OutputChannel, capture::CaptureEncoder},
+};
+
+/// Number of batches of tapped events buffered between the tap and the capture file.
+const CAPTURE_CHANNEL_SIZE: usize = 100;
+
+/// Writes tapped events to a capture file, for playback with the `replay` source.
+pub(super) struct Capture {
+    path: PathBuf,
+    writer: JoinHandle<Result<usize, String>>,
+}
+
+impl Capture {
+    /// Creates the capture file at `path`, returning the capture along with the output channel
+    /// the tap should send events to.
+    #[allow(clippy::print_stderr)]
+    pub(super) async fn create(path: PathBuf) -> Result<(Self, OutputChannel), exitcode::ExitCode> {
+        let file = File::create(&path).await.map_err(|error| {
+            eprintln!("[tap] Failed to create \"{}\": {error}", path.display());
+            exitcode::CANTCREAT
+        })?;
+        let (tx, rx) = mpsc::channel(CAPTURE_CHANNEL_SIZE);
+        let writer = tokio::spawn(write_capture(file, rx));
+        Ok((Self { path, writer }, OutputChannel::AsyncChannel(tx)))
+    }
+
+    /// Waits for all tapped events to be written, once the output channel has been dropped.
+    #[allow(clippy::print_stderr)]
+    pub(super) async fn finish(self, quiet: bool) -> exitcode::ExitCode {
+        match self.writer.await {
+            Ok(Ok(count)) => {
+                if !quiet {
+                    eprintln!(
+                        "[tap] Captured {count} events to \"{}\".",
+                        self.path.display()
+                    );
+                }
+                exitcode::OK
+            }
+            Ok(Err(error)) => {
+                eprintln!("[tap] {error}");
+                exitcode::IOERR
+            }
+            Err(error) => {
+                eprintln!("[tap] Capture failed: {error}");
+                exitcode::SOFTWARE
+            }
+        }
+    }
+}
+
+#[allow(clippy::print_stderr)]
+async fn write_capture(
+    mut file: File,
+    mut rx: mpsc::Receiver<Vec<StreamOutputEventsResponse>>,
+) -> Result<usize, String> {
+    let mut encoder = CaptureEncoder::default();
+    let mut buffer = BytesMut::new();
+    let mut count = 0;
+
+    while let Some(responses) = rx.recv().await {
+        for response in responses {
+            match response.event {
+                Some(Event::TappedEvent(tapped)) => {
+                    if let Err(error) = encoder.encode(&tapped, &mut buffer) {
+                        error!(message = "Failed to capture event.", %error);
+                        continue;
+                    }
+                    count += 1;
+                }
+                Some(Event::Notification(notification)) => eprintln!("{}", notification.message),
+                None => {}
+            }
+        }
+
+        file.write_all_buf(&mut buffer)
+            .await
+            .map_err(|error| format!("Failed to write capture file: {error}"))?;
+    }
+
+    file.flush()
+        .await
+        .map_err(|error| format!("Failed to write capture file: {error}"))?;
+    Ok(count)
+}
@@ -5,6 +5,7 @@ use vector_lib::{
     tap::{EventFormatter, OutputChannel, TapRunner},
 };
 
+use super::capture::Capture;
 use crate::signal::{SignalRx, SignalTo};
 
 /// CLI command func for issuing 'tap' queries, and communicating with a local/remote
@@ -17,6 +18,9 @@ pub(crate) async fn cmd(opts: &super::Opts, signal_rx: SignalRx) -> exitcode::Ex
         return exitcode::USAGE;
     };
     let mut client = Client::new(uri);
+    if let Some(token) = &opts.control_token {
+        client = client.with_control_token(token);
+    }
 
     if client.connect().await.is_err() || client.health().await.is_err() {
         eprintln!(
@@ -34,6 +38,10 @@ pub(crate) async fn cmd(opts: &super::Opts, signal_rx: SignalRx) -> exitcode::Ex
         return exitcode::UNAVAILABLE;
     }
 
+    if let Some(component_id) = &opts.inject {
+        return super::inject::inject(opts, component_id, client).await;
+    }
+
     tap_internal(opts, signal_rx, Some(client)).await
 }
 
@@ -48,13 +56,26 @@ async fn tap_internal(
     mut client_opt: Option<Client>,
 ) -> exitcode::ExitCode {
     let url = opts.url();
-    let output_channel = OutputChannel::Stdout(EventFormatter::new(opts.meta, opts.format));
+    let (capture, output_channel) = match &opts.capture {
+        Some(path) => match Capture::create(path.clone()).await {
+            Ok((capture, output_channel)) => (Some(capture), output_channel),
+            Err(exit_code) => return exit_code,
+        },
+        None => (
+            None,
+            OutputChannel::Stdout(EventFormatter::new(opts.meta, opts.format)),
+        ),
+    };
     let tap_runner = TapRunner::new(
         &url,
         opts.inputs_of.clone(),
         opts.outputs_patterns().clone(),
         &output_channel,
-    );
+    )
+    .with_filter(opts.filter.clone())
+    .with_sample_ratio(opts.sample_ratio)
+    .with_max_events_per_second(opts.max_events_per_second)
+    .with_unsampled(opts.capture.is_some());
 
     loop {
         tokio::select! {
@@ -102,5 +123,12 @@ async fn tap_internal(
         }
     }
 
+    if let Some(capture) = capture {
+        // Dropping the output channel lets the capture writer finish.
+        drop(tap_runner);
+        drop(output_channel);
+        return capture.finish(opts.quiet).await;
+    }
+
     exitcode::OK
 }
@@ -0,0 +1,55 @@
+use tokio::io::{AsyncBufReadExt, BufReader};
+use vector_lib::api_client::Client;
+
+/// Maximum number of events sent to the API in a single request.
+const INJECT_BATCH_SIZE: usize = 100;
+
+/// Reads events from stdin, one per line, and injects them into the input of the given
+/// component.
+#[allow(clippy::print_stderr)]
+pub(super) async fn inject(
+    opts: &super::Opts,
+    component_id: &str,
+    mut client: Client,
+) -> exitcode::ExitCode {
+    let encoding = opts.inject_format.into();
+    let mut lines = BufReader::new(tokio::io::stdin()).lines();
+    let mut batch = Vec::with_capacity(INJECT_BATCH_SIZE);
+    let mut injected_events = 0;
+
+    loop {
+        let line = match lines.next_line().await {
+            Ok(line) => line,
+            Err(error) => {
+                eprintln!("[tap] Failed to read events from stdin: {error}");
+                return exitcode::IOERR;
+            }
+        };
+        let done = line.is_none();
+        if let Some(line) = line.filter(|line| !line.trim().is_empty()) {
+            batch.push(line);
+        }
+
+        if batch.len() >= INJECT_BATCH_SIZE || (done && !batch.is_empty()) {
+            match client
+                .inject_events(component_id, std::mem::take(&mut batch), encoding)
+                .await
+            {
+                Ok(response) => injected_events += response.injected_events,
+                Err(error) => {
+                    eprintln!("[tap] Failed to inject events: {error}");
+                    return exitcode::UNAVAILABLE;
+                }
+            }
+        }
+
+        if done {
+            break;
+        }
+    }
+
+    if !opts.quiet {
+        eprintln!("[tap] Injected {injected_events} events into \"{component_id}\".");
+    }
+    exitcode::OK
+}
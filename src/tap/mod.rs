@@ -1,11 +1,15 @@
 //! Tap subcommand
+mod capture;
 mod cmd;
+mod inject;
+
+use std::path::PathBuf;
 
 use clap::Parser;
 pub(crate) use cmd::cmd;
 pub use cmd::tap;
 use url::Url;
-use vector_lib::tap::TapEncodingFormat;
+use vector_lib::{api_client::proto::InjectEncoding, tap::TapEncodingFormat};
 
 use crate::config::api::default_grpc_url;
 
@@ -56,6 +60,52 @@ pub struct Opts {
     /// Specifies a duration (in milliseconds) to sample logs (e.g. specifying 10000 will sample logs for 10 seconds then exit)
     #[arg(short = 'd', long)]
     duration_ms: Option<u64>,
+
+    /// VRL boolean expression that events must satisfy to be shown, evaluated by the server (e.g. `.status >= 500`)
+    #[arg(long, value_name = "VRL")]
+    filter: Option<String>,
+
+    /// Probability, within (0, 1], that each event matching the filter is shown. Sampling is done by the server
+    #[arg(long)]
+    sample_ratio: Option<f64>,
+
+    /// Maximum number of events shown per second, enforced by the server (must be >= 1)
+    #[arg(long, value_parser = clap::value_parser!(u32).range(1..))]
+    max_events_per_second: Option<u32>,
+
+    /// Write tapped events, with the IDs of the components they were tapped from and when, to the given file instead of printing them. Every tapped event is captured rather than a sample of `--limit` events per interval, and events dropped because they couldn't be sent in time are reported. The file can be played back with the `replay` source
+    #[arg(long, value_name = "PATH")]
+    capture: Option<PathBuf>,
+
+    /// Instead of observing events, inject events read from stdin (one per line) into the input of the given transform or sink. Injected events carry `vector.injected: true` in their metadata
+    #[arg(long, value_name = "COMPONENT_ID")]
+    inject: Option<String>,
+
+    /// Encoding of the events to inject
+    #[arg(default_value = "json", long)]
+    inject_format: InjectFormat,
+
+    /// Control token of the gRPC API server, required to inject events
+    #[arg(long, env = "VECTOR_API_CONTROL_TOKEN")]
+    control_token: Option<String>,
+}
+
+/// Encoding of events injected with `--inject`
+#[derive(Clone, Copy, Debug, clap::ValueEnum)]
+pub enum InjectFormat {
+    /// A JSON object, decoded into a log event
+    Json,
+    /// Vector's native JSON format, for logs, metrics, or traces
+    NativeJson,
+}
+
+impl From<InjectFormat> for InjectEncoding {
+    fn from(format: InjectFormat) -> Self {
+        match format {
+            InjectFormat::Json => Self::Json,
+            InjectFormat::NativeJson => Self::NativeJson,
+        }
+    }
 }
 
 impl Opts {
@@ -13,13 +13,13 @@ use stream_cancel::{StreamExt as StreamCancelExt, Trigger, Tripwire};
 use tokio::{
     select,
     sync::{mpsc::UnboundedSender, oneshot},
-    time::timeout,
+    time::{MissedTickBehavior, timeout},
 };
 use tracing::{Instrument, Span};
 use vector_lib::{
     EstimatedJsonEncodedSizeOf,
     buffers::{
-        BufferType, WhenFull,
+        BufferPool, BufferType, WhenFull,
         topology::{
             builder::TopologyBuilder,
             channel::{
@@ -39,7 +39,7 @@ use vector_vrl_metrics::MetricsStorage;
 
 use super::{
     BuiltBuffer, ConfigDiff,
-    fanout::{self, Fanout},
+    fanout::{self, Fanout, Isolation},
     schema,
     task::{Task, TaskOutput, TaskResult},
 };
@@ -47,14 +47,18 @@ use crate::{
     SourceSender,
     config::{
         ComponentKey, Config, DataType, EnrichmentTableConfig, Input, Inputs, OutputId,
-        ProxyConfig, SinkContext, SinkOuter, SourceContext, SourceOuter, TransformContext,
-        TransformOuter, TransformOutput,
+        ProxyConfig, Retirement, SinkContext, SinkOuter, SourceContext, SourceOuter,
+        TransformContext, TransformOuter, TransformOutput,
     },
     cpu_time::{CpuTimedExt, spawn_timed},
     event::{EventArray, EventContainer},
     extra_context::ExtraContext,
     internal_events::EventsReceived,
     shutdown::SourceShutdownCoordinator,
+    sinks::util::{
+        buffer_priority::BufferPriorityCondition,
+        dead_letter::{DeadLetterRegistry, DeadLetterRouter},
+    },
     spawn_named,
     topology::task::TaskError,
     transforms::{SyncTransform, TaskTransform, Transform, TransformOutputs, TransformOutputsBuf},
@@ -90,12 +94,15 @@ struct Builder<'a> {
     outputs: HashMap<OutputId, UnboundedSender<fanout::ControlMessage>>,
     tasks: HashMap<ComponentKey, Task>,
     buffers: HashMap<ComponentKey, BuiltBuffer>,
-    inputs: HashMap<ComponentKey, (BufferSender<EventArray>, Inputs<OutputId>)>,
+    inputs: HashMap<ComponentKey, (BufferSender<EventArray>, Inputs<OutputId>, Isolation)>,
     healthchecks: HashMap<ComponentKey, Task>,
     detach_triggers: HashMap<ComponentKey, Trigger>,
+    retirements: HashMap<ComponentKey, Retirement>,
     extra_context: ExtraContext,
     utilization_emitter: Option<UtilizationEmitter>,
     utilization_registry: UtilizationRegistry,
+    dead_letters: DeadLetterRegistry,
+    buffer_pool: Option<BufferPool>,
 }
 
 impl<'a> Builder<'a> {
@@ -105,6 +112,8 @@ impl<'a> Builder<'a> {
         buffers: HashMap<ComponentKey, BuiltBuffer>,
         extra_context: ExtraContext,
         utilization_registry: Option<UtilizationRegistry>,
+        dead_letters: DeadLetterRegistry,
+        buffer_pool: Option<BufferPool>,
     ) -> Self {
         // If registry is not passed, we need to build a whole new utilization emitter + registry
         // Otherwise, we just store the registry and reuse it for this build
@@ -125,9 +134,12 @@ impl<'a> Builder<'a> {
             inputs: HashMap::new(),
             healthchecks: HashMap::new(),
             detach_triggers: HashMap::new(),
+            retirements: HashMap::new(),
             extra_context,
             utilization_emitter: emitter,
             utilization_registry: registry,
+            dead_letters,
+            buffer_pool,
         }
     }
 
@@ -151,10 +163,13 @@ impl<'a> Builder<'a> {
                 healthchecks: self.healthchecks,
                 shutdown_coordinator: self.shutdown_coordinator,
                 detach_triggers: self.detach_triggers,
+                retirements: self.retirements,
                 metrics_storage: METRICS_STORAGE.clone(),
                 utilization: self
                     .utilization_emitter
                     .map(|e| (e, self.utilization_registry)),
+                dead_letters: self.dead_letters,
+                buffer_pool: self.buffer_pool,
             })
         } else {
             Err(self.errors)
@@ -372,6 +387,7 @@ impl<'a> Builder<'a> {
             schema_definitions,
             schema: self.config.schema,
             extra_context: self.extra_context.clone(),
+            dead_letters: self.dead_letters.clone(),
         };
         let server = match source.inner.build(context).await {
             Err(error) => {
@@ -536,7 +552,10 @@ impl<'a> Builder<'a> {
             } else {
                 None
             },
+            retirement: Retirement::default(),
         };
+        self.retirements
+            .insert(key.clone(), context.retirement.clone());
 
         let node = TransformNode::from_parts(key.clone(), &context, transform, &input_definitions);
 
@@ -553,6 +572,18 @@ impl<'a> Builder<'a> {
             Ok(transform) => transform,
         };
 
+        // Running concurrently would split the events held by the transform between its tasks and
+        // never flush them, so transforms that flush on an interval must run inline.
+        if node.enable_concurrency
+            && let Transform::Synchronous(t) = &transform
+            && t.flush_interval().is_some()
+        {
+            self.errors.push(format!(
+                "Transform \"{key}\": transforms that flush events on an interval can't enable concurrency"
+            ));
+            return;
+        }
+
         let metrics = ChannelMetricMetadata::new(BufferChannelKind::Transform, None);
         let (input_tx, input_rx) = TopologyBuilder::standalone_memory(
             TOPOLOGY_BUFFER_SIZE,
@@ -562,8 +593,10 @@ impl<'a> Builder<'a> {
             self.config.global.buffer_utilization_ewma_half_life_seconds,
         );
 
-        self.inputs
-            .insert(key.clone(), (input_tx, node.inputs.clone()));
+        self.inputs.insert(
+            key.clone(),
+            (input_tx, node.inputs.clone(), Isolation::Block),
+        );
 
         let (transform_task, transform_outputs) = self.build_transform(transform, node, input_rx);
 
@@ -611,6 +644,7 @@ impl<'a> Builder<'a> {
         enrichment_tables: &vector_lib::enrichment::TableRegistry,
     ) {
         let sink_inputs = &sink.inputs;
+        let isolation = sink.isolation;
         let healthcheck = sink.healthcheck();
         let enable_healthcheck = healthcheck.enabled && self.config.healthchecks.enabled;
         let healthcheck_timeout = healthcheck.timeout;
@@ -635,14 +669,39 @@ impl<'a> Builder<'a> {
                     BufferType::DiskV2 { .. } => "disk",
                 };
                 let buffer_span = error_span!("sink", buffer_type);
-                let buffer = sink
-                    .buffer
-                    .build(
-                        self.config.global.data_dir.clone(),
-                        key.to_string(),
-                        buffer_span,
-                    )
-                    .await;
+                let buffer_pool = self.buffer_pool.as_ref();
+                let buffer = match sink.buffer_priority.as_ref() {
+                    None => {
+                        sink.buffer
+                            .build(
+                                self.config.global.data_dir.clone(),
+                                key.to_string(),
+                                buffer_pool,
+                                buffer_span,
+                            )
+                            .await
+                    }
+                    Some(condition) => {
+                        let condition = match condition.build(enrichment_tables, &METRICS_STORAGE) {
+                            Ok(condition) => condition,
+                            Err(error) => {
+                                self.errors.push(format!(
+                                    "Sink \"{key}\": Invalid `buffer_priority`: {error}"
+                                ));
+                                return;
+                            }
+                        };
+                        sink.buffer
+                            .build_with_priority(
+                                self.config.global.data_dir.clone(),
+                                key.to_string(),
+                                buffer_pool,
+                                BufferPriorityCondition::new(condition),
+                                buffer_span,
+                            )
+                            .await
+                    }
+                };
                 match buffer {
                     Err(error) => {
                         self.errors.push(format!("Sink \"{key}\": {error}"));
@@ -665,6 +724,10 @@ impl<'a> Builder<'a> {
             extra_context: self.extra_context.clone(),
         };
 
+        let dead_letter_router = sink
+            .reroute_failed
+            .then(|| DeadLetterRouter::new(key.clone(), typetag, self.dead_letters.clone()));
+
         let (sink, healthcheck) = match sink.inner.build(cx).await {
             Err(error) => {
                 self.errors.push(format!("Sink \"{key}\": {error}"));
@@ -706,6 +769,15 @@ impl<'a> Builder<'a> {
                             events.estimated_json_encoded_size_of(),
                         ))
                     })
+                    .then(move |events| {
+                        let router = dead_letter_router.clone();
+                        async move {
+                            match router {
+                                Some(router) => router.track(events).await,
+                                None => events,
+                            }
+                        }
+                    })
                     .take_until_if(tripwire),
             )
             .await
@@ -759,7 +831,8 @@ impl<'a> Builder<'a> {
 
         let healthcheck_task = Task::new(key.clone(), typetag, healthcheck_task);
 
-        self.inputs.insert(key.clone(), (tx, sink_inputs.clone()));
+        self.inputs
+            .insert(key.clone(), (tx, sink_inputs.clone(), isolation));
         self.healthchecks.insert(key.clone(), healthcheck_task);
         self.tasks.insert(key.clone(), task);
         self.detach_triggers.insert(key.clone(), trigger);
@@ -1048,15 +1121,19 @@ pub async fn reload_enrichment_tables(config: &Config) {
 }
 
 pub struct TopologyPieces {
-    pub(super) inputs: HashMap<ComponentKey, (BufferSender<EventArray>, Inputs<OutputId>)>,
+    pub(super) inputs:
+        HashMap<ComponentKey, (BufferSender<EventArray>, Inputs<OutputId>, Isolation)>,
     pub(crate) outputs: HashMap<ComponentKey, HashMap<Option<String>, fanout::ControlChannel>>,
     pub(super) tasks: HashMap<ComponentKey, Task>,
     pub(crate) source_tasks: HashMap<ComponentKey, Task>,
     pub(super) healthchecks: HashMap<ComponentKey, Task>,
     pub(crate) shutdown_coordinator: SourceShutdownCoordinator,
     pub(crate) detach_triggers: HashMap<ComponentKey, Trigger>,
+    pub(crate) retirements: HashMap<ComponentKey, Retirement>,
     pub(crate) metrics_storage: MetricsStorage,
     pub(crate) utilization: Option<(UtilizationEmitter, UtilizationRegistry)>,
+    pub(crate) dead_letters: DeadLetterRegistry,
+    pub(crate) buffer_pool: Option<BufferPool>,
 }
 
 /// Builder for constructing TopologyPieces with a fluent API.
@@ -1076,10 +1153,15 @@ pub struct TopologyPiecesBuilder<'a> {
     buffers: HashMap<ComponentKey, BuiltBuffer>,
     extra_context: ExtraContext,
     utilization_registry: Option<UtilizationRegistry>,
+    dead_letters: DeadLetterRegistry,
+    buffer_pool: Option<BufferPool>,
 }
 
 impl<'a> TopologyPiecesBuilder<'a> {
     /// Creates a new builder with required parameters.
+    ///
+    /// The disk buffers get a new pool sized from the global `buffer_pool` option, unless the pool
+    /// of a running topology is passed with [`Self::with_buffer_pool`].
     pub fn new(config: &'a Config, diff: &'a ConfigDiff) -> Self {
         Self {
             config,
@@ -1087,6 +1169,12 @@ impl<'a> TopologyPiecesBuilder<'a> {
             buffers: HashMap::new(),
             extra_context: ExtraContext::default(),
             utilization_registry: None,
+            dead_letters: DeadLetterRegistry::default(),
+            buffer_pool: config
+                .global
+                .buffer_pool
+                .as_ref()
+                .map(|pool| BufferPool::new(pool.max_size.get())),
         }
     }
 
@@ -1108,6 +1196,21 @@ impl<'a> TopologyPiecesBuilder<'a> {
         self
     }
 
+    /// Sets the registry of the subscriptions to the failed events of the sinks of the topology.
+    pub fn with_dead_letter_registry(mut self, registry: DeadLetterRegistry) -> Self {
+        self.dead_letters = registry;
+        self
+    }
+
+    /// Sets the pool shared by the disk buffers of the topology.
+    ///
+    /// Buffers outlive the topology pieces they were built for across reloads, so a running
+    /// topology passes the pool it was started with to every rebuild.
+    pub fn with_buffer_pool(mut self, buffer_pool: Option<BufferPool>) -> Self {
+        self.buffer_pool = buffer_pool;
+        self
+    }
+
     /// Builds the topology pieces, returning errors if any occur.
     ///
     /// Use this method when you need to handle errors explicitly,
@@ -1119,6 +1222,8 @@ impl<'a> TopologyPiecesBuilder<'a> {
             self.buffers,
             self.extra_context,
             self.utilization_registry,
+            self.dead_letters,
+            self.buffer_pool,
         )
         .build()
         .await
@@ -1274,15 +1379,37 @@ impl Runner {
             .into_stream()
             .filter(move |events| ready(filter_events_type(events, self.input_type)));
 
+        let mut flush_interval = self.transform.flush_interval().map(|period| {
+            let mut interval = tokio::time::interval(period);
+            interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+            interval
+        });
+
         self.timer_tx.try_send_start_wait();
-        while let Some(events) = input_rx.next().await {
-            self.on_events_received(&events);
-            self.transform.transform_all(events, &mut outputs_buf);
+        loop {
+            select! {
+                events = input_rx.next() => {
+                    let Some(events) = events else { break };
+                    self.on_events_received(&events);
+                    self.transform.transform_all(events, &mut outputs_buf);
+                }
+                _ = async { flush_interval.as_mut().expect("flush interval").tick().await },
+                    if flush_interval.is_some() =>
+                {
+                    self.timer_tx.try_send_stop_wait();
+                    self.transform.flush(&mut outputs_buf);
+                }
+            }
             self.send_outputs(&mut outputs_buf)
                 .await
                 .map_err(TaskError::wrapped)?;
         }
 
+        self.transform.flush_all(&mut outputs_buf);
+        self.send_outputs(&mut outputs_buf)
+            .await
+            .map_err(TaskError::wrapped)?;
+
         Ok(TaskOutput::Transform)
     }
 
@@ -0,0 +1,242 @@
+//! Runtime control of a running topology.
+//!
+//! Control requests are issued by the API and handled by the application's main loop, which owns
+//! the [`TopologyController`][super::TopologyController]. Each request carries a oneshot channel
+//! used to report the outcome back to the caller.
+
+use snafu::Snafu;
+use tokio::sync::{mpsc, oneshot};
+use vector_lib::{buffers::topology::channel::BufferSender, config::LogNamespace};
+
+use crate::{
+    config::{BufferAction, ComponentKey, ConfigDiff, Format},
+    event::EventArray,
+};
+
+/// Maximum number of control requests that can be queued before callers have to wait.
+const CONTROL_CHANNEL_CAPACITY: usize = 16;
+
+pub type ControlTx = mpsc::Sender<ControlRequest>;
+pub type ControlRx = mpsc::Receiver<ControlRequest>;
+
+/// Creates the channel used to send control requests to the application's main loop.
+pub fn control_channel() -> (ControlTx, ControlRx) {
+    mpsc::channel(CONTROL_CHANNEL_CAPACITY)
+}
+
+/// An operation to apply to a single component of the running topology.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ControlCommand {
+    /// Stops a sink in place, keeping its buffer so that upstream components continue writing
+    /// into it.
+    Pause(ComponentKey),
+
+    /// Restarts a paused sink or a drained source using its current configuration.
+    Resume(ComponentKey),
+
+    /// Gracefully shuts down a source, letting in-flight events flow downstream.
+    Drain(ComponentKey),
+}
+
+impl ControlCommand {
+    /// Gets the key of the component targeted by this command.
+    pub const fn component_key(&self) -> &ComponentKey {
+        match self {
+            Self::Pause(key) | Self::Resume(key) | Self::Drain(key) => key,
+        }
+    }
+
+    /// Gets the name of the operation, for use in logs and metrics.
+    pub const fn operation(&self) -> &'static str {
+        match self {
+            Self::Pause(_) => "pause",
+            Self::Resume(_) => "resume",
+            Self::Drain(_) => "drain",
+        }
+    }
+}
+
+/// A request handled by the application's main loop, along with the channel used to send back
+/// its outcome.
+#[derive(Debug)]
+pub enum ControlRequest {
+    /// Applies a command to a single component.
+    Component {
+        command: ControlCommand,
+        reply: oneshot::Sender<Result<ComponentState, ControlError>>,
+    },
+
+    /// Gets the input of a transform or sink, so that events can be injected into it.
+    ComponentInput {
+        key: ComponentKey,
+        reply: oneshot::Sender<Result<ComponentInput, ControlError>>,
+    },
+
+    /// Reloads the configuration from disk.
+    Reload {
+        reply: oneshot::Sender<ReloadReport>,
+    },
+
+    /// Computes the changes a reload would make, without applying them.
+    DiffConfig {
+        /// The configuration to compare against the running one, along with its format. When
+        /// unset, the configuration is loaded from disk as a reload would.
+        config: Option<(String, Format)>,
+        reply: oneshot::Sender<DiffReport>,
+    },
+
+    /// Serializes the configuration of the running topology, with secrets redacted.
+    EffectiveConfig {
+        format: Format,
+        reply: oneshot::Sender<Result<String, String>>,
+    },
+}
+
+impl ControlRequest {
+    /// Creates a new component request, returning it along with the receiver for its outcome.
+    pub fn component(
+        command: ControlCommand,
+    ) -> (
+        Self,
+        oneshot::Receiver<Result<ComponentState, ControlError>>,
+    ) {
+        let (reply, rx) = oneshot::channel();
+        (Self::Component { command, reply }, rx)
+    }
+
+    /// Creates a new component input request, returning it along with the receiver for the input.
+    pub fn component_input(
+        key: ComponentKey,
+    ) -> (
+        Self,
+        oneshot::Receiver<Result<ComponentInput, ControlError>>,
+    ) {
+        let (reply, rx) = oneshot::channel();
+        (Self::ComponentInput { key, reply }, rx)
+    }
+
+    /// Creates a new reload request, returning it along with the receiver for its outcome.
+    pub fn reload() -> (Self, oneshot::Receiver<ReloadReport>) {
+        let (reply, rx) = oneshot::channel();
+        (Self::Reload { reply }, rx)
+    }
+
+    /// Creates a new dry-run reload request, returning it along with the receiver for its outcome.
+    pub fn diff_config(config: Option<(String, Format)>) -> (Self, oneshot::Receiver<DiffReport>) {
+        let (reply, rx) = oneshot::channel();
+        (Self::DiffConfig { config, reply }, rx)
+    }
+
+    /// Creates a new effective configuration request, returning it along with the receiver for
+    /// the serialized configuration.
+    pub fn effective_config(format: Format) -> (Self, oneshot::Receiver<Result<String, String>>) {
+        let (reply, rx) = oneshot::channel();
+        (Self::EffectiveConfig { format, reply }, rx)
+    }
+}
+
+/// The input buffer of a transform or sink.
+///
+/// Events sent into it are processed by the component as if they came from one of its inputs.
+/// The sender keeps pointing at the same buffer if the component is later rebuilt by a reload, in
+/// which case events are only delivered if the buffer is reused.
+#[derive(Debug)]
+pub struct ComponentInput {
+    pub sender: BufferSender<EventArray>,
+
+    /// The log namespace of the running configuration, used to decode injected log events.
+    pub log_namespace: LogNamespace,
+}
+
+/// The outcome of a reload requested through the control channel.
+#[derive(Debug)]
+pub struct ReloadReport {
+    pub status: ReloadStatus,
+
+    /// The changes between the previous and the new configuration, if the new configuration
+    /// could be loaded.
+    pub diff: Option<ConfigDiff>,
+
+    /// The errors that prevented the new configuration from being loaded or applied.
+    pub errors: Vec<String>,
+}
+
+impl ReloadReport {
+    /// Creates a report for a configuration that could not be loaded.
+    pub const fn rejected(errors: Vec<String>) -> Self {
+        Self {
+            status: ReloadStatus::Rejected,
+            diff: None,
+            errors,
+        }
+    }
+}
+
+/// The changes a reload would make, as computed by a dry run.
+#[derive(Debug, Default)]
+pub struct DiffReport {
+    /// The changes between the running and the new configuration, if the new configuration
+    /// could be loaded.
+    pub diff: Option<ConfigDiff>,
+
+    /// What would happen to the buffer of every changed or removed sink.
+    pub buffers: Vec<(ComponentKey, BufferAction)>,
+
+    /// The errors that would prevent the new configuration from being loaded or applied.
+    pub errors: Vec<String>,
+}
+
+/// How a reload requested through the control channel ended.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ReloadStatus {
+    /// The new configuration is running.
+    Applied,
+
+    /// The new configuration could not be applied, and the previous one is still running.
+    RolledBack,
+
+    /// The new configuration could not be loaded, and the running topology was left untouched.
+    Rejected,
+
+    /// The previous configuration could not be restored, and Vector is shutting down.
+    Failed,
+}
+
+/// The lifecycle state of a component with respect to control operations.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ComponentState {
+    /// The component is running normally.
+    Running,
+
+    /// The sink has been stopped, and its buffer is held until it is resumed.
+    Paused,
+
+    /// The source has been shut down, and will not produce events until it is resumed.
+    Drained,
+}
+
+#[derive(Debug, Snafu)]
+pub enum ControlError {
+    #[snafu(display("component \"{key}\" does not exist"))]
+    NotFound { key: ComponentKey },
+    #[snafu(display("cannot {operation} \"{key}\": this operation only applies to {supported}"))]
+    Unsupported {
+        key: ComponentKey,
+        operation: &'static str,
+        supported: &'static str,
+    },
+    #[snafu(display("component \"{key}\" is already stopped"))]
+    AlreadyStopped { key: ComponentKey },
+    #[snafu(display("component \"{key}\" is not paused or drained"))]
+    NotStopped { key: ComponentKey },
+    #[snafu(display(
+        "cannot drain \"{key}\": it is the last running source, and draining it would shut down Vector"
+    ))]
+    LastSource { key: ComponentKey },
+    #[snafu(display("component \"{key}\" failed while stopping, and its buffer was lost"))]
+    StopFailed { key: ComponentKey },
+    #[snafu(display("failed to rebuild component \"{key}\""))]
+    BuildFailed { key: ComponentKey },
+    #[snafu(display("the topology is shutting down"))]
+    ShuttingDown,
+}
@@ -10,7 +10,10 @@ use crate::{
     extra_context::ExtraContext,
     internal_events::{VectorRecoveryError, VectorReloadError, VectorReloaded},
     signal::ShutdownError,
-    topology::{ReloadError, RunningTopology},
+    topology::{
+        ReloadError, RunningTopology,
+        control::{ComponentState, ControlCommand, ControlError, DiffReport},
+    },
 };
 
 #[derive(Clone, Debug)]
@@ -40,6 +43,8 @@ pub struct TopologyController {
     pub require_healthy: Option<bool>,
     #[cfg(feature = "api")]
     pub api_server: Option<api::GrpcServer>,
+    #[cfg(feature = "api")]
+    pub control_tx: crate::topology::control::ControlTx,
     pub extra_context: ExtraContext,
 }
 
@@ -56,7 +61,9 @@ impl std::fmt::Debug for TopologyController {
 pub enum ReloadOutcome {
     MissingApiKey,
     Success,
-    RolledBack,
+    /// The new configuration was not applied, and the previous one is still running. Carries the
+    /// reasons the reload failed.
+    RolledBack(Vec<String>),
     FatalError(ShutdownError),
 }
 
@@ -76,7 +83,13 @@ impl TopologyController {
         } else if self.api_server.is_none() {
             debug!("Starting gRPC API server.");
 
-            match api::GrpcServer::start(self.topology.config(), self.topology.watch()).await {
+            match api::GrpcServer::start(
+                self.topology.config(),
+                self.topology.watch(),
+                self.control_tx.clone(),
+            )
+            .await
+            {
                 Ok(api_server) => {
                     let addr = api_server.addr();
                     info!(
@@ -101,11 +114,17 @@ impl TopologyController {
             }
         }
 
-        match self
+        let result = self
             .topology
             .reload_config_and_respawn(new_config, self.extra_context.clone())
-            .await
-        {
+            .await;
+        let errors = match &result {
+            Ok(()) => Vec::new(),
+            Err(ReloadError::TopologyBuildFailed { errors }) => errors.clone(),
+            Err(error) => vec![error.to_string()],
+        };
+
+        match result {
             Ok(()) => {
                 emit!(VectorReloaded {
                     config_paths: &self.config_paths
@@ -121,7 +140,7 @@ impl TopologyController {
                 emit!(VectorReloadError {
                     reason: "global_options_changed",
                 });
-                ReloadOutcome::RolledBack
+                ReloadOutcome::RolledBack(errors)
             }
             Err(ReloadError::GlobalDiffFailed { source }) => {
                 error!(
@@ -132,13 +151,24 @@ impl TopologyController {
                 emit!(VectorReloadError {
                     reason: "global_diff_failed",
                 });
-                ReloadOutcome::RolledBack
+                ReloadOutcome::RolledBack(errors)
             }
-            Err(ReloadError::TopologyBuildFailed) => {
+            Err(ReloadError::ResumeFailed { source }) => {
+                error!(
+                    message = "Config reload rejected because stopped components could not be resumed.",
+                    error = %source,
+                    internal_log_rate_limit = false,
+                );
+                emit!(VectorReloadError {
+                    reason: "resume_failed",
+                });
+                ReloadOutcome::RolledBack(errors)
+            }
+            Err(ReloadError::TopologyBuildFailed { .. }) => {
                 emit!(VectorReloadError {
                     reason: "topology_build_failed",
                 });
-                ReloadOutcome::RolledBack
+                ReloadOutcome::RolledBack(errors)
             }
             Err(ReloadError::FailedToRestore) => {
                 emit!(VectorReloadError {
@@ -150,7 +180,59 @@ impl TopologyController {
         }
     }
 
-    #[cfg_attr(not(feature = "api"), allow(unused_mut))]
+    /// Applies a control command to a single component of the running topology.
+    ///
+    /// Returns the state of the component once the command has been applied.
+    pub async fn control(
+        &mut self,
+        command: ControlCommand,
+    ) -> Result<ComponentState, ControlError> {
+        let key = command.component_key().clone();
+        match command {
+            ControlCommand::Pause(_) => self.topology.pause_component(&key).await?,
+            ControlCommand::Drain(_) => self.topology.drain_component(&key).await?,
+            ControlCommand::Resume(_) => {
+                self.topology
+                    .resume_component(&key, self.extra_context.clone())
+                    .await?
+            }
+        }
+        Ok(self.topology.component_state(&key))
+    }
+
+    /// Computes the changes a reload from disk with the given configuration would make, without
+    /// applying them.
+    pub fn diff_config(&self, new_config: &config::Config) -> DiffReport {
+        let running = self.topology.config();
+
+        // Reloading from disk also rebuilds transforms that depend on external files.
+        let diff = self
+            .topology
+            .config_diff(new_config, new_config.transform_keys_with_external_files());
+        let buffers = diff.sink_buffer_changes(running, new_config);
+
+        let errors = ReloadError::check_global_options(running, new_config)
+            .err()
+            .map(|error| error.to_string())
+            .into_iter()
+            .collect();
+
+        DiffReport {
+            diff: Some(diff),
+            buffers,
+            errors,
+        }
+    }
+
+    /// Serializes the configuration of the running topology in the given format.
+    ///
+    /// Secrets are resolved when the configuration is loaded, so every sensitive option, as well as
+    /// every value interpolated from environment variables or secret backends, is redacted in the
+    /// output.
+    pub fn effective_config(&self, format: config::Format) -> Result<String, String> {
+        self.topology.config().serialize_redacted(format)
+    }
+
     pub async fn stop(mut self) {
         // Phase 1: Mark the gRPC API as unavailable so that external probes
         // (e.g. Kubernetes readiness) fail early and stop routing traffic
@@ -160,7 +242,21 @@ impl TopologyController {
             server.set_not_serving().await;
         }
 
-        // Phase 2: Drain the topology -- shuts down sources, waits for
+        // Phase 2: Restart any paused sinks so that their buffered events are
+        // flushed during shutdown like everything else. Drained sources stay
+        // stopped.
+        if let Err(error) = self
+            .topology
+            .resume_paused_sinks(self.extra_context.clone())
+            .await
+        {
+            warn!(
+                message = "Failed to resume paused sinks before shutdown.",
+                %error,
+            );
+        }
+
+        // Phase 3: Drain the topology -- shuts down sources, waits for
         // in-flight events to flush through transforms and sinks.
         self.topology.stop().await;
     }
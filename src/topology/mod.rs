@@ -11,6 +11,7 @@ pub(super) use vector_lib::fanout;
 pub mod schema;
 
 pub mod builder;
+pub mod control;
 mod controller;
 mod ready_arrays;
 mod running;
@@ -12,7 +12,7 @@ use tokio::{
 };
 use tracing::Instrument;
 use vector_lib::{
-    buffers::topology::channel::BufferSender,
+    buffers::{BufferPool, topology::channel::BufferSender},
     shutdown::ShutdownSignal,
     tap::topology::{TapOutput, TapResource, WatchRx, WatchTx},
     trigger::DisabledTrigger,
@@ -21,16 +21,21 @@ use vector_lib::{
 use super::{
     BuiltBuffer, TaskHandle,
     builder::{self, TopologyPieces, TopologyPiecesBuilder, reload_enrichment_tables},
-    fanout::{ControlChannel, ControlMessage},
+    control::{ComponentInput, ComponentState, ControlError},
+    fanout::{ControlChannel, ControlMessage, Isolation},
     handle_errors, retain, take_healthchecks,
     task::{Task, TaskOutput},
 };
 use crate::{
-    config::{ComponentKey, Config, ConfigDiff, HealthcheckOptions, Inputs, OutputId, Resource},
+    config::{
+        ComponentKey, Config, ConfigDiff, HealthcheckOptions, Inputs, OutputId, Resource,
+        Retirement,
+    },
     event::EventArray,
     extra_context::ExtraContext,
     shutdown::SourceShutdownCoordinator,
     signal::ShutdownError,
+    sinks::util::dead_letter::DeadLetterRegistry,
     spawn_named,
     utilization::UtilizationRegistry,
 };
@@ -44,9 +49,33 @@ pub enum ReloadError {
     #[snafu(display("failed to compute global diff: {}", source))]
     GlobalDiffFailed { source: serde_json::Error },
     #[snafu(display("topology build failed"))]
-    TopologyBuildFailed,
+    TopologyBuildFailed { errors: Vec<String> },
     #[snafu(display("failed to restore previous config"))]
     FailedToRestore,
+    #[snafu(display("failed to resume stopped components: {}", source))]
+    ResumeFailed { source: ControlError },
+}
+
+impl ReloadError {
+    /// Checks that a reload from `old` to `new` doesn't change any global options, which can't be
+    /// reloaded.
+    pub fn check_global_options(old: &Config, new: &Config) -> Result<(), Self> {
+        if old.global == new.global {
+            return Ok(());
+        }
+        match old.global.diff(&new.global) {
+            Ok(changed_fields) => Err(Self::GlobalOptionsChanged { changed_fields }),
+            Err(source) => Err(Self::GlobalDiffFailed { source }),
+        }
+    }
+}
+
+/// A component that was stopped through a control request and is waiting to be resumed.
+enum StoppedComponent {
+    /// A paused sink, along with its buffer.
+    Sink(BuiltBuffer),
+    /// A drained source.
+    Source,
 }
 
 #[allow(dead_code)]
@@ -60,16 +89,20 @@ pub struct RunningTopology {
     tasks: HashMap<ComponentKey, TaskHandle>,
     shutdown_coordinator: SourceShutdownCoordinator,
     detach_triggers: HashMap<ComponentKey, DisabledTrigger>,
+    retirements: HashMap<ComponentKey, Retirement>,
     pub(crate) config: Config,
     pub(crate) abort_tx: mpsc::UnboundedSender<ShutdownError>,
     watch: (WatchTx, WatchRx),
     graceful_shutdown_duration: Option<Duration>,
     utilization_registry: Option<UtilizationRegistry>,
+    dead_letters: DeadLetterRegistry,
+    buffer_pool: Option<BufferPool>,
     utilization_task: Option<TaskHandle>,
     utilization_task_shutdown_trigger: Option<Trigger>,
     metrics_task: Option<TaskHandle>,
     metrics_task_shutdown_trigger: Option<Trigger>,
     pending_reload: Option<HashSet<ComponentKey>>,
+    stopped_components: HashMap<ComponentKey, StoppedComponent>,
 }
 
 impl RunningTopology {
@@ -82,6 +115,7 @@ impl RunningTopology {
             component_type_names: HashMap::new(),
             shutdown_coordinator: SourceShutdownCoordinator::default(),
             detach_triggers: HashMap::new(),
+            retirements: HashMap::new(),
             source_tasks: HashMap::new(),
             tasks: HashMap::new(),
             abort_tx,
@@ -89,11 +123,14 @@ impl RunningTopology {
             graceful_shutdown_duration: config.graceful_shutdown_duration,
             config,
             utilization_registry: None,
+            dead_letters: DeadLetterRegistry::default(),
+            buffer_pool: None,
             utilization_task: None,
             utilization_task_shutdown_trigger: None,
             metrics_task: None,
             metrics_task_shutdown_trigger: None,
             pending_reload: None,
+            stopped_components: HashMap::new(),
         }
     }
 
@@ -285,23 +322,20 @@ impl RunningTopology {
     ) -> Result<(), ReloadError> {
         info!("Reloading running topology with new configuration.");
 
-        if self.config.global != new_config.global {
-            return match self.config.global.diff(&new_config.global) {
-                Ok(changed_fields) => Err(ReloadError::GlobalOptionsChanged { changed_fields }),
-                Err(source) => Err(ReloadError::GlobalDiffFailed { source }),
-            };
-        }
+        // Bring back any paused or drained components first, since the reload process assumes
+        // that every component in the current configuration is running.
+        self.resume_all_components(extra_context.clone())
+            .await
+            .map_err(|source| ReloadError::ResumeFailed { source })?;
+
+        ReloadError::check_global_options(&self.config, &new_config)?;
 
         // Calculate the change between the current configuration and the new configuration, and
         // shutdown any components that are changing so that we can reclaim their buffers before
         // spawning the new version of the component.
         //
         // We also shutdown any component that is simply being removed entirely.
-        let diff = if let Some(components) = &self.pending_reload {
-            ConfigDiff::new(&self.config, &new_config, components.clone())
-        } else {
-            ConfigDiff::new(&self.config, &new_config, HashSet::new())
-        };
+        let diff = self.config_diff(&new_config, HashSet::new());
         let buffers = self.shutdown_diff(&diff, &new_config).await;
 
         // Gives windows some time to make available any port
@@ -315,29 +349,41 @@ impl RunningTopology {
         // Try to build all of the new components coming from the new configuration.  If we can
         // successfully build them, we'll attempt to connect them up to the topology and spawn their
         // respective component tasks.
-        if let Some(mut new_pieces) = TopologyPiecesBuilder::new(&new_config, &diff)
+        let build_errors = match TopologyPiecesBuilder::new(&new_config, &diff)
             .with_buffers(buffers.clone())
             .with_extra_context(extra_context.clone())
             .with_utilization_registry(self.utilization_registry.clone())
-            .build_or_log_errors()
+            .with_dead_letter_registry(self.dead_letters.clone())
+            .with_buffer_pool(self.buffer_pool.clone())
+            .build()
             .await
         {
-            // If healthchecks are configured for any of the changing/new components, try running
-            // them before moving forward with connecting and spawning.  In some cases, healthchecks
-            // failing may be configured as a non-blocking issue and so we'll still continue on.
-            if self
-                .run_healthchecks(&diff, &mut new_pieces, new_config.healthchecks)
-                .await
-            {
-                self.connect_diff(&diff, &mut new_pieces).await;
-                self.spawn_diff(&diff, new_pieces);
-                self.config = new_config;
+            Ok(mut new_pieces) => {
+                // If healthchecks are configured for any of the changing/new components, try
+                // running them before moving forward with connecting and spawning.  In some
+                // cases, healthchecks failing may be configured as a non-blocking issue and so
+                // we'll still continue on.
+                if self
+                    .run_healthchecks(&diff, &mut new_pieces, new_config.healthchecks)
+                    .await
+                {
+                    self.connect_diff(&diff, &mut new_pieces).await;
+                    self.spawn_diff(&diff, new_pieces);
+                    self.config = new_config;
 
-                info!("New configuration loaded successfully.");
+                    info!("New configuration loaded successfully.");
 
-                return Ok(());
+                    return Ok(());
+                }
+                vec!["Sinks unhealthy.".to_string()]
             }
-        }
+            Err(errors) => {
+                for error in &errors {
+                    error!(message = "Configuration error.", %error, internal_log_rate_limit = false);
+                }
+                errors
+            }
+        };
 
         // We failed to build, connect, and spawn all of the changed/new components, so we flip
         // around the configuration differential to generate all the components that we need to
@@ -349,6 +395,8 @@ impl RunningTopology {
             .with_buffers(buffers)
             .with_extra_context(extra_context.clone())
             .with_utilization_registry(self.utilization_registry.clone())
+            .with_dead_letter_registry(self.dead_letters.clone())
+            .with_buffer_pool(self.buffer_pool.clone())
             .build_or_log_errors()
             .await
             && self
@@ -360,7 +408,9 @@ impl RunningTopology {
 
             info!("Old configuration restored successfully.");
 
-            return Err(ReloadError::TopologyBuildFailed);
+            return Err(ReloadError::TopologyBuildFailed {
+                errors: build_errors,
+            });
         }
 
         error!(
@@ -371,6 +421,244 @@ impl RunningTopology {
         Err(ReloadError::FailedToRestore)
     }
 
+    /// Computes the differential between the running configuration and the given one, as a reload
+    /// with that configuration would apply it.
+    ///
+    /// Components in `components_to_reload` are treated as changed even if their configuration is
+    /// the same, on top of those already scheduled for reload.
+    pub fn config_diff(
+        &self,
+        new_config: &Config,
+        mut components_to_reload: HashSet<ComponentKey>,
+    ) -> ConfigDiff {
+        if let Some(pending) = &self.pending_reload {
+            components_to_reload.extend(pending.iter().cloned());
+        }
+        ConfigDiff::new(&self.config, new_config, components_to_reload)
+    }
+
+    /// Gets the input buffer of the given transform or sink.
+    pub fn component_input(&self, key: &ComponentKey) -> Result<ComponentInput, ControlError> {
+        if !contains_component(&self.config, key) {
+            return Err(ControlError::NotFound { key: key.clone() });
+        }
+
+        let sender = self
+            .inputs
+            .get(key)
+            .cloned()
+            .ok_or_else(|| ControlError::Unsupported {
+                key: key.clone(),
+                operation: "inject into",
+                supported: "transforms and sinks",
+            })?;
+
+        Ok(ComponentInput {
+            sender,
+            log_namespace: self.config.schema.log_namespace(),
+        })
+    }
+
+    /// Gets the control state of the given component.
+    pub fn component_state(&self, key: &ComponentKey) -> ComponentState {
+        match self.stopped_components.get(key) {
+            None => ComponentState::Running,
+            Some(StoppedComponent::Sink(_)) => ComponentState::Paused,
+            Some(StoppedComponent::Source) => ComponentState::Drained,
+        }
+    }
+
+    /// Stops the given sink in place, keeping its buffer.
+    ///
+    /// Upstream components stay connected to the buffer, so events accumulate in it while the
+    /// sink is paused, and the buffer's `when_full` behavior applies once it fills up. The sink
+    /// is rebuilt from its current configuration, and picks up where it left off, when it is
+    /// resumed with [`RunningTopology::resume_component`].
+    pub async fn pause_component(&mut self, key: &ComponentKey) -> Result<(), ControlError> {
+        self.check_stoppable(key, "pause", "sinks", |config| config.sink(key).is_some())?;
+
+        debug!(component_id = %key, "Pausing sink.");
+
+        // Cancelling the detach trigger ends the sink's input stream without closing the buffer,
+        // which lets the sink finish naturally and hand its buffer receiver back to us.
+        let tx = self.inputs.get(key).cloned().expect("sink input exists");
+        self.detach_triggers
+            .remove(key)
+            .expect("sink detach trigger exists")
+            .into_inner()
+            .cancel();
+
+        let task = self.tasks.remove(key).expect("sink task exists");
+        match task.await {
+            Ok(Ok(TaskOutput::Sink(rx))) => {
+                let buffer = (tx, Arc::new(Mutex::new(Some(rx.into_inner()))));
+                self.stopped_components
+                    .insert(key.clone(), StoppedComponent::Sink(buffer));
+                info!(message = "Sink paused.", component_id = %key);
+                Ok(())
+            }
+            _ => Err(ControlError::StopFailed { key: key.clone() }),
+        }
+    }
+
+    /// Gracefully shuts down the given source.
+    ///
+    /// The source stops accepting new data and its in-flight events are sent downstream. It is
+    /// rebuilt from its current configuration when resumed with
+    /// [`RunningTopology::resume_component`].
+    pub async fn drain_component(&mut self, key: &ComponentKey) -> Result<(), ControlError> {
+        self.check_stoppable(key, "drain", "sources", |config| {
+            config.source(key).is_some()
+        })?;
+
+        // Once every source is finished the application shuts down, so refuse to drain the last
+        // one that is still running.
+        let running_sources = self
+            .config
+            .sources()
+            .filter(|(source_key, _)| !self.stopped_components.contains_key(source_key))
+            .count();
+        if running_sources <= 1 {
+            return Err(ControlError::LastSource { key: key.clone() });
+        }
+
+        debug!(component_id = %key, "Draining source.");
+
+        // The outputs of the source are left in place: downstream components keep referring to
+        // them, and they are replaced when the source is resumed.
+        let deadline = Instant::now() + Duration::from_secs(30);
+        self.shutdown_coordinator
+            .shutdown_source(key, deadline)
+            .await;
+
+        if let Some(task) = self.source_tasks.remove(key)
+            && !matches!(task.await, Ok(Ok(_)))
+        {
+            return Err(ControlError::StopFailed { key: key.clone() });
+        }
+
+        self.stopped_components
+            .insert(key.clone(), StoppedComponent::Source);
+        info!(message = "Source drained.", component_id = %key);
+        Ok(())
+    }
+
+    /// Restarts a component that was stopped by [`RunningTopology::pause_component`] or
+    /// [`RunningTopology::drain_component`].
+    ///
+    /// If the component can't be rebuilt, it stays stopped, and paused sinks retain their buffer.
+    pub async fn resume_component(
+        &mut self,
+        key: &ComponentKey,
+        extra_context: ExtraContext,
+    ) -> Result<(), ControlError> {
+        let Some(stopped) = self.stopped_components.remove(key) else {
+            return if contains_component(&self.config, key) {
+                Err(ControlError::NotStopped { key: key.clone() })
+            } else {
+                Err(ControlError::NotFound { key: key.clone() })
+            };
+        };
+
+        debug!(component_id = %key, "Resuming component.");
+
+        let diff = ConfigDiff::new(&self.config, &self.config, HashSet::from([key.clone()]));
+        let buffers = match &stopped {
+            StoppedComponent::Sink(buffer) => HashMap::from([(key.clone(), buffer.clone())]),
+            StoppedComponent::Source => HashMap::new(),
+        };
+
+        let Some(mut new_pieces) = TopologyPiecesBuilder::new(&self.config, &diff)
+            .with_buffers(buffers)
+            .with_extra_context(extra_context)
+            .with_utilization_registry(self.utilization_registry.clone())
+            .with_dead_letter_registry(self.dead_letters.clone())
+            .with_buffer_pool(self.buffer_pool.clone())
+            .build_or_log_errors()
+            .await
+        else {
+            self.stopped_components.insert(key.clone(), stopped);
+            return Err(ControlError::BuildFailed { key: key.clone() });
+        };
+
+        if matches!(stopped, StoppedComponent::Sink(_)) {
+            // Upstream fanouts still hold the sender for the buffer we're reusing, so we pause
+            // those connections here and let `connect_diff` replace them, just as a reload does
+            // for sinks whose buffer is reused.
+            for input in self.config.inputs_for_node(key).into_iter().flatten() {
+                if let Some(output) = self.outputs.get(input) {
+                    _ = output.send(ControlMessage::Pause(key.clone()));
+                }
+            }
+        }
+
+        self.connect_diff(&diff, &mut new_pieces).await;
+        self.spawn_diff(&diff, new_pieces);
+
+        info!(message = "Component resumed.", component_id = %key);
+        Ok(())
+    }
+
+    /// Restarts every component that is currently paused or drained.
+    pub async fn resume_all_components(
+        &mut self,
+        extra_context: ExtraContext,
+    ) -> Result<(), ControlError> {
+        self.resume_stopped_components(extra_context, |_| true)
+            .await
+    }
+
+    /// Restarts every paused sink, leaving drained sources stopped.
+    pub async fn resume_paused_sinks(
+        &mut self,
+        extra_context: ExtraContext,
+    ) -> Result<(), ControlError> {
+        self.resume_stopped_components(extra_context, |stopped| {
+            matches!(stopped, StoppedComponent::Sink(_))
+        })
+        .await
+    }
+
+    async fn resume_stopped_components(
+        &mut self,
+        extra_context: ExtraContext,
+        filter: impl Fn(&StoppedComponent) -> bool,
+    ) -> Result<(), ControlError> {
+        let keys = self
+            .stopped_components
+            .iter()
+            .filter(|(_, stopped)| filter(stopped))
+            .map(|(key, _)| key.clone())
+            .collect::<Vec<_>>();
+        for key in keys {
+            self.resume_component(&key, extra_context.clone()).await?;
+        }
+        Ok(())
+    }
+
+    fn check_stoppable(
+        &self,
+        key: &ComponentKey,
+        operation: &'static str,
+        supported: &'static str,
+        is_supported: impl FnOnce(&Config) -> bool,
+    ) -> Result<(), ControlError> {
+        if !contains_component(&self.config, key) {
+            return Err(ControlError::NotFound { key: key.clone() });
+        }
+        if !is_supported(&self.config) {
+            return Err(ControlError::Unsupported {
+                key: key.clone(),
+                operation,
+                supported,
+            });
+        }
+        if self.stopped_components.contains_key(key) {
+            return Err(ControlError::AlreadyStopped { key: key.clone() });
+        }
+        Ok(())
+    }
+
     /// Attempts to reload enrichment tables.
     pub(crate) async fn reload_enrichment_tables(&self) {
         reload_enrichment_tables(&self.config).await;
@@ -419,6 +707,15 @@ impl RunningTopology {
         diff: &ConfigDiff,
         new_config: &Config,
     ) -> HashMap<ComponentKey, BuiltBuffer> {
+        // Changed/removed transforms are told up front that they're retired, before the flow of
+        // events into them stops, so that they pass on the state they hold instead of keeping it for
+        // the next time Vector starts.
+        for key in diff.transforms.removed_and_changed() {
+            if let Some(retirement) = self.retirements.remove(key) {
+                retirement.retire();
+            }
+        }
+
         // First, we shutdown any changed/removed sources. This ensures that we can allow downstream
         // components to terminate naturally by virtue of the flow of events stopping.
         if diff.sources.any_changed_or_removed() {
@@ -466,6 +763,7 @@ impl RunningTopology {
         // components, the shutdown of sources they depend on, or the shutdown of transforms they
         // depend on, and thus the closing of their buffer, will naturally cause them to shutdown,
         // which is why we don't do any manual triggering of shutdown here.
+
         for key in &diff.transforms.to_remove {
             debug!(component_id = %key, "Removing transform.");
 
@@ -563,22 +861,7 @@ impl RunningTopology {
             .sinks
             .to_change
             .iter()
-            .filter(|&key| {
-                if diff.components_to_reload.contains(key) {
-                    return false;
-                }
-                self.config.sink(key).map(|s| s.buffer.clone()).or_else(|| {
-                    self.config
-                        .enrichment_table(key)
-                        .and_then(|t| t.as_sink(key))
-                        .map(|(_, s)| s.buffer)
-                }) == new_config.sink(key).map(|s| s.buffer.clone()).or_else(|| {
-                    self.config
-                        .enrichment_table(key)
-                        .and_then(|t| t.as_sink(key))
-                        .map(|(_, s)| s.buffer)
-                })
-            })
+            .filter(|&key| diff.reuses_sink_buffer(&self.config, new_config, key))
             .cloned()
             .collect::<HashSet<_>>();
 
@@ -949,7 +1232,7 @@ impl RunningTopology {
         diff: &ConfigDiff,
         new_pieces: &mut builder::TopologyPieces,
     ) {
-        let (tx, inputs) = new_pieces.inputs.remove(key).unwrap();
+        let (tx, inputs, isolation) = new_pieces.inputs.remove(key).unwrap();
 
         let old_inputs = self
             .config
@@ -971,7 +1254,7 @@ impl RunningTopology {
                 // output for the first time, since there's nothing to actually replace at this point.
                 debug!(component_id = %key, fanout_id = %input, "Adding component input to fanout.");
 
-                _ = output.send(ControlMessage::Add(key.clone(), tx.clone()));
+                _ = output.send(ControlMessage::Add(key.clone(), tx.clone(), isolation));
             } else {
                 // We know that if this component is connected to a given input, and neither
                 // components were changed, then the output must still exist, which means we paused
@@ -979,7 +1262,7 @@ impl RunningTopology {
                 // now:
                 debug!(component_id = %key, fanout_id = %input, "Replacing component input in fanout.");
 
-                _ = output.send(ControlMessage::Replace(key.clone(), tx.clone()));
+                _ = output.send(ControlMessage::Replace(key.clone(), tx.clone(), isolation));
             }
         }
 
@@ -1048,7 +1331,11 @@ impl RunningTopology {
 
                 let input = self.inputs.get(transform_key).cloned().unwrap();
                 let output = self.outputs.get_mut(&output_id).unwrap();
-                _ = output.send(ControlMessage::Add(transform_key.clone(), input));
+                _ = output.send(ControlMessage::Add(
+                    transform_key.clone(),
+                    input,
+                    Isolation::Block,
+                ));
             }
         }
 
@@ -1063,7 +1350,7 @@ impl RunningTopology {
 
                 let input = self.inputs.get(sink_key).cloned().unwrap();
                 let output = self.outputs.get_mut(&output_id).unwrap();
-                _ = output.send(ControlMessage::Add(sink_key.clone(), input));
+                _ = output.send(ControlMessage::Add(sink_key.clone(), input, sink.isolation));
             }
         }
     }
@@ -1196,6 +1483,9 @@ impl RunningTopology {
 
     fn spawn_transform(&mut self, key: &ComponentKey, new_pieces: &mut builder::TopologyPieces) {
         let task = new_pieces.tasks.remove(key).unwrap();
+        if let Some(retirement) = new_pieces.retirements.remove(key) {
+            self.retirements.insert(key.clone(), retirement);
+        }
         let span = error_span!(
             "transform",
             component_kind = "transform",
@@ -1367,6 +1657,8 @@ impl RunningTopology {
             .metrics_storage_refresh_period
             .map(Duration::from_secs_f64);
         let mut running_topology = Self::new(config, abort_tx);
+        running_topology.dead_letters = pieces.dead_letters.clone();
+        running_topology.buffer_pool = pieces.buffer_pool.clone();
 
         if !running_topology
             .run_healthchecks(&diff, &mut pieces, running_topology.config.healthchecks)
@@ -1412,6 +1704,10 @@ impl RunningTopology {
     }
 }
 
+fn contains_component(config: &Config, key: &ComponentKey) -> bool {
+    config.source(key).is_some() || config.transform(key).is_some() || config.sink(key).is_some()
+}
+
 fn get_changed_outputs(diff: &ConfigDiff, output_ids: Inputs<OutputId>) -> Vec<OutputId> {
     let mut changed_outputs = Vec::new();
 
@@ -1,5 +1,6 @@
 use std::{
     iter,
+    num::NonZeroU64,
     sync::{
         Arc,
         atomic::{AtomicBool, AtomicUsize, Ordering},
@@ -12,7 +13,9 @@ use tokio::{
     time::{Duration, sleep},
 };
 use vector_lib::{
-    buffers::{BufferConfig, BufferType, WhenFull},
+    buffers::{
+        BufferConfig, BufferPool, BufferPoolConfig, BufferType, DiskBufferCompression, WhenFull,
+    },
     config::{ComponentKey, OutputId},
     source_sender::SourceSenderItem,
 };
@@ -815,7 +818,7 @@ async fn topology_healthcheck_run_for_changes_on_reload() {
         .await;
 
     // Should fail with TopologyBuildFailed error due to healthcheck failure
-    assert!(matches!(result, Err(TopologyBuildFailed)));
+    assert!(matches!(result, Err(TopologyBuildFailed { .. })));
 }
 
 #[tokio::test]
@@ -841,6 +844,10 @@ async fn topology_disk_buffer_flushes_on_idle() {
     sink1_outer.buffer = BufferConfig::Single(BufferType::DiskV2 {
         max_size: std::num::NonZeroU64::new(268435488).unwrap(),
         when_full: WhenFull::DropNewest,
+        compression: DiskBufferCompression::None,
+        encryption: None,
+        max_age_secs: None,
+        pool: None,
     });
     config.add_sink_outer("out1", sink1_outer);
 
@@ -897,6 +904,55 @@ async fn topology_transform_error_definition() {
     );
 }
 
+#[tokio::test]
+async fn topology_buffer_pool_is_sized_per_config() {
+    trace_init();
+
+    let build_config = |max_size| {
+        let mut config = Config::builder();
+        config.global.buffer_pool = Some(BufferPoolConfig {
+            max_size: NonZeroU64::new(max_size).unwrap(),
+        });
+        config.add_source("in", basic_source().1);
+        config.add_sink("out", &["in"], basic_sink(10).1);
+        config.build().unwrap()
+    };
+
+    let small = build_config(1024 * 1024);
+    let small_diff = ConfigDiff::initial(&small);
+    let small_pieces = TopologyPiecesBuilder::new(&small, &small_diff)
+        .build()
+        .await
+        .unwrap();
+
+    let large = build_config(2 * 1024 * 1024);
+    let large_diff = ConfigDiff::initial(&large);
+    let large_pieces = TopologyPiecesBuilder::new(&large, &large_diff)
+        .build()
+        .await
+        .unwrap();
+
+    // Building a second topology must not resize the pool of the first one.
+    assert_eq!(small_pieces.buffer_pool.unwrap().max_size(), 1024 * 1024);
+    assert_eq!(
+        large_pieces.buffer_pool.unwrap().max_size(),
+        2 * 1024 * 1024
+    );
+
+    // Rebuilds of a running topology keep sharing its pool, whatever size the rebuilt config asks
+    // for, since the global options can't change on reload.
+    let running_pool = BufferPool::new(4096);
+    let rebuilt_pieces = TopologyPiecesBuilder::new(&large, &large_diff)
+        .with_buffer_pool(Some(running_pool.clone()))
+        .build()
+        .await
+        .unwrap();
+    let rebuilt_pool = rebuilt_pieces.buffer_pool.unwrap();
+    assert_eq!(rebuilt_pool.max_size(), 4096);
+    rebuilt_pool.set_max_size(8192);
+    assert_eq!(running_pool.max_size(), 8192);
+}
+
 #[tokio::test]
 async fn source_metadata_reaches_sink() {
     trace_init();
@@ -9,7 +9,7 @@ use futures::StreamExt;
 use tokio::time::sleep;
 use tokio_stream::wrappers::UnboundedReceiverStream;
 use vector_lib::{
-    buffers::{BufferConfig, BufferType, MemoryBufferSize, WhenFull},
+    buffers::{BufferConfig, BufferType, DiskBufferCompression, MemoryBufferSize, WhenFull},
     config::ComponentKey,
 };
 
@@ -98,7 +98,7 @@ async fn topology_rebuild_old() {
         .await;
 
     // Should fail with TopologyBuildFailed error due to port conflict
-    assert!(matches!(result, Err(TopologyBuildFailed)));
+    assert!(matches!(result, Err(TopologyBuildFailed { .. })));
 }
 
 #[tokio::test]
@@ -194,6 +194,10 @@ async fn topology_disk_buffer_conflict() {
     old_config.sinks[&sink_key].buffer = BufferConfig::Single(BufferType::DiskV2 {
         max_size: NonZeroU64::new(268435488).unwrap(),
         when_full: WhenFull::Block,
+        compression: DiskBufferCompression::None,
+        encryption: None,
+        max_age_secs: None,
+        pool: None,
     });
 
     let mut new_config = old_config.clone();
@@ -201,6 +205,10 @@ async fn topology_disk_buffer_conflict() {
     new_config.sinks[&sink_key].buffer = BufferConfig::Single(BufferType::DiskV2 {
         max_size: NonZeroU64::new(268435488).unwrap(),
         when_full: WhenFull::Block,
+        compression: DiskBufferCompression::None,
+        encryption: None,
+        max_age_secs: None,
+        pool: None,
     });
 
     reload_sink_test(
@@ -342,6 +350,10 @@ async fn topology_disk_buffer_config_change_does_not_stall() {
     old_config.sinks[&sink_key].buffer = BufferConfig::Single(BufferType::DiskV2 {
         max_size: NonZeroU64::new(268435488).unwrap(),
         when_full: WhenFull::Block,
+        compression: DiskBufferCompression::None,
+        encryption: None,
+        max_age_secs: None,
+        pool: None,
     });
 
     // Change only the disk buffer's max_size.
@@ -349,6 +361,10 @@ async fn topology_disk_buffer_config_change_does_not_stall() {
     new_config.sinks[&sink_key].buffer = BufferConfig::Single(BufferType::DiskV2 {
         max_size: NonZeroU64::new(536870912).unwrap(),
         when_full: WhenFull::Block,
+        compression: DiskBufferCompression::None,
+        encryption: None,
+        max_age_secs: None,
+        pool: None,
     });
 
     let (mut topology, crash) = start_topology(old_config.build().unwrap(), true).await;
@@ -409,6 +425,10 @@ async fn topology_disk_buffer_config_change_chained_does_not_stall() {
         BufferType::DiskV2 {
             max_size: NonZeroU64::new(268435488).unwrap(),
             when_full: WhenFull::Block,
+            compression: DiskBufferCompression::None,
+            encryption: None,
+            max_age_secs: None,
+            pool: None,
         },
     ]);
 
@@ -419,6 +439,10 @@ async fn topology_disk_buffer_config_change_chained_does_not_stall() {
         BufferType::DiskV2 {
             max_size: NonZeroU64::new(536870912).unwrap(),
             when_full: WhenFull::Block,
+            compression: DiskBufferCompression::None,
+            encryption: None,
+            max_age_secs: None,
+            pool: None,
         },
     ]);
 
@@ -16,15 +16,18 @@ use vector_lib::{
 
 use crate::{
     config::{DataType, Input, OutputId, TransformConfig, TransformContext, TransformOutput},
-    event::{Event, EventMetadata},
+    event::{Event, EventArray, EventMetadata, Value},
     internal_events::{AggregateEventRecorded, AggregateFlushed, AggregateUpdateFailed},
     schema,
-    transforms::{TaskTransform, Transform},
+    transforms::{
+        TaskTransform, Transform,
+        state_snapshot::{StateSnapshot, StateSnapshotConfig},
+    },
 };
 
 /// Configuration for the `aggregate` transform.
 #[configurable_component(transform("aggregate", "Aggregate metrics passing through a topology."))]
-#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
 #[serde(deny_unknown_fields)]
 pub struct AggregateConfig {
     /// The interval between flushes, in milliseconds.
@@ -39,6 +42,10 @@ pub struct AggregateConfig {
     #[serde(default = "default_mode")]
     #[configurable(derived)]
     pub mode: AggregationMode,
+
+    #[configurable(derived)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state_snapshot: Option<StateSnapshotConfig>,
 }
 
 #[configurable_component]
@@ -146,8 +153,13 @@ impl_generate_config_from_default!(AggregateConfig);
 #[async_trait::async_trait]
 #[typetag::serde(name = "aggregate")]
 impl TransformConfig for AggregateConfig {
-    async fn build(&self, _context: &TransformContext) -> crate::Result<Transform> {
-        Aggregate::new(self).map(Transform::event_task)
+    async fn build(&self, context: &TransformContext) -> crate::Result<Transform> {
+        let mut aggregate = Aggregate::new(self)?;
+        if let Some(config) = &self.state_snapshot {
+            let snapshot = StateSnapshot::new(config, context, STATE_SNAPSHOT_VERSION)?;
+            aggregate = aggregate.with_state_snapshot(snapshot);
+        }
+        Ok(Transform::event_task(aggregate))
     }
 
     fn input(&self) -> Input {
@@ -165,11 +177,21 @@ impl TransformConfig for AggregateConfig {
 
 type MetricEntry = (MetricData, EventMetadata);
 
+/// The version of the layout of the aggregated metrics in state snapshots.
+const STATE_SNAPSHOT_VERSION: u32 = 1;
+
+/// The metadata field recording which part of the state a snapshotted metric belongs to.
+const STATE_SNAPSHOT_PART: &str = "aggregate_state";
+const PENDING_PART: &str = "pending";
+const PREVIOUS_PART: &str = "previous";
+const SAMPLE_PART: &str = "sample";
+
 #[derive(Debug)]
 pub struct Aggregate {
     interval: Duration,
     map: HashMap<MetricSeries, MetricEntry>,
     mode: InnerMode,
+    state_snapshot: Option<StateSnapshot>,
 }
 
 impl Aggregate {
@@ -178,9 +200,113 @@ impl Aggregate {
             interval: Duration::from_millis(config.interval_ms),
             map: Default::default(),
             mode: config.mode.into(),
+            state_snapshot: None,
         })
     }
 
+    /// Restores the metrics snapshotted by the previous instance of the transform when it starts,
+    /// and snapshots the metrics left when the transform stops instead of flushing them, unless a
+    /// reload removes the transform or disables its snapshots.
+    pub fn with_state_snapshot(mut self, snapshot: StateSnapshot) -> Self {
+        self.state_snapshot = Some(snapshot);
+        self
+    }
+
+    async fn restore_state_snapshot(&mut self) {
+        if let Some(snapshot) = self.state_snapshot.as_mut()
+            && let Some(events) = snapshot.take().await
+        {
+            self.restore_state(events);
+        }
+    }
+
+    fn restore_state(&mut self, events: EventArray) {
+        let EventArray::Metrics(metrics) = events else {
+            return;
+        };
+        for metric in metrics {
+            let (series, data, mut metadata) = metric.into_parts();
+            let part = match metadata.value_mut() {
+                Value::Object(fields) => fields.remove(STATE_SNAPSHOT_PART),
+                _ => None,
+            };
+            match (
+                part.as_ref().and_then(Value::as_str).as_deref(),
+                &mut self.mode,
+            ) {
+                (Some(PENDING_PART), _) => {
+                    self.map.insert(series, (data, metadata));
+                }
+                (Some(PREVIOUS_PART), InnerMode::Diff { prev_map }) => {
+                    prev_map.insert(series, (data, metadata));
+                }
+                (
+                    Some(SAMPLE_PART),
+                    InnerMode::Mean { multi_map } | InnerMode::Stdev { multi_map },
+                ) => {
+                    multi_map.entry(series).or_default().push((data, metadata));
+                }
+                // Parts of the state of another aggregation mode are left out.
+                _ => {}
+            }
+        }
+    }
+
+    /// Takes the state of the transform as metrics for a state snapshot.
+    fn take_state(&mut self) -> Vec<Metric> {
+        let into_metric = |part: &'static str| {
+            move |(series, (data, mut metadata)): (MetricSeries, MetricEntry)| {
+                if let Value::Object(fields) = metadata.value_mut() {
+                    fields.insert(STATE_SNAPSHOT_PART.into(), part.into());
+                }
+                Metric::from_parts(series, data, metadata)
+            }
+        };
+
+        let mut metrics = std::mem::take(&mut self.map)
+            .into_iter()
+            .map(into_metric(PENDING_PART))
+            .collect::<Vec<_>>();
+        match &mut self.mode {
+            InnerMode::Diff { prev_map } => metrics.extend(
+                std::mem::take(prev_map)
+                    .into_iter()
+                    .map(into_metric(PREVIOUS_PART)),
+            ),
+            InnerMode::Mean { multi_map } | InnerMode::Stdev { multi_map } => metrics.extend(
+                std::mem::take(multi_map)
+                    .into_iter()
+                    .flat_map(|(series, entries)| {
+                        entries
+                            .into_iter()
+                            .map(move |entry| (series.clone(), entry))
+                    })
+                    .map(into_metric(SAMPLE_PART)),
+            ),
+            _ => {}
+        }
+        metrics
+    }
+
+    /// Snapshots the state of the transform as it stops, or flushes it when it isn't snapshotted or
+    /// a reload has retired the transform for good.
+    async fn flush_or_snapshot_into(&mut self, output: &mut Vec<Event>) {
+        if let Some(mut snapshot) = self.state_snapshot.take()
+            && snapshot.keeps_state()
+        {
+            let metrics = self.take_state();
+            if metrics.is_empty() {
+                return;
+            }
+            match snapshot.save(EventArray::Metrics(metrics)).await {
+                Ok(()) => return,
+                // The metrics are flushed rather than lost.
+                Err(events) => self.restore_state(events),
+            }
+        }
+        self.flush_into(output);
+    }
+
     pub fn record(&mut self, event: Event) -> Option<Event> {
         let (series, data, metadata) = event.into_metric().into_parts();
 
@@ -387,6 +513,7 @@ impl TaskTransform<Event> for Aggregate {
         let mut flush_stream = tokio::time::interval(self.interval);
 
         Box::pin(stream! {
+            self.restore_state_snapshot().await;
             let mut output = Vec::new();
             let mut done = false;
             while !done {
@@ -397,7 +524,7 @@ impl TaskTransform<Event> for Aggregate {
                     maybe_event = input_rx.next() => {
                         match maybe_event {
                             None => {
-                                self.flush_into(&mut output);
+                                self.flush_or_snapshot_into(&mut output).await;
                                 done = true;
                             }
                             Some(event) => {
@@ -460,6 +587,7 @@ mod tests {
         let mut agg = Aggregate::new(&AggregateConfig {
             interval_ms: 1000_u64,
             mode: AggregationMode::Auto,
+            ..Default::default()
         })
         .unwrap();
 
@@ -531,6 +659,7 @@ mod tests {
         let mut agg = Aggregate::new(&AggregateConfig {
             interval_ms: 1000_u64,
             mode: AggregationMode::Auto,
+            ..Default::default()
         })
         .unwrap();
 
@@ -597,6 +726,7 @@ mod tests {
         let mut agg = Aggregate::new(&AggregateConfig {
             interval_ms: 1000_u64,
             mode: AggregationMode::Count,
+            ..Default::default()
         })
         .unwrap();
 
@@ -653,6 +783,7 @@ mod tests {
         let mut agg = Aggregate::new(&AggregateConfig {
             interval_ms: 1000_u64,
             mode: AggregationMode::Max,
+            ..Default::default()
         })
         .unwrap();
 
@@ -699,6 +830,7 @@ mod tests {
         let mut agg = Aggregate::new(&AggregateConfig {
             interval_ms: 1000_u64,
             mode: AggregationMode::Min,
+            ..Default::default()
         })
         .unwrap();
 
@@ -745,6 +877,7 @@ mod tests {
         let mut agg = Aggregate::new(&AggregateConfig {
             interval_ms: 1000_u64,
             mode: AggregationMode::Diff,
+            ..Default::default()
         })
         .unwrap();
 
@@ -801,6 +934,7 @@ mod tests {
         let mut agg = Aggregate::new(&AggregateConfig {
             interval_ms: 1000_u64,
             mode: AggregationMode::Diff,
+            ..Default::default()
         })
         .unwrap();
 
@@ -836,6 +970,7 @@ mod tests {
         let mut agg = Aggregate::new(&AggregateConfig {
             interval_ms: 1000_u64,
             mode: AggregationMode::Mean,
+            ..Default::default()
         })
         .unwrap();
 
@@ -893,6 +1028,7 @@ mod tests {
         let mut agg = Aggregate::new(&AggregateConfig {
             interval_ms: 1000_u64,
             mode: AggregationMode::Stdev,
+            ..Default::default()
         })
         .unwrap();
 
@@ -954,6 +1090,7 @@ mod tests {
         let mut agg = Aggregate::new(&AggregateConfig {
             interval_ms: 1000_u64,
             mode: AggregationMode::Sum,
+            ..Default::default()
         })
         .unwrap();
 
@@ -1003,6 +1140,7 @@ mod tests {
         let mut agg = Aggregate::new(&AggregateConfig {
             interval_ms: 1000_u64,
             mode: AggregationMode::Auto,
+            ..Default::default()
         })
         .unwrap();
 
@@ -1061,6 +1199,7 @@ mod tests {
         let mut agg = Aggregate::new(&AggregateConfig {
             interval_ms: 1000_u64,
             mode: AggregationMode::Auto,
+            ..Default::default()
         })
         .unwrap();
 
@@ -1250,4 +1389,101 @@ interval_ms = 999999
         })
         .await;
     }
+
+    #[tokio::test]
+    async fn restores_state_snapshot() {
+        let data_dir = tempfile::tempdir().unwrap();
+        let transform_config = AggregateConfig {
+            state_snapshot: Some(StateSnapshotConfig {
+                data_dir: Some(data_dir.path().to_owned()),
+            }),
+            ..Default::default()
+        };
+
+        let counter_a_1 = make_metric(
+            "counter_a",
+            MetricKind::Incremental,
+            MetricValue::Counter { value: 42.0 },
+        );
+        let counter_a_2 = make_metric(
+            "counter_a",
+            MetricKind::Incremental,
+            MetricValue::Counter { value: 43.0 },
+        );
+
+        // The pending metrics are snapshotted instead of flushed when the transform shuts down.
+        let (tx, rx) = mpsc::channel(10);
+        let (topology, out) =
+            create_topology(ReceiverStream::new(rx), transform_config.clone()).await;
+        let mut out = ReceiverStream::new(out);
+
+        tokio::time::pause();
+
+        // tokio interval is always immediately ready, so we poll once to make sure
+        // we trip it/set the interval in the future
+        assert_eq!(Poll::Pending, futures::poll!(out.next()));
+
+        tx.send(counter_a_1).await.unwrap();
+        tx.send(counter_a_2).await.unwrap();
+        drop(tx);
+        topology.stop().await;
+        assert_eq!(out.next().await, None);
+
+        // After restarting, they're flushed with the first interval.
+        let (tx, rx) = mpsc::channel(10);
+        let (topology, out) = create_topology(ReceiverStream::new(rx), transform_config).await;
+        let mut out = ReceiverStream::new(out);
+        let event = out.next().await.unwrap();
+        assert_eq!(event.as_metric().name(), "counter_a");
+        assert_eq!(
+            event.as_metric().value(),
+            &MetricValue::Counter { value: 85.0 }
+        );
+
+        drop(tx);
+        topology.stop().await;
+        assert_eq!(out.next().await, None);
+    }
+
+    #[tokio::test]
+    async fn retired_transform_flushes_instead_of_snapshotting() {
+        let data_dir = tempfile::tempdir().unwrap();
+        let context = TransformContext {
+            key: Some(ComponentKey::from("aggregate")),
+            ..Default::default()
+        };
+        let agg = AggregateConfig {
+            interval_ms: 999999,
+            state_snapshot: Some(StateSnapshotConfig {
+                data_dir: Some(data_dir.path().to_owned()),
+            }),
+            ..Default::default()
+        }
+        .build(&context)
+        .await
+        .unwrap()
+        .into_task();
+
+        // A reload removes the transform, or replaces it with one that doesn't snapshot its state.
+        context.retirement.retire();
+
+        let counter = make_metric(
+            "counter_a",
+            MetricKind::Incremental,
+            MetricValue::Counter { value: 42.0 },
+        );
+        let out = agg
+            .transform_events(Box::pin(stream::iter(vec![counter.clone()])))
+            .collect::<Vec<_>>()
+            .await;
+
+        assert_eq!(out, vec![counter]);
+        assert!(
+            !data_dir
+                .path()
+                .join("aggregate")
+                .join("state.snapshot")
+                .exists()
+        );
+    }
 }
@@ -1,4 +1,8 @@
-use std::{num::NonZeroUsize, time::Duration};
+use std::{
+    num::{NonZeroU64, NonZeroUsize},
+    path::PathBuf,
+    time::Duration,
+};
 
 use serde_with::serde_as;
 use vector_lib::{configurable::configurable_component, lookup::lookup_v2::ConfigTargetPath};
@@ -12,6 +16,55 @@ use crate::config::log_schema;
 pub struct CacheConfig {
     /// Number of events to cache and use for comparing incoming events to previously seen events.
     pub num_events: NonZeroUsize,
+
+    #[configurable(derived)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub persistence: Option<CachePersistenceConfig>,
+}
+
+/// Configuration for persisting the deduplication cache to disk.
+///
+/// When configured, the cache is periodically written to disk, as well as when the transform
+/// shuts down, and is restored when the transform starts. This allows deduplicating events that
+/// are replayed by sources after Vector restarts or reloads its configuration.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct CachePersistenceConfig {
+    /// The directory used to persist the cache.
+    ///
+    /// By default, the [global `data_dir` option][global_data_dir] is used. Make sure the running
+    /// user has write permissions to this directory.
+    ///
+    /// If this directory is specified, then Vector will attempt to create it.
+    ///
+    /// [global_data_dir]: https://vector.dev/docs/reference/configuration/global-options/#data_dir
+    #[serde(default)]
+    #[configurable(metadata(docs::examples = "/var/lib/vector"))]
+    #[configurable(metadata(docs::human_name = "Data Directory"))]
+    pub data_dir: Option<PathBuf>,
+
+    /// The interval between writes of the cache to disk, in seconds.
+    #[serde(default = "default_snapshot_interval_secs")]
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    #[configurable(metadata(docs::human_name = "Snapshot Interval"))]
+    pub snapshot_interval_secs: NonZeroU64,
+
+    /// The maximum age of the persisted cache entries, in seconds.
+    ///
+    /// Entries that were last seen longer ago are left out of the persisted cache, and are not
+    /// restored when the transform starts. When `time_settings` is configured, entries older than
+    /// `max_age_ms` are always left out.
+    ///
+    /// By default, entries are kept until they are evicted from the cache.
+    #[serde(default)]
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    #[configurable(metadata(docs::human_name = "Time To Live"))]
+    pub ttl_secs: Option<NonZeroU64>,
+}
+
+const fn default_snapshot_interval_secs() -> NonZeroU64 {
+    NonZeroU64::new(30).expect("static non-zero number")
 }
 
 /// Configuration for time based cache.
@@ -32,6 +85,7 @@ pub struct TimedCacheConfig {
 pub const fn default_cache_config() -> CacheConfig {
     CacheConfig {
         num_events: NonZeroUsize::new(5000).expect("static non-zero number"),
+        persistence: None,
     }
 }
 
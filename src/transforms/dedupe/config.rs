@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use vector_lib::{config::clone_input_definitions, configurable::configurable_component};
 
 use super::{
@@ -5,6 +7,7 @@ use super::{
         CacheConfig, FieldMatchConfig, TimedCacheConfig, default_cache_config,
         fill_default_fields_match,
     },
+    persistence::CacheSnapshot,
     timed_transform::TimedDedupe,
     transform::Dedupe,
 };
@@ -46,20 +49,53 @@ impl GenerateConfig for DedupeConfig {
     }
 }
 
+impl DedupeConfig {
+    fn cache_snapshot(&self, context: &TransformContext) -> crate::Result<Option<CacheSnapshot>> {
+        let Some(persistence) = self.cache.persistence.as_ref() else {
+            return Ok(None);
+        };
+        let key = context
+            .key
+            .as_ref()
+            .ok_or("The cache can only be persisted for transforms with a component ID.")?;
+        let data_dir = context
+            .globals
+            .resolve_and_make_data_subdir(persistence.data_dir.as_ref(), key.id())?;
+
+        // Entries older than the maximum age of the timed cache would never be used again.
+        let max_age = self.time_settings.as_ref().map(|config| config.max_age_ms);
+        let ttl = persistence
+            .ttl_secs
+            .map(|secs| Duration::from_secs(secs.get()))
+            .into_iter()
+            .chain(max_age)
+            .min();
+
+        Ok(Some(CacheSnapshot::new(
+            data_dir,
+            Duration::from_secs(persistence.snapshot_interval_secs.get()),
+            ttl,
+        )))
+    }
+}
+
 #[async_trait::async_trait]
 #[typetag::serde(name = "dedupe")]
 impl TransformConfig for DedupeConfig {
-    async fn build(&self, _context: &TransformContext) -> crate::Result<Transform> {
+    async fn build(&self, context: &TransformContext) -> crate::Result<Transform> {
+        let snapshot = self.cache_snapshot(context)?;
         if let Some(time_config) = &self.time_settings {
             Ok(Transform::event_task(TimedDedupe::new(
                 self.cache.num_events,
                 fill_default_fields_match(self.fields.as_ref()),
                 time_config.clone(),
+                snapshot,
             )))
         } else {
             Ok(Transform::event_task(Dedupe::new(
                 self.cache.num_events,
                 fill_default_fields_match(self.fields.as_ref()),
+                snapshot,
             )))
         }
     }
@@ -97,7 +133,7 @@ mod tests {
         test_util::components::assert_transform_compliance,
         transforms::{
             dedupe::{
-                common::TimedCacheConfig,
+                common::{CachePersistenceConfig, TimedCacheConfig},
                 config::{CacheConfig, DedupeConfig, FieldMatchConfig},
             },
             test::create_topology,
@@ -130,6 +166,7 @@ mod tests {
         DedupeConfig {
             cache: CacheConfig {
                 num_events: std::num::NonZeroUsize::new(num_events).expect("non-zero num_events"),
+                persistence: None,
             },
             fields: Some(FieldMatchConfig::MatchFields(fields)),
             time_settings: None,
@@ -147,6 +184,7 @@ mod tests {
         DedupeConfig {
             cache: CacheConfig {
                 num_events: std::num::NonZeroUsize::new(num_events).expect("non-zero num_events"),
+                persistence: None,
             },
             fields: Some(FieldMatchConfig::IgnoreFields(fields)),
             time_settings: None,
@@ -432,6 +470,93 @@ mod tests {
         .await;
     }
 
+    #[tokio::test]
+    async fn dedupe_persisted_cache_survives_restart() {
+        let data_dir = tempfile::tempdir().unwrap();
+        let mut transform_config = make_match_transform_config(5, vec!["matched".into()]);
+        transform_config.cache.persistence = Some(CachePersistenceConfig {
+            data_dir: Some(data_dir.path().to_owned()),
+            snapshot_interval_secs: std::num::NonZeroU64::new(30).unwrap(),
+            ttl_secs: None,
+        });
+
+        let mut event1 = Event::Log(LogEvent::from("message"));
+        event1.as_mut_log().insert("matched", "some value");
+        let mut event2 = Event::Log(LogEvent::from("message"));
+        event2.as_mut_log().insert("matched", "some value2");
+
+        // The cache is persisted when the transform shuts down.
+        let (tx, rx) = mpsc::channel(1);
+        let (topology, mut out) =
+            create_topology(ReceiverStream::new(rx), transform_config.clone()).await;
+        tx.send(event1.clone()).await.unwrap();
+        assert!(out.recv().await.is_some());
+        drop(tx);
+        topology.stop().await;
+        assert_eq!(out.recv().await, None);
+
+        // After restarting, the first event is still known to be a duplicate.
+        let (tx, rx) = mpsc::channel(1);
+        let (topology, mut out) = create_topology(ReceiverStream::new(rx), transform_config).await;
+        tx.send(event1).await.unwrap();
+        tx.send(event2.clone()).await.unwrap();
+        let new_event = out.recv().await.unwrap();
+
+        set_expected_metadata(&mut event2);
+        assert_eq!(new_event, event2);
+
+        drop(tx);
+        topology.stop().await;
+        assert_eq!(out.recv().await, None);
+    }
+
+    #[tokio::test]
+    async fn dedupe_cache_is_written_when_stopped() {
+        cache_is_written_when_stopped(make_match_transform_config(5, vec!["matched".into()])).await;
+    }
+
+    #[tokio::test]
+    async fn dedupe_timed_cache_is_written_when_stopped() {
+        cache_is_written_when_stopped(DedupeConfig {
+            time_settings: Some(TimedCacheConfig {
+                max_age_ms: Duration::from_secs(60),
+                refresh_on_drop: false,
+            }),
+            ..make_match_transform_config(5, vec!["matched".into()])
+        })
+        .await;
+    }
+
+    async fn cache_is_written_when_stopped(mut transform_config: DedupeConfig) {
+        let data_dir = tempfile::tempdir().unwrap();
+        transform_config.cache.persistence = Some(CachePersistenceConfig {
+            data_dir: Some(data_dir.path().to_owned()),
+            snapshot_interval_secs: std::num::NonZeroU64::new(30).unwrap(),
+            ttl_secs: None,
+        });
+
+        let mut event = Event::Log(LogEvent::from("message"));
+        event.as_mut_log().insert("matched", "some value");
+
+        let (tx, rx) = mpsc::channel(1);
+        let (topology, mut out) = create_topology(ReceiverStream::new(rx), transform_config).await;
+        tx.send(event).await.unwrap();
+        assert!(out.recv().await.is_some());
+        drop(tx);
+        topology.stop().await;
+
+        // The last write of the cache has finished once the transform is stopped.
+        let snapshot = std::fs::read_to_string(
+            data_dir
+                .path()
+                .join(TEST_UPSTREAM_COMPONENT_ID)
+                .join("dedupe_cache.json"),
+        )
+        .unwrap();
+        // "some value", encoded as base64.
+        assert!(snapshot.contains(r#""c29tZSB2YWx1ZQ==""#), "{snapshot}");
+    }
+
     #[tokio::test]
     async fn dedupe_match_type_matching() {
         let transform_config = make_match_transform_config(5, vec!["matched".into()]);
@@ -0,0 +1,309 @@
+use std::{
+    fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use chrono::{DateTime, TimeDelta, Utc};
+use lru::LruCache;
+use serde::{Deserialize, Serialize};
+use tokio::{
+    sync::{Mutex as AsyncMutex, OwnedMutexGuard},
+    task::JoinHandle,
+};
+use tracing::Instrument;
+
+use super::transform::CacheEntry;
+use crate::{
+    internal_events::{DedupeCachePersistError, DedupeCacheRestoreError},
+    transforms::state_snapshot::handover,
+};
+
+const SNAPSHOT_FILE: &str = "dedupe_cache.json";
+
+/// An entry of the cache as it's written to disk.
+///
+/// Entries are written in the order they were last seen, with the oldest first, so that restoring
+/// them in order also restores the order in which they would be evicted from the cache.
+#[derive(Deserialize, Serialize)]
+struct SnapshotEntry {
+    entry: CacheEntry,
+    seen_at: DateTime<Utc>,
+}
+
+/// The on-disk snapshot of a deduplication cache.
+///
+/// The snapshot is read and written on the blocking thread pool, so that the transform keeps
+/// processing events while it's written. On reload, the new instance of the transform only restores
+/// the snapshot once the old one has written its cache.
+#[derive(Debug)]
+pub struct CacheSnapshot {
+    path: PathBuf,
+    interval: Duration,
+    ttl: Option<Duration>,
+    handover: Arc<AsyncMutex<()>>,
+    /// Held from restoring the snapshot until the last write of the cache has finished.
+    owner: Option<OwnedMutexGuard<()>>,
+    write: Option<JoinHandle<()>>,
+}
+
+impl CacheSnapshot {
+    /// Creates a snapshot stored in the given directory.
+    ///
+    /// Entries older than `ttl`, if given, are neither written nor restored.
+    pub fn new(data_dir: PathBuf, interval: Duration, ttl: Option<Duration>) -> Self {
+        let path = data_dir.join(SNAPSHOT_FILE);
+        Self {
+            handover: handover(&path),
+            path,
+            interval,
+            ttl,
+            owner: None,
+            write: None,
+        }
+    }
+
+    /// The interval between writes of the snapshot.
+    pub const fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    fn is_expired(&self, age: Duration) -> bool {
+        self.ttl.is_some_and(|ttl| age >= ttl)
+    }
+
+    /// Restores the entries of the snapshot into `cache`, which keeps the time each entry was last
+    /// seen.
+    ///
+    /// If a previous instance of the transform is still shutting down, this waits until it has
+    /// written its cache.
+    pub(crate) async fn restore(&mut self, cache: &mut LruCache<CacheEntry, Instant>) {
+        self.owner = Some(Arc::clone(&self.handover).lock_owned().await);
+
+        let path = self.path.clone();
+        let result = tokio::task::spawn_blocking(move || read_entries(&path))
+            .await
+            .unwrap_or_else(|error| Err(io::Error::other(error)));
+        let entries = match result {
+            Ok(entries) => entries,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => return,
+            Err(error) => {
+                emit!(DedupeCacheRestoreError {
+                    error,
+                    path: self.path.clone(),
+                });
+                return;
+            }
+        };
+
+        let now = Instant::now();
+        let now_utc = Utc::now();
+        for SnapshotEntry { entry, seen_at } in entries {
+            let age = (now_utc - seen_at).to_std().unwrap_or_default();
+            if !self.is_expired(age) {
+                cache.put(entry, now.checked_sub(age).unwrap_or(now));
+            }
+        }
+    }
+
+    /// Writes the entries of `cache` to disk in the background, replacing the previous snapshot.
+    ///
+    /// Nothing is written while the previous write is still in progress.
+    pub(crate) fn persist(&mut self, cache: &LruCache<CacheEntry, Instant>) {
+        if self
+            .write
+            .as_ref()
+            .is_some_and(|write| !write.is_finished())
+        {
+            return;
+        }
+        self.write = Some(tokio::spawn(
+            self.write_snapshot(cache, None, None).in_current_span(),
+        ));
+    }
+
+    /// Writes the entries of `cache` to disk once the previous write has finished, then hands the
+    /// snapshot over to the next instance of the transform.
+    ///
+    /// The snapshot is complete on disk when this returns.
+    pub(crate) async fn persist_last(&mut self, cache: &LruCache<CacheEntry, Instant>) {
+        let previous = self.write.take();
+        let owner = self.owner.take();
+        self.write_snapshot(cache, previous, owner).await;
+    }
+
+    fn write_snapshot(
+        &self,
+        cache: &LruCache<CacheEntry, Instant>,
+        previous: Option<JoinHandle<()>>,
+        owner: Option<OwnedMutexGuard<()>>,
+    ) -> impl Future<Output = ()> + Send + 'static {
+        let now = Instant::now();
+        let now_utc = Utc::now();
+        let entries = cache
+            .iter()
+            .rev()
+            .filter_map(|(entry, &seen)| {
+                let age = now.saturating_duration_since(seen);
+                (!self.is_expired(age)).then(|| SnapshotEntry {
+                    entry: entry.clone(),
+                    seen_at: now_utc - TimeDelta::from_std(age).unwrap_or(TimeDelta::zero()),
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let path = self.path.clone();
+        async move {
+            if let Some(previous) = previous {
+                _ = previous.await;
+            }
+            let result = tokio::task::spawn_blocking({
+                let path = path.clone();
+                move || write_entries(&path, &entries)
+            })
+            .await
+            .unwrap_or_else(|error| Err(io::Error::other(error)));
+            if let Err(error) = result {
+                emit!(DedupeCachePersistError { error, path });
+            }
+            drop(owner);
+        }
+    }
+}
+
+fn read_entries(path: &Path) -> io::Result<Vec<SnapshotEntry>> {
+    let data = fs::read(path)?;
+    serde_json::from_slice(&data).map_err(Into::into)
+}
+
+fn write_entries(path: &Path, entries: &[SnapshotEntry]) -> io::Result<()> {
+    // The snapshot is written to a temporary file and synced to disk before it replaces the previous
+    // one, so that a crash while writing it never leaves a partial snapshot behind.
+    let temp_path = path.with_extension("json.tmp");
+    let data = serde_json::to_vec(entries)?;
+    let mut file = fs::File::create(&temp_path)?;
+    file.write_all(&data)?;
+    file.sync_all()?;
+    drop(file);
+    fs::rename(&temp_path, path)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroUsize;
+
+    use super::*;
+    use crate::{
+        event::{Event, LogEvent},
+        transforms::dedupe::{common::FieldMatchConfig, transform::build_cache_entry},
+    };
+
+    fn entry(message: &str, fields: &FieldMatchConfig) -> CacheEntry {
+        build_cache_entry(&Event::Log(LogEvent::from(message)), fields)
+    }
+
+    /// Persists `cache` as the last write of a transform instance, and restores it into a cache of
+    /// the given size, as the next instance would.
+    async fn hand_over(
+        snapshot: &CacheSnapshot,
+        cache: &LruCache<CacheEntry, Instant>,
+        size: usize,
+    ) -> LruCache<CacheEntry, Instant> {
+        let mut previous = CacheSnapshot::new(
+            snapshot.path.parent().unwrap().to_owned(),
+            snapshot.interval,
+            snapshot.ttl,
+        );
+        previous
+            .restore(&mut LruCache::new(NonZeroUsize::new(1).unwrap()))
+            .await;
+
+        let mut next = CacheSnapshot::new(
+            snapshot.path.parent().unwrap().to_owned(),
+            snapshot.interval,
+            snapshot.ttl,
+        );
+        previous.persist_last(cache).await;
+        let mut restored = LruCache::new(NonZeroUsize::new(size).unwrap());
+        next.restore(&mut restored).await;
+        restored
+    }
+
+    #[tokio::test]
+    async fn restores_persisted_entries_in_order() {
+        let data_dir = tempfile::tempdir().unwrap();
+        let snapshot = CacheSnapshot::new(data_dir.path().to_owned(), Duration::from_secs(1), None);
+
+        for fields in [
+            FieldMatchConfig::MatchFields(vec!["message".into()]),
+            FieldMatchConfig::IgnoreFields(vec!["timestamp".into()]),
+        ] {
+            let mut cache = LruCache::new(NonZeroUsize::new(3).unwrap());
+            for message in ["a", "b", "c"] {
+                cache.put(entry(message, &fields), Instant::now());
+            }
+            // Makes "a" the most recently seen entry.
+            cache.get(&entry("a", &fields));
+
+            let restored = hand_over(&snapshot, &cache, 2).await;
+
+            // The least recently seen entry is evicted when the cache is smaller.
+            assert!(!restored.contains(&entry("b", &fields)));
+            assert!(restored.contains(&entry("c", &fields)));
+            assert!(restored.contains(&entry("a", &fields)));
+            assert_eq!(
+                restored.peek_lru().map(|(entry, _)| entry),
+                Some(&entry("c", &fields))
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn leaves_out_expired_entries() {
+        let data_dir = tempfile::tempdir().unwrap();
+        let snapshot = CacheSnapshot::new(
+            data_dir.path().to_owned(),
+            Duration::from_secs(1),
+            Some(Duration::from_secs(60)),
+        );
+        let fields = FieldMatchConfig::MatchFields(vec!["message".into()]);
+
+        let mut cache = LruCache::new(NonZeroUsize::new(2).unwrap());
+        cache.put(
+            entry("old", &fields),
+            Instant::now() - Duration::from_secs(120),
+        );
+        cache.put(entry("new", &fields), Instant::now());
+
+        let restored = hand_over(&snapshot, &cache, 2).await;
+        assert_eq!(restored.len(), 1);
+        assert!(restored.contains(&entry("new", &fields)));
+    }
+
+    #[tokio::test]
+    async fn missing_snapshot_restores_nothing() {
+        let data_dir = tempfile::tempdir().unwrap();
+        let mut snapshot =
+            CacheSnapshot::new(data_dir.path().to_owned(), Duration::from_secs(1), None);
+
+        let mut restored = LruCache::new(NonZeroUsize::new(2).unwrap());
+        snapshot.restore(&mut restored).await;
+        assert!(restored.is_empty());
+    }
+
+    #[tokio::test]
+    async fn encodes_bytes_as_base64() {
+        let data_dir = tempfile::tempdir().unwrap();
+        let snapshot = CacheSnapshot::new(data_dir.path().to_owned(), Duration::from_secs(1), None);
+        let fields = FieldMatchConfig::MatchFields(vec!["message".into()]);
+
+        let mut cache = LruCache::new(NonZeroUsize::new(1).unwrap());
+        cache.put(entry("hello", &fields), Instant::now());
+        hand_over(&snapshot, &cache, 1).await;
+
+        let data = fs::read_to_string(&snapshot.path).unwrap();
+        assert!(data.contains(r#""aGVsbG8=""#), "{data}");
+    }
+}
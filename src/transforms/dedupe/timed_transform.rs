@@ -1,19 +1,21 @@
 use std::{future::ready, num::NonZeroUsize, pin::Pin, time::Instant};
 
-use futures::{Stream, StreamExt};
+use futures::{Stream, StreamExt, stream};
 use lru::LruCache;
+use vector_lib::stream::expiration_map::{Emitter, map_with_expiration};
 
 use super::{
     common::{FieldMatchConfig, TimedCacheConfig},
+    persistence::CacheSnapshot,
     transform::{CacheEntry, build_cache_entry},
 };
 use crate::{event::Event, internal_events::DedupeEventsDropped, transforms::TaskTransform};
 
-#[derive(Clone)]
 pub struct TimedDedupe {
     fields: FieldMatchConfig,
     cache: LruCache<CacheEntry, Instant>,
     time_config: TimedCacheConfig,
+    snapshot: Option<CacheSnapshot>,
 }
 
 impl TimedDedupe {
@@ -21,11 +23,13 @@ impl TimedDedupe {
         num_entries: NonZeroUsize,
         fields: FieldMatchConfig,
         time_config: TimedCacheConfig,
+        snapshot: Option<CacheSnapshot>,
     ) -> Self {
         Self {
             fields,
             cache: LruCache::new(num_entries),
             time_config,
+            snapshot,
         }
     }
 
@@ -52,6 +56,24 @@ impl TimedDedupe {
             Some(event)
         }
     }
+
+    async fn restore_cache(&mut self) {
+        if let Some(snapshot) = self.snapshot.as_mut() {
+            snapshot.restore(&mut self.cache).await;
+        }
+    }
+
+    fn persist_cache(&mut self) {
+        if let Some(snapshot) = self.snapshot.as_mut() {
+            snapshot.persist(&self.cache);
+        }
+    }
+
+    async fn persist_cache_last(&mut self) {
+        if let Some(snapshot) = self.snapshot.as_mut() {
+            snapshot.persist_last(&self.cache).await;
+        }
+    }
 }
 
 impl TaskTransform<Event> for TimedDedupe {
@@ -63,6 +85,30 @@ impl TaskTransform<Event> for TimedDedupe {
         Self: 'static,
     {
         let mut inner = self;
-        Box::pin(task.filter_map(move |v| ready(inner.transform_one(v))))
+        match inner.snapshot.as_ref().map(CacheSnapshot::interval) {
+            None => Box::pin(task.filter_map(move |v| ready(inner.transform_one(v)))),
+            // The cache is restored before the first event is processed.
+            Some(interval) => Box::pin(
+                stream::once(async move {
+                    inner.restore_cache().await;
+                    map_with_expiration(
+                        inner,
+                        task,
+                        interval,
+                        |me: &mut Box<Self>, event, emitter: &mut Emitter<Event>| {
+                            if let Some(event) = me.transform_one(event) {
+                                emitter.emit(event);
+                            }
+                        },
+                        |me: &mut Box<Self>, _: &mut Emitter<Event>| me.persist_cache(),
+                        |mut me: Box<Self>| async move {
+                            me.persist_cache_last().await;
+                            Emitter::new()
+                        },
+                    )
+                })
+                .flatten(),
+            ),
+        }
     }
 }
@@ -1,22 +1,26 @@
-use std::{future::ready, num::NonZeroUsize, pin::Pin};
+use std::{future::ready, num::NonZeroUsize, pin::Pin, time::Instant};
 
 use bytes::Bytes;
-use futures::{Stream, StreamExt};
+use futures::{Stream, StreamExt, stream};
 use lru::LruCache;
-use vector_lib::lookup::lookup_v2::ConfigTargetPath;
-use vrl::path::OwnedTargetPath;
+use serde::{Deserialize, Serialize};
+use serde_with::{base64::Base64, serde_as};
+use vector_lib::{
+    lookup::lookup_v2::ConfigTargetPath,
+    stream::expiration_map::{Emitter, map_with_expiration},
+};
 
-use super::common::FieldMatchConfig;
+use super::{common::FieldMatchConfig, persistence::CacheSnapshot};
 use crate::{
     event::{Event, Value},
     internal_events::DedupeEventsDropped,
     transforms::TaskTransform,
 };
 
-#[derive(Clone)]
 pub struct Dedupe {
     fields: FieldMatchConfig,
-    cache: LruCache<CacheEntry, bool>,
+    cache: LruCache<CacheEntry, Instant>,
+    snapshot: Option<CacheSnapshot>,
 }
 
 type TypeId = u8;
@@ -44,10 +48,13 @@ type TypeId = u8;
 /// iterating over the fields of the incoming Events, we know that the
 /// CacheEntries for 2 equivalent events will always contain the fields in the
 /// same order.
-#[derive(Clone, PartialEq, Eq, Hash)]
+///
+/// When persisted, the data of the fields is encoded as base64.
+#[serde_as]
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Deserialize, Serialize)]
 pub(crate) enum CacheEntry {
-    Match(Vec<Option<(TypeId, Bytes)>>),
-    Ignore(Vec<(OwnedTargetPath, TypeId, Bytes)>),
+    Match(#[serde_as(as = "Vec<Option<(_, Base64)>>")] Vec<Option<(TypeId, Bytes)>>),
+    Ignore(#[serde_as(as = "Vec<(_, _, Base64)>")] Vec<(ConfigTargetPath, TypeId, Bytes)>),
 }
 
 /// Assigns a unique number to each of the types supported by Event::Value.
@@ -66,22 +73,45 @@ const fn type_id_for_value(val: &Value) -> TypeId {
 }
 
 impl Dedupe {
-    pub fn new(num_entries: NonZeroUsize, fields: FieldMatchConfig) -> Self {
+    pub fn new(
+        num_entries: NonZeroUsize,
+        fields: FieldMatchConfig,
+        snapshot: Option<CacheSnapshot>,
+    ) -> Self {
         Self {
             fields,
             cache: LruCache::new(num_entries),
+            snapshot,
         }
     }
 
     pub fn transform_one(&mut self, event: Event) -> Option<Event> {
         let cache_entry = build_cache_entry(&event, &self.fields);
-        if self.cache.put(cache_entry, true).is_some() {
+        if self.cache.put(cache_entry, Instant::now()).is_some() {
             emit!(DedupeEventsDropped { count: 1 });
             None
         } else {
             Some(event)
         }
     }
+
+    async fn restore_cache(&mut self) {
+        if let Some(snapshot) = self.snapshot.as_mut() {
+            snapshot.restore(&mut self.cache).await;
+        }
+    }
+
+    fn persist_cache(&mut self) {
+        if let Some(snapshot) = self.snapshot.as_mut() {
+            snapshot.persist(&self.cache);
+        }
+    }
+
+    async fn persist_cache_last(&mut self) {
+        if let Some(snapshot) = self.snapshot.as_mut() {
+            snapshot.persist_last(&self.cache).await;
+        }
+    }
 }
 
 /// Takes in an Event and returns a CacheEntry to place into the LRU cache
@@ -110,7 +140,7 @@ pub(crate) fn build_cache_entry(event: &Event, fields: &FieldMatchConfig) -> Cac
                     if let Ok(path) = ConfigTargetPath::try_from(field_name)
                         && !fields.contains(&path)
                     {
-                        entry.push((path.0, type_id_for_value(value), value.coerce_to_bytes()));
+                        entry.push((path, type_id_for_value(value), value.coerce_to_bytes()));
                     }
                 }
             }
@@ -129,6 +159,30 @@ impl TaskTransform<Event> for Dedupe {
         Self: 'static,
     {
         let mut inner = self;
-        Box::pin(task.filter_map(move |v| ready(inner.transform_one(v))))
+        match inner.snapshot.as_ref().map(CacheSnapshot::interval) {
+            None => Box::pin(task.filter_map(move |v| ready(inner.transform_one(v)))),
+            // The cache is restored before the first event is processed.
+            Some(interval) => Box::pin(
+                stream::once(async move {
+                    inner.restore_cache().await;
+                    map_with_expiration(
+                        inner,
+                        task,
+                        interval,
+                        |me: &mut Box<Self>, event, emitter: &mut Emitter<Event>| {
+                            if let Some(event) = me.transform_one(event) {
+                                emitter.emit(event);
+                            }
+                        },
+                        |me: &mut Box<Self>, _: &mut Emitter<Event>| me.persist_cache(),
+                        |mut me: Box<Self>| async move {
+                            me.persist_cache_last().await;
+                            Emitter::new()
+                        },
+                    )
+                })
+                .flatten(),
+            ),
+        }
     }
 }
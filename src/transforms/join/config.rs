@@ -0,0 +1,259 @@
+use std::{collections::HashMap, num::NonZeroUsize, time::Duration};
+
+use serde_with::serde_as;
+use vector_lib::{config::clone_input_definitions, configurable::configurable_component};
+use vrl::value::{Kind, kind::Collection};
+
+use super::transform::{Join, UNMATCHED_OUTPUT};
+use crate::{
+    config::{
+        DataType, GenerateConfig, Input, OutputId, TransformConfig, TransformContext,
+        TransformOutput,
+    },
+    schema::{self, Definition},
+    template::Template,
+    transforms::Transform,
+};
+
+/// Configuration for the `join` transform.
+#[serde_as]
+#[configurable_component(transform(
+    "join",
+    "Correlate log events from two groups of inputs that share a key within a time window."
+))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct JoinConfig {
+    #[configurable(derived)]
+    pub left: JoinSideConfig,
+
+    #[configurable(derived)]
+    pub right: JoinSideConfig,
+
+    /// The maximum period of time, in milliseconds, that an event waits for matching events from the
+    /// other side of the join.
+    ///
+    /// Events are joined with every matching event from the other side received within this window,
+    /// and are considered unmatched once it elapses without a match.
+    #[serde(default = "default_window_ms")]
+    #[serde_as(as = "serde_with::DurationMilliSeconds<u64>")]
+    #[configurable(metadata(docs::human_name = "Join Window"))]
+    pub window_ms: Duration,
+
+    /// The interval to check for and flush any expired events, in milliseconds.
+    #[serde(default = "default_flush_period_ms")]
+    #[serde_as(as = "serde_with::DurationMilliSeconds<u64>")]
+    #[configurable(metadata(docs::human_name = "Flush Period"))]
+    pub flush_period_ms: Duration,
+
+    /// The maximum number of events waiting for matching events from the other side of the join.
+    ///
+    /// Once reached, the oldest waiting events are evicted to make room for new ones. Evicted events
+    /// that were never matched are dropped.
+    #[configurable(metadata(docs::type_unit = "events"))]
+    pub max_pending: Option<NonZeroUsize>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub mode: JoinMode,
+}
+
+/// Configuration for one side of the join.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct JoinSideConfig {
+    /// The inputs of the transform whose events are on this side of the join.
+    ///
+    /// Each input must also be listed in the `inputs` of the transform. Events received from inputs
+    /// that are on neither side of the join are sent to the `_unmatched` output.
+    #[configurable(metadata(docs::examples = "requests", docs::examples = "router.responses"))]
+    pub inputs: Vec<String>,
+
+    /// The template used to compute the key events are joined on.
+    ///
+    /// Events whose key can't be rendered are sent to the `_unmatched` output.
+    #[configurable(metadata(docs::examples = "{{ request_id }}"))]
+    pub key: Template,
+}
+
+/// The kind of join to perform.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum JoinMode {
+    /// Only joined events are sent to the default output.
+    ///
+    /// Events from either side that expire without a match are sent to the `_unmatched` output.
+    #[default]
+    Inner,
+
+    /// Joined events, as well as events from the left side that expire without a match, are sent to
+    /// the default output.
+    ///
+    /// Events from the right side that expire without a match are sent to the `_unmatched` output.
+    Left,
+
+    /// Joined events, as well as events from either side that expire without a match, are sent to
+    /// the default output.
+    Outer,
+}
+
+const fn default_window_ms() -> Duration {
+    Duration::from_millis(30000)
+}
+
+const fn default_flush_period_ms() -> Duration {
+    Duration::from_millis(1000)
+}
+
+impl GenerateConfig for JoinConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"left.inputs = ["requests"]
+left.key = "{{ request_id }}"
+right.inputs = ["responses"]
+right.key = "{{ request_id }}""#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "join")]
+impl TransformConfig for JoinConfig {
+    async fn build(&self, _context: &TransformContext) -> crate::Result<Transform> {
+        Ok(Transform::synchronous(Join::new(self)))
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn validate(&self, _: &schema::Definition) -> Result<(), Vec<String>> {
+        let errors = self
+            .left
+            .inputs
+            .iter()
+            .filter(|input| self.right.inputs.contains(*input))
+            .map(|input| format!("input `{input}` can't be on both sides of the join"))
+            .collect::<Vec<_>>();
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    fn validate_inputs(&self, inputs: &[String]) -> Result<(), Vec<String>> {
+        let errors = [("left", &self.left), ("right", &self.right)]
+            .into_iter()
+            .flat_map(|(side, config)| {
+                config
+                    .inputs
+                    .iter()
+                    .filter(|input| !inputs.contains(*input))
+                    .map(move |input| {
+                        format!(
+                            "input `{input}` on the {side} side of the join isn't one of the inputs of the transform"
+                        )
+                    })
+            })
+            .collect::<Vec<_>>();
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    fn outputs(
+        &self,
+        _: &TransformContext,
+        input_definitions: &[(OutputId, schema::Definition)],
+    ) -> Vec<TransformOutput> {
+        // Joined events hold the events of each side under the `left` and `right` fields.
+        let joined_kind = Kind::object(
+            Collection::empty()
+                .with_known("left", Kind::any().or_undefined())
+                .with_known("right", Kind::any().or_undefined()),
+        );
+        let joined_definitions = input_definitions
+            .iter()
+            .map(|(output, definition)| {
+                (
+                    output.clone(),
+                    Definition::new_with_default_metadata(
+                        joined_kind.clone(),
+                        definition.log_namespaces().clone(),
+                    ),
+                )
+            })
+            .collect::<HashMap<_, _>>();
+
+        vec![
+            TransformOutput::new(DataType::Log, joined_definitions),
+            TransformOutput::new(DataType::Log, clone_input_definitions(input_definitions))
+                .with_port(UNMATCHED_OUTPUT),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<JoinConfig>();
+    }
+
+    #[test]
+    fn rejects_input_on_both_sides() {
+        let config = toml::from_str::<JoinConfig>(
+            r#"
+left.inputs = ["requests", "both"]
+left.key = "{{ request_id }}"
+right.inputs = ["both", "responses"]
+right.key = "{{ request_id }}"
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            config.validate(&Definition::any()),
+            Err(vec![
+                "input `both` can't be on both sides of the join".to_owned()
+            ])
+        );
+    }
+
+    #[test]
+    fn rejects_side_input_missing_from_inputs() {
+        let config = toml::from_str::<JoinConfig>(
+            r#"
+left.inputs = ["requests"]
+left.key = "{{ request_id }}"
+right.inputs = ["responses", "router.responses"]
+right.key = "{{ request_id }}"
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            config.validate_inputs(&["requests".to_owned(), "router.responses".to_owned()]),
+            Err(vec![
+                "input `responses` on the right side of the join isn't one of the inputs of the transform"
+                    .to_owned()
+            ])
+        );
+        assert_eq!(
+            config.validate_inputs(&[
+                "requests".to_owned(),
+                "responses".to_owned(),
+                "router.responses".to_owned()
+            ]),
+            Ok(())
+        );
+    }
+}
@@ -0,0 +1,462 @@
+use std::{
+    collections::{HashMap, VecDeque, hash_map::Entry},
+    num::NonZeroUsize,
+    time::{Duration, Instant},
+};
+
+use vector_lib::transform::{SyncTransform, TransformOutputsBuf};
+
+use super::config::{JoinConfig, JoinMode};
+use crate::{
+    event::{Event, EventMetadata, LogEvent, ObjectMap, Value},
+    internal_events::{JoinEventsDropped, TemplateRenderingError},
+    template::Template,
+};
+
+pub(crate) const UNMATCHED_OUTPUT: &str = "_unmatched";
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Side {
+    Left,
+    Right,
+}
+
+impl Side {
+    const fn other(self) -> Self {
+        match self {
+            Self::Left => Self::Right,
+            Self::Right => Self::Left,
+        }
+    }
+}
+
+/// An event waiting for matching events from the other side of the join.
+#[derive(Clone, Debug)]
+struct PendingEvent {
+    event: LogEvent,
+    expires_at: Instant,
+    matched: bool,
+}
+
+/// The events of each side of the join sharing a key, in the order they were received.
+#[derive(Clone, Debug, Default)]
+struct PendingEvents {
+    left: VecDeque<PendingEvent>,
+    right: VecDeque<PendingEvent>,
+}
+
+impl PendingEvents {
+    const fn side(&self, side: Side) -> &VecDeque<PendingEvent> {
+        match side {
+            Side::Left => &self.left,
+            Side::Right => &self.right,
+        }
+    }
+
+    const fn side_mut(&mut self, side: Side) -> &mut VecDeque<PendingEvent> {
+        match side {
+            Side::Left => &mut self.left,
+            Side::Right => &mut self.right,
+        }
+    }
+
+    const fn is_empty(&self) -> bool {
+        self.left.is_empty() && self.right.is_empty()
+    }
+}
+
+#[derive(Clone)]
+pub struct Join {
+    left_inputs: Vec<String>,
+    right_inputs: Vec<String>,
+    left_key: Template,
+    right_key: Template,
+    window: Duration,
+    flush_period: Duration,
+    mode: JoinMode,
+    max_pending: Option<NonZeroUsize>,
+    pending: HashMap<String, PendingEvents>,
+    /// The key and side of every pending event, in the order they were received.
+    ///
+    /// Every event waits for the same window, so this is also the order they expire in.
+    arrivals: VecDeque<(String, Side)>,
+}
+
+impl Join {
+    pub fn new(config: &JoinConfig) -> Self {
+        Self {
+            left_inputs: config.left.inputs.clone(),
+            right_inputs: config.right.inputs.clone(),
+            left_key: config.left.key.clone(),
+            right_key: config.right.key.clone(),
+            window: config.window_ms,
+            flush_period: config.flush_period_ms,
+            mode: config.mode,
+            max_pending: config.max_pending,
+            pending: HashMap::new(),
+            arrivals: VecDeque::new(),
+        }
+    }
+
+    fn side_of(&self, log: &LogEvent) -> Option<Side> {
+        let upstream_id = log.metadata().upstream_id()?.to_string();
+        if self.left_inputs.contains(&upstream_id) {
+            Some(Side::Left)
+        } else if self.right_inputs.contains(&upstream_id) {
+            Some(Side::Right)
+        } else {
+            None
+        }
+    }
+
+    const fn key(&self, side: Side) -> &Template {
+        match side {
+            Side::Left => &self.left_key,
+            Side::Right => &self.right_key,
+        }
+    }
+
+    /// The time the oldest pending event expires at, if any.
+    fn oldest_expires_at(&self) -> Option<Instant> {
+        let (key, side) = self.arrivals.front()?;
+        self.pending[key]
+            .side(*side)
+            .front()
+            .map(|event| event.expires_at)
+    }
+
+    /// Removes the oldest pending event, along with its key once no events are left for it.
+    fn pop_oldest(&mut self) -> Option<(Side, PendingEvent)> {
+        let (key, side) = self.arrivals.pop_front()?;
+        let Entry::Occupied(mut pending) = self.pending.entry(key) else {
+            unreachable!("every arrival has a pending event");
+        };
+        let event = pending.get_mut().side_mut(side).pop_front()?;
+        if pending.get().is_empty() {
+            pending.remove();
+        }
+        Some((side, event))
+    }
+
+    /// Writes out the events that expired at `now`, or all of the pending events if `now` is `None`.
+    fn flush_expired(&mut self, now: Option<Instant>, output: &mut TransformOutputsBuf) {
+        while self
+            .oldest_expires_at()
+            .is_some_and(|expires_at| now.is_none_or(|now| expires_at <= now))
+        {
+            let Some((side, event)) = self.pop_oldest() else {
+                break;
+            };
+            if !event.matched {
+                emit_unmatched(self.mode, side, event.event, output);
+            }
+        }
+    }
+}
+
+/// Writes out an event that expired without matching any event from the other side of the join.
+fn emit_unmatched(mode: JoinMode, side: Side, event: LogEvent, output: &mut TransformOutputsBuf) {
+    match (mode, side) {
+        (JoinMode::Left | JoinMode::Outer, Side::Left) => {
+            output.push(None, joined(Some(event), None).into())
+        }
+        (JoinMode::Outer, Side::Right) => output.push(None, joined(None, Some(event)).into()),
+        _ => output.push(Some(UNMATCHED_OUTPUT), event.into()),
+    }
+}
+
+/// Combines the events of each side of the join into a single event, with the events nested under
+/// the `left` and `right` fields.
+fn joined(left: Option<LogEvent>, right: Option<LogEvent>) -> LogEvent {
+    let mut fields = ObjectMap::new();
+    let mut metadata: Option<EventMetadata> = None;
+    for (field, log) in [("left", left), ("right", right)] {
+        let Some(log) = log else { continue };
+        let (value, log_metadata) = log.into_parts();
+        fields.insert(field.into(), value);
+        match metadata.as_mut() {
+            Some(metadata) => metadata.merge(log_metadata),
+            None => metadata = Some(log_metadata),
+        }
+    }
+    LogEvent::from_parts(Value::Object(fields), metadata.unwrap_or_default())
+}
+
+impl SyncTransform for Join {
+    fn transform(&mut self, event: Event, output: &mut TransformOutputsBuf) {
+        let log = event.into_log();
+        let Some(side) = self.side_of(&log) else {
+            output.push(Some(UNMATCHED_OUTPUT), log.into());
+            return;
+        };
+        let key = match self.key(side).render_string(&log) {
+            Ok(key) => key,
+            Err(error) => {
+                emit!(TemplateRenderingError {
+                    error,
+                    field: Some("key"),
+                    drop_event: false,
+                });
+                output.push(Some(UNMATCHED_OUTPUT), log.into());
+                return;
+            }
+        };
+
+        let now = Instant::now();
+        let pending = self.pending.entry(key.clone()).or_default();
+        let mut matched = false;
+        for other in pending
+            .side_mut(side.other())
+            .iter_mut()
+            .filter(|other| other.expires_at > now)
+        {
+            other.matched = true;
+            matched = true;
+            let (left, right) = match side {
+                Side::Left => (log.clone(), other.event.clone()),
+                Side::Right => (other.event.clone(), log.clone()),
+            };
+            output.push(None, joined(Some(left), Some(right)).into());
+        }
+        pending.side_mut(side).push_back(PendingEvent {
+            event: log,
+            expires_at: now + self.window,
+            matched,
+        });
+        self.arrivals.push_back((key, side));
+
+        if self
+            .max_pending
+            .is_some_and(|max_pending| self.arrivals.len() > max_pending.get())
+            && let Some((_, evicted)) = self.pop_oldest()
+            && !evicted.matched
+        {
+            emit!(JoinEventsDropped { count: 1 });
+        }
+    }
+
+    fn flush_interval(&self) -> Option<Duration> {
+        Some(self.flush_period)
+    }
+
+    fn flush(&mut self, output: &mut TransformOutputsBuf) {
+        self.flush_expired(Some(Instant::now()), output);
+    }
+
+    fn flush_all(&mut self, output: &mut TransformOutputsBuf) {
+        self.flush_expired(None, output);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use vector_lib::config::{DataType, OutputId, TransformOutput};
+
+    use super::*;
+
+    fn join(config: &str) -> Join {
+        let config = format!(
+            r#"
+left.inputs = ["requests"]
+left.key = "{{{{ id }}}}"
+right.inputs = ["responses"]
+right.key = "{{{{ request_id }}}}"
+{config}
+"#
+        );
+        Join::new(&toml::from_str(&config).unwrap())
+    }
+
+    fn outputs() -> TransformOutputsBuf {
+        TransformOutputsBuf::new_with_capacity(
+            vec![
+                TransformOutput::new(DataType::all_bits(), HashMap::new()),
+                TransformOutput::new(DataType::all_bits(), HashMap::new())
+                    .with_port(UNMATCHED_OUTPUT),
+            ],
+            1,
+        )
+    }
+
+    fn event(input: &str, fields: serde_json::Value) -> Event {
+        let mut log = LogEvent::try_from(fields).unwrap();
+        log.metadata_mut()
+            .set_upstream_id(Arc::new(OutputId::from(input)));
+        log.into()
+    }
+
+    fn drain(outputs: &mut TransformOutputsBuf) -> (Vec<Value>, Vec<Value>) {
+        let primary = outputs
+            .drain()
+            .map(|event| event.into_log().into_parts().0)
+            .collect();
+        let unmatched = outputs
+            .drain_named(UNMATCHED_OUTPUT)
+            .map(|event| event.into_log().into_parts().0)
+            .collect();
+        (primary, unmatched)
+    }
+
+    #[test]
+    fn joins_matching_events() {
+        let mut join = join("");
+        let mut outputs = outputs();
+
+        join.transform(
+            event("requests", serde_json::json!({"id": "a", "path": "/"})),
+            &mut outputs,
+        );
+        join.transform(
+            event("responses", serde_json::json!({"request_id": "b"})),
+            &mut outputs,
+        );
+        join.transform(
+            event(
+                "responses",
+                serde_json::json!({"request_id": "a", "status": 200}),
+            ),
+            &mut outputs,
+        );
+
+        let (primary, unmatched) = drain(&mut outputs);
+        assert_eq!(
+            primary,
+            vec![Value::from(serde_json::json!({
+                "left": {"id": "a", "path": "/"},
+                "right": {"request_id": "a", "status": 200},
+            }))]
+        );
+        assert!(unmatched.is_empty());
+
+        join.flush_all(&mut outputs);
+        let (primary, unmatched) = drain(&mut outputs);
+        assert!(primary.is_empty());
+        assert_eq!(
+            unmatched,
+            vec![Value::from(serde_json::json!({"request_id": "b"}))]
+        );
+    }
+
+    #[test]
+    fn joins_each_matching_pair() {
+        let mut join = join("");
+        let mut outputs = outputs();
+
+        for status in [200, 500] {
+            join.transform(
+                event(
+                    "responses",
+                    serde_json::json!({"request_id": "a", "status": status}),
+                ),
+                &mut outputs,
+            );
+        }
+        join.transform(
+            event("requests", serde_json::json!({"id": "a"})),
+            &mut outputs,
+        );
+
+        let (primary, _) = drain(&mut outputs);
+        assert_eq!(primary.len(), 2);
+    }
+
+    #[test]
+    fn emits_unmatched_events_by_mode() {
+        for (mode, joined, unmatched) in [("inner", 0, 2), ("left", 1, 1), ("outer", 2, 0)] {
+            let mut join = join(&format!("mode = \"{mode}\""));
+            let mut outputs = outputs();
+
+            join.transform(
+                event("requests", serde_json::json!({"id": "a"})),
+                &mut outputs,
+            );
+            join.transform(
+                event("responses", serde_json::json!({"request_id": "b"})),
+                &mut outputs,
+            );
+            join.flush_all(&mut outputs);
+
+            let (primary, rest) = drain(&mut outputs);
+            assert_eq!((primary.len(), rest.len()), (joined, unmatched), "{mode}");
+        }
+    }
+
+    #[test]
+    fn expires_events_after_window() {
+        let mut join = join("window_ms = 0\nmode = \"left\"");
+        let mut outputs = outputs();
+
+        join.transform(
+            event("requests", serde_json::json!({"id": "a"})),
+            &mut outputs,
+        );
+        join.flush(&mut outputs);
+        join.transform(
+            event("responses", serde_json::json!({"request_id": "a"})),
+            &mut outputs,
+        );
+
+        let (primary, unmatched) = drain(&mut outputs);
+        assert_eq!(
+            primary,
+            vec![Value::from(serde_json::json!({"left": {"id": "a"}}))]
+        );
+        assert!(unmatched.is_empty());
+        assert!(join.pending.contains_key("a"));
+    }
+
+    #[test]
+    fn routes_unknown_inputs_and_unrenderable_keys_to_unmatched() {
+        let mut join = join("");
+        let mut outputs = outputs();
+
+        join.transform(event("other", serde_json::json!({"id": "a"})), &mut outputs);
+        join.transform(
+            event("requests", serde_json::json!({"message": "no id"})),
+            &mut outputs,
+        );
+
+        let (primary, unmatched) = drain(&mut outputs);
+        assert!(primary.is_empty());
+        assert_eq!(unmatched.len(), 2);
+        assert!(join.pending.is_empty());
+    }
+
+    #[test]
+    fn evicts_oldest_events_over_max_pending() {
+        let mut join = join("max_pending = 2\nmode = \"outer\"");
+        let mut outputs = outputs();
+
+        for id in ["a", "b", "c"] {
+            join.transform(
+                event("requests", serde_json::json!({"id": id})),
+                &mut outputs,
+            );
+        }
+        join.transform(
+            event("responses", serde_json::json!({"request_id": "a"})),
+            &mut outputs,
+        );
+
+        let (primary, unmatched) = drain(&mut outputs);
+        assert!(primary.is_empty());
+        assert!(unmatched.is_empty());
+        assert_eq!(join.arrivals.len(), 2);
+        assert!(!join.pending.contains_key("a"));
+        assert!(!join.pending.contains_key("b"));
+
+        join.flush_all(&mut outputs);
+        let (primary, _) = drain(&mut outputs);
+        assert_eq!(
+            primary,
+            vec![
+                Value::from(serde_json::json!({"left": {"id": "c"}})),
+                Value::from(serde_json::json!({"right": {"request_id": "a"}})),
+            ]
+        );
+        assert!(join.pending.is_empty());
+        assert!(join.arrivals.is_empty());
+    }
+}
@@ -19,6 +19,8 @@ mod exclusive_route;
 pub mod filter;
 #[cfg(feature = "transforms-incremental_to_absolute")]
 pub mod incremental_to_absolute;
+#[cfg(feature = "transforms-join")]
+pub mod join;
 #[cfg(feature = "transforms-log_to_metric")]
 pub mod log_to_metric;
 #[cfg(feature = "transforms-lua")]
@@ -29,8 +31,18 @@ pub mod metric_to_log;
 pub mod remap;
 #[cfg(feature = "transforms-route")]
 pub mod route;
+#[cfg(feature = "transforms-span_to_metric")]
+pub mod span_to_metric;
+#[cfg(any(
+    feature = "transforms-aggregate",
+    feature = "transforms-impl-dedupe",
+    feature = "transforms-impl-reduce"
+))]
+pub mod state_snapshot;
 #[cfg(feature = "transforms-tag_cardinality_limit")]
 pub mod tag_cardinality_limit;
+#[cfg(feature = "transforms-tail_sample")]
+pub mod tail_sample;
 #[cfg(feature = "transforms-throttle")]
 pub mod throttle;
 #[cfg(feature = "transforms-trace_to_log")]
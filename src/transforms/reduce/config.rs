@@ -16,7 +16,11 @@ use crate::{
     schema::Definition,
     transforms::{
         Transform,
-        reduce::{merge_strategy::MergeStrategy, transform::Reduce},
+        reduce::{
+            merge_strategy::MergeStrategy,
+            transform::{Reduce, STATE_SNAPSHOT_VERSION},
+        },
+        state_snapshot::{StateSnapshot, StateSnapshotConfig},
     },
 };
 
@@ -103,6 +107,10 @@ pub struct ReduceConfig {
     /// If this condition resolves to `true` for an event, the previous transaction is flushed
     /// (without this event) and a new transaction is started.
     pub starts_when: Option<AnyCondition>,
+
+    #[configurable(derived)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state_snapshot: Option<StateSnapshotConfig>,
 }
 
 const fn default_expire_after_ms() -> Duration {
@@ -119,8 +127,12 @@ impl_generate_config_from_default!(ReduceConfig);
 #[typetag::serde(name = "reduce")]
 impl TransformConfig for ReduceConfig {
     async fn build(&self, context: &TransformContext) -> crate::Result<Transform> {
-        Reduce::new(self, &context.enrichment_tables, &context.metrics_storage)
-            .map(Transform::event_task)
+        let mut reduce = Reduce::new(self, &context.enrichment_tables, &context.metrics_storage)?;
+        if let Some(config) = &self.state_snapshot {
+            let snapshot = StateSnapshot::new(config, context, STATE_SNAPSHOT_VERSION)?;
+            reduce = reduce.with_state_snapshot(snapshot);
+        }
+        Ok(Transform::event_task(reduce))
     }
 
     fn input(&self) -> Input {
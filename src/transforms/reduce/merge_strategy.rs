@@ -7,7 +7,7 @@ use ordered_float::NotNan;
 use vector_lib::configurable::configurable_component;
 use vrl::path::OwnedTargetPath;
 
-use crate::event::{LogEvent, Value};
+use crate::event::{LogEvent, ObjectMap, Value};
 
 /// Strategies for merging events.
 #[configurable_component]
@@ -70,6 +70,10 @@ impl ReduceValueMerger for DiscardMerger {
         Ok(())
     }
 
+    fn snapshot(&self) -> Value {
+        merger_snapshot("discard", [("value", self.v.clone())])
+    }
+
     fn insert_into(
         self: Box<Self>,
         path: &OwnedTargetPath,
@@ -100,6 +104,10 @@ impl ReduceValueMerger for RetainMerger {
         Ok(())
     }
 
+    fn snapshot(&self) -> Value {
+        merger_snapshot("retain", [("value", self.v.clone())])
+    }
+
     fn insert_into(
         self: Box<Self>,
         path: &OwnedTargetPath,
@@ -144,6 +152,14 @@ impl ReduceValueMerger for ConcatMerger {
         }
     }
 
+    fn snapshot(&self) -> Value {
+        let mut snapshot = merger_snapshot("concat", [("value", self.v.clone().freeze().into())]);
+        if let (Value::Object(fields), Some(join_by)) = (&mut snapshot, &self.join_by) {
+            fields.insert("join_by".into(), Bytes::from(join_by.clone()).into());
+        }
+        snapshot
+    }
+
     fn insert_into(
         self: Box<Self>,
         path: &OwnedTargetPath,
@@ -175,6 +191,10 @@ impl ReduceValueMerger for ConcatArrayMerger {
         Ok(())
     }
 
+    fn snapshot(&self) -> Value {
+        merger_snapshot("concat_array", [("value", Value::Array(self.v.clone()))])
+    }
+
     fn insert_into(
         self: Box<Self>,
         path: &OwnedTargetPath,
@@ -202,6 +222,10 @@ impl ReduceValueMerger for ArrayMerger {
         Ok(())
     }
 
+    fn snapshot(&self) -> Value {
+        merger_snapshot("array", [("value", Value::Array(self.v.clone()))])
+    }
+
     fn insert_into(
         self: Box<Self>,
         path: &OwnedTargetPath,
@@ -238,6 +262,10 @@ impl ReduceValueMerger for LongestArrayMerger {
         }
     }
 
+    fn snapshot(&self) -> Value {
+        merger_snapshot("longest_array", [("value", Value::Array(self.v.clone()))])
+    }
+
     fn insert_into(
         self: Box<Self>,
         path: &OwnedTargetPath,
@@ -274,6 +302,10 @@ impl ReduceValueMerger for ShortestArrayMerger {
         }
     }
 
+    fn snapshot(&self) -> Value {
+        merger_snapshot("shortest_array", [("value", Value::Array(self.v.clone()))])
+    }
+
     fn insert_into(
         self: Box<Self>,
         path: &OwnedTargetPath,
@@ -323,6 +355,13 @@ impl ReduceValueMerger for FlatUniqueMerger {
         Ok(())
     }
 
+    fn snapshot(&self) -> Value {
+        merger_snapshot(
+            "flat_unique",
+            [("value", Value::Array(self.v.iter().cloned().collect()))],
+        )
+    }
+
     fn insert_into(
         self: Box<Self>,
         path: &OwnedTargetPath,
@@ -361,6 +400,16 @@ impl ReduceValueMerger for TimestampWindowMerger {
         Ok(())
     }
 
+    fn snapshot(&self) -> Value {
+        merger_snapshot(
+            "timestamp_window",
+            [
+                ("started", Value::Timestamp(self.started)),
+                ("latest", Value::Timestamp(self.latest)),
+            ],
+        )
+    }
+
     fn insert_into(
         self: Box<Self>,
         path: &OwnedTargetPath,
@@ -393,6 +442,26 @@ impl From<NotNan<f64>> for NumberMergerValue {
     }
 }
 
+impl NumberMergerValue {
+    const fn to_value(&self) -> Value {
+        match self {
+            NumberMergerValue::Int(i) => Value::Integer(*i),
+            NumberMergerValue::Float(f) => Value::Float(*f),
+        }
+    }
+
+    fn from_value(v: Value) -> Result<Self, String> {
+        match v {
+            Value::Integer(i) => Ok(i.into()),
+            Value::Float(f) => Ok(f.into()),
+            _ => Err(format!(
+                "expected number value, found: '{}'",
+                v.to_string_lossy()
+            )),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 struct AddNumbersMerger {
     v: NumberMergerValue,
@@ -429,6 +498,10 @@ impl ReduceValueMerger for AddNumbersMerger {
         Ok(())
     }
 
+    fn snapshot(&self) -> Value {
+        merger_snapshot("sum", [("value", self.v.to_value())])
+    }
+
     fn insert_into(
         self: Box<Self>,
         path: &OwnedTargetPath,
@@ -492,6 +565,10 @@ impl ReduceValueMerger for MaxNumberMerger {
         Ok(())
     }
 
+    fn snapshot(&self) -> Value {
+        merger_snapshot("max", [("value", self.v.to_value())])
+    }
+
     fn insert_into(
         self: Box<Self>,
         path: &OwnedTargetPath,
@@ -555,6 +632,10 @@ impl ReduceValueMerger for MinNumberMerger {
         Ok(())
     }
 
+    fn snapshot(&self) -> Value {
+        merger_snapshot("min", [("value", self.v.to_value())])
+    }
+
     fn insert_into(
         self: Box<Self>,
         path: &OwnedTargetPath,
@@ -570,6 +651,8 @@ impl ReduceValueMerger for MinNumberMerger {
 
 pub trait ReduceValueMerger: std::fmt::Debug + Send + Sync + DynClone {
     fn add(&mut self, v: Value) -> Result<(), String>;
+    /// Captures the state of the merger, to be restored by [`restore_value_merger`].
+    fn snapshot(&self) -> Value;
     fn insert_into(self: Box<Self>, path: &OwnedTargetPath, v: &mut LogEvent)
     -> Result<(), String>;
 }
@@ -664,6 +747,101 @@ pub(crate) fn get_value_merger(
     }
 }
 
+fn merger_snapshot<const N: usize>(merger: &str, fields: [(&str, Value); N]) -> Value {
+    let mut snapshot = ObjectMap::from([("merger".into(), merger.into())]);
+    snapshot.extend(fields.map(|(field, value)| (field.into(), value)));
+    Value::Object(snapshot)
+}
+
+fn take_snapshot_field(snapshot: &mut ObjectMap, field: &str) -> Result<Value, String> {
+    snapshot
+        .remove(field)
+        .ok_or_else(|| format!("missing field in merger snapshot: '{field}'"))
+}
+
+fn expect_bytes(v: Value) -> Result<Bytes, String> {
+    match v {
+        Value::Bytes(b) => Ok(b),
+        _ => Err(format!(
+            "expected string value, found: '{}'",
+            v.to_string_lossy()
+        )),
+    }
+}
+
+fn expect_array(v: Value) -> Result<Vec<Value>, String> {
+    match v {
+        Value::Array(a) => Ok(a),
+        _ => Err(format!(
+            "expected array value, found: '{}'",
+            v.to_string_lossy()
+        )),
+    }
+}
+
+fn expect_timestamp(v: Value) -> Result<DateTime<Utc>, String> {
+    match v {
+        Value::Timestamp(ts) => Ok(ts),
+        _ => Err(format!(
+            "expected timestamp value, found: '{}'",
+            v.to_string_lossy()
+        )),
+    }
+}
+
+/// Restores a merger from the state captured by [`ReduceValueMerger::snapshot`].
+pub(crate) fn restore_value_merger(snapshot: Value) -> Result<Box<dyn ReduceValueMerger>, String> {
+    let Value::Object(mut snapshot) = snapshot else {
+        return Err(format!(
+            "expected merger snapshot, found: '{}'",
+            snapshot.to_string_lossy()
+        ));
+    };
+    let merger = take_snapshot_field(&mut snapshot, "merger")?;
+    let mut value = || take_snapshot_field(&mut snapshot, "value");
+
+    Ok(match merger.as_str().as_deref() {
+        Some("discard") => Box::new(DiscardMerger::new(value()?)),
+        Some("retain") => Box::new(RetainMerger::new(value()?)),
+        Some("concat") => {
+            let v = BytesMut::from(&expect_bytes(value()?)?[..]);
+            let join_by = snapshot
+                .remove("join_by")
+                .map(|join_by| expect_bytes(join_by).map(|b| b.to_vec()))
+                .transpose()?;
+            Box::new(ConcatMerger { v, join_by })
+        }
+        Some("concat_array") => Box::new(ConcatArrayMerger::new(expect_array(value()?)?)),
+        Some("array") => Box::new(ArrayMerger {
+            v: expect_array(value()?)?,
+        }),
+        Some("longest_array") => Box::new(LongestArrayMerger::new(expect_array(value()?)?)),
+        Some("shortest_array") => Box::new(ShortestArrayMerger::new(expect_array(value()?)?)),
+        Some("flat_unique") => Box::new(FlatUniqueMerger {
+            v: expect_array(value()?)?.into_iter().collect(),
+        }),
+        Some("timestamp_window") => Box::new(TimestampWindowMerger {
+            started: expect_timestamp(take_snapshot_field(&mut snapshot, "started")?)?,
+            latest: expect_timestamp(take_snapshot_field(&mut snapshot, "latest")?)?,
+        }),
+        Some("sum") => Box::new(AddNumbersMerger::new(NumberMergerValue::from_value(
+            value()?,
+        )?)),
+        Some("max") => Box::new(MaxNumberMerger::new(NumberMergerValue::from_value(
+            value()?
+        )?)),
+        Some("min") => Box::new(MinNumberMerger::new(NumberMergerValue::from_value(
+            value()?
+        )?)),
+        _ => {
+            return Err(format!(
+                "unknown merger in snapshot: '{}'",
+                merger.to_string_lossy()
+            ));
+        }
+    })
+}
+
 #[cfg(test)]
 mod test {
     use serde_json::json;
@@ -930,6 +1108,65 @@ mod test {
         }
     }
 
+    #[test]
+    fn snapshot_restores_mergers() {
+        let ts = Utc::now();
+        let cases: [(Value, Value, MergeStrategy); 13] = [
+            ("foo".into(), "bar".into(), MergeStrategy::Discard),
+            ("foo".into(), Value::Null, MergeStrategy::Retain),
+            (1.into(), 2.5.into(), MergeStrategy::Sum),
+            (1.into(), 2.into(), MergeStrategy::Max),
+            (1.into(), 2.into(), MergeStrategy::Min),
+            ("foo".into(), 2.into(), MergeStrategy::Array),
+            ("foo".into(), "bar".into(), MergeStrategy::Concat),
+            (json!([1]).into(), json!([2]).into(), MergeStrategy::Concat),
+            ("foo".into(), "bar".into(), MergeStrategy::ConcatNewline),
+            ("foo".into(), "bar".into(), MergeStrategy::ConcatRaw),
+            (
+                json!([1, 2]).into(),
+                json!([3]).into(),
+                MergeStrategy::ShortestArray,
+            ),
+            (
+                json!([1]).into(),
+                json!([2, 3]).into(),
+                MergeStrategy::LongestArray,
+            ),
+            ("foo".into(), "foo".into(), MergeStrategy::FlatUnique),
+        ];
+
+        let mut mergers = cases
+            .into_iter()
+            .map(|(initial, additional, strategy)| {
+                let mut merger = get_value_merger(initial, &strategy).unwrap();
+                merger.add(additional).unwrap();
+                merger
+            })
+            .collect::<Vec<_>>();
+        let mut timestamp_merger = Box::<dyn ReduceValueMerger>::from(Value::Timestamp(ts));
+        timestamp_merger
+            .add(Value::Timestamp(ts + chrono::Duration::seconds(1)))
+            .unwrap();
+        mergers.push(timestamp_merger);
+
+        let path = owned_event_path!("out");
+        for merger in mergers {
+            let restored = restore_value_merger(merger.snapshot()).unwrap();
+            let mut expected = LogEvent::default();
+            let mut actual = LogEvent::default();
+            merger.insert_into(&path, &mut expected).unwrap();
+            restored.insert_into(&path, &mut actual).unwrap();
+            assert_eq!(expected, actual);
+        }
+    }
+
+    #[test]
+    fn restoring_invalid_snapshot_fails() {
+        assert!(restore_value_merger("foo".into()).is_err());
+        assert!(restore_value_merger(json!({"merger": "unknown"}).into()).is_err());
+        assert!(restore_value_merger(json!({"merger": "sum", "value": "foo"}).into()).is_err());
+    }
+
     fn merge(initial: Value, additional: Value, strategy: &MergeStrategy) -> Result<Value, String> {
         let mut merger = get_value_merger(initial, strategy)?;
         merger.add(additional)?;
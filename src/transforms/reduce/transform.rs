@@ -4,9 +4,11 @@ use std::{
     time::{Duration, Instant},
 };
 
-use futures::Stream;
+use async_stream::stream;
+use chrono::{DateTime, TimeDelta, Utc};
+use futures::{Stream, StreamExt};
 use indexmap::IndexMap;
-use vector_lib::stream::expiration_map::{Emitter, map_with_expiration};
+use vector_lib::stream::expiration_map::Emitter;
 use vector_vrl_metrics::MetricsStorage;
 use vrl::{
     path::{OwnedTargetPath, parse_target_path},
@@ -15,17 +17,25 @@ use vrl::{
 
 use crate::{
     conditions::Condition,
-    event::{Event, EventMetadata, LogEvent, discriminant::Discriminant},
+    event::{
+        Event, EventArray, EventMetadata, LogEvent, ObjectMap, Value, discriminant::Discriminant,
+    },
     internal_events::{ReduceAddEventError, ReduceStaleEventFlushed},
     transforms::{
         TaskTransform,
         reduce::{
             config::ReduceConfig,
-            merge_strategy::{MergeStrategy, ReduceValueMerger, get_value_merger},
+            merge_strategy::{
+                MergeStrategy, ReduceValueMerger, get_value_merger, restore_value_merger,
+            },
         },
+        state_snapshot::StateSnapshot,
     },
 };
 
+/// The version of the layout of the groups in state snapshots.
+pub(crate) const STATE_SNAPSHOT_VERSION: u32 = 1;
+
 #[derive(Clone, Debug)]
 struct ReduceState {
     events: usize,
@@ -137,9 +147,115 @@ impl ReduceState {
         self.events = 0;
         event
     }
+
+    /// Converts the group into an event for a state snapshot.
+    fn into_snapshot(self, discriminant: &Discriminant, group_by: &[String]) -> LogEvent {
+        let now = Instant::now();
+        let now_utc = Utc::now();
+        let timestamp = |instant: Instant| {
+            let age = now.saturating_duration_since(instant);
+            now_utc - TimeDelta::from_std(age).unwrap_or(TimeDelta::zero())
+        };
+
+        let group = group_by
+            .iter()
+            .zip(discriminant.values())
+            .filter_map(|(field, value)| Some((field.as_str().into(), value.clone()?)))
+            .collect::<ObjectMap>();
+        let fields = self
+            .fields
+            .iter()
+            .map(|(path, merger)| {
+                Value::Object(ObjectMap::from([
+                    ("path".into(), path.to_string().into()),
+                    ("merger".into(), merger.snapshot()),
+                ]))
+            })
+            .collect::<Vec<_>>();
+
+        let mut event = LogEvent::new_with_metadata(self.metadata);
+        event.insert("group", group);
+        event.insert("events", self.events as i64);
+        event.insert("stale_since", timestamp(self.stale_since));
+        event.insert("created_at", timestamp(self.creation));
+        event.insert("fields", fields);
+        event
+    }
+
+    /// Restores a group from an event of a state snapshot, along with its discriminant for the
+    /// given `group_by` fields.
+    fn from_snapshot(
+        mut event: LogEvent,
+        group_by: &[String],
+    ) -> Result<(Discriminant, Self), String> {
+        let mut take = |field: &str| {
+            event
+                .remove(field)
+                .ok_or_else(|| format!("missing field in state snapshot: '{field}'"))
+        };
+        let (
+            Value::Object(group),
+            Value::Integer(events),
+            Value::Timestamp(stale_since),
+            Value::Timestamp(created_at),
+            Value::Array(fields),
+        ) = (
+            take("group")?,
+            take("events")?,
+            take("stale_since")?,
+            take("created_at")?,
+            take("fields")?,
+        )
+        else {
+            return Err("unexpected field types in state snapshot".to_owned());
+        };
+
+        let mut group_event = LogEvent::default();
+        for (field, value) in group {
+            group_event
+                .parse_path_and_insert(field.as_str(), value)
+                .map_err(|error| error.to_string())?;
+        }
+        let discriminant = Discriminant::from_log_event(&group_event, group_by);
+
+        let fields = fields
+            .into_iter()
+            .map(|field| {
+                let Value::Object(mut field) = field else {
+                    return Err("unexpected field in state snapshot".to_owned());
+                };
+                let path = field
+                    .remove("path")
+                    .and_then(|path| parse_target_path(&path.to_string_lossy()).ok())
+                    .ok_or("invalid field path in state snapshot")?;
+                let merger = restore_value_merger(
+                    field
+                        .remove("merger")
+                        .ok_or("missing field merger in state snapshot")?,
+                )?;
+                Ok((path, merger))
+            })
+            .collect::<Result<_, String>>()?;
+
+        let now = Instant::now();
+        let now_utc = Utc::now();
+        let instant = |timestamp: DateTime<Utc>| {
+            let age = (now_utc - timestamp).to_std().unwrap_or_default();
+            now.checked_sub(age).unwrap_or(now)
+        };
+
+        let state = Self {
+            events: usize::try_from(events).unwrap_or_default(),
+            fields,
+            stale_since: instant(stale_since),
+            creation: instant(created_at),
+            metadata: event.into_parts().1,
+        };
+        Ok((discriminant, state))
+    }
 }
 
-#[derive(Clone, Debug)]
+#[derive(Debug)]
 pub struct Reduce {
     expire_after: Duration,
     flush_period: Duration,
@@ -150,6 +266,7 @@ pub struct Reduce {
     ends_when: Option<Condition>,
     starts_when: Option<Condition>,
     max_events: Option<usize>,
+    state_snapshot: Option<StateSnapshot>,
 }
 
 fn validate_merge_strategies(strategies: IndexMap<KeyString, MergeStrategy>) -> crate::Result<()> {
@@ -219,9 +336,44 @@ impl Reduce {
             ends_when,
             starts_when,
             max_events,
+            state_snapshot: None,
         })
     }
 
+    /// Restores the groups snapshotted by the previous instance of the transform when it starts,
+    /// and snapshots the groups left when the transform stops instead of flushing them, unless a
+    /// reload removes the transform or disables its snapshots.
+    pub fn with_state_snapshot(mut self, snapshot: StateSnapshot) -> Self {
+        self.state_snapshot = Some(snapshot);
+        self
+    }
+
+    async fn restore_state_snapshot(&mut self) {
+        if let Some(snapshot) = self.state_snapshot.as_mut()
+            && let Some(events) = snapshot.take().await
+        {
+            self.restore_states(events);
+        }
+    }
+
+    fn restore_states(&mut self, events: EventArray) {
+        let EventArray::Logs(logs) = events else {
+            return;
+        };
+        for log in logs {
+            match ReduceState::from_snapshot(log, &self.group_by) {
+                Ok((discriminant, state)) => {
+                    self.reduce_merge_states.insert(discriminant, state);
+                }
+                Err(error) => {
+                    if let Some(snapshot) = self.state_snapshot.as_ref() {
+                        snapshot.report_invalid(error);
+                    }
+                }
+            }
+        }
+    }
+
     fn flush_into(&mut self, emitter: &mut Emitter<Event>) {
         let mut flush_discriminants = Vec::new();
         let now = Instant::now();
@@ -244,7 +396,23 @@ impl Reduce {
         }
     }
 
-    fn flush_all_into(&mut self, emitter: &mut Emitter<Event>) {
+    async fn flush_all_into(&mut self, emitter: &mut Emitter<Event>) {
+        if let Some(snapshot) = self.state_snapshot.as_mut()
+            && snapshot.keeps_state()
+            && !self.reduce_merge_states.is_empty()
+        {
+            let logs = self
+                .reduce_merge_states
+                .drain()
+                .map(|(discriminant, state)| state.into_snapshot(&discriminant, &self.group_by))
+                .collect();
+            match snapshot.save(EventArray::Logs(logs)).await {
+                Ok(()) => return,
+                // The groups are flushed rather than lost.
+                Err(events) => self.restore_states(events),
+            }
+        }
+
         self.reduce_merge_states
             .drain()
             .for_each(|(_, s)| emitter.emit(Event::from(s.flush())));
@@ -329,28 +497,45 @@ impl TaskTransform<Event> for Reduce {
 }
 
 pub fn construct_output_stream(
-    reduce: Box<Reduce>,
-    input_rx: Pin<Box<dyn Stream<Item = Event> + Send>>,
+    mut reduce: Box<Reduce>,
+    mut input_rx: Pin<Box<dyn Stream<Item = Event> + Send>>,
     mut transform_fn: impl FnMut(&mut Box<Reduce>, Event, &mut Emitter<Event>) + Send + Sync + 'static,
 ) -> Pin<Box<dyn Stream<Item = Event> + Send>>
 where
     Reduce: 'static,
 {
-    let flush_period = reduce.flush_period;
-    Box::pin(map_with_expiration(
-        reduce,
-        input_rx,
-        flush_period,
-        move |me, event, emitter| {
-            transform_fn(me, event, emitter);
-        },
-        |me, emitter| {
-            me.flush_into(emitter);
-        },
-        |me, emitter| {
-            me.flush_all_into(emitter);
-        },
-    ))
+    let mut flush_stream = tokio::time::interval(reduce.flush_period);
+
+    Box::pin(stream! {
+        reduce.restore_state_snapshot().await;
+        loop {
+            let mut emitter = Emitter::new();
+            let done = tokio::select! {
+                _ = flush_stream.tick() => {
+                    reduce.flush_into(&mut emitter);
+                    false
+                }
+                maybe_event = input_rx.next() => {
+                    match maybe_event {
+                        None => {
+                            reduce.flush_all_into(&mut emitter).await;
+                            true
+                        }
+                        Some(event) => {
+                            transform_fn(&mut reduce, event, &mut emitter);
+                            false
+                        }
+                    }
+                }
+            };
+            for event in emitter {
+                yield event;
+            }
+            if done {
+                break;
+            }
+        }
+    })
 }
 
 #[cfg(test)]
@@ -366,10 +551,14 @@ mod test {
 
     use super::*;
     use crate::{
-        config::{OutputId, TransformConfig, schema, schema::Definition},
-        event::{LogEvent, Value},
-        test_util::components::assert_transform_compliance,
-        transforms::test::create_topology,
+        config::{Config, OutputId, TransformConfig, schema, schema::Definition},
+        event::{EventContainer, LogEvent, Value},
+        test_util::{
+            components::assert_transform_compliance,
+            mock::{basic_sink, basic_source},
+            start_topology,
+        },
+        transforms::{state_snapshot::StateSnapshotConfig, test::create_topology},
     };
 
     #[tokio::test]
@@ -1047,4 +1236,122 @@ merge_strategies.bar = "concat"
         })
         .await
     }
+
+    #[tokio::test]
+    async fn reduce_restores_state_snapshot() {
+        let data_dir = tempfile::tempdir().unwrap();
+        let mut reduce_config = toml::from_str::<ReduceConfig>(indoc! {r#"
+            group_by = [ "request_id" ]
+
+            [ends_when]
+              type = "vrl"
+              source = "exists(.test_end)"
+        "#})
+        .unwrap();
+        reduce_config.state_snapshot = Some(StateSnapshotConfig {
+            data_dir: Some(data_dir.path().to_owned()),
+        });
+
+        let mut e_1 = LogEvent::from("test message 1");
+        e_1.insert("counter", 1);
+        e_1.insert("request_id", "1");
+        let mut e_2 = LogEvent::from("test message 2");
+        e_2.insert("counter", 2);
+        e_2.insert("request_id", "1");
+        e_2.insert("test_end", "yes");
+
+        // The group is snapshotted instead of flushed when the transform shuts down.
+        let (tx, rx) = mpsc::channel(1);
+        let (topology, mut out) =
+            create_topology(ReceiverStream::new(rx), reduce_config.clone()).await;
+        tx.send(e_1.into()).await.unwrap();
+        drop(tx);
+        topology.stop().await;
+        assert_eq!(out.recv().await, None);
+
+        // After restarting, the group is completed from where it was left.
+        let (tx, rx) = mpsc::channel(1);
+        let (topology, mut out) = create_topology(ReceiverStream::new(rx), reduce_config).await;
+        tx.send(e_2.into()).await.unwrap();
+        let output = out.recv().await.unwrap().into_log();
+        assert_eq!(output["message"], "test message 1".into());
+        assert_eq!(output["counter"], 3.into());
+        assert_eq!(output["test_end"], "yes".into());
+
+        drop(tx);
+        topology.stop().await;
+        assert_eq!(out.recv().await, None);
+    }
+
+    #[tokio::test]
+    async fn reduce_hands_in_flight_groups_over_on_reload() {
+        let data_dir = tempfile::tempdir().unwrap();
+        let reduce_config = |expire_after_ms: u64| {
+            let mut reduce_config = toml::from_str::<ReduceConfig>(&format!(
+                indoc! {r#"
+                    group_by = [ "request_id" ]
+                    expire_after_ms = {}
+
+                    [merge_strategies]
+                      message = "concat"
+
+                    [ends_when]
+                      type = "vrl"
+                      source = "exists(.test_end)"
+                "#},
+                expire_after_ms
+            ))
+            .unwrap();
+            reduce_config.state_snapshot = Some(StateSnapshotConfig {
+                data_dir: Some(data_dir.path().to_owned()),
+            });
+            reduce_config
+        };
+        let event = |message: &str, end: bool| {
+            let mut log = LogEvent::from(message);
+            log.insert("request_id", "1");
+            if end {
+                log.insert("test_end", "yes");
+            }
+            Event::from(log)
+        };
+
+        let (mut source_tx, source) = basic_source();
+        let (out, sink) = basic_sink(10);
+        let mut config = Config::builder();
+        config.add_source("in", source);
+        config.add_transform("reduce", &["in"], reduce_config(60_000));
+        config.add_sink("out", &["reduce"], sink);
+        let (mut topology, _crash) = start_topology(config.build().unwrap(), false).await;
+        let output = tokio::spawn(out.collect::<Vec<_>>());
+
+        source_tx.send_event(event("a", false)).await.unwrap();
+        source_tx.send_event(event("b", false)).await.unwrap();
+
+        // Changing the transform rebuilds it while its group is still in flight.
+        let (_, source) = basic_source();
+        let (_, sink) = basic_sink(10);
+        let mut config = Config::builder();
+        config.add_source("in", source);
+        config.add_transform("reduce", &["in"], reduce_config(120_000));
+        config.add_sink("out", &["reduce"], sink);
+        topology
+            .reload_config_and_respawn(config.build().unwrap(), Default::default())
+            .await
+            .unwrap();
+
+        // The new instance completes the group left by the old one.
+        source_tx.send_event(event("c", true)).await.unwrap();
+        drop(source_tx);
+        topology.stop().await;
+
+        let output = output
+            .await
+            .unwrap()
+            .into_iter()
+            .flat_map(|item| item.events.into_events())
+            .collect::<Vec<_>>();
+        assert_eq!(output.len(), 1);
+        assert_eq!(output[0].as_log()["message"], "a b c".into());
+    }
 }
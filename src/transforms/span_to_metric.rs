@@ -0,0 +1,436 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use indexmap::IndexMap;
+use vector_lib::{
+    configurable::configurable_component,
+    lookup::{lookup_v2::ConfigValuePath, path},
+};
+
+use crate::{
+    config::{
+        DataType, GenerateConfig, Input, OutputId, TransformConfig, TransformContext,
+        TransformOutput,
+    },
+    event::{
+        Event, EventMetadata, Value,
+        metric::{Metric, MetricKind, MetricTags, MetricValue, Sample, StatisticKind},
+    },
+    schema::Definition,
+    transforms::{FunctionTransform, OutputBuffer, Transform},
+};
+
+const SPANS_TOTAL: &str = "spans_total";
+const SPAN_DURATION_SECONDS: &str = "span_duration_seconds";
+
+/// The OpenTelemetry status codes of spans.
+const OTEL_STATUS_CODE_OK: i64 = 1;
+const OTEL_STATUS_CODE_ERROR: i64 = 2;
+
+/// Configuration for the `span_to_metric` transform.
+#[configurable_component(transform(
+    "span_to_metric",
+    "Derive request rate, error, and duration metrics from the spans of trace events."
+))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct SpanToMetricConfig {
+    /// The namespace of the generated metrics.
+    #[configurable(metadata(docs::examples = "traces"))]
+    pub namespace: Option<String>,
+
+    /// Additional tags to add to the generated metrics, as a map of tag names to the span fields
+    /// holding their values.
+    ///
+    /// Fields are looked up within each span. Tags whose field is missing from a span are omitted.
+    #[serde(default)]
+    #[configurable(metadata(
+        docs::additional_props_description = "The span field holding the value of the tag.",
+        docs::examples = "example_dimensions()"
+    ))]
+    pub dimensions: IndexMap<String, ConfigValuePath>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub duration_metric: DurationMetric,
+
+    /// The upper limits of the buckets of the span duration histogram, in seconds.
+    ///
+    /// Only used when `duration_metric` is set to `histogram`, in which case at least one limit must
+    /// be set, in strictly increasing order.
+    #[serde(default = "default_buckets")]
+    pub buckets: Vec<f64>,
+}
+
+/// The type of metric used for span durations.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum DurationMetric {
+    /// An aggregated histogram, with the buckets set in `buckets`.
+    #[default]
+    Histogram,
+
+    /// A distribution of the individual span durations, left for downstream components to
+    /// aggregate.
+    Distribution,
+}
+
+fn default_buckets() -> Vec<f64> {
+    vec![
+        0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+    ]
+}
+
+fn example_dimensions() -> HashMap<String, String> {
+    HashMap::<_, _>::from_iter([
+        (
+            "http_method".to_owned(),
+            "attributes.\"http.method\"".to_owned(),
+        ),
+        ("env".to_owned(), "meta.env".to_owned()),
+    ])
+}
+
+impl GenerateConfig for SpanToMetricConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(r#"namespace = "traces""#).unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "span_to_metric")]
+impl TransformConfig for SpanToMetricConfig {
+    async fn build(&self, _context: &TransformContext) -> crate::Result<Transform> {
+        if self.duration_metric == DurationMetric::Histogram
+            && (self.buckets.is_empty()
+                || !self.buckets.windows(2).all(|bounds| bounds[0] < bounds[1]))
+        {
+            return Err("`buckets` must be a non-empty list of strictly increasing limits".into());
+        }
+        Ok(Transform::function(SpanToMetric::new(self)))
+    }
+
+    fn enable_concurrency(&self) -> bool {
+        true
+    }
+
+    fn input(&self) -> Input {
+        Input::trace()
+    }
+
+    fn outputs(&self, _: &TransformContext, _: &[(OutputId, Definition)]) -> Vec<TransformOutput> {
+        // Converting the trace to metrics means we lose all incoming `Definition`s.
+        vec![TransformOutput::new(DataType::Metric, HashMap::new())]
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct SpanToMetric {
+    namespace: Option<String>,
+    dimensions: IndexMap<String, ConfigValuePath>,
+    duration_metric: DurationMetric,
+    buckets: Vec<f64>,
+}
+
+impl SpanToMetric {
+    pub fn new(config: &SpanToMetricConfig) -> Self {
+        Self {
+            namespace: config.namespace.clone(),
+            dimensions: config.dimensions.clone(),
+            duration_metric: config.duration_metric,
+            buckets: config.buckets.clone(),
+        }
+    }
+
+    fn tags(&self, span: &Value) -> MetricTags {
+        let mut tags = MetricTags::default();
+        tags.replace("service".to_owned(), service(span));
+        tags.replace("operation".to_owned(), operation(span));
+        tags.replace("status".to_owned(), status(span));
+        for (tag, field) in &self.dimensions {
+            if let Some(value) = span.get(field) {
+                tags.replace(tag.clone(), value.to_string_lossy().into_owned());
+            }
+        }
+        tags
+    }
+
+    fn push_metrics(&self, output: &mut OutputBuffer, span: &Value, metadata: &EventMetadata) {
+        let tags = self.tags(span);
+        let timing = timing(span);
+        let timestamp = timing.map(|(end, _)| end);
+
+        let metric = |name: &str, value| {
+            Metric::new_with_metadata(name, MetricKind::Incremental, value, metadata.clone())
+                .with_namespace(self.namespace.clone())
+                .with_tags(Some(tags.clone()))
+                .with_timestamp(timestamp)
+        };
+
+        output.push(Event::Metric(metric(
+            SPANS_TOTAL,
+            MetricValue::Counter { value: 1.0 },
+        )));
+        if let Some((_, duration)) = timing {
+            let distribution = MetricValue::Distribution {
+                samples: vec![Sample {
+                    value: duration,
+                    rate: 1,
+                }],
+                statistic: StatisticKind::Histogram,
+            };
+            let value = match self.duration_metric {
+                DurationMetric::Histogram => distribution
+                    .distribution_to_agg_histogram(&self.buckets)
+                    .expect("value is a distribution"),
+                DurationMetric::Distribution => distribution,
+            };
+            output.push(Event::Metric(metric(SPAN_DURATION_SECONDS, value)));
+        }
+    }
+}
+
+impl FunctionTransform for SpanToMetric {
+    fn transform(&mut self, output: &mut OutputBuffer, event: Event) {
+        let trace = event.into_trace();
+        let metadata = trace.metadata();
+        match trace.value().get(path!("spans")) {
+            // Datadog traces hold all of their spans.
+            Some(Value::Array(spans)) => {
+                for span in spans.iter().filter(|span| span.is_object()) {
+                    self.push_metrics(output, span, metadata);
+                }
+            }
+            _ => self.push_metrics(output, trace.value(), metadata),
+        }
+    }
+}
+
+/// The name of the service of the span, as reported by Datadog spans or the resource of
+/// OpenTelemetry spans.
+fn service(span: &Value) -> String {
+    span.get(path!("service"))
+        .or_else(|| span.get(path!("resources", "service.name")))
+        .map_or_else(
+            || "unknown".to_owned(),
+            |service| service.to_string_lossy().into_owned(),
+        )
+}
+
+fn operation(span: &Value) -> String {
+    span.get(path!("name")).map_or_else(
+        || "unknown".to_owned(),
+        |name| name.to_string_lossy().into_owned(),
+    )
+}
+
+fn status(span: &Value) -> &'static str {
+    if let Some(Value::Integer(code)) = span.get(path!("status", "code")) {
+        match *code {
+            OTEL_STATUS_CODE_OK => "ok",
+            OTEL_STATUS_CODE_ERROR => "error",
+            _ => "unset",
+        }
+    } else if let Some(Value::Integer(error)) = span.get(path!("error")) {
+        if *error == 0 { "ok" } else { "error" }
+    } else {
+        "unset"
+    }
+}
+
+/// The end time and duration, in seconds, of the span, as reported by the `start_time_unix_nano`
+/// and `end_time_unix_nano` fields of OpenTelemetry spans, or the `start` and `duration` fields of
+/// Datadog spans.
+fn timing(span: &Value) -> Option<(DateTime<Utc>, f64)> {
+    let (start, end) = match (
+        span.get(path!("start_time_unix_nano")),
+        span.get(path!("end_time_unix_nano")),
+        span.get(path!("start")),
+        span.get(path!("duration")),
+    ) {
+        (Some(Value::Timestamp(start)), Some(Value::Timestamp(end)), _, _) => (*start, *end),
+        (_, _, Some(Value::Timestamp(start)), Some(Value::Integer(duration))) => {
+            (*start, *start + chrono::Duration::nanoseconds(*duration))
+        }
+        _ => return None,
+    };
+    let duration = (end - start).num_nanoseconds()? as f64 / 1_000_000_000.0;
+    Some((end, duration))
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+    use vector_lib::event::{ObjectMap, TraceEvent};
+
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<SpanToMetricConfig>();
+    }
+
+    #[tokio::test]
+    async fn rejects_invalid_buckets() {
+        for buckets in ["[]", "[0.1, 0.1]", "[1.0, 0.1]"] {
+            let config =
+                toml::from_str::<SpanToMetricConfig>(&format!("buckets = {buckets}")).unwrap();
+            let error = config
+                .build(&TransformContext::default())
+                .await
+                .err()
+                .expect("invalid buckets are rejected");
+            assert_eq!(
+                error.to_string(),
+                "`buckets` must be a non-empty list of strictly increasing limits",
+                "{buckets}"
+            );
+        }
+
+        let config = toml::from_str::<SpanToMetricConfig>(
+            "duration_metric = \"distribution\"\nbuckets = []",
+        )
+        .unwrap();
+        assert!(config.build(&TransformContext::default()).await.is_ok());
+    }
+
+    fn transform(config: &str, trace: ObjectMap) -> Vec<Metric> {
+        let config = toml::from_str::<SpanToMetricConfig>(config).unwrap();
+        let mut output = OutputBuffer::default();
+        SpanToMetric::new(&config).transform(&mut output, TraceEvent::from(trace).into());
+        output
+            .into_events()
+            .map(|event| event.into_metric())
+            .collect()
+    }
+
+    fn at(nanos: i64) -> Value {
+        Value::Timestamp(Utc.timestamp_nanos(nanos))
+    }
+
+    #[test]
+    fn converts_opentelemetry_span() {
+        let mut span = ObjectMap::new();
+        span.insert("name".into(), "GET /users".into());
+        span.insert("start_time_unix_nano".into(), at(0));
+        span.insert("end_time_unix_nano".into(), at(30_000_000));
+        span.insert(
+            "resources".into(),
+            Value::from(serde_json::json!({"service.name": "users"})),
+        );
+        span.insert("status".into(), Value::from(serde_json::json!({"code": 2})));
+        span.insert(
+            "attributes".into(),
+            Value::from(serde_json::json!({"http.method": "GET"})),
+        );
+
+        let metrics = transform(
+            r#"
+namespace = "traces"
+dimensions.http_method = 'attributes."http.method"'
+buckets = [0.01, 0.1]
+"#,
+            span,
+        );
+
+        assert_eq!(metrics.len(), 2);
+        for metric in &metrics {
+            assert_eq!(metric.namespace(), Some("traces"));
+            assert_eq!(metric.kind(), MetricKind::Incremental);
+            let tags = metric.tags().unwrap();
+            assert_eq!(tags.get("service"), Some("users"));
+            assert_eq!(tags.get("operation"), Some("GET /users"));
+            assert_eq!(tags.get("status"), Some("error"));
+            assert_eq!(tags.get("http_method"), Some("GET"));
+        }
+        assert_eq!(metrics[0].name(), SPANS_TOTAL);
+        assert_eq!(metrics[0].value(), &MetricValue::Counter { value: 1.0 });
+        assert_eq!(metrics[1].name(), SPAN_DURATION_SECONDS);
+        match metrics[1].value() {
+            MetricValue::AggregatedHistogram {
+                buckets,
+                count,
+                sum,
+            } => {
+                assert_eq!(*count, 1);
+                assert!((sum - 0.03).abs() < f64::EPSILON);
+                assert_eq!(
+                    buckets
+                        .iter()
+                        .map(|bucket| bucket.count)
+                        .collect::<Vec<_>>(),
+                    [0, 1]
+                );
+            }
+            value => panic!("unexpected value {value:?}"),
+        }
+    }
+
+    #[test]
+    fn converts_each_datadog_span() {
+        let mut ok = ObjectMap::new();
+        ok.insert("service".into(), "web".into());
+        ok.insert("name".into(), "request".into());
+        ok.insert("error".into(), Value::Integer(0));
+        ok.insert("start".into(), at(0));
+        ok.insert("duration".into(), Value::Integer(2_000_000_000));
+        let mut failed = ok.clone();
+        failed.insert("name".into(), "query".into());
+        failed.insert("error".into(), Value::Integer(1));
+        let mut trace = ObjectMap::new();
+        trace.insert(
+            "spans".into(),
+            Value::Array(vec![Value::Object(ok), Value::Object(failed)]),
+        );
+
+        let metrics = transform(r#"duration_metric = "distribution""#, trace);
+
+        let summary = metrics
+            .iter()
+            .map(|metric| {
+                let tags = metric.tags().unwrap();
+                (
+                    metric.name(),
+                    tags.get("operation").unwrap(),
+                    tags.get("status").unwrap(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            summary,
+            [
+                (SPANS_TOTAL, "request", "ok"),
+                (SPAN_DURATION_SECONDS, "request", "ok"),
+                (SPANS_TOTAL, "query", "error"),
+                (SPAN_DURATION_SECONDS, "query", "error"),
+            ]
+        );
+        assert_eq!(
+            metrics[1].value(),
+            &MetricValue::Distribution {
+                samples: vec![Sample {
+                    value: 2.0,
+                    rate: 1
+                }],
+                statistic: StatisticKind::Histogram,
+            }
+        );
+        assert_eq!(
+            metrics[1].timestamp(),
+            Some(Utc.timestamp_nanos(2_000_000_000))
+        );
+    }
+
+    #[test]
+    fn counts_spans_without_timing() {
+        let mut span = ObjectMap::new();
+        span.insert("name".into(), "orphan".into());
+        let metrics = transform("", span);
+
+        assert_eq!(metrics.len(), 1);
+        let tags = metrics[0].tags().unwrap();
+        assert_eq!(tags.get("service"), Some("unknown"));
+        assert_eq!(tags.get("status"), Some("unset"));
+    }
+}
@@ -0,0 +1,345 @@
+//! Snapshots of the in-flight state of stateful transforms.
+//!
+//! Transforms such as `reduce` and `aggregate` hold partially combined events in memory between
+//! flushes. When snapshots are enabled, they write that state to their data directory as a list of
+//! events when they're shut down or reloaded, and restore it the next time they're built.
+//!
+//! Each transform stamps its snapshots with the version of its state layout. A snapshot written
+//! with a different version is discarded rather than misread.
+//!
+//! On reload, the new instance of a transform is built while the old one is still running. The
+//! new instance only takes the state once the old one has written it, which is coordinated
+//! through the [`handover`] lock of the snapshot. When a reload removes the transform, or replaces
+//! it with an instance that doesn't snapshot its state, the old instance flushes its state instead,
+//! since nothing would ever restore it.
+
+use std::{
+    collections::HashMap,
+    io,
+    path::{Path, PathBuf},
+    sync::{Arc, LazyLock, Mutex, Weak},
+};
+
+use tokio::{
+    fs,
+    io::AsyncWriteExt,
+    sync::{Mutex as AsyncMutex, OwnedMutexGuard},
+};
+use vector_lib::{
+    buffers::encoding::{AsMetadata, Encodable},
+    configurable::configurable_component,
+};
+
+use crate::{
+    config::{Retirement, TransformContext},
+    event::{EventArray, EventStatus, Finalizable},
+    internal_events::{TransformStatePersistError, TransformStateRestoreError},
+};
+
+const SNAPSHOT_FILE: &str = "state.snapshot";
+
+/// Identifies the file as a transform state snapshot.
+const MAGIC: &[u8; 4] = b"VTSS";
+
+/// The magic, followed by the state version and the event encoding metadata.
+const HEADER_LEN: usize = 12;
+
+/// The locks that hand the state of each transform over from one instance to the next, by path.
+static HANDOVERS: LazyLock<Mutex<HashMap<PathBuf, Weak<AsyncMutex<()>>>>> =
+    LazyLock::new(Default::default);
+
+/// Gets the lock that hands the state stored at `path` over from one instance of a transform to
+/// the next.
+///
+/// An instance holds the lock from restoring its state until it has written it back, so that the
+/// next instance doesn't restore the state before it's written.
+pub(crate) fn handover(path: &Path) -> Arc<AsyncMutex<()>> {
+    let mut handovers = HANDOVERS.lock().expect("poisoned lock");
+    handovers.retain(|_, handover| handover.strong_count() > 0);
+    if let Some(handover) = handovers.get(path).and_then(Weak::upgrade) {
+        return handover;
+    }
+    let handover = Arc::default();
+    handovers.insert(path.to_owned(), Arc::downgrade(&handover));
+    handover
+}
+
+/// Configuration for snapshotting the state of a transform.
+///
+/// When configured, the in-flight state of the transform is written to disk when Vector shuts
+/// down or reloads the transform, and restored when the transform starts, instead of being flushed.
+///
+/// When a reload removes the transform, or replaces it with one that doesn't snapshot its state,
+/// the state is flushed as usual.
+#[configurable_component]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct StateSnapshotConfig {
+    /// The directory used to store the state snapshot.
+    ///
+    /// By default, the [global `data_dir` option][global_data_dir] is used. Make sure the running
+    /// user has write permissions to this directory.
+    ///
+    /// If this directory is specified, then Vector will attempt to create it.
+    ///
+    /// [global_data_dir]: https://vector.dev/docs/reference/configuration/global-options/#data_dir
+    #[serde(default)]
+    #[configurable(metadata(docs::examples = "/var/lib/vector"))]
+    #[configurable(metadata(docs::human_name = "Data Directory"))]
+    pub data_dir: Option<PathBuf>,
+}
+
+/// The on-disk snapshot of the state of a transform.
+#[derive(Debug)]
+pub struct StateSnapshot {
+    path: PathBuf,
+    version: u32,
+    handover: Arc<AsyncMutex<()>>,
+    /// Held from taking the state until it's written back.
+    owner: Option<OwnedMutexGuard<()>>,
+    retirement: Retirement,
+}
+
+impl StateSnapshot {
+    /// Creates the snapshot of the transform being built in `context`, for state of the given
+    /// layout version.
+    pub fn new(
+        config: &StateSnapshotConfig,
+        context: &TransformContext,
+        version: u32,
+    ) -> crate::Result<Self> {
+        let key = context
+            .key
+            .as_ref()
+            .ok_or("The state can only be snapshotted for transforms with a component ID.")?;
+        let data_dir = context
+            .globals
+            .resolve_and_make_data_subdir(config.data_dir.as_ref(), key.id())?;
+
+        Ok(Self::in_dir(data_dir, version, context.retirement.clone()))
+    }
+
+    fn in_dir(data_dir: PathBuf, version: u32, retirement: Retirement) -> Self {
+        let path = data_dir.join(SNAPSHOT_FILE);
+        Self {
+            handover: handover(&path),
+            path,
+            version,
+            owner: None,
+            retirement,
+        }
+    }
+
+    /// Whether the transform should save its state as it stops, rather than flush it.
+    ///
+    /// The state is saved when the whole topology stops, and when a reload replaces the transform
+    /// with an instance that snapshots its state to the same place. It's flushed when a reload
+    /// removes the transform, or replaces it with an instance that doesn't snapshot its state.
+    pub fn keeps_state(&self) -> bool {
+        // The next instance holds the handover lock from the time it's built, along with this
+        // instance and the guard of the state it has taken.
+        let held = 1 + usize::from(self.owner.is_some());
+        !self.retirement.is_retired() || Arc::strong_count(&self.handover) > held
+    }
+
+    /// Reports a part of the restored state that the transform can't make sense of, and leaves out.
+    pub fn report_invalid(&self, error: impl Into<Box<dyn std::error::Error + Send + Sync>>) {
+        emit!(TransformStateRestoreError {
+            error: io::Error::new(io::ErrorKind::InvalidData, error),
+            path: self.path.clone(),
+        });
+    }
+
+    /// Takes the state written by the previous instance of the transform, if any.
+    ///
+    /// If the previous instance is still shutting down, this waits until it has written its state.
+    /// The snapshot is removed once read, so that a crash before the next snapshot is written never
+    /// restores the same state twice.
+    pub async fn take(&mut self) -> Option<EventArray> {
+        self.owner = Some(Arc::clone(&self.handover).lock_owned().await);
+
+        let data = match fs::read(&self.path).await {
+            Ok(data) => data,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => return None,
+            Err(error) => {
+                emit!(TransformStateRestoreError {
+                    error,
+                    path: self.path.clone(),
+                });
+                return None;
+            }
+        };
+        if let Err(error) = fs::remove_file(&self.path).await {
+            emit!(TransformStateRestoreError {
+                error,
+                path: self.path.clone(),
+            });
+        }
+
+        self.decode(&data)
+            .map_err(|error| {
+                emit!(TransformStateRestoreError {
+                    error,
+                    path: self.path.clone(),
+                })
+            })
+            .ok()
+    }
+
+    fn decode(&self, data: &[u8]) -> io::Result<EventArray> {
+        let invalid = |message: String| io::Error::new(io::ErrorKind::InvalidData, message);
+
+        let (header, payload) = data
+            .split_at_checked(HEADER_LEN)
+            .filter(|(header, _)| header.starts_with(MAGIC))
+            .ok_or_else(|| invalid("not a transform state snapshot".to_owned()))?;
+        let version = u32::from_be_bytes(header[4..8].try_into().expect("4 bytes"));
+        if version != self.version {
+            return Err(invalid(format!(
+                "snapshot has state version {version}, but version {} is expected",
+                self.version
+            )));
+        }
+        let metadata = u32::from_be_bytes(header[8..12].try_into().expect("4 bytes"));
+        let metadata = <EventArray as Encodable>::Metadata::from_u32(metadata)
+            .filter(|metadata| EventArray::can_decode(*metadata))
+            .ok_or_else(|| invalid(format!("unsupported event encoding {metadata:#x}")))?;
+
+        EventArray::decode(metadata, payload).map_err(|error| invalid(error.to_string()))
+    }
+
+    /// Writes `events` as the state of the transform, replacing any previous snapshot, and hands the
+    /// snapshot over to the next instance of the transform.
+    ///
+    /// Once written, the events are acknowledged as delivered. If they can't be written, they're
+    /// handed back so that the transform can flush them instead.
+    pub async fn save(&mut self, mut events: EventArray) -> Result<(), EventArray> {
+        let mut data = Vec::with_capacity(HEADER_LEN);
+        data.extend_from_slice(MAGIC);
+        data.extend_from_slice(&self.version.to_be_bytes());
+        data.extend_from_slice(&EventArray::get_metadata().into_u32().to_be_bytes());
+
+        // The snapshot is written to a temporary file and synced to disk before it replaces the
+        // previous one, so that a crash while writing it never leaves a partial snapshot behind.
+        let temp_path = self.path.with_extension("snapshot.tmp");
+        let result = async {
+            events.clone().encode(&mut data).map_err(io::Error::other)?;
+            let mut file = fs::File::create(&temp_path).await?;
+            file.write_all(&data).await?;
+            file.sync_all().await?;
+            drop(file);
+            fs::rename(&temp_path, &self.path).await
+        }
+        .await;
+        self.owner = None;
+
+        match result {
+            Ok(()) => {
+                events
+                    .take_finalizers()
+                    .update_status(EventStatus::Delivered);
+                Ok(())
+            }
+            Err(error) => {
+                emit!(TransformStatePersistError {
+                    error,
+                    path: self.path.clone(),
+                });
+                Err(events)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::LogEvent;
+
+    fn messages(events: EventArray) -> Vec<String> {
+        events
+            .iter_events()
+            .map(|event| event.as_log()["message"].to_string_lossy().into_owned())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn restores_saved_state_once() {
+        let data_dir = tempfile::tempdir().unwrap();
+        let mut snapshot =
+            StateSnapshot::in_dir(data_dir.path().to_owned(), 1, Retirement::default());
+
+        let logs = vec![LogEvent::from("a"), LogEvent::from("b")];
+        snapshot.save(EventArray::Logs(logs)).await.unwrap();
+
+        let mut next = StateSnapshot::in_dir(data_dir.path().to_owned(), 1, Retirement::default());
+        assert_eq!(messages(next.take().await.unwrap()), ["a", "b"]);
+        drop(next);
+
+        let mut next = StateSnapshot::in_dir(data_dir.path().to_owned(), 1, Retirement::default());
+        assert!(next.take().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn waits_for_previous_instance_to_save_state() {
+        let data_dir = tempfile::tempdir().unwrap();
+        let mut previous =
+            StateSnapshot::in_dir(data_dir.path().to_owned(), 1, Retirement::default());
+        assert!(previous.take().await.is_none());
+
+        // The next instance is built while the previous one still holds its state.
+        let mut next = StateSnapshot::in_dir(data_dir.path().to_owned(), 1, Retirement::default());
+        let mut take = tokio_test::task::spawn(next.take());
+        tokio_test::assert_pending!(take.poll());
+
+        previous
+            .save(EventArray::from(LogEvent::from("a")))
+            .await
+            .unwrap();
+        let state = take.await.unwrap();
+        assert_eq!(messages(state), ["a"]);
+    }
+
+    #[tokio::test]
+    async fn retired_instance_keeps_state_only_for_next_instance() {
+        let data_dir = tempfile::tempdir().unwrap();
+        let retirement = Retirement::default();
+        let mut previous = StateSnapshot::in_dir(data_dir.path().to_owned(), 1, retirement.clone());
+        assert!(previous.take().await.is_none());
+        assert!(previous.keeps_state());
+
+        // Removed, or replaced by an instance that doesn't snapshot its state.
+        retirement.retire();
+        assert!(!previous.keeps_state());
+
+        // Replaced by an instance that snapshots its state.
+        let next = StateSnapshot::in_dir(data_dir.path().to_owned(), 1, Retirement::default());
+        assert!(previous.keeps_state());
+        drop(next);
+        assert!(!previous.keeps_state());
+    }
+
+    #[tokio::test]
+    async fn discards_state_of_other_version() {
+        let data_dir = tempfile::tempdir().unwrap();
+        StateSnapshot::in_dir(data_dir.path().to_owned(), 1, Retirement::default())
+            .save(EventArray::from(LogEvent::from("a")))
+            .await
+            .unwrap();
+
+        let mut snapshot =
+            StateSnapshot::in_dir(data_dir.path().to_owned(), 2, Retirement::default());
+        assert!(snapshot.take().await.is_none());
+        assert!(!data_dir.path().join(SNAPSHOT_FILE).exists());
+    }
+
+    #[tokio::test]
+    async fn discards_invalid_snapshot() {
+        let data_dir = tempfile::tempdir().unwrap();
+        std::fs::write(data_dir.path().join(SNAPSHOT_FILE), b"garbage").unwrap();
+
+        let mut snapshot =
+            StateSnapshot::in_dir(data_dir.path().to_owned(), 1, Retirement::default());
+        assert!(snapshot.take().await.is_none());
+    }
+}
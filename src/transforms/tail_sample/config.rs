@@ -0,0 +1,224 @@
+use std::{num::NonZeroUsize, time::Duration};
+
+use serde_with::serde_as;
+use snafu::Snafu;
+use vector_lib::{
+    config::clone_input_definitions, configurable::configurable_component,
+    lookup::lookup_v2::ConfigTargetPath,
+};
+use vrl::owned_event_path;
+
+use super::transform::TailSample;
+use crate::{
+    conditions::AnyCondition,
+    config::{
+        DataType, GenerateConfig, Input, OutputId, TransformConfig, TransformContext,
+        TransformOutput,
+    },
+    schema,
+    transforms::Transform,
+};
+
+#[derive(Debug, Snafu)]
+pub enum TailSampleError {
+    #[snafu(display(
+        "Only numbers between 0 and 1 are allowed values for `policies.ratio`, value: {ratio}"
+    ))]
+    InvalidRatio { ratio: f64 },
+}
+
+/// Configuration for the `tail_sample` transform.
+#[serde_as]
+#[configurable_component(transform(
+    "tail_sample",
+    "Sample traces as a whole, once their spans have been received, based on a set of policies."
+))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct TailSampleConfig {
+    /// The field holding the ID of the trace that a span belongs to.
+    ///
+    /// Spans without this field are forwarded without being sampled.
+    #[serde(default = "default_trace_id_field")]
+    #[configurable(metadata(docs::examples = "trace_id"))]
+    pub trace_id_field: ConfigTargetPath,
+
+    /// A condition matching the span that completes its trace, such as its root span.
+    ///
+    /// When a span matches, its trace is sampled right away rather than once `decision_wait_ms`
+    /// elapses. Spans of the trace received afterwards follow the same sampling decision.
+    pub complete_when: Option<AnyCondition>,
+
+    /// The maximum period of time to wait for the spans of a trace, in milliseconds, after its first
+    /// span is received.
+    ///
+    /// Once elapsed, the trace is sampled based on the spans received so far. Spans of the trace
+    /// received afterwards follow the same sampling decision.
+    #[serde(default = "default_decision_wait_ms")]
+    #[serde_as(as = "serde_with::DurationMilliSeconds<u64>")]
+    #[configurable(metadata(docs::human_name = "Decision Wait"))]
+    pub decision_wait_ms: Duration,
+
+    /// The interval to check for and sample any expired traces, in milliseconds.
+    #[serde(default = "default_flush_period_ms")]
+    #[serde_as(as = "serde_with::DurationMilliSeconds<u64>")]
+    #[configurable(metadata(docs::human_name = "Flush Period"))]
+    pub flush_period_ms: Duration,
+
+    /// The maximum number of traces to hold in memory while waiting for their spans.
+    ///
+    /// When the limit is reached, the oldest trace is sampled right away based on the spans received
+    /// so far. This is also the number of sampling decisions remembered for spans of the trace that
+    /// are received afterwards.
+    #[serde(default = "default_max_traces")]
+    pub max_traces: NonZeroUsize,
+
+    /// The maximum number of spans to hold in memory across all traces.
+    ///
+    /// When the limit is reached, the oldest traces are sampled right away based on the spans
+    /// received so far.
+    #[serde(default = "default_max_spans")]
+    pub max_spans: NonZeroUsize,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub policies: TailSamplePolicies,
+}
+
+/// The policies used to decide whether a trace is kept.
+///
+/// A trace is kept if any of the policies match it, and dropped otherwise.
+#[serde_as]
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct TailSamplePolicies {
+    /// Keep traces with at least one span that has an error.
+    ///
+    /// OpenTelemetry spans with a `status.code` of `2`, and Datadog spans with a non-zero `error`,
+    /// have an error.
+    #[serde(default = "crate::serde::default_true")]
+    pub keep_errors: bool,
+
+    /// Keep traces that last longer than this threshold, in milliseconds.
+    ///
+    /// The duration of a trace spans from the start of its earliest span to the end of its latest
+    /// span, as reported by the `start_time_unix_nano` and `end_time_unix_nano` fields of
+    /// OpenTelemetry spans, or the `start` and `duration` fields of Datadog spans.
+    #[serde_as(as = "Option<serde_with::DurationMilliSeconds<u64>>")]
+    #[configurable(metadata(docs::examples = 500))]
+    #[configurable(metadata(docs::human_name = "Latency Threshold"))]
+    pub latency_threshold_ms: Option<Duration>,
+
+    /// Keep traces with at least one span matching this condition.
+    pub condition: Option<AnyCondition>,
+
+    /// The ratio of the remaining traces to keep.
+    ///
+    /// Traces are picked by hashing their ID, so every instance of Vector sampling the same
+    /// traces keeps the same ones.
+    #[configurable(metadata(docs::examples = 0.1))]
+    #[configurable(validation(range(min = 0.0, max = 1.0)))]
+    pub ratio: Option<f64>,
+}
+
+impl Default for TailSamplePolicies {
+    fn default() -> Self {
+        Self {
+            keep_errors: true,
+            latency_threshold_ms: None,
+            condition: None,
+            ratio: None,
+        }
+    }
+}
+
+fn default_trace_id_field() -> ConfigTargetPath {
+    ConfigTargetPath(owned_event_path!("trace_id"))
+}
+
+const fn default_decision_wait_ms() -> Duration {
+    Duration::from_millis(30000)
+}
+
+const fn default_flush_period_ms() -> Duration {
+    Duration::from_millis(1000)
+}
+
+const fn default_max_traces() -> NonZeroUsize {
+    NonZeroUsize::new(10_000).expect("static non-zero number")
+}
+
+const fn default_max_spans() -> NonZeroUsize {
+    NonZeroUsize::new(100_000).expect("static non-zero number")
+}
+
+impl GenerateConfig for TailSampleConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"decision_wait_ms = 30000
+policies.keep_errors = true
+policies.latency_threshold_ms = 500
+policies.ratio = 0.1"#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "tail_sample")]
+impl TransformConfig for TailSampleConfig {
+    async fn build(&self, context: &TransformContext) -> crate::Result<Transform> {
+        if let Some(ratio) = self.policies.ratio
+            && !(0.0..=1.0).contains(&ratio)
+        {
+            return Err(TailSampleError::InvalidRatio { ratio }.into());
+        }
+        let build_condition = |condition: &Option<AnyCondition>| {
+            condition
+                .as_ref()
+                .map(|condition| {
+                    condition.build(&context.enrichment_tables, &context.metrics_storage)
+                })
+                .transpose()
+        };
+
+        Ok(Transform::synchronous(TailSample::new(
+            self,
+            build_condition(&self.complete_when)?,
+            build_condition(&self.policies.condition)?,
+        )))
+    }
+
+    fn input(&self) -> Input {
+        Input::trace()
+    }
+
+    fn outputs(
+        &self,
+        _: &TransformContext,
+        input_definitions: &[(OutputId, schema::Definition)],
+    ) -> Vec<TransformOutput> {
+        vec![TransformOutput::new(
+            DataType::Trace,
+            clone_input_definitions(input_definitions),
+        )]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<TailSampleConfig>();
+    }
+
+    #[tokio::test]
+    async fn rejects_invalid_ratio() {
+        let config = toml::from_str::<TailSampleConfig>("policies.ratio = 1.5").unwrap();
+
+        assert!(config.build(&TransformContext::default()).await.is_err());
+    }
+}
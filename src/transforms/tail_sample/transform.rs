@@ -0,0 +1,511 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    time::{Duration, Instant},
+};
+
+use chrono::{DateTime, Utc};
+use lru::LruCache;
+use vector_lib::{
+    lookup::lookup_v2::ConfigTargetPath,
+    transform::{SyncTransform, TransformOutputsBuf},
+};
+
+use super::config::TailSampleConfig;
+use crate::{
+    conditions::Condition,
+    event::{Event, ObjectMap, TraceEvent, Value},
+    internal_events::{
+        TailSampleBuffered, TailSampleSpansDiscarded, TailSampleTraceEvicted,
+        TailSampleTraceSampled,
+    },
+};
+
+/// The OpenTelemetry status code of spans that have an error.
+const OTEL_STATUS_CODE_ERROR: i64 = 2;
+
+/// The spans of a trace waiting for the trace to complete, along with what is known about the trace
+/// so far.
+#[derive(Clone, Debug)]
+struct PendingTrace {
+    spans: Vec<TraceEvent>,
+    expires_at: Instant,
+    error: bool,
+    matched_condition: bool,
+    start: Option<DateTime<Utc>>,
+    end: Option<DateTime<Utc>>,
+}
+
+impl PendingTrace {
+    const fn new(expires_at: Instant) -> Self {
+        Self {
+            spans: Vec::new(),
+            expires_at,
+            error: false,
+            matched_condition: false,
+            start: None,
+            end: None,
+        }
+    }
+
+    /// Records what the span tells about the trace, as reported by OpenTelemetry spans or Datadog
+    /// traces.
+    fn observe(&mut self, span: &TraceEvent) {
+        let fields = span.as_map();
+        self.observe_span(fields, "start_time_unix_nano", "end_time_unix_nano");
+        // Datadog traces hold all of their spans, as well as the time range of the whole trace.
+        self.observe_span(fields, "start_time", "end_time");
+        if let Some(Value::Array(spans)) = fields.get("spans") {
+            for span in spans.iter().filter_map(Value::as_object) {
+                self.error |= has_error(span);
+                if let (Some(Value::Timestamp(start)), Some(Value::Integer(duration))) =
+                    (span.get("start"), span.get("duration"))
+                {
+                    self.extend(*start, *start + chrono::Duration::nanoseconds(*duration));
+                }
+            }
+        }
+    }
+
+    fn observe_span(&mut self, fields: &ObjectMap, start_field: &str, end_field: &str) {
+        self.error |= has_error(fields);
+        if let (Some(Value::Timestamp(start)), Some(Value::Timestamp(end))) =
+            (fields.get(start_field), fields.get(end_field))
+        {
+            self.extend(*start, *end);
+        }
+    }
+
+    fn extend(&mut self, start: DateTime<Utc>, end: DateTime<Utc>) {
+        self.start = Some(self.start.map_or(start, |current| current.min(start)));
+        self.end = Some(self.end.map_or(end, |current| current.max(end)));
+    }
+
+    fn latency(&self) -> Option<Duration> {
+        (self.end? - self.start?).to_std().ok()
+    }
+}
+
+fn has_error(span: &ObjectMap) -> bool {
+    let otel_error = span
+        .get("status")
+        .and_then(Value::as_object)
+        .and_then(|status| status.get("code"))
+        .is_some_and(|code| *code == Value::Integer(OTEL_STATUS_CODE_ERROR));
+    let datadog_error = matches!(span.get("error"), Some(Value::Integer(error)) if *error != 0);
+    otel_error || datadog_error
+}
+
+#[derive(Clone)]
+pub struct TailSample {
+    trace_id_field: ConfigTargetPath,
+    complete_when: Option<Condition>,
+    decision_wait: Duration,
+    flush_period: Duration,
+    max_traces: usize,
+    max_spans: usize,
+    keep_errors: bool,
+    latency_threshold: Option<Duration>,
+    condition: Option<Condition>,
+    ratio_threshold: Option<u64>,
+    pending: HashMap<String, PendingTrace>,
+    /// The traces in the order they were first seen, used to sample the oldest traces first.
+    /// Traces sampled early are left behind and skipped once they reach the front, and are removed
+    /// once they outnumber the pending traces.
+    arrivals: VecDeque<(String, Instant)>,
+    buffered_spans: usize,
+    /// The sampling decisions of recent traces, applied to their late spans.
+    decisions: LruCache<String, bool>,
+}
+
+impl TailSample {
+    pub fn new(
+        config: &TailSampleConfig,
+        complete_when: Option<Condition>,
+        condition: Option<Condition>,
+    ) -> Self {
+        Self {
+            trace_id_field: config.trace_id_field.clone(),
+            complete_when,
+            decision_wait: config.decision_wait_ms,
+            flush_period: config.flush_period_ms,
+            max_traces: config.max_traces.get(),
+            max_spans: config.max_spans.get(),
+            keep_errors: config.policies.keep_errors,
+            latency_threshold: config.policies.latency_threshold_ms,
+            condition,
+            // As in the `sample` transform, the ratio is scaled to the width of the hash rather than
+            // converting the hash to a ratio, to avoid issues with precision.
+            ratio_threshold: config
+                .policies
+                .ratio
+                .map(|ratio| (ratio * (u64::MAX as u128) as f64) as u64),
+            pending: HashMap::new(),
+            arrivals: VecDeque::new(),
+            buffered_spans: 0,
+            decisions: LruCache::new(config.max_traces),
+        }
+    }
+
+    /// Returns the policy the trace is kept by, if any.
+    fn keep_policy(&self, trace_id: &str, trace: &PendingTrace) -> Option<&'static str> {
+        if self.keep_errors && trace.error {
+            Some("error")
+        } else if self
+            .latency_threshold
+            .zip(trace.latency())
+            .is_some_and(|(threshold, latency)| latency > threshold)
+        {
+            Some("latency")
+        } else if trace.matched_condition {
+            Some("condition")
+        } else if self
+            .ratio_threshold
+            .is_some_and(|threshold| seahash::hash(trace_id.as_bytes()) <= threshold)
+        {
+            Some("ratio")
+        } else {
+            None
+        }
+    }
+
+    fn sample(&mut self, trace_id: String, output: &mut TransformOutputsBuf) {
+        let Some(trace) = self.pending.remove(&trace_id) else {
+            return;
+        };
+        self.buffered_spans -= trace.spans.len();
+        self.compact_arrivals();
+
+        let policy = self.keep_policy(&trace_id, &trace);
+        emit!(TailSampleTraceSampled {
+            policy,
+            spans: trace.spans.len(),
+        });
+        if policy.is_some() {
+            for span in trace.spans {
+                output.push(None, span.into());
+            }
+        }
+        self.decisions.put(trace_id, policy.is_some());
+    }
+
+    /// Removes the arrivals of traces that are no longer pending once they outnumber the pending
+    /// traces, so traces sampled early by `complete_when` don't grow the queue without bound.
+    fn compact_arrivals(&mut self) {
+        if self.arrivals.len() > 2 * self.pending.len() {
+            let pending = &self.pending;
+            self.arrivals.retain(|(trace_id, expires_at)| {
+                pending
+                    .get(trace_id)
+                    .is_some_and(|trace| trace.expires_at == *expires_at)
+            });
+        }
+    }
+
+    /// Samples the oldest trace still pending, regardless of whether it expired.
+    fn evict_oldest(&mut self, output: &mut TransformOutputsBuf) {
+        while let Some((trace_id, expires_at)) = self.arrivals.pop_front() {
+            if self
+                .pending
+                .get(&trace_id)
+                .is_some_and(|trace| trace.expires_at == expires_at)
+            {
+                emit!(TailSampleTraceEvicted {
+                    max_traces: self.max_traces,
+                    max_spans: self.max_spans,
+                });
+                self.sample(trace_id, output);
+                return;
+            }
+        }
+    }
+
+    /// Samples the traces that expired at `now`, or all of the pending traces if `now` is `None`.
+    fn sample_expired(&mut self, now: Option<Instant>, output: &mut TransformOutputsBuf) {
+        // Every trace waits for the same period, so the expired ones are always first.
+        while let Some((_, expires_at)) = self.arrivals.front()
+            && now.is_none_or(|now| *expires_at <= now)
+        {
+            let (trace_id, expires_at) = self.arrivals.pop_front().expect("front exists");
+            if self
+                .pending
+                .get(&trace_id)
+                .is_some_and(|trace| trace.expires_at == expires_at)
+            {
+                self.sample(trace_id, output);
+            }
+        }
+        emit!(TailSampleBuffered {
+            traces: self.pending.len(),
+            spans: self.buffered_spans,
+        });
+    }
+}
+
+impl SyncTransform for TailSample {
+    fn transform(&mut self, event: Event, output: &mut TransformOutputsBuf) {
+        let span = event.into_trace();
+        let Some(trace_id) = span
+            .get(&self.trace_id_field)
+            .map(|trace_id| trace_id.to_string_lossy().into_owned())
+        else {
+            output.push(None, span.into());
+            return;
+        };
+
+        // Spans received after their trace was sampled follow the same decision.
+        if let Some(keep) = self.decisions.get(&trace_id) {
+            if *keep {
+                output.push(None, span.into());
+            } else {
+                emit!(TailSampleSpansDiscarded { count: 1 });
+            }
+            return;
+        }
+
+        let (matched_condition, span) = match &self.condition {
+            Some(condition) => {
+                let (matched, event) = condition.check(span.into());
+                (matched, event.into_trace())
+            }
+            None => (false, span),
+        };
+        let (complete, span) = match &self.complete_when {
+            Some(condition) => {
+                let (complete, event) = condition.check(span.into());
+                (complete, event.into_trace())
+            }
+            None => (false, span),
+        };
+
+        if !self.pending.contains_key(&trace_id) {
+            if self.pending.len() >= self.max_traces {
+                self.evict_oldest(output);
+            }
+            let expires_at = Instant::now() + self.decision_wait;
+            self.arrivals.push_back((trace_id.clone(), expires_at));
+            self.pending
+                .insert(trace_id.clone(), PendingTrace::new(expires_at));
+        }
+        let trace = self.pending.get_mut(&trace_id).expect("trace is pending");
+        trace.observe(&span);
+        trace.matched_condition |= matched_condition;
+        trace.spans.push(span);
+        self.buffered_spans += 1;
+
+        if complete {
+            self.sample(trace_id, output);
+        }
+        while self.buffered_spans > self.max_spans {
+            self.evict_oldest(output);
+        }
+    }
+
+    fn flush_interval(&self) -> Option<Duration> {
+        Some(self.flush_period)
+    }
+
+    fn flush(&mut self, output: &mut TransformOutputsBuf) {
+        self.sample_expired(Some(Instant::now()), output);
+    }
+
+    fn flush_all(&mut self, output: &mut TransformOutputsBuf) {
+        self.sample_expired(None, output);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+    use vector_lib::{
+        config::{DataType, TransformOutput},
+        lookup::event_path,
+    };
+
+    use super::*;
+    use crate::{conditions::AnyCondition, config::TransformContext};
+
+    fn tail_sample(config: &str) -> TailSample {
+        let config = toml::from_str::<TailSampleConfig>(config).unwrap();
+        let build = |condition: &Option<AnyCondition>| {
+            condition.as_ref().map(|condition| {
+                let context = TransformContext::default();
+                condition
+                    .build(&context.enrichment_tables, &context.metrics_storage)
+                    .unwrap()
+            })
+        };
+        TailSample::new(
+            &config,
+            build(&config.complete_when),
+            build(&config.policies.condition),
+        )
+    }
+
+    fn outputs() -> TransformOutputsBuf {
+        TransformOutputsBuf::new_with_capacity(
+            vec![TransformOutput::new(DataType::all_bits(), HashMap::new())],
+            1,
+        )
+    }
+
+    fn span(trace_id: &str, name: &str, fields: serde_json::Value) -> Event {
+        let mut span = ObjectMap::new();
+        span.insert("trace_id".into(), trace_id.into());
+        span.insert("name".into(), name.into());
+        if let Value::Object(fields) = Value::from(fields) {
+            span.extend(fields);
+        }
+        TraceEvent::from(span).into()
+    }
+
+    fn names(outputs: &mut TransformOutputsBuf) -> Vec<String> {
+        outputs
+            .drain()
+            .map(|event| {
+                event.as_trace().as_map()["name"]
+                    .to_string_lossy()
+                    .into_owned()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn keeps_traces_with_errors() {
+        let mut sample = tail_sample("");
+        let mut outputs = outputs();
+
+        sample.transform(span("a", "a1", serde_json::json!({})), &mut outputs);
+        sample.transform(
+            span("a", "a2", serde_json::json!({"status": {"code": 2}})),
+            &mut outputs,
+        );
+        sample.transform(span("b", "b1", serde_json::json!({})), &mut outputs);
+        assert!(names(&mut outputs).is_empty());
+
+        sample.flush_all(&mut outputs);
+        assert_eq!(names(&mut outputs), ["a1", "a2"]);
+    }
+
+    #[test]
+    fn keeps_slow_traces() {
+        let mut sample = tail_sample("policies.latency_threshold_ms = 500");
+        let mut outputs = outputs();
+
+        let at = |millis| Utc.timestamp_millis_opt(millis).unwrap();
+        for (trace_id, start, end) in [("a", 0, 200), ("a", 300, 600), ("b", 0, 400)] {
+            let mut event = span(trace_id, trace_id, serde_json::json!({}));
+            let span = event.as_mut_trace();
+            span.insert(event_path!("start_time_unix_nano"), at(start));
+            span.insert(event_path!("end_time_unix_nano"), at(end));
+            sample.transform(event, &mut outputs);
+        }
+
+        sample.flush_all(&mut outputs);
+        assert_eq!(names(&mut outputs), ["a", "a"]);
+    }
+
+    #[test]
+    fn keeps_traces_matching_condition_and_applies_decision_to_late_spans() {
+        let mut sample = tail_sample(
+            r#"
+complete_when = '.parent_span_id == ""'
+policies.condition = '.name == "checkout"'
+"#,
+        );
+        let mut outputs = outputs();
+
+        sample.transform(span("a", "checkout", serde_json::json!({})), &mut outputs);
+        sample.transform(
+            span("a", "root", serde_json::json!({"parent_span_id": ""})),
+            &mut outputs,
+        );
+        sample.transform(
+            span("b", "root", serde_json::json!({"parent_span_id": ""})),
+            &mut outputs,
+        );
+        assert_eq!(names(&mut outputs), ["checkout", "root"]);
+
+        sample.transform(span("a", "late", serde_json::json!({})), &mut outputs);
+        sample.transform(span("b", "late", serde_json::json!({})), &mut outputs);
+        assert_eq!(names(&mut outputs), ["late"]);
+        assert!(sample.pending.is_empty());
+        assert!(sample.arrivals.is_empty());
+    }
+
+    #[test]
+    fn removes_arrivals_of_completed_traces() {
+        let mut sample = tail_sample(r#"complete_when = '.parent_span_id == ""'"#);
+        let mut outputs = outputs();
+
+        sample.transform(span("open", "child", serde_json::json!({})), &mut outputs);
+        for trace_id in 0..100 {
+            sample.transform(
+                span(
+                    &trace_id.to_string(),
+                    "root",
+                    serde_json::json!({"parent_span_id": ""}),
+                ),
+                &mut outputs,
+            );
+        }
+
+        assert_eq!(sample.pending.len(), 1);
+        assert!(sample.arrivals.len() <= 2);
+        assert_eq!(sample.arrivals.front().unwrap().0, "open");
+    }
+
+    #[test]
+    fn keeps_ratio_of_traces_consistently() {
+        let mut sample = tail_sample("policies.ratio = 0.5");
+        let mut outputs = outputs();
+
+        let trace_ids = (0..100).map(|id| id.to_string()).collect::<Vec<_>>();
+        for trace_id in &trace_ids {
+            sample.transform(
+                span(trace_id, trace_id, serde_json::json!({})),
+                &mut outputs,
+            );
+        }
+        sample.flush_all(&mut outputs);
+        let kept = names(&mut outputs);
+        assert!(!kept.is_empty() && kept.len() < trace_ids.len());
+
+        let mut other = tail_sample("policies.ratio = 0.5");
+        for trace_id in &trace_ids {
+            other.transform(
+                span(trace_id, trace_id, serde_json::json!({})),
+                &mut outputs,
+            );
+        }
+        other.flush_all(&mut outputs);
+        assert_eq!(names(&mut outputs), kept);
+    }
+
+    #[test]
+    fn samples_oldest_traces_over_limits() {
+        let mut sample = tail_sample("max_traces = 2\nmax_spans = 3\npolicies.ratio = 1.0");
+        let mut outputs = outputs();
+
+        sample.transform(span("a", "a1", serde_json::json!({})), &mut outputs);
+        sample.transform(span("b", "b1", serde_json::json!({})), &mut outputs);
+        sample.transform(span("c", "c1", serde_json::json!({})), &mut outputs);
+        assert_eq!(names(&mut outputs), ["a1"]);
+
+        sample.transform(span("b", "b2", serde_json::json!({})), &mut outputs);
+        sample.transform(span("c", "c2", serde_json::json!({})), &mut outputs);
+        assert_eq!(names(&mut outputs), ["b1", "b2"]);
+        assert_eq!(sample.buffered_spans, 2);
+    }
+
+    #[test]
+    fn forwards_spans_without_trace_id() {
+        let mut sample = tail_sample("");
+        let mut outputs = outputs();
+
+        let mut span = ObjectMap::new();
+        span.insert("name".into(), "orphan".into());
+        sample.transform(TraceEvent::from(span).into(), &mut outputs);
+
+        assert_eq!(names(&mut outputs), ["orphan"]);
+    }
+}
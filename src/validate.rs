@@ -1,15 +1,20 @@
 #![allow(missing_docs)]
 
-use std::{fmt, fs::remove_dir_all, path::PathBuf};
+use std::{collections::HashSet, fmt, fs::remove_dir_all, path::PathBuf};
 
 use clap::Parser;
 use colored::*;
 use exitcode::ExitCode;
+#[cfg(feature = "api-client")]
+use vector_lib::api_client::{
+    Client,
+    proto::{self, ConfigFormat},
+};
 
 use crate::{
-    config::{self, Config, ConfigDiff, loading::ConfigBuilderLoader},
+    config::{self, BufferAction, Config, ConfigDiff, Difference, loading::ConfigBuilderLoader},
     topology::{
-        self,
+        self, ReloadError,
         builder::{TopologyPieces, TopologyPiecesBuilder},
     },
 };
@@ -86,6 +91,19 @@ pub struct Opts {
         default_value = "false"
     )]
     pub disable_env_var_interpolation: bool,
+
+    /// Print the components and sink buffers that reloading into the validated configuration
+    /// would change.
+    ///
+    /// Either the URL of a running Vector's API, such as `http://127.0.0.1:8686`, or a config
+    /// file or directory to compare against. Dropped or rebuilt buffers are reported as
+    /// warnings.
+    #[arg(long, value_name = "RUNNING_API_URL|CONFIG")]
+    pub diff_against: Option<String>,
+
+    /// The control token of the running Vector's API, used with `--diff-against <URL>`.
+    #[arg(long, env = "VECTOR_API_CONTROL_TOKEN")]
+    pub control_token: Option<String>,
 }
 
 impl Opts {
@@ -117,6 +135,10 @@ pub async fn validate(opts: &Opts, color: bool) -> ExitCode {
         None => return exitcode::CONFIG,
     };
 
+    if let Some(target) = &opts.diff_against {
+        validated &= validate_diff(opts, target, &config, &mut fmt).await;
+    }
+
     if !opts.no_environment {
         if let Some(tmp_directory) = create_tmp_directory(&mut config, &mut fmt) {
             validated &= validate_environment(opts, &config, &mut fmt).await;
@@ -259,6 +281,205 @@ async fn validate_healthchecks(
     validated
 }
 
+/// The changes a reload would make to a topology.
+struct ReloadImpact {
+    /// Added, changed and removed components, by kind of component.
+    components: Vec<(&'static str, Vec<String>, Vec<String>, Vec<String>)>,
+    buffers: Vec<(String, BufferAction)>,
+    /// Reasons the reload would fail.
+    errors: Vec<String>,
+}
+
+impl ReloadImpact {
+    fn from_diff(diff: &ConfigDiff, old: &Config, new: &Config) -> Self {
+        let sorted = |keys: &HashSet<config::ComponentKey>| {
+            let mut ids = keys.iter().map(ToString::to_string).collect::<Vec<_>>();
+            ids.sort();
+            ids
+        };
+        let changes = |kind, difference: &Difference| {
+            (
+                kind,
+                sorted(&difference.to_add),
+                sorted(&difference.to_change),
+                sorted(&difference.to_remove),
+            )
+        };
+
+        Self {
+            components: vec![
+                changes("source", &diff.sources),
+                changes("transform", &diff.transforms),
+                changes("sink", &diff.sinks),
+                changes("enrichment table", &diff.enrichment_tables),
+            ],
+            buffers: diff
+                .sink_buffer_changes(old, new)
+                .into_iter()
+                .map(|(key, action)| (key.to_string(), action))
+                .collect(),
+            errors: ReloadError::check_global_options(old, new)
+                .err()
+                .map(|error| error.to_string())
+                .into_iter()
+                .collect(),
+        }
+    }
+
+    #[cfg(feature = "api-client")]
+    fn from_proto(response: proto::DiffConfigResponse) -> Result<Self, Vec<String>> {
+        let Some(diff) = response.diff else {
+            return Err(response.errors);
+        };
+        let changes = |kind, difference: Option<proto::ComponentDiff>| {
+            let difference = difference.unwrap_or_default();
+            (
+                kind,
+                difference.added,
+                difference.changed,
+                difference.removed,
+            )
+        };
+
+        Ok(Self {
+            components: vec![
+                changes("source", diff.sources),
+                changes("transform", diff.transforms),
+                changes("sink", diff.sinks),
+                changes("enrichment table", diff.enrichment_tables),
+            ],
+            buffers: response
+                .buffers
+                .into_iter()
+                .filter_map(|change| {
+                    let action = match change.action() {
+                        proto::BufferAction::Reused => BufferAction::Reused,
+                        proto::BufferAction::Rebuilt => BufferAction::Rebuilt,
+                        proto::BufferAction::Dropped => BufferAction::Dropped,
+                        proto::BufferAction::Unspecified => return None,
+                    };
+                    Some((change.component_id, action))
+                })
+                .collect(),
+            errors: response.errors,
+        })
+    }
+
+    /// Prints the changes, returning whether the reload would go through.
+    fn print(&self, target: &str, deny_warnings: bool, fmt: &mut Formatter) -> bool {
+        fmt.title(format!("Changes against {target:?}"));
+
+        let mut unchanged = true;
+        for (kind, added, changed, removed) in &self.components {
+            for (ids, verb) in [(added, "added"), (changed, "changed"), (removed, "removed")] {
+                for id in ids {
+                    unchanged = false;
+                    fmt.print(format!("{kind} \"{id}\" {verb}\n"));
+                }
+            }
+        }
+        if unchanged {
+            fmt.print("No component changes\n");
+        }
+
+        let mut validated = true;
+        for (id, action) in &self.buffers {
+            match action {
+                BufferAction::Reused => fmt.success(format!("Buffer of sink \"{id}\" reused")),
+                BufferAction::Rebuilt => {
+                    fmt.warning(format!("Buffer of sink \"{id}\" rebuilt"));
+                    validated &= !deny_warnings;
+                }
+                BufferAction::Dropped => {
+                    fmt.warning(format!("Buffer of sink \"{id}\" dropped"));
+                    validated &= !deny_warnings;
+                }
+            }
+        }
+
+        for error in &self.errors {
+            fmt.error(error);
+            validated = false;
+        }
+        fmt.space();
+
+        validated
+    }
+}
+
+/// Compares the configuration against a running Vector or another configuration, and prints the
+/// changes reloading into it would make.
+async fn validate_diff(opts: &Opts, target: &str, config: &Config, fmt: &mut Formatter) -> bool {
+    let impact = if target.starts_with("http://") || target.starts_with("https://") {
+        diff_against_api(opts, target).await
+    } else {
+        diff_against_config(opts, target, config)
+    };
+
+    match impact {
+        Ok(impact) => impact.print(target, opts.deny_warnings, fmt),
+        Err(errors) => {
+            fmt.title(format!("Failed to compare against {target:?}"));
+            fmt.sub_error(errors);
+            false
+        }
+    }
+}
+
+fn diff_against_config(
+    opts: &Opts,
+    target: &str,
+    config: &Config,
+) -> Result<ReloadImpact, Vec<String>> {
+    let path = PathBuf::from(target);
+    let path = if path.is_dir() {
+        config::ConfigPath::Dir(path)
+    } else {
+        config::ConfigPath::File(path, None)
+    };
+    let old = config::load_from_paths(&[path], !opts.disable_env_var_interpolation)?;
+
+    // Reloading also rebuilds transforms that depend on external files.
+    let diff = ConfigDiff::new(&old, config, config.transform_keys_with_external_files());
+    Ok(ReloadImpact::from_diff(&diff, &old, config))
+}
+
+#[cfg(feature = "api-client")]
+async fn diff_against_api(opts: &Opts, url: &str) -> Result<ReloadImpact, Vec<String>> {
+    let uri = url
+        .parse()
+        .map_err(|error| vec![format!("Invalid API URL: {error}")])?;
+    let mut client = Client::new(uri);
+    if let Some(token) = &opts.control_token {
+        client = client.with_control_token(token);
+    }
+    client
+        .connect()
+        .await
+        .map_err(|error| vec![format!("Vector API server isn't reachable: {error}")])?;
+
+    // The running Vector resolves secrets itself, so the configuration is sent as loaded.
+    let paths = config::process_paths(&opts.paths_with_formats())
+        .ok_or_else(|| vec!["No config file paths".to_owned()])?;
+    let builder = ConfigBuilderLoader::default()
+        .interpolate_env(!opts.disable_env_var_interpolation)
+        .load_from_paths(&paths)?;
+    let input = config::format::serialize(&builder, config::Format::Json).map_err(|e| vec![e])?;
+
+    let response = client
+        .diff_config(Some(input), ConfigFormat::Json)
+        .await
+        .map_err(|error| vec![error.to_string()])?;
+    ReloadImpact::from_proto(response)
+}
+
+#[cfg(not(feature = "api-client"))]
+async fn diff_against_api(_opts: &Opts, _url: &str) -> Result<ReloadImpact, Vec<String>> {
+    Err(vec![
+        "Comparing against a running Vector requires the `api-client` feature".to_owned(),
+    ])
+}
+
 /// For data directory that we write to:
 /// 1. Create a tmp directory in it.
 /// 2. Change config to point to that tmp directory.
@@ -0,0 +1,454 @@
+//! Integration tests for the control-plane calls of the observability API:
+//! pausing, resuming and draining individual components, reloading the
+//! configuration, fetching the effective configuration, and injecting events.
+
+use std::time::{Duration, Instant};
+
+use tokio_stream::StreamExt;
+use vector_lib::api_client::{
+    Client, Error,
+    proto::{
+        BufferAction, BufferChange, ComponentState, ConfigFormat, InjectEncoding, ReloadStatus,
+        StreamOutputEventsRequest, stream_output_events_response::Event,
+    },
+};
+
+use super::{common::*, harness::*, tap::TAP_TIMEOUT};
+
+/// Returns the gRPC status code of a failed API call.
+fn status_code<T: std::fmt::Debug>(result: Result<T, Error>) -> tonic::Code {
+    match result {
+        Err(Error::Grpc { source }) => source.code(),
+        other => panic!("expected a gRPC error, got {other:?}"),
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+/// Verifies that a sink can be paused and resumed in place without disturbing
+/// the rest of the pipeline.
+#[tokio::test]
+async fn pause_and_resume_sink() {
+    let config = single_source_config("demo", 0.1, None);
+    let mut harness = TestHarness::new(&config)
+        .await
+        .expect("Vector should start");
+
+    let response = harness
+        .api_client()
+        .pause_component("blackhole")
+        .await
+        .expect("pausing the sink should succeed");
+    assert_eq!(response.state, ComponentState::Paused as i32);
+
+    let result = harness.api_client().pause_component("blackhole").await;
+    assert_eq!(status_code(result), tonic::Code::FailedPrecondition);
+
+    let response = harness
+        .api_client()
+        .resume_component("blackhole")
+        .await
+        .expect("resuming the sink should succeed");
+    assert_eq!(response.state, ComponentState::Running as i32);
+
+    wait_for_component_events(harness.api_client(), "demo", 10, EVENT_PROCESSING_TIMEOUT)
+        .await
+        .expect("source should keep sending events after resume");
+
+    assert!(harness.check_running(), "Vector should still be running");
+}
+
+/// Verifies that a source can be drained and resumed while another source keeps
+/// running.
+#[tokio::test]
+async fn drain_and_resume_source() {
+    let config = dual_source_config("demo1", "demo2", 0.1, None);
+    let mut harness = TestHarness::new(&config)
+        .await
+        .expect("Vector should start");
+
+    let response = harness
+        .api_client()
+        .drain_component("demo1")
+        .await
+        .expect("draining the source should succeed");
+    assert_eq!(response.state, ComponentState::Drained as i32);
+
+    // Draining the last running source would shut Vector down.
+    let result = harness.api_client().drain_component("demo2").await;
+    assert_eq!(status_code(result), tonic::Code::FailedPrecondition);
+
+    let response = harness
+        .api_client()
+        .resume_component("demo1")
+        .await
+        .expect("resuming the source should succeed");
+    assert_eq!(response.state, ComponentState::Running as i32);
+
+    assert!(harness.check_running(), "Vector should still be running");
+}
+
+/// Verifies that control operations are rejected for components of the wrong
+/// kind, and for components that don't exist.
+#[tokio::test]
+async fn rejects_invalid_targets() {
+    let config = single_source_config("demo", 1.0, None);
+    let mut harness = TestHarness::new(&config)
+        .await
+        .expect("Vector should start");
+
+    let result = harness.api_client().pause_component("demo").await;
+    assert_eq!(status_code(result), tonic::Code::InvalidArgument);
+
+    let result = harness.api_client().drain_component("blackhole").await;
+    assert_eq!(status_code(result), tonic::Code::InvalidArgument);
+
+    let result = harness.api_client().pause_component("missing").await;
+    assert_eq!(status_code(result), tonic::Code::NotFound);
+
+    let result = harness.api_client().resume_component("blackhole").await;
+    assert_eq!(status_code(result), tonic::Code::FailedPrecondition);
+}
+
+/// Verifies that a reload triggered over the API reports the components it
+/// changed.
+#[tokio::test]
+async fn reload_config_reports_diff() {
+    let config = single_source_config("demo", 0.1, None);
+    let mut harness = TestHarness::new(&config)
+        .await
+        .expect("Vector should start");
+
+    harness.write_config(&dual_source_config("demo", "demo2", 0.1, None));
+    let response = harness
+        .api_client()
+        .reload_config()
+        .await
+        .expect("reload should succeed");
+
+    assert_eq!(response.status, ReloadStatus::Applied as i32);
+    assert!(response.errors.is_empty(), "errors: {:?}", response.errors);
+
+    let diff = response.diff.expect("diff should be reported");
+    let sources = diff.sources.expect("source changes should be reported");
+    assert_eq!(sources.added, vec!["demo2"]);
+    assert!(sources.changed.is_empty());
+    assert!(sources.removed.is_empty());
+    let sinks = diff.sinks.expect("sink changes should be reported");
+    assert_eq!(sinks.changed, vec!["blackhole"]);
+
+    wait_for_component_events(harness.api_client(), "demo2", 10, EVENT_PROCESSING_TIMEOUT)
+        .await
+        .expect("the added source should send events");
+}
+
+/// Verifies that a configuration that can't be loaded is reported without
+/// touching the running topology.
+#[tokio::test]
+async fn reload_config_rejects_invalid_config() {
+    let config = single_source_config("demo", 0.1, None);
+    let mut harness = TestHarness::new(&config)
+        .await
+        .expect("Vector should start");
+
+    harness.write_config(indoc::indoc! {"
+        sinks:
+          blackhole:
+            type: blackhole
+            inputs: ['missing']
+    "});
+    let response = harness
+        .api_client()
+        .reload_config()
+        .await
+        .expect("the reload call itself should succeed");
+
+    assert_eq!(response.status, ReloadStatus::Rejected as i32);
+    assert!(response.diff.is_none());
+    assert!(!response.errors.is_empty());
+    assert!(harness.check_running(), "Vector should still be running");
+}
+
+/// Verifies that a dry-run reload reports the components and buffers it would
+/// change without applying them.
+#[tokio::test]
+async fn diff_config_does_not_apply_changes() {
+    let config = single_source_config("demo", 0.1, None);
+    let mut harness = TestHarness::new(&config)
+        .await
+        .expect("Vector should start");
+
+    let new_config = indoc::indoc! {"
+        sources:
+          demo:
+            type: demo_logs
+            format: json
+            interval: 0.1
+        sinks:
+          blackhole2:
+            type: blackhole
+            inputs: ['demo']
+    "};
+    let response = harness
+        .api_client()
+        .diff_config(Some(new_config.to_string()), ConfigFormat::Yaml)
+        .await
+        .expect("diffing should succeed");
+
+    assert!(response.errors.is_empty(), "errors: {:?}", response.errors);
+    let diff = response.diff.expect("diff should be reported");
+    let sources = diff.sources.expect("source changes should be reported");
+    assert!(sources.added.is_empty() && sources.changed.is_empty() && sources.removed.is_empty());
+    let sinks = diff.sinks.expect("sink changes should be reported");
+    assert_eq!(sinks.added, vec!["blackhole2"]);
+    assert_eq!(sinks.removed, vec!["blackhole"]);
+    assert_eq!(
+        response.buffers,
+        vec![BufferChange {
+            component_id: "blackhole".to_string(),
+            action: BufferAction::Dropped as i32,
+        }]
+    );
+
+    // Without a config, the one on disk is used, and it still matches the running one.
+    let response = harness
+        .api_client()
+        .diff_config(None, ConfigFormat::Unspecified)
+        .await
+        .expect("diffing should succeed");
+    let sinks = response
+        .diff
+        .and_then(|diff| diff.sinks)
+        .expect("sink changes should be reported");
+    assert!(sinks.added.is_empty() && sinks.changed.is_empty() && sinks.removed.is_empty());
+    assert!(response.buffers.is_empty());
+}
+
+/// Verifies that the effective configuration is returned with secrets
+/// redacted.
+#[tokio::test]
+async fn effective_config_redacts_secrets() {
+    let config = single_source_config("demo", 1.0, None);
+    let mut harness = TestHarness::new(&config)
+        .await
+        .expect("Vector should start");
+
+    let config = harness
+        .api_client()
+        .get_effective_config(ConfigFormat::Json)
+        .await
+        .expect("fetching the effective config should succeed");
+    let config: serde_json::Value =
+        serde_json::from_str(&config).expect("config should be valid JSON");
+
+    assert_eq!(config["api"]["control_token"], "**REDACTED**");
+    assert_eq!(config["sources"]["demo"]["type"], "demo_logs");
+    assert_eq!(config["sinks"]["blackhole"]["type"], "blackhole");
+}
+
+/// Verifies that events can be injected into a sink, and that injection is
+/// rejected for sources, missing components and malformed events.
+#[tokio::test]
+async fn inject_events_into_sink() {
+    let config = single_source_config("demo", 1.0, None);
+    let mut harness = TestHarness::new(&config)
+        .await
+        .expect("Vector should start");
+
+    let response = harness
+        .api_client()
+        .inject_events(
+            "blackhole",
+            vec![
+                r#"{"message":"first"}"#.to_string(),
+                r#"{"message":"second"}"#.to_string(),
+            ],
+            InjectEncoding::Json,
+        )
+        .await
+        .expect("injecting into the sink should succeed");
+    assert_eq!(response.injected_events, 2);
+
+    let result = harness
+        .api_client()
+        .inject_events("demo", vec!["{}".to_string()], InjectEncoding::Json)
+        .await;
+    assert_eq!(status_code(result), tonic::Code::InvalidArgument);
+
+    let result = harness
+        .api_client()
+        .inject_events("missing", vec!["{}".to_string()], InjectEncoding::Json)
+        .await;
+    assert_eq!(status_code(result), tonic::Code::NotFound);
+
+    let result = harness
+        .api_client()
+        .inject_events(
+            "blackhole",
+            vec!["not json".to_string()],
+            InjectEncoding::Json,
+        )
+        .await;
+    assert_eq!(status_code(result), tonic::Code::InvalidArgument);
+
+    assert!(harness.check_running(), "Vector should still be running");
+}
+
+/// Verifies that a malformed event is reported along with its index, and that
+/// none of the events of the request are injected.
+#[tokio::test]
+async fn inject_events_reports_index_of_invalid_event() {
+    let config = single_source_config("demo", 1.0, None);
+    let mut harness = TestHarness::new(&config)
+        .await
+        .expect("Vector should start");
+
+    let result = harness
+        .api_client()
+        .inject_events(
+            "blackhole",
+            vec![
+                r#"{"log":{"message":"valid"}}"#.to_string(),
+                "not json".to_string(),
+            ],
+            InjectEncoding::NativeJson,
+        )
+        .await;
+    match result {
+        Err(Error::Grpc { source }) => {
+            assert_eq!(source.code(), tonic::Code::InvalidArgument);
+            assert!(
+                source.message().starts_with("Invalid event at index 1:"),
+                "unexpected message: {}",
+                source.message()
+            );
+        }
+        other => panic!("expected a gRPC error, got {other:?}"),
+    }
+
+    assert!(harness.check_running(), "Vector should still be running");
+}
+
+/// Verifies that events in Vector's native JSON encoding, mixing logs and
+/// metrics, are all injected.
+#[tokio::test]
+async fn inject_native_json_events() {
+    let config = single_source_config("demo", 1.0, None);
+    let mut harness = TestHarness::new(&config)
+        .await
+        .expect("Vector should start");
+
+    let response = harness
+        .api_client()
+        .inject_events(
+            "blackhole",
+            vec![
+                r#"{"log":{"message":"first"}}"#.to_string(),
+                r#"{"metric":{"name":"injected","kind":"incremental","counter":{"value":1.0}}}"#
+                    .to_string(),
+                r#"{"log":{"message":"second"}}"#.to_string(),
+            ],
+            InjectEncoding::NativeJson,
+        )
+        .await
+        .expect("injecting native JSON events should succeed");
+    assert_eq!(response.injected_events, 3);
+}
+
+/// Verifies that injected events are processed by the target transform, and
+/// carry the `vector.injected` metadata tag.
+#[tokio::test]
+async fn injected_events_are_tagged() {
+    let config = indoc::indoc! {"
+        sources:
+          demo:
+            type: demo_logs
+            format: json
+            interval: 3600
+        transforms:
+          tagger:
+            type: remap
+            inputs: ['demo']
+            source: '.tagged = true'
+        sinks:
+          blackhole:
+            type: blackhole
+            inputs: ['tagger']
+    "};
+    let mut harness = TestHarness::new(config).await.expect("Vector should start");
+
+    let mut stream = harness
+        .api_client()
+        .stream_output_events(StreamOutputEventsRequest {
+            outputs_patterns: vec!["tagger".to_string()],
+            limit: 10,
+            interval_ms: 100,
+            filter: Some("%vector.injected == true && .tagged == true".to_string()),
+            unsampled: true,
+            ..Default::default()
+        })
+        .await
+        .expect("Failed to open tap stream");
+    // Give the tap time to subscribe to the output of the transform.
+    tokio::time::sleep(Duration::from_secs(1)).await;
+
+    harness
+        .api_client()
+        .inject_events(
+            "tagger",
+            vec![r#"{"message":"injected"}"#.to_string()],
+            InjectEncoding::Json,
+        )
+        .await
+        .expect("injecting into the transform should succeed");
+
+    let start = Instant::now();
+    loop {
+        let remaining = TAP_TIMEOUT
+            .checked_sub(start.elapsed())
+            .expect("Timeout waiting for the injected event");
+        match tokio::time::timeout(remaining, stream.next()).await {
+            Ok(Some(Ok(response))) => {
+                if let Some(Event::TappedEvent(tapped)) = response.event {
+                    assert_eq!(tapped.component_id, "tagger");
+                    break;
+                }
+            }
+            Ok(Some(Err(error))) => panic!("Stream error: {error}"),
+            Ok(None) => panic!("Stream ended unexpectedly"),
+            Err(_) => panic!("Timeout waiting for the injected event"),
+        }
+    }
+}
+
+/// Verifies that control-plane calls require the configured bearer token.
+#[tokio::test]
+async fn requires_control_token() {
+    let config = single_source_config("demo", 1.0, None);
+    let harness = TestHarness::new(&config)
+        .await
+        .expect("Vector should start");
+
+    let url = format!("http://127.0.0.1:{}", harness.api_port());
+
+    let mut client = Client::new(url.parse().unwrap());
+    client.connect().await.expect("client should connect");
+    let result = client.pause_component("blackhole").await;
+    assert_eq!(status_code(result), tonic::Code::Unauthenticated);
+
+    let result = client
+        .inject_events("blackhole", vec!["{}".to_string()], InjectEncoding::Json)
+        .await;
+    assert_eq!(status_code(result), tonic::Code::Unauthenticated);
+
+    let mut client = Client::new(url.parse().unwrap()).with_control_token("wrong");
+    client.connect().await.expect("client should connect");
+    let result = client.pause_component("blackhole").await;
+    assert_eq!(status_code(result), tonic::Code::Unauthenticated);
+    let result = client
+        .inject_events("blackhole", vec!["{}".to_string()], InjectEncoding::Json)
+        .await;
+    assert_eq!(status_code(result), tonic::Code::Unauthenticated);
+}
@@ -23,6 +23,7 @@ use vector_lib::api_client::Client;
 // Constants
 pub const STARTUP_TIMEOUT: Duration = Duration::from_secs(10);
 pub const EVENT_PROCESSING_TIMEOUT: Duration = Duration::from_secs(30);
+pub const CONTROL_TOKEN: &str = "vector-api-tests";
 
 /// Test harness for Vector instances with API enabled
 ///
@@ -87,6 +88,7 @@ impl TestHarness {
             api:
               enabled: true
               address: \"127.0.0.1:{api_port}\"
+              control_token: \"{CONTROL_TOKEN}\"
 
             {pipeline_config}
         "};
@@ -116,7 +118,8 @@ impl TestHarness {
             .map_err(|e| format!("Failed to spawn vector: {e}"))?;
 
         let url = format!("http://127.0.0.1:{api_port}");
-        let mut api_client = Client::new(url.parse().expect("valid URL"));
+        let mut api_client =
+            Client::new(url.parse().expect("valid URL")).with_control_token(CONTROL_TOKEN);
 
         // Wait for Vector startup with crash detection
         // This will repeatedly try to connect until Vector is ready
@@ -154,15 +157,7 @@ impl TestHarness {
         new_pipeline_config: &str,
         expected_component_ids: &[&str],
     ) -> Result<(), String> {
-        let new_config = formatdoc! {"
-            api:
-              enabled: true
-              address: \"127.0.0.1:{port}\"
-
-            {new_pipeline_config}
-        ", port = self.api_port};
-
-        overwrite_config_file(&self.config_path, &new_config);
+        self.write_config(new_pipeline_config);
 
         // Send SIGHUP only if not in watch mode (watch mode auto-reloads on file change)
         if !self.watch_mode {
@@ -182,6 +177,23 @@ impl TestHarness {
         .await
     }
 
+    /// Overwrites the config file with a new pipeline configuration, without reloading Vector
+    ///
+    /// # Arguments
+    /// * `new_pipeline_config` - The new pipeline configuration (without the API section)
+    pub fn write_config(&self, new_pipeline_config: &str) {
+        let new_config = formatdoc! {"
+            api:
+              enabled: true
+              address: \"127.0.0.1:{port}\"
+              control_token: \"{CONTROL_TOKEN}\"
+
+            {new_pipeline_config}
+        ", port = self.api_port};
+
+        overwrite_config_file(&self.config_path, &new_config);
+    }
+
     /// Checks if Vector is still running
     pub fn check_running(&mut self) -> bool {
         self.vector.try_wait().unwrap().is_none()
@@ -1,9 +1,11 @@
 //! Integration tests for Vector's gRPC API
 //!
 //! This test suite verifies the gRPC API that powers both `vector top` and `vector tap` commands.
-//! Tests cover component discovery, metrics collection, event streaming, and config reloading.
+//! Tests cover component discovery, metrics collection, event streaming, config reloading, and
+//! component control.
 
 mod common;
+mod control;
 mod harness;
 mod health;
 mod tap;
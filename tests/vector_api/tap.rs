@@ -10,6 +10,37 @@ use vector_lib::api_client::proto::{StreamOutputEventsRequest, StreamOutputEvent
 
 pub const TAP_TIMEOUT: Duration = Duration::from_secs(10);
 
+/// Counts the tapped events received on a tap stream over the given duration.
+async fn count_tapped_events_for(
+    harness: &mut TestHarness,
+    request: StreamOutputEventsRequest,
+    duration: Duration,
+) -> usize {
+    use vector_lib::api_client::proto::stream_output_events_response::Event;
+
+    let mut stream = harness
+        .api_client()
+        .stream_output_events(request)
+        .await
+        .expect("Failed to open tap stream");
+
+    let start = Instant::now();
+    let mut count = 0;
+    while let Some(remaining) = duration.checked_sub(start.elapsed()) {
+        match tokio::time::timeout(remaining, stream.next()).await {
+            Ok(Some(Ok(event))) => {
+                if matches!(&event.event, Some(Event::TappedEvent(_))) {
+                    count += 1;
+                }
+            }
+            Ok(Some(Err(e))) => panic!("Stream error: {e}"),
+            Ok(None) => panic!("Stream ended unexpectedly"),
+            Err(_) => break,
+        }
+    }
+    count
+}
+
 impl TestHarness {
     /// Collects tap events from the given patterns
     ///
@@ -27,6 +58,7 @@ impl TestHarness {
             inputs_patterns: vec![],
             limit: DEFAULT_LIMIT,
             interval_ms: DEFAULT_INTERVAL_MS,
+            ..Default::default()
         };
 
         let mut stream = self
@@ -164,6 +196,7 @@ async fn tap_survives_config_reload() {
         inputs_patterns: vec![],
         limit: 1000,
         interval_ms: 100,
+        ..Default::default()
     };
     let mut stream = harness
         .api_client()
@@ -374,3 +407,128 @@ async fn multiple_concurrent_subscriptions() {
         );
     }
 }
+
+#[tokio::test]
+async fn tap_filters_on_server() {
+    let config = single_source_config("demo", 0.01, None);
+    let mut harness = TestHarness::new(&config)
+        .await
+        .expect("Failed to start Vector");
+
+    let request = |filter: &str| StreamOutputEventsRequest {
+        outputs_patterns: vec!["demo".to_string()],
+        limit: 1000,
+        interval_ms: 100,
+        filter: Some(filter.to_string()),
+        ..Default::default()
+    };
+
+    let matched = count_tapped_events_for(
+        &mut harness,
+        request("exists(.message)"),
+        Duration::from_secs(1),
+    )
+    .await;
+    assert!(matched > 0, "Events matching the filter should be tapped");
+
+    let matched =
+        count_tapped_events_for(&mut harness, request("false"), Duration::from_secs(1)).await;
+    assert_eq!(
+        matched, 0,
+        "Events not matching the filter should be dropped"
+    );
+
+    let result = harness
+        .api_client()
+        .stream_output_events(request(".message =="))
+        .await;
+    assert!(result.is_err(), "Invalid filters should be rejected");
+
+    let result = harness
+        .api_client()
+        .stream_output_events(StreamOutputEventsRequest {
+            sample_ratio: Some(1.5),
+            ..request("true")
+        })
+        .await;
+    assert!(result.is_err(), "Sample ratios above 1 should be rejected");
+}
+
+#[tokio::test]
+async fn tap_caps_events_per_second() {
+    let config = single_source_config("demo", 0.01, None);
+    let mut harness = TestHarness::new(&config)
+        .await
+        .expect("Failed to start Vector");
+
+    let request = StreamOutputEventsRequest {
+        outputs_patterns: vec!["demo".to_string()],
+        limit: 1000,
+        interval_ms: 100,
+        max_events_per_second: Some(5),
+        ..Default::default()
+    };
+    let count = count_tapped_events_for(&mut harness, request, Duration::from_millis(2500)).await;
+
+    // The 2.5 seconds span at most three one-second windows.
+    assert!(count > 0, "Should receive tapped events");
+    assert!(count <= 15, "Should receive at most 15 events, got {count}");
+}
+
+#[tokio::test]
+async fn tap_unsampled_sends_every_event() {
+    let config = single_source_config("demo", 0.01, None);
+    let mut harness = TestHarness::new(&config)
+        .await
+        .expect("Failed to start Vector");
+
+    // A sampled tap would send at most one event per second.
+    let request = StreamOutputEventsRequest {
+        outputs_patterns: vec!["demo".to_string()],
+        limit: 1,
+        interval_ms: 1000,
+        unsampled: true,
+        ..Default::default()
+    };
+    let count = count_tapped_events_for(&mut harness, request, Duration::from_millis(2500)).await;
+
+    assert!(count > 10, "Should receive every event, got {count}");
+}
+
+#[tokio::test]
+async fn tap_unsampled_reports_dropped_events() {
+    use vector_lib::api_client::proto::stream_output_events_response::Event;
+
+    let config = single_source_config("demo", 0.01, None);
+    let mut harness = TestHarness::new(&config)
+        .await
+        .expect("Failed to start Vector");
+
+    let mut stream = harness
+        .api_client()
+        .stream_output_events(StreamOutputEventsRequest {
+            outputs_patterns: vec!["demo".to_string()],
+            limit: 1000,
+            interval_ms: 100,
+            max_events_per_second: Some(5),
+            unsampled: true,
+            ..Default::default()
+        })
+        .await
+        .expect("Failed to open tap stream");
+
+    let notification = tokio::time::timeout(TAP_TIMEOUT, async {
+        while let Some(Ok(response)) = stream.next().await {
+            if let Some(Event::Notification(notification)) = response.event
+                && notification.message.contains("Dropped")
+            {
+                return notification.message;
+            }
+        }
+        panic!("Stream ended unexpectedly");
+    })
+    .await
+    .expect("Dropped events should be reported");
+
+    assert!(notification.starts_with("[tap] Dropped "));
+}
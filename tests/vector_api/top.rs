@@ -479,3 +479,103 @@ async fn gcp_pubsub_spawned_task_errors_carry_component_span() {
          so its metrics counters had no component_id tag and were invisible to the API."
     );
 }
+
+/// The resources view of `vector top` relies on gauge streams for utilization and buffer usage,
+/// reported as ratios between 0 and 1.
+#[tokio::test]
+async fn streams_component_utilization_gauges() {
+    let mut runner = TestHarness::new(&single_source_config("demo", 0.01, None))
+        .await
+        .expect("Failed to start Vector");
+
+    runner
+        .wait_for_events("demo", 10)
+        .await
+        .expect("Source never sent expected events");
+
+    for metric in [MetricName::Utilization, MetricName::BufferUtilization] {
+        let mut stream = runner
+            .api_client()
+            .stream_component_metrics(metric, 200)
+            .await
+            .unwrap_or_else(|e| panic!("Failed to open {metric:?} stream: {e}"));
+
+        let deadline = tokio::time::Instant::now() + EVENT_PROCESSING_TIMEOUT;
+        let mut found = false;
+
+        while tokio::time::Instant::now() < deadline {
+            match tokio::time::timeout(std::time::Duration::from_millis(500), stream.next()).await {
+                Ok(Some(Ok(msg))) => {
+                    if msg.component_id == "blackhole"
+                        && let Some(Value::Gauge(gauge)) = msg.value
+                    {
+                        assert!(
+                            (0.0..=1.0).contains(&gauge.value),
+                            "Expected {metric:?} for 'blackhole' between 0 and 1, got {}",
+                            gauge.value
+                        );
+                        found = true;
+                        break;
+                    }
+                }
+                Ok(Some(Err(e))) => panic!("Stream error: {e}"),
+                Ok(None) => panic!("Stream ended unexpectedly"),
+                Err(_) => continue, // poll timeout, keep looping
+            }
+        }
+
+        assert!(
+            found,
+            "Never received {metric:?} for 'blackhole' within timeout"
+        );
+    }
+}
+
+/// The graph view of `vector top` is built from the inputs reported for each component.
+#[tokio::test]
+async fn reports_component_inputs() {
+    let mut runner = TestHarness::new(indoc! {"
+        sources:
+          demo:
+            type: demo_logs
+            format: json
+            interval: 0.1
+
+        transforms:
+          splitter:
+            type: route
+            inputs: ['demo']
+            route:
+              all: 'true'
+
+        sinks:
+          sink:
+            type: blackhole
+            inputs: ['demo', 'splitter.all']
+    "})
+    .await
+    .expect("Failed to start Vector");
+
+    let response = runner
+        .query_components()
+        .await
+        .expect("Failed to query components");
+    let inputs = |component_id: &str| {
+        let component = response
+            .components
+            .iter()
+            .find(|c| c.component_id == component_id)
+            .unwrap_or_else(|| panic!("{component_id} not found"));
+        let mut inputs = component
+            .inputs
+            .iter()
+            .map(|i| (i.component_id.as_str(), i.output_id.as_str()))
+            .collect::<Vec<_>>();
+        inputs.sort_unstable();
+        inputs
+    };
+
+    assert!(inputs("demo").is_empty());
+    assert_eq!(inputs("splitter"), [("demo", "_default")]);
+    assert_eq!(inputs("sink"), [("demo", "_default"), ("splitter", "all")]);
+}
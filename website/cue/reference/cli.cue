@@ -71,7 +71,7 @@ cli: {
 		}
 	}
 
-	#OptionType: "string" | "integer" | "enum" | "list"
+	#OptionType: "string" | "integer" | "float" | "enum" | "list"
 
 	name:     !=""
 	flags:    #Flags
@@ -204,6 +204,74 @@ cli: {
 	options: _core_options
 
 	commands: {
+		"buffer": {
+			description: """
+				Inspect, verify, and recover the disk buffer of a sink, then exit. Vector must not be
+				running the sink while its buffer is managed.
+
+				The `info` subcommand shows the state of the buffer and its data files, `dump` prints the
+				buffered events as JSON, and `verify` checks the checksums of all records. The `truncate`
+				subcommand cuts off corrupted tails of data files so that the records before them can
+				still be read, and `rehome --to` moves the buffer to another sink, such as when the sink
+				it belonged to was removed or renamed. The priority lane of the buffer, if any, is moved
+				along with it.
+				"""
+
+			example: "vector buffer --config /etc/vector/vector.yaml --component my_sink dump"
+
+			options: _core_config_options & {
+				"component": {
+					description: "The ID of the sink component whose disk buffer to manage. The priority lane of a sink's buffer is managed with the ID `<sink_id>.priority`"
+					type:        "string"
+					required:    true
+				}
+				"data-dir": {
+					description: "The data directory that the disk buffer is in. Defaults to the `data_dir` of the configuration"
+					type:        "string"
+				}
+			}
+
+			args: {
+				subcommand: {
+					description: "The operation to perform: `info`, `dump`, `verify`, `truncate`, or `rehome`"
+					type:        "string"
+				}
+			}
+		}
+
+		"checkpoints": {
+			description: """
+				List, export, import, reset, and edit the checkpoints of a `file` source, then exit.
+				Vector must not be running the source while its checkpoints are changed, as it would
+				overwrite them: the `import`, `reset`, and `edit` subcommands refuse to run while a
+				Vector process running the source holds the lock on its checkpoints.
+
+				The `list` and `export` subcommands print the checkpoints, and `import` loads checkpoints
+				that were exported, such as on another node. The `reset` and `edit` subcommands change
+				the checkpoint of a single file, selected with `--file` or `--fingerprint`, and `reset
+				--all` removes all checkpoints. When `edit` selects the file with `--file`, the data just
+				before the new position is checksummed, so the source can still detect if the file is
+				later truncated or replaced.
+				"""
+
+			example: "vector checkpoints --config /etc/vector/vector.yaml --component my_file_source reset --file /var/log/app.log"
+
+			options: _core_config_options & {
+				"component": {
+					description: "The ID of the `file` source component whose checkpoints to manage"
+					type:        "string"
+					required:    true
+				}
+			}
+
+			args: {
+				subcommand: {
+					description: "The operation to perform: `list`, `export`, `import`, `reset`, or `edit`"
+					type:        "string"
+				}
+			}
+		}
+
 		"graph": {
 			description: """
 				Generate a visual representation of topologies. The output is in the [DOT format](\(urls.dot_format)),
@@ -362,6 +430,45 @@ cli: {
 					description: "Components (sources, transforms) to observe for their inputs (comma-separated; accepts glob patterns)"
 					type:        "list"
 				}
+				"filter": {
+					description: "VRL boolean expression that events must satisfy to be shown, evaluated by the Vector instance being tapped"
+					type:        "string"
+				}
+				"sample-ratio": {
+					description: "Probability, within (0, 1], that each event matching the filter is shown. Sampling is done by the Vector instance being tapped"
+					type:        "float"
+				}
+				"max-events-per-second": {
+					description: "Maximum number of events shown per second, enforced by the Vector instance being tapped"
+					type:        "integer"
+				}
+				"capture": {
+					description: "Write tapped events, with the IDs of the components they were tapped from and when, to the given file instead of printing them. Every tapped event is captured rather than a sample of `--limit` events per interval, and events dropped because they couldn't be sent in time are reported. The file can be played back with the `replay` source"
+					type:        "string"
+				}
+				"inject": {
+					description: """
+						Instead of observing events, inject events read from stdin (one per line)
+						into the input of the given transform or sink. Injected events carry
+						`vector.injected: true` in their metadata, so they can be told apart with
+						`%vector.injected` in VRL. Requires the API's `control_token`.
+						"""
+					type: "string"
+				}
+				"inject-format": {
+					description: "Encoding of the events to inject"
+					type:        "enum"
+					default:     "json"
+					enum: {
+						json:        "Each line is a JSON object, decoded into a log event"
+						"native-json": "Each line is an event in Vector's native JSON format, and can be a log, metric, or trace"
+					}
+				}
+				"control-token": {
+					description: "Control token of the Vector gRPC API server, required to inject events"
+					type:        "string"
+					env_var:     "VECTOR_API_CONTROL_TOKEN"
+				}
 			}
 
 			args: {
@@ -465,6 +572,24 @@ cli: {
 						"""
 					type: "string"
 				}
+				"diff-against": {
+					description: """
+						Print the components and sink buffers that reloading into the
+						validated config would change. Either the URL of a running Vector's
+						API, such as `http://127.0.0.1:8686`, or a config file or directory to
+						compare against. Dropped or rebuilt buffers are reported as warnings,
+						so `--deny-warnings` fails validation on them.
+						"""
+					type: "string"
+				}
+				"control-token": {
+					description: """
+						The control token of the running Vector's API, used with
+						`--diff-against <URL>`.
+						"""
+					type:    "string"
+					env_var: "VECTOR_API_CONTROL_TOKEN"
+				}
 			}
 
 			args: {
@@ -12,6 +12,76 @@ generated: components: sinks: configuration: {
 			"""
 		required: false
 		type: object: options: {
+			compression: {
+				description: """
+					Compresses records before they are written to disk.
+
+					Each record is compressed individually, and `max_size` applies to the compressed size of
+					records. Records that don't shrink when compressed are stored as-is. Records already in the
+					buffer remain readable when the compression algorithm is changed.
+					"""
+				relevant_when: "type = \"disk\""
+				required:      false
+				type: string: {
+					default: "none"
+					enum: {
+						lz4: """
+														Records are compressed with [LZ4][lz4].
+
+														This is faster than `zstd`, but usually achieves a lower compression ratio.
+
+														[lz4]: https://lz4.org/
+														"""
+						none: "Records are not compressed."
+						zstd: """
+														Records are compressed with [Zstandard][zstd].
+
+														[zstd]: https://facebook.github.io/zstd/
+														"""
+					}
+				}
+			}
+			encryption: {
+				description: """
+					Encrypts records before they are written to disk.
+
+					Records written while encryption was disabled remain readable after enabling it.
+					"""
+				relevant_when: "type = \"disk\""
+				required:      false
+				type: object: options: {
+					key: {
+						description: "The key used to encrypt new records."
+						required:    true
+						type: string: examples: ["SECRET[backend.buffer_key]"]
+					}
+					previous_keys: {
+						description: """
+							Keys that were previously used to encrypt records.
+
+							When rotating keys, move the old key here so that records written before the rotation can
+							still be read. Once all of those records have been read, it can be removed.
+							"""
+						required: false
+						type: array: {
+							default: []
+							items: type: string: examples: ["SECRET[backend.previous_buffer_key]"]
+						}
+					}
+				}
+			}
+			max_age_secs: {
+				description: """
+					The maximum age of the data in the buffer.
+
+					Data files whose records are all older than this are dropped, even if they have not been
+					read yet. Like with `when_full = "drop_oldest"`, the data files currently being read from
+					and written to are never dropped.
+					"""
+				relevant_when: "type = \"disk\""
+				required:      false
+				type: uint: unit: "seconds"
+			}
 			max_events: {
 				description:   "The maximum number of events allowed in the buffer."
 				relevant_when: "type = \"memory\""
@@ -27,6 +97,40 @@ generated: components: sinks: configuration: {
 				required: true
 				type: uint: unit: "bytes"
 			}
+			pool: {
+				description: """
+					The share of the disk buffer pool this buffer is entitled to.
+
+					When the global `buffer_pool` option is configured, `max_size` caps how much of the pool
+					this buffer can use.
+					"""
+				relevant_when: "type = \"disk\""
+				required:      false
+				type: object: options: {
+					min_size: {
+						description: """
+							The amount of space in the pool reserved for this buffer.
+
+							The buffer can always grow up to this size, even when other buffers have used up the rest of
+							the pool. The sum of `min_size` across all disk buffers must not exceed the size of the pool.
+							"""
+						required: false
+						type: uint: {
+							default: 0
+							unit:    "bytes"
+						}
+					}
+					priority: {
+						description: """
+							The priority of this buffer when the pool is full.
+
+							Space freed up in the pool goes to the waiting buffers with the highest priority first.
+							"""
+						required: false
+						type: uint: default: 0
+					}
+				}
+			}
 			type: {
 				description: "The type of buffer to use."
 				required:    false
@@ -70,11 +174,36 @@ generated: components: sinks: configuration: {
 														highest priority, and it is preferable to temporarily lose events rather than cause a
 														slowdown in the acceptance/consumption of events.
 														"""
+						drop_oldest: """
+														Drops the oldest events in the buffer to make room for new events.
+
+														Events are dropped a whole data file at a time, and the data files currently being read from
+														and written to are never dropped, so writes still wait for free space when nothing else can
+														be dropped. This mode is typically used when recent events are more valuable than old ones,
+														such as during long outages of the sink's destination.
+
+														This mode can only be used with disk buffers.
+														"""
 					}
 				}
 			}
 		}
 	}
+	buffer_priority: {
+		description: """
+			A condition that determines which events are high priority in the sink's buffer.
+
+			When set, the buffer gets a priority lane, which is a second buffer with the same
+			configuration that the matching events are sent to instead. When the sink falls behind,
+			the events waiting in the priority lane are sent to the sink before any of the other
+			buffered events. Events are sent in the order they were received within each lane.
+
+			The priority lane has its own `max_size`, and with disk buffers it reserves its own
+			`pool.min_size` from the buffer pool, so the buffer can take up twice the configured size.
+			"""
+		required: false
+		type: condition: {}
+	}
 	graph: {
 		description: """
 			Extra graph configuration
@@ -182,6 +311,50 @@ generated: components: sinks: configuration: {
 		required: true
 		type: array: items: type: string: examples: ["my-source-or-transform-id", "prefix-*"]
 	}
+	isolation: {
+		description: """
+			How a fanout treats a downstream component that isn't keeping up with the events sent to it.
+
+			By default, the fanout waits for every downstream component to accept the events before sending
+			the next ones, so a single slow component slows down all of the other components fed by the same
+			upstream component. Isolating a component stops it from holding up the others, at the cost of
+			dropping some of the events sent to it.
+			"""
+		required: false
+		type: object: options: {
+			mode: {
+				description: "The isolation mode."
+				required:    false
+				type: string: {
+					default: "block"
+					enum: {
+						block: "Wait for the component to accept the events."
+						detach: """
+							Wait for the component to accept the events for up to `timeout_secs`, then detach it.
+
+							While detached, events for the component are dropped, until it accepts the events it was
+							waiting on.
+							"""
+						drop: """
+							Drop the events for this component only, if it can't accept them right away.
+
+							Events that the component started accepting are still delivered to it in the background,
+							unless the component is removed first.
+							"""
+					}
+				}
+			}
+			timeout_secs: {
+				description:   "How long to wait for the component before detaching it, in seconds."
+				relevant_when: "mode = \"detach\""
+				required:      false
+				type: uint: {
+					default: 5
+					unit:    "seconds"
+				}
+			}
+		}
+	}
 	proxy: {
 		description: """
 			Proxy configuration.
@@ -241,4 +414,20 @@ generated: components: sinks: configuration: {
 			}
 		}
 	}
+	reroute_failed: {
+		description: """
+			Reroutes events that the sink fails to deliver to `dead_letter` sources.
+
+			By default, events that are rejected by the downstream service, or that fail to be sent
+			after all retries are exhausted, are dropped and reported back to the source as failed. In
+			some cases, it may be desirable to keep the events around for further analysis, debugging,
+			or retrying.
+
+			In these cases, `reroute_failed` can be set to `true`, which forwards the original events to
+			any `dead_letter` source that subscribes to this sink. The events are annotated with
+			additional fields describing why their delivery failed.
+			"""
+		required: false
+		type: bool: default: false
+	}
 }
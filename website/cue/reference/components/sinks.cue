@@ -662,6 +662,8 @@ components: sinks: [Name=string]: {
 
 	telemetry: metrics: {
 		buffer_byte_size:                     components.sources.internal_metrics.output.metrics.buffer_byte_size
+		buffer_compressed_bytes_total:        components.sources.internal_metrics.output.metrics.buffer_compressed_bytes_total
+		buffer_compression_ratio:             components.sources.internal_metrics.output.metrics.buffer_compression_ratio
 		buffer_discarded_events_total:        components.sources.internal_metrics.output.metrics.buffer_discarded_events_total
 		buffer_size_bytes:                    components.sources.internal_metrics.output.metrics.buffer_size_bytes
 		buffer_size_events:                   components.sources.internal_metrics.output.metrics.buffer_size_events
@@ -670,6 +672,7 @@ components: sinks: [Name=string]: {
 		buffer_received_bytes_total:          components.sources.internal_metrics.output.metrics.buffer_received_bytes_total
 		buffer_sent_events_total:             components.sources.internal_metrics.output.metrics.buffer_sent_events_total
 		buffer_sent_bytes_total:              components.sources.internal_metrics.output.metrics.buffer_sent_bytes_total
+		buffer_uncompressed_bytes_total:      components.sources.internal_metrics.output.metrics.buffer_uncompressed_bytes_total
 		component_discarded_events_total:     components.sources.internal_metrics.output.metrics.component_discarded_events_total
 		component_errors_total:               components.sources.internal_metrics.output.metrics.component_errors_total
 		component_received_events_count:      components.sources.internal_metrics.output.metrics.component_received_events_count
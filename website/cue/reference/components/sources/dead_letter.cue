@@ -0,0 +1,102 @@
+package metadata
+
+components: sources: dead_letter: {
+	title:       "Dead Letter"
+	description: "The dead letter source receives the events that sinks with `reroute_failed` enabled failed to deliver."
+
+	classes: {
+		delivery: "at_least_once"
+		deployment_roles: ["aggregator", "daemon", "sidecar"]
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		acknowledgements: false
+		collect: {
+			checkpoint: enabled: false
+			from: service: {
+				name:     "Vector instance"
+				thing:    "a \(name)"
+				url:      urls.vector_docs
+				versions: ">= 0.57.0"
+			}
+		}
+		multiline: enabled: false
+	}
+
+	support: {
+		notices: []
+		requirements: []
+		warnings: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: generated.components.sources.dead_letter.configuration
+
+	output: {
+		logs: event: {
+			description: "A log event that a sink failed to deliver."
+			fields: {
+				"*": {
+					description: "The fields of the event are forwarded as they were sent to the sink."
+					required:    true
+					type: "*": {}
+				}
+				dead_letter: {
+					description: """
+						Why the delivery of the event failed. In the `Legacy` log namespace, this is stored
+						under the `log_schema.metadata_key` field. In the `Vector` log namespace, it's stored
+						in the `vector.dead_letter` metadata.
+						"""
+					required: true
+					type: object: {
+						examples: [{
+							reason:         "rejected"
+							message:        "HTTP request failed with status 400 Bad Request."
+							http_status:    400
+							component_id:   "my_http_sink"
+							component_type: "http"
+							component_kind: "sink"
+						}]
+						options: {}
+					}
+				}
+			}
+		}
+		metrics: {
+			counter:      output._passthrough_counter
+			distribution: output._passthrough_distribution
+			gauge:        output._passthrough_gauge
+			histogram:    output._passthrough_histogram
+			set:          output._passthrough_set
+		}
+		traces: "": {
+			description: "Trace events that a sink failed to deliver."
+		}
+	}
+
+	how_it_works: {
+		acknowledgements: {
+			title: "Acknowledgements"
+			body: """
+				Events rerouted to this source keep the acknowledgements of their original source. The
+				original source is notified of the outcome once the rerouted events are delivered,
+				or fail to be delivered, by the components downstream of this source. If no
+				`dead_letter` source subscribes to a sink, its failed events are reported back to their
+				source as failed, as if `reroute_failed` wasn't enabled.
+				"""
+		}
+		metrics: {
+			title: "Metrics"
+			body: """
+				Metric events are annotated with `dead_letter.*` tags under the
+				`log_schema.metadata_key` prefix instead of an object field.
+				"""
+		}
+	}
+}
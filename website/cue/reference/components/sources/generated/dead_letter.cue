@@ -0,0 +1,16 @@
+package metadata
+
+generated: components: sources: dead_letter: configuration: sinks: {
+	description: """
+		The IDs of the sinks to receive failed events from.
+
+		Only sinks with `reroute_failed` enabled send their failed events to this source. If empty,
+		the failed events of all such sinks are received. The events received from a sink can't be
+		sent back to that sink, directly or through transforms.
+		"""
+	required: false
+	type: array: {
+		default: []
+		items: type: string: examples: ["my_http_sink"]
+	}
+}
@@ -36,6 +36,23 @@ generated: components: sources: file: configuration: {
 		required: false
 		type: string: examples: ["/var/local/lib/vector/"]
 	}
+	discovery: {
+		description: "Method used to discover files, and to detect when files have new data to read."
+		required:    false
+		type: string: {
+			default: "poll"
+			enum: {
+				inotify: """
+					Use inotify to be notified of new files, and of watched files with new data.
+
+					Files are only searched for again when a watched directory changes, and files are only read
+					when they have been written to. This is only supported on Linux. Polling is used instead on
+					other platforms, or when the inotify watch limit (`fs.inotify.max_user_watches`) is reached.
+					"""
+				poll: "Periodically search for files, and poll watched files for new data."
+			}
+		}
+	}
 	encoding: {
 		description: "Character set encoding."
 		required:    false
@@ -98,12 +115,12 @@ generated: components: sources: file: configuration: {
 			ignored_header_bytes: {
 				description: """
 					The number of bytes to skip ahead (or ignore) when reading the data used for generating the checksum.
-					If the file is compressed, the number of bytes refer to the header in the uncompressed content. Only
-					gzip is supported at this time.
+					If the file is compressed, the number of bytes refer to the header in the uncompressed content. The
+					gzip, bzip2, zstd, and xz formats are supported.
 
 					This can be helpful if all files share a common header that should be skipped.
 					"""
-				relevant_when: "strategy = \"checksum\""
+				relevant_when: "strategy = \"checksum\" or strategy = \"content_hash\""
 				required:      false
 				type: uint: {
 					default: 0
@@ -114,12 +131,12 @@ generated: components: sources: file: configuration: {
 				description: """
 					The number of lines to read for generating the checksum.
 
-					The number of lines are determined from the uncompressed content if the file is compressed. Only
-					gzip is supported at this time.
+					The number of lines are determined from the uncompressed content if the file is compressed. The
+					gzip, bzip2, zstd, and xz formats are supported.
 
 					If the file has less than this amount of lines, it won’t be read at all.
 					"""
-				relevant_when: "strategy = \"checksum\""
+				relevant_when: "strategy = \"checksum\" or strategy = \"content_hash\""
 				required:      false
 				type: uint: {
 					default: 1
@@ -137,6 +154,17 @@ generated: components: sources: file: configuration: {
 					default: "checksum"
 					enum: {
 						checksum: "Read lines from the beginning of the file and compute a checksum over them."
+						content_hash: """
+															Combine a checksum of the first lines with the [device and inode][inode], and verify the
+															content of files against a checksum of the data last read from them.
+
+															This identifies files with identical headers apart, and detects when a file is truncated or
+															replaced, in which case it's read from the beginning again. When a file is a copy of another
+															one, such as with `copytruncate` rotation, reading it resumes where the original left off.
+															Checkpoints made with the other strategies are migrated automatically.
+
+															[inode]: https://en.wikipedia.org/wiki/Inode
+															"""
 						device_and_inode: """
 															Use the [device and inode][inode] as the identifier.
 
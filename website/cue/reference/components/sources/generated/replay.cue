@@ -0,0 +1,22 @@
+package metadata
+
+generated: components: sources: replay: configuration: {
+	path: {
+		description: "The path of the capture file to play back."
+		required:    true
+		type: string: examples: ["/var/lib/vector/capture.bin"]
+	}
+	speed: {
+		description: """
+			How fast to play back events, relative to the timing they were captured with.
+
+			For example, `2.0` plays events back twice as fast as they were captured. To play events
+			back as quickly as possible, set `speed` to `0.0`.
+			"""
+		required: false
+		type: float: {
+			default: 1.0
+			examples: [1.0, 10.0, 0.0]
+		}
+	}
+}
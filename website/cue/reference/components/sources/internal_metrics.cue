@@ -344,6 +344,27 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _buffer_tags
 		}
+		buffer_compressed_bytes_total: {
+			description:       "The number of bytes written to this disk buffer after compression."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _buffer_tags
+		}
+		buffer_compression_ratio: {
+			description: """
+				The ratio of the size of records before and after compression, for the records written
+				to this disk buffer since the metric was last reported.
+				"""
+			type:              "gauge"
+			default_namespace: "vector"
+			tags:              _buffer_tags
+		}
+		buffer_uncompressed_bytes_total: {
+			description:       "The number of bytes written to this disk buffer before compression."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _buffer_tags
+		}
 		buffer_discarded_events_total: {
 			description:       "The number of events dropped by this non-blocking buffer."
 			type:              "counter"
@@ -408,6 +429,18 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _buffer_tags
 		}
+		buffered_spans: {
+			description:       "The number of spans held in memory by the `tail_sample` transform while waiting for their traces to complete."
+			type:              "gauge"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		buffered_traces: {
+			description:       "The number of traces held in memory by the `tail_sample` transform while waiting for them to complete."
+			type:              "gauge"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
 		component_cpu_usage_ns_total: {
 			description:       "The CPU time consumed by a component in nanoseconds. Available for transforms only. Emitted only on Linux, macOS, and Windows."
 			type:              "counter"
@@ -649,6 +682,30 @@ components: sources: internal_metrics: {
 				}
 			}
 		}
+		fanout_detached_total: {
+			description:       "The number of times a downstream component was detached from this component for not keeping up with it. Only emitted for components with `isolation` enabled."
+			type:              "counter"
+			default_namespace: "vector"
+			tags: _component_tags & {
+				consumer_id: _consumer_id
+			}
+		}
+		fanout_discarded_events_total: {
+			description:       "The number of events dropped for a downstream component that wasn't keeping up with this component. Only emitted for components with `isolation` enabled."
+			type:              "counter"
+			default_namespace: "vector"
+			tags: _component_tags & {
+				consumer_id: _consumer_id
+			}
+		}
+		fanout_send_duration_seconds: {
+			description:       "The duration spent sending events to a sink isolated with the `drop` or `detach` mode. Slow sinks show up with long send durations."
+			type:              "histogram"
+			default_namespace: "vector"
+			tags: _component_tags & {
+				consumer_id: _consumer_id
+			}
+		}
 		files_added_total: {
 			description:       "The total number of files Vector has found to watch."
 			type:              "counter"
@@ -681,6 +738,18 @@ components: sources: internal_metrics: {
 				file: _file
 			}
 		}
+		inotify_fallbacks_total: {
+			description:       "The total number of times Vector has fallen back to polling for file changes because inotify could not be used."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		inotify_watches: {
+			description:       "The number of directories watched with inotify."
+			type:              "gauge"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
 		open_files: {
 			description:       "The total number of open files."
 			type:              "gauge"
@@ -1011,6 +1080,38 @@ components: sources: internal_metrics: {
 				}
 			}
 		}
+		traces_evicted_total: {
+			description:       "The number of traces sampled before they completed to keep the `tail_sample` transform within its memory limits."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		traces_sampled_total: {
+			description:       "The number of traces sampled by the `tail_sample` transform."
+			type:              "counter"
+			default_namespace: "vector"
+			tags: _component_tags & {
+				decision: {
+					description: "Whether the trace was kept or dropped."
+					required:    true
+					enum: {
+						"kept":    "The trace was kept."
+						"dropped": "The trace was dropped."
+					}
+				}
+				policy: {
+					description: "The policy the trace was kept by, or `none` if it was dropped."
+					required:    true
+					enum: {
+						"error":     "The trace has a span with an error."
+						"latency":   "The trace lasted longer than the latency threshold."
+						"condition": "The trace has a span matching the condition."
+						"ratio":     "The trace was picked by the ratio."
+						"none":      "The trace was dropped."
+					}
+				}
+			}
+		}
 		transform_buffer_max_byte_size: {
 			description:        "The maximum number of bytes the buffer that feeds into a transform can hold."
 			type:               "gauge"
@@ -1202,6 +1303,10 @@ components: sources: internal_metrics: {
 			description: "The numbered stage within the buffer pipeline."
 			required:    true
 		}
+		_consumer_id: {
+			description: "The ID of the downstream component that the events were sent to."
+			required:    true
+		}
 		_endpoint: {
 			description: "The absolute path of originating file."
 			required:    true
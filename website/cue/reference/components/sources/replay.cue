@@ -0,0 +1,87 @@
+package metadata
+
+components: sources: replay: {
+	title: "Replay"
+
+	description: """
+		Play back events recorded to a capture file by `vector tap --capture`, either with the timing
+		they were captured with or faster. This can be used to reproduce the shape of production
+		traffic in staging or tests.
+		"""
+
+	classes: {
+		delivery: "best_effort"
+		deployment_roles: ["aggregator", "daemon", "sidecar"]
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		acknowledgements: false
+		collect: {
+			checkpoint: enabled: false
+			from: service:       services.vector
+		}
+		multiline: enabled: false
+	}
+
+	support: {
+		notices: []
+		requirements: []
+		warnings: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: generated.components.sources.replay.configuration
+
+	output: {
+		logs: event: {
+			description: "A captured log event"
+			fields: {
+				"*": {
+					description: "Captured events are played back as they were tapped. The `replay` source doesn't modify or add fields."
+					required:    true
+					type: "*": {}
+				}
+			}
+		}
+		metrics: {
+			counter:      output._passthrough_counter
+			distribution: output._passthrough_distribution
+			gauge:        output._passthrough_gauge
+			histogram:    output._passthrough_histogram
+			set:          output._passthrough_set
+		}
+		traces: "": {
+			description: "Captured trace events."
+		}
+	}
+
+	how_it_works: {
+		capture_files: {
+			title: "Capture files"
+			body: """
+				Capture files are written by `vector tap --capture <path>`. Each event is stored in
+				Vector's native protobuf encoding, prefixed with its length as a 4-byte big-endian
+				integer, so capture files can also be read by any source using the `native` codec with
+				`length_delimited` framing.
+
+				The ID of the component each event was tapped from, and the time it was tapped, are
+				recorded in its metadata, and can be read in VRL as `%vector.tap.component_id` and
+				`%vector.tap.tapped_at`.
+				"""
+		}
+		timing: {
+			title: "Timing"
+			body: """
+				Events are played back with the same spacing they were tapped with, scaled by `speed`.
+				Only the events the tap sent are captured, so to reproduce the full rate of a component,
+				capture it with a `--limit` high enough that no events are sampled out.
+				"""
+		}
+	}
+}
@@ -189,6 +189,17 @@ components: transforms: aggregate: {
 				"""
 		}
 
+		state_snapshots: {
+			title: "State Snapshots"
+			body: """
+				By default, the metrics aggregated during the current interval are flushed when
+				Vector shuts down or the transform is reloaded. When `state_snapshot` is configured,
+				they're written to a snapshot in the data directory instead, along with the previous
+				values of the `Diff` mode and the samples of the `Mean` and `Stdev` modes, and
+				restored when the transform starts. Snapshots written by an incompatible version of
+				Vector are discarded.
+				"""
+		}
 	}
 
 	telemetry: metrics: {
@@ -52,6 +52,24 @@ components: transforms: dedupe: {
 				"""
 		}
 
+		cache_persistence: {
+			title: "Cache Persistence"
+			body: """
+				By default, the cache only lives in memory, so it starts out empty
+				whenever Vector restarts or the transform is reloaded. Sources that
+				replay events after a restart, such as `kafka` or `aws_s3`, can
+				then cause duplicates to be emitted again.
+
+				When `cache.persistence` is configured, the cache is written to a
+				file in the data directory every `snapshot_interval_secs`, as well
+				as when the transform shuts down, and is restored when the
+				transform starts. Entries older than `ttl_secs`, or older than
+				`time_settings.max_age_ms` when using a timed cache, are left out
+				of the persisted cache. Events processed since the last snapshot
+				are not deduplicated after a crash.
+				"""
+		}
+
 		memory_usage_details: {
 			title: "Memory Usage Details"
 			body: """
@@ -4,10 +4,59 @@ generated: components: transforms: dedupe: configuration: {
 	cache: {
 		description: "Caching configuration for deduplication."
 		required:    false
-		type: object: options: num_events: {
-			description: "Number of events to cache and use for comparing incoming events to previously seen events."
-			required:    false
-			type: uint: default: 5000
+		type: object: options: {
+			num_events: {
+				description: "Number of events to cache and use for comparing incoming events to previously seen events."
+				required:    false
+				type: uint: default: 5000
+			}
+			persistence: {
+				description: """
+					Configuration for persisting the deduplication cache to disk.
+
+					When configured, the cache is periodically written to disk, as well as when the transform
+					shuts down, and is restored when the transform starts. This allows deduplicating events that
+					are replayed by sources after Vector restarts or reloads its configuration.
+					"""
+				required: false
+				type: object: options: {
+					data_dir: {
+						description: """
+							The directory used to persist the cache.
+
+							By default, the [global `data_dir` option][global_data_dir] is used. Make sure the running
+							user has write permissions to this directory.
+
+							If this directory is specified, then Vector will attempt to create it.
+
+							[global_data_dir]: https://vector.dev/docs/reference/configuration/global-options/#data_dir
+							"""
+						required: false
+						type: string: examples: ["/var/lib/vector"]
+					}
+					snapshot_interval_secs: {
+						description: "The interval between writes of the cache to disk, in seconds."
+						required:    false
+						type: uint: {
+							default: 30
+							unit:    "seconds"
+						}
+					}
+					ttl_secs: {
+						description: """
+							The maximum age of the persisted cache entries, in seconds.
+
+							Entries that were last seen longer ago are left out of the persisted cache, and are not
+							restored when the transform starts. When `time_settings` is configured, entries older than
+							`max_age_ms` are always left out.
+
+							By default, entries are kept until they are evicted from the cache.
+							"""
+						required: false
+						type: uint: unit: "seconds"
+					}
+				}
+			}
 		}
 	}
 	fields: {
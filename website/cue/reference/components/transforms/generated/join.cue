@@ -0,0 +1,116 @@
+package metadata
+
+generated: components: transforms: join: configuration: {
+	flush_period_ms: {
+		description: "The interval to check for and flush any expired events, in milliseconds."
+		required:    false
+		type: uint: {
+			default: 1000
+			unit:    "milliseconds"
+		}
+	}
+	left: {
+		description: "Configuration for one side of the join."
+		required:    true
+		type: object: options: {
+			inputs: {
+				description: """
+					The inputs of the transform whose events are on this side of the join.
+
+					Each input must also be listed in the `inputs` of the transform. Events received from inputs
+					that are on neither side of the join are sent to the `_unmatched` output.
+					"""
+				required: true
+				type: array: items: type: string: examples: ["requests", "router.responses"]
+			}
+			key: {
+				description: """
+					The template used to compute the key events are joined on.
+
+					Events whose key can't be rendered are sent to the `_unmatched` output.
+					"""
+				required: true
+				type: string: {
+					examples: ["{{ request_id }}"]
+					syntax: "template"
+				}
+			}
+		}
+	}
+	max_pending: {
+		description: """
+			The maximum number of events waiting for matching events from the other side of the join.
+
+			Once reached, the oldest waiting events are evicted to make room for new ones. Evicted events
+			that were never matched are dropped.
+			"""
+		required: false
+		type: uint: unit: "events"
+	}
+	mode: {
+		description: "The kind of join to perform."
+		required:    false
+		type: string: {
+			default: "inner"
+			enum: {
+				inner: """
+					Only joined events are sent to the default output.
+
+					Events from either side that expire without a match are sent to the `_unmatched` output.
+					"""
+				left: """
+					Joined events, as well as events from the left side that expire without a match, are sent to
+					the default output.
+
+					Events from the right side that expire without a match are sent to the `_unmatched` output.
+					"""
+				outer: """
+					Joined events, as well as events from either side that expire without a match, are sent to
+					the default output.
+					"""
+			}
+		}
+	}
+	right: {
+		description: "Configuration for one side of the join."
+		required:    true
+		type: object: options: {
+			inputs: {
+				description: """
+					The inputs of the transform whose events are on this side of the join.
+
+					Each input must also be listed in the `inputs` of the transform. Events received from inputs
+					that are on neither side of the join are sent to the `_unmatched` output.
+					"""
+				required: true
+				type: array: items: type: string: examples: ["requests", "router.responses"]
+			}
+			key: {
+				description: """
+					The template used to compute the key events are joined on.
+
+					Events whose key can't be rendered are sent to the `_unmatched` output.
+					"""
+				required: true
+				type: string: {
+					examples: ["{{ request_id }}"]
+					syntax: "template"
+				}
+			}
+		}
+	}
+	window_ms: {
+		description: """
+			The maximum period of time, in milliseconds, that an event waits for matching events from the
+			other side of the join.
+
+			Events are joined with every matching event from the other side received within this window,
+			and are considered unmatched once it elapses without a match.
+			"""
+		required: false
+		type: uint: {
+			default: 30000
+			unit:    "milliseconds"
+		}
+	}
+}
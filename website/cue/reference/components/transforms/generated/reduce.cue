@@ -112,4 +112,30 @@ generated: components: transforms: reduce: configuration: {
 		required: false
 		type: condition: {}
 	}
+	state_snapshot: {
+		description: """
+			Configuration for snapshotting the state of a transform.
+
+			When configured, the in-flight state of the transform is written to disk when Vector shuts
+			down or reloads the transform, and restored when the transform starts, instead of being flushed.
+
+			When a reload removes the transform, or replaces it with one that doesn't snapshot its state,
+			the state is flushed as usual.
+			"""
+		required: false
+		type: object: options: data_dir: {
+			description: """
+				The directory used to store the state snapshot.
+
+				By default, the [global `data_dir` option][global_data_dir] is used. Make sure the running
+				user has write permissions to this directory.
+
+				If this directory is specified, then Vector will attempt to create it.
+
+				[global_data_dir]: https://vector.dev/docs/reference/configuration/global-options/#data_dir
+				"""
+			required: false
+			type: string: examples: ["/var/lib/vector"]
+		}
+	}
 }
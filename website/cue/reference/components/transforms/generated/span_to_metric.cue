@@ -0,0 +1,56 @@
+package metadata
+
+generated: components: transforms: span_to_metric: configuration: {
+	buckets: {
+		description: """
+			The upper limits of the buckets of the span duration histogram, in seconds.
+
+			Only used when `duration_metric` is set to `histogram`, in which case at least one limit must
+			be set, in strictly increasing order.
+			"""
+		required: false
+		type: array: {
+			default: [0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0]
+			items: type: float: {}
+		}
+	}
+	dimensions: {
+		description: """
+			Additional tags to add to the generated metrics, as a map of tag names to the span fields
+			holding their values.
+
+			Fields are looked up within each span. Tags whose field is missing from a span are omitted.
+			"""
+		required: false
+		type: object: {
+			examples: [{
+				env:         "meta.env"
+				http_method: "attributes.\"http.method\""
+			}]
+			options: "*": {
+				description: "The span field holding the value of the tag."
+				required:    true
+				type: string: {}
+			}
+		}
+	}
+	duration_metric: {
+		description: "The type of metric used for span durations."
+		required:    false
+		type: string: {
+			default: "histogram"
+			enum: {
+				distribution: """
+					A distribution of the individual span durations, left for downstream components to
+					aggregate.
+					"""
+				histogram: "An aggregated histogram, with the buckets set in `buckets`."
+			}
+		}
+	}
+	namespace: {
+		description: "The namespace of the generated metrics."
+		required:    false
+		type: string: examples: ["traces"]
+	}
+}
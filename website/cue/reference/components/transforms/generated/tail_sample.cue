@@ -0,0 +1,117 @@
+package metadata
+
+generated: components: transforms: tail_sample: configuration: {
+	complete_when: {
+		description: """
+			A condition matching the span that completes its trace, such as its root span.
+
+			When a span matches, its trace is sampled right away rather than once `decision_wait_ms`
+			elapses. Spans of the trace received afterwards follow the same sampling decision.
+			"""
+		required: false
+		type: condition: {}
+	}
+	decision_wait_ms: {
+		description: """
+			The maximum period of time to wait for the spans of a trace, in milliseconds, after its first
+			span is received.
+
+			Once elapsed, the trace is sampled based on the spans received so far. Spans of the trace
+			received afterwards follow the same sampling decision.
+			"""
+		required: false
+		type: uint: {
+			default: 30000
+			unit:    "milliseconds"
+		}
+	}
+	flush_period_ms: {
+		description: "The interval to check for and sample any expired traces, in milliseconds."
+		required:    false
+		type: uint: {
+			default: 1000
+			unit:    "milliseconds"
+		}
+	}
+	max_spans: {
+		description: """
+			The maximum number of spans to hold in memory across all traces.
+
+			When the limit is reached, the oldest traces are sampled right away based on the spans
+			received so far.
+			"""
+		required: false
+		type: uint: default: 100000
+	}
+	max_traces: {
+		description: """
+			The maximum number of traces to hold in memory while waiting for their spans.
+
+			When the limit is reached, the oldest trace is sampled right away based on the spans received
+			so far. This is also the number of sampling decisions remembered for spans of the trace that
+			are received afterwards.
+			"""
+		required: false
+		type: uint: default: 10000
+	}
+	policies: {
+		description: """
+			The policies used to decide whether a trace is kept.
+
+			A trace is kept if any of the policies match it, and dropped otherwise.
+			"""
+		required: false
+		type: object: options: {
+			condition: {
+				description: "Keep traces with at least one span matching this condition."
+				required:    false
+				type: condition: {}
+			}
+			keep_errors: {
+				description: """
+					Keep traces with at least one span that has an error.
+
+					OpenTelemetry spans with a `status.code` of `2`, and Datadog spans with a non-zero `error`,
+					have an error.
+					"""
+				required: false
+				type: bool: default: true
+			}
+			latency_threshold_ms: {
+				description: """
+					Keep traces that last longer than this threshold, in milliseconds.
+
+					The duration of a trace spans from the start of its earliest span to the end of its latest
+					span, as reported by the `start_time_unix_nano` and `end_time_unix_nano` fields of
+					OpenTelemetry spans, or the `start` and `duration` fields of Datadog spans.
+					"""
+				required: false
+				type: uint: examples: [500]
+			}
+			ratio: {
+				description: """
+					The ratio of the remaining traces to keep.
+
+					Traces are picked by hashing their ID, so every instance of Vector sampling the same
+					traces keeps the same ones.
+					"""
+				required: false
+				type: float: examples: [
+					0.1,
+				]
+			}
+		}
+	}
+	trace_id_field: {
+		description: """
+			The field holding the ID of the trace that a span belongs to.
+
+			Spans without this field are forwarded without being sampled.
+			"""
+		required: false
+		type: string: {
+			default: "trace_id"
+			examples: ["trace_id"]
+		}
+	}
+}
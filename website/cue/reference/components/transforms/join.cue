@@ -0,0 +1,102 @@
+package metadata
+
+components: transforms: join: {
+	title: "Join"
+
+	description: """
+		Correlates log events from two groups of inputs that share a key within a time window, such as
+		requests and their responses, and combines each matching pair into a single event.
+		"""
+
+	classes: {
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      true
+	}
+
+	features: {
+		reduce: {}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: generated.components.transforms.join.configuration
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	output: {
+		logs: "": {
+			description: "The joined `log` event, or the `log` event left unmatched by a `left` or `outer` join."
+		}
+	}
+
+	examples: [
+		{
+			title: "Join requests with their responses"
+			input: [
+				{log: {request_id: "a1", path: "/login"}},
+				{log: {request_id: "a1", status: 200}},
+			]
+
+			configuration: {
+				left: {
+					inputs: ["requests"]
+					key: "{{ request_id }}"
+				}
+				right: {
+					inputs: ["responses"]
+					key: "{{ request_id }}"
+				}
+				window_ms: 5000
+			}
+
+			output: [
+				{log: {
+					left: {request_id: "a1", path: "/login"}
+					right: {request_id: "a1", status: 200}
+				}},
+			]
+		},
+	]
+
+	how_it_works: {
+		joining: {
+			title: "Joining events"
+			body: """
+				Each event received by the transform is assigned to the `left` or `right` side of the join
+				based on the input it was received from, and its key is rendered from the `key` template of
+				that side. The event is then joined with every event from the other side with the same key
+				received within the last `window_ms` milliseconds, and kept for `window_ms` milliseconds to be
+				joined with events from the other side received later.
+
+				Each joined event holds the event from the left side under the `left` field and the event from
+				the right side under the `right` field.
+
+				Pending events are kept in memory, so any events still waiting for a match are lost in case
+				of a hard system crash. When Vector shuts down, pending events are flushed as if their window
+				had elapsed.
+				"""
+		}
+
+		unmatched_events: {
+			title: "Unmatched events"
+			body: """
+				Events that expire without being joined are handled according to the `mode` option. With an
+				`inner` join, they're sent to the `<transform_name>._unmatched` output. A `left` join sends
+				unmatched events from the left side to the default output, holding only the `left` field, and an
+				`outer` join does the same for events from either side.
+
+				Events received from an input that is on neither side of the join, or whose key can't be
+				rendered, are sent to the `<transform_name>._unmatched` output right away.
+				"""
+		}
+	}
+}
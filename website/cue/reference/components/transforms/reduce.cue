@@ -153,6 +153,23 @@ components: transforms: reduce: {
 		},
 	]
 
+	how_it_works: {
+		state_snapshots: {
+			title: "State Snapshots"
+			body: """
+				By default, the events being reduced are flushed when Vector shuts down
+				or the transform is reloaded, even if their transactions aren't complete.
+
+				When `state_snapshot` is configured, they're written to a snapshot in the
+				data directory instead, and restored when the transform starts, so that
+				their transactions can be completed by the events received afterwards.
+				The time elapsed while Vector was stopped counts towards `expire_after_ms`
+				and `end_every_period_ms`. Snapshots written by an incompatible version of
+				Vector are discarded.
+				"""
+		}
+	}
+
 	telemetry: metrics: {
 		stale_events_flushed_total: components.sources.internal_metrics.output.metrics.stale_events_flushed_total
 	}
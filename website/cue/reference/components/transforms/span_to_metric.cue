@@ -0,0 +1,75 @@
+package metadata
+
+components: transforms: span_to_metric: {
+	title: "Span to Metric"
+
+	description: """
+		Derives request rate, error, and duration (RED) metrics from the spans of trace events.
+		"""
+
+	classes: {
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		convert: {}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: generated.components.transforms.span_to_metric.configuration
+
+	input: {
+		logs:    false
+		metrics: null
+		traces:  true
+	}
+
+	output: metrics: {
+		counter:      output._passthrough_counter
+		distribution: output._passthrough_distribution
+		histogram:    output._passthrough_histogram
+	}
+
+	how_it_works: {
+		spans: {
+			title: "Spans"
+			body: """
+				OpenTelemetry trace events each hold a single span. Datadog trace events hold all of the
+				spans of a trace in their `spans` field, and metrics are derived from each of them.
+				"""
+		}
+
+		metrics: {
+			title: "Generated metrics"
+			body: """
+				For each span, the transform emits a `spans_total` counter, incremented by one, and a
+				`span_duration_seconds` metric holding the duration of the span. The duration metric is an
+				aggregated histogram with the configured `buckets`, or a distribution when
+				`duration_metric` is set to `distribution`. It's omitted for spans whose duration is
+				unknown.
+
+				Both metrics are tagged with:
+
+				- `service`: the `service` field of Datadog spans, or the `service.name` resource of
+				  OpenTelemetry spans.
+				- `operation`: the `name` field of the span.
+				- `status`: `ok`, `error`, or `unset`, based on the `error` field of Datadog spans or the
+				  `status.code` field of OpenTelemetry spans.
+
+				along with any tags configured in `dimensions`. Spans without a service or name are tagged
+				with `unknown`.
+
+				The metrics are incremental, so aggregating them downstream, for example with the
+				`aggregate` transform or a metrics sink, gives request rates, error rates, and duration
+				percentiles per service and operation.
+				"""
+		}
+	}
+}
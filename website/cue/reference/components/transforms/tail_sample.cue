@@ -0,0 +1,83 @@
+package metadata
+
+components: transforms: tail_sample: {
+	title: "Tail Sample"
+
+	description: """
+		Samples traces as a whole, once their spans have been received, keeping the traces that
+		matter most, such as traces with errors or slow traces, along with a ratio of the others.
+
+		Unlike the [Sample](\(urls.vector_sample_transform)) transform, which decides for each event
+		independently, all of the spans of a trace are either kept or dropped together.
+		"""
+
+	classes: {
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      true
+	}
+
+	features: {
+		filter: {}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: generated.components.transforms.tail_sample.configuration
+
+	input: {
+		logs:    false
+		metrics: null
+		traces:  true
+	}
+
+	output: {
+		traces: "": {
+			description: "The input `trace` event, if its trace is kept."
+		}
+	}
+
+	how_it_works: {
+		buffering: {
+			title: "Buffering spans"
+			body: """
+				Spans are held in memory, grouped by the trace ID found in `trace_id_field`, until their trace
+				completes. A trace completes when one of its spans matches the `complete_when` condition, or
+				once `decision_wait_ms` milliseconds have elapsed since its first span was received. The
+				trace is then sampled, and all of its spans are either forwarded or dropped.
+
+				Spans of a trace received after it was sampled follow the same decision, for as long as the
+				decision is among the `max_traces` most recent ones.
+
+				To bound memory usage, the oldest trace is sampled early whenever more than `max_traces`
+				traces or `max_spans` spans are held. Held spans are lost in case of a hard system crash.
+				When Vector shuts down, all held traces are sampled.
+				"""
+		}
+
+		policies: {
+			title: "Sampling policies"
+			body: """
+				A trace is kept if any of the following policies match it:
+
+				- `keep_errors`: at least one of its spans has an error.
+				- `latency_threshold_ms`: it lasted longer than the threshold.
+				- `condition`: at least one of its spans matches the condition.
+				- `ratio`: its trace ID hashes within the ratio.
+
+				Traces matching none of the policies are dropped.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		buffered_spans:       components.sources.internal_metrics.output.metrics.buffered_spans
+		buffered_traces:      components.sources.internal_metrics.output.metrics.buffered_traces
+		traces_evicted_total: components.sources.internal_metrics.output.metrics.traces_evicted_total
+		traces_sampled_total: components.sources.internal_metrics.output.metrics.traces_sampled_total
+	}
+}
@@ -17,6 +17,17 @@ generated: configuration: {
 					common:   true
 					required: false
 				}
+				control_token: {
+					type: string: examples: ["${VECTOR_API_CONTROL_TOKEN}", "SECRET[secret_backend.api_token]"]
+					description: """
+						The bearer token that clients must present to use control-plane API calls, such as
+						pausing or resuming a component.
+
+						Clients pass the token in the `authorization` request metadata as `Bearer <token>`. When
+						no token is set, control-plane calls are rejected.
+						"""
+					required: false
+				}
 				enabled: {
 					type: bool: default: false
 					description: "Whether the API is enabled for this Vector instance."
@@ -25,7 +36,7 @@ generated: configuration: {
 				}
 			}
 			description: "API options."
-			warnings: ["The API currently does not support authentication. Only enable it in isolated environments or for debugging. It must not be exposed to untrusted clients."]
+			warnings: ["Only control-plane API calls are authenticated. Only enable the API in isolated environments or for debugging. It must not be exposed to untrusted clients."]
 			group: "api"
 		}
 		enrichment_tables: {
@@ -1328,6 +1339,21 @@ generated: configuration: {
 				"""
 			group: "global_options"
 		}
+		buffer_pool: {
+			type: object: options: max_size: {
+				type: uint: unit: "bytes"
+				description: "The total size of the pool, shared by all disk buffers."
+				required:    true
+			}
+			description: """
+				A pool of disk space shared by all disk buffers.
+
+				When configured, disk buffers draw their space from this pool, and their own `max_size` acts
+				as a cap on how much of the pool they can use. Space in the pool can be reserved for a disk
+				buffer, and prioritized between disk buffers, with the buffer's `pool` option.
+				"""
+			group: "global_options"
+		}
 		data_dir: {
 			type: string: default: "/var/lib/vector/"
 			description: """
@@ -627,6 +627,7 @@ urls: {
 	vector_route_transform:                     "/docs/reference/configuration/transforms/route"
 	vector_exclusive_route_transform:           "/docs/reference/configuration/transforms/exclusive_route"
 	vector_rpm_source_files:                    "\(vector_repo)/tree/master/distribution/rpm"
+	vector_sample_transform:                    "/docs/reference/configuration/transforms/sample/"
 	vector_security_policy:                     "\(vector_repo)/security/policy"
 	vector_semantic_yml:                        "\(vector_repo)/blob/master/.github/semantic.yml"
 	vector_sink:                                "/docs/reference/configuration/sinks/vector"